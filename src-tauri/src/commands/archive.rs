@@ -0,0 +1,170 @@
+// Snapshot export. `git archive` writes any commit, tag or tree as a zip or tarball
+// without a checkout; its `--verbose` file list, measured against the tree's file count,
+// drives the `archive_progress` events.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::Stdio;
+
+#[derive(Debug, Clone, Serialize)]
+struct ArchiveProgressEvent {
+    repo_path: String,
+    out_path: String,
+    files_done: u64,
+    files_total: u64,
+    percent: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitArchiveResult {
+    path: String,
+    format: String,
+    commit: String,
+    files: u64,
+    /// Size of the written archive.
+    bytes: u64,
+}
+
+/// Runs the archive, calling `on_progress(files_done, files_total, percent)` whenever the
+/// percentage changes.
+fn archive_impl(
+    repo_path: &str,
+    rev: &str,
+    format: &str,
+    out_path: &str,
+    prefix: Option<&str>,
+    mut on_progress: impl FnMut(u64, u64, u32),
+) -> Result<GitArchiveResult, String> {
+    let spec = format!("{rev}^{{tree}}");
+    crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", spec.as_str()])
+        .map_err(|_| format!("Revision not found: {rev}"))?;
+    // Trees have no commit; the archive then carries no commit id either.
+    let commit = crate::resolve_commit(repo_path, rev).unwrap_or_default();
+
+    let files_total = crate::run_git_stdout_raw(repo_path, &["ls-tree", "-r", "-z", "--name-only", rev])?
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .count() as u64;
+
+    let prefix = match prefix {
+        Some(p) => p.trim().trim_start_matches('/').to_string(),
+        None => {
+            let repo_name = Path::new(repo_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| String::from("repo"));
+            format!("{repo_name}-{}", rev.replace(['/', '\\', ':', '~', '^'], "-"))
+        }
+    };
+    if prefix.split(['/', '\\']).any(|c| c == "..") {
+        return Err(String::from("prefix must not contain '..'"));
+    }
+    let prefix = if prefix.is_empty() || prefix.ends_with('/') { prefix } else { format!("{prefix}/") };
+
+    let format_arg = format!("--format={format}");
+    let prefix_arg = format!("--prefix={prefix}");
+    let mut child = crate::git_command_in_repo(repo_path)
+        .args(["archive", "--verbose", format_arg.as_str(), prefix_arg.as_str(), "-o", out_path, rev])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn git archive: {e}"))?;
+
+    // Every archived path is listed on stderr; anything else is an error message.
+    let mut errors: Vec<String> = Vec::new();
+    let mut files_done = 0u64;
+    let mut last_percent: Option<u32> = None;
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).split(b'\n') {
+            let Ok(line) = line else {
+                break;
+            };
+            let line = String::from_utf8_lossy(&line).to_string();
+            if !line.starts_with(prefix.as_str()) || line.starts_with("fatal:") || line.starts_with("error:") {
+                errors.push(line);
+                continue;
+            }
+            if line.ends_with('/') {
+                continue;
+            }
+            files_done += 1;
+            let percent = (files_done * 100).checked_div(files_total).unwrap_or(100).min(100) as u32;
+            if last_percent != Some(percent) {
+                last_percent = Some(percent);
+                on_progress(files_done, files_total, percent);
+            }
+        }
+    }
+    let status = child.wait().map_err(|e| format!("Failed to wait for git archive: {e}"))?;
+    if !status.success() {
+        let _ = std::fs::remove_file(out_path);
+        return Err(format!("git archive failed: {}", errors.join("\n")));
+    }
+
+    let bytes = std::fs::metadata(out_path).map(|m| m.len()).unwrap_or(0);
+    Ok(GitArchiveResult {
+        path: out_path.to_string(),
+        format: format.to_string(),
+        commit,
+        files: files_done,
+        bytes,
+    })
+}
+
+/// Writes `rev` (a commit, tag or tree) to `out_path` as a "zip", "tar" or "tar.gz"
+/// archive. Every path is placed under `prefix`, by default `<repo>-<rev>/`; pass an
+/// empty prefix to archive the files at the top level. Progress is reported through
+/// `archive_progress` events.
+#[tauri::command]
+pub(crate) async fn git_archive(
+    app: AppHandle,
+    repo_path: String,
+    rev: String,
+    format: String,
+    out_path: String,
+    prefix: Option<String>,
+) -> Result<GitArchiveResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        let rev = rev.trim().to_string();
+        if rev.is_empty() {
+            return Err(String::from("rev is empty"));
+        }
+        if rev.starts_with('-') {
+            return Err(format!("Invalid revision: {rev}"));
+        }
+        let format = match format.trim().to_lowercase().as_str() {
+            "zip" => "zip",
+            "tar" => "tar",
+            "tar.gz" | "tgz" => "tar.gz",
+            _ => return Err(String::from("Invalid archive format. Use: zip, tar or tar.gz.")),
+        };
+        let out_path = out_path.trim().to_string();
+        if out_path.is_empty() {
+            return Err(String::from("out_path is empty"));
+        }
+        let parent = Path::new(&out_path).parent().filter(|p| !p.as_os_str().is_empty());
+        if parent.is_some_and(|p| !p.is_dir()) {
+            return Err(String::from("The output directory does not exist."));
+        }
+
+        archive_impl(&repo_path, &rev, format, &out_path, prefix.as_deref(), |files_done, files_total, percent| {
+            let _ = app.emit(
+                "archive_progress",
+                ArchiveProgressEvent {
+                    repo_path: repo_path.clone(),
+                    out_path: out_path.clone(),
+                    files_done,
+                    files_total,
+                    percent,
+                },
+            );
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to run git archive: {e}"))?
+}
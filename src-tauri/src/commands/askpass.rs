@@ -0,0 +1,216 @@
+// Credential prompt bridge. Git commands run with GIT_ASKPASS/SSH_ASKPASS pointing at
+// the Graphoria executable itself; started that way it acts as a tiny client that forwards
+// the prompt over a loopback socket to the running app, which asks the frontend through a
+// `git_credential_prompt` event and relays the answer from `git_credential_prompt_reply`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::Duration;
+
+use tauri::Emitter;
+
+const ENV_PORT: &str = "GRAPHORIA_ASKPASS_PORT";
+const ENV_TOKEN: &str = "GRAPHORIA_ASKPASS_TOKEN";
+
+/// How long a prompt waits for the user before git is told there is no answer.
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(300);
+
+struct AskpassServer {
+    port: u16,
+    token: String,
+    exe: PathBuf,
+}
+
+static SERVER: OnceLock<AskpassServer> = OnceLock::new();
+
+type PendingPrompts = Mutex<HashMap<u64, mpsc::Sender<Option<String>>>>;
+
+static PENDING_PROMPTS: OnceLock<PendingPrompts> = OnceLock::new();
+
+static NEXT_PROMPT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn pending_prompts() -> &'static PendingPrompts {
+    PENDING_PROMPTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AskpassRequest {
+    token: String,
+    prompt: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AskpassResponse {
+    value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitCredentialPromptEvent {
+    id: u64,
+    prompt: String,
+    /// `username`, `password`, `passphrase`, `confirm` (SSH host key) or `other`.
+    kind: String,
+    secret: bool,
+}
+
+fn prompt_kind(prompt: &str) -> (&'static str, bool) {
+    let lower = prompt.to_lowercase();
+    if lower.contains("username") {
+        ("username", false)
+    } else if lower.contains("passphrase") {
+        ("passphrase", true)
+    } else if lower.contains("password") {
+        ("password", true)
+    } else if lower.contains("(yes/no") {
+        ("confirm", false)
+    } else {
+        ("other", true)
+    }
+}
+
+fn random_token() -> String {
+    let a = RandomState::new().build_hasher().finish();
+    let mut h = RandomState::new().build_hasher();
+    h.write_u32(std::process::id());
+    format!("{a:016x}{:016x}", h.finish())
+}
+
+/// Starts the loopback listener the askpass client talks to. Network commands keep their
+/// plain behavior when this was never called (or failed), e.g. in tests.
+pub(crate) fn start(app: tauri::AppHandle) -> Result<(), String> {
+    if SERVER.get().is_some() {
+        return Ok(());
+    }
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate executable: {e}"))?;
+    let listener =
+        TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).map_err(|e| format!("Failed to start askpass listener: {e}"))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to start askpass listener: {e}"))?
+        .port();
+    let token = random_token();
+    if SERVER.set(AskpassServer { port, token: token.clone(), exe }).is_err() {
+        return Ok(());
+    }
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let app = app.clone();
+            let token = token.clone();
+            std::thread::spawn(move || handle_client(app, stream, token.as_str()));
+        }
+    });
+    Ok(())
+}
+
+fn handle_client(app: tauri::AppHandle, stream: TcpStream, token: &str) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut line = String::new();
+    if BufReader::new(stream).read_line(&mut line).is_err() {
+        return;
+    }
+    let Ok(req) = serde_json::from_str::<AskpassRequest>(line.trim()) else {
+        return;
+    };
+    if req.token != token {
+        return;
+    }
+
+    let id = NEXT_PROMPT_ID.fetch_add(1, Ordering::SeqCst);
+    let (tx, rx) = mpsc::channel::<Option<String>>();
+    if let Ok(mut guard) = pending_prompts().lock() {
+        guard.insert(id, tx);
+    }
+
+    let (kind, secret) = prompt_kind(req.prompt.as_str());
+    let emitted = app.emit(
+        "git_credential_prompt",
+        GitCredentialPromptEvent {
+            id,
+            prompt: req.prompt,
+            kind: kind.to_string(),
+            secret,
+        },
+    );
+    let value = if emitted.is_ok() {
+        rx.recv_timeout(PROMPT_TIMEOUT).ok().flatten()
+    } else {
+        None
+    };
+    if let Ok(mut guard) = pending_prompts().lock() {
+        guard.remove(&id);
+    }
+
+    if let Ok(mut json) = serde_json::to_string(&AskpassResponse { value }) {
+        json.push('\n');
+        let _ = writer.write_all(json.as_bytes());
+    }
+}
+
+/// Points git (and the ssh it spawns) at the askpass client. Nothing changes when the user
+/// set up GIT_ASKPASS or SSH_ASKPASS themselves; SSH_ASKPASS_REQUIRE is only filled in when
+/// unset, since ssh otherwise skips askpass without a display.
+pub(crate) fn apply_env(cmd: &mut Command) {
+    let Some(server) = SERVER.get() else {
+        return;
+    };
+    if std::env::var_os("GIT_ASKPASS").is_some() || std::env::var_os("SSH_ASKPASS").is_some() {
+        return;
+    }
+    cmd.env("GIT_ASKPASS", &server.exe)
+        .env("SSH_ASKPASS", &server.exe)
+        .env(ENV_PORT, server.port.to_string())
+        .env(ENV_TOKEN, &server.token);
+    if std::env::var_os("SSH_ASKPASS_REQUIRE").is_none() {
+        cmd.env("SSH_ASKPASS_REQUIRE", "prefer");
+    }
+}
+
+/// Answers a `git_credential_prompt` event; `None` cancels it and git fails the
+/// operation as if no credentials were entered. Returns whether the prompt was pending.
+#[tauri::command]
+pub(crate) async fn git_credential_prompt_reply(id: u64, value: Option<String>) -> Result<bool, String> {
+    let tx = pending_prompts()
+        .lock()
+        .map_err(|_| String::from("Failed to lock credential prompts."))?
+        .remove(&id);
+    Ok(tx.is_some_and(|tx| tx.send(value).is_ok()))
+}
+
+/// Entry point when this executable was started by git as an askpass helper. Returns the
+/// exit code, or `None` for a normal app start.
+pub(crate) fn run_client() -> Option<i32> {
+    let port = std::env::var(ENV_PORT).ok()?;
+    let token = std::env::var(ENV_TOKEN).ok()?;
+    let prompt = std::env::args().nth(1).unwrap_or_default();
+
+    let answer = (|| -> Option<String> {
+        let port: u16 = port.trim().parse().ok()?;
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).ok()?;
+        let mut json = serde_json::to_string(&AskpassRequest { token, prompt }).ok()?;
+        json.push('\n');
+        stream.write_all(json.as_bytes()).ok()?;
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).ok()?;
+        serde_json::from_str::<AskpassResponse>(line.trim()).ok()?.value
+    })();
+
+    match answer {
+        Some(value) => {
+            println!("{value}");
+            Some(0)
+        }
+        None => Some(1),
+    }
+}
@@ -0,0 +1,384 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Diff drivers git ships with (`userdiff.c`); they need no configuration.
+const BUILTIN_DIFF_DRIVERS: &[&str] = &[
+    "ada", "bash", "bibtex", "cpp", "csharp", "css", "dts", "elixir", "fortran", "fountain", "golang", "html", "java",
+    "kotlin", "markdown", "matlab", "objc", "pascal", "perl", "php", "python", "r", "ruby", "rust", "scheme", "tex",
+];
+
+const BUILTIN_MERGE_DRIVERS: &[&str] = &["text", "binary", "union"];
+
+/// `git lfs track` writes `diff=lfs merge=lfs` next to the filter; those are covered by
+/// the filter check.
+const LFS_DRIVER: &str = "lfs";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitAttribute {
+    name: String,
+    /// `set`, `unset`, `unspecified` or the assigned value.
+    value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitAttributeEntry {
+    /// Attributes file relative to the repository root, e.g. `.gitattributes`,
+    /// `docs/.gitattributes` or `.git/info/attributes`.
+    file: String,
+    line: u32,
+    /// `[attr]name` for macro definitions.
+    pattern: String,
+    attributes: Vec<GitAttribute>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitPathAttributes {
+    path: String,
+    attributes: Vec<GitAttribute>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitAttributeProblem {
+    file: String,
+    line: u32,
+    pattern: String,
+    /// `error` when git ignores or rejects the line, `warning` when it likely does not do
+    /// what was intended.
+    severity: String,
+    message: String,
+}
+
+fn parse_attribute(token: &str) -> GitAttribute {
+    let (name, value) = if let Some(name) = token.strip_prefix('-') {
+        (name, String::from("unset"))
+    } else if let Some(name) = token.strip_prefix('!') {
+        (name, String::from("unspecified"))
+    } else if let Some((name, value)) = token.split_once('=') {
+        (name, value.to_string())
+    } else {
+        (token, String::from("set"))
+    };
+    GitAttribute {
+        name: name.to_string(),
+        value,
+    }
+}
+
+/// Splits a line into its pattern and attribute tokens. Patterns may be C-quoted.
+fn parse_line(line: &str) -> Option<(String, Vec<GitAttribute>)> {
+    let line = line.trim_end_matches('\r').trim_start();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (pattern, rest) = if let Some(quoted) = line.strip_prefix('"') {
+        let mut pattern = String::new();
+        let mut chars = quoted.char_indices();
+        let mut end = quoted.len();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some((_, n)) = chars.next() {
+                        pattern.push(match n {
+                            't' => '\t',
+                            'n' => '\n',
+                            other => other,
+                        });
+                    }
+                }
+                '"' => {
+                    end = i + 1;
+                    break;
+                }
+                c => pattern.push(c),
+            }
+        }
+        (pattern, &quoted[end..])
+    } else {
+        let end = line.find(char::is_whitespace).unwrap_or(line.len());
+        (line[..end].to_string(), &line[end..])
+    };
+    Some((pattern, rest.split_whitespace().map(parse_attribute).collect()))
+}
+
+fn format_pattern(pattern: &str) -> String {
+    if pattern.contains(char::is_whitespace) || pattern.contains('"') {
+        format!("\"{}\"", pattern.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        pattern.to_string()
+    }
+}
+
+/// Attribute files of the repository in the order git applies them (later ones win):
+/// tracked `.gitattributes` from the root down, then `info/attributes`.
+fn attribute_files(repo_path: &str) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    let mut tracked: Vec<String> = crate::run_git_stdout_raw(repo_path, &["ls-files", "-z", "--", ":(glob)**/.gitattributes"])
+        .unwrap_or_default()
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect();
+    if !tracked.iter().any(|p| p == ".gitattributes") && Path::new(repo_path).join(".gitattributes").is_file() {
+        tracked.push(String::from(".gitattributes"));
+    }
+    tracked.sort_by_key(|p| p.matches('/').count());
+    for rel in tracked {
+        let abs = Path::new(repo_path).join(&rel);
+        files.push((rel, abs));
+    }
+    if let Some(info) = super::handles::git_common_dir(repo_path)
+        .map(|d| d.join("info").join("attributes"))
+        .filter(|p| p.is_file())
+    {
+        let rel = info
+            .strip_prefix(repo_path)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| info.to_string_lossy().to_string());
+        files.push((rel, info));
+    }
+    files
+}
+
+fn read_entries(rel: &str, abs: &Path) -> Vec<GitAttributeEntry> {
+    fs::read_to_string(abs)
+        .unwrap_or_default()
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let (pattern, attributes) = parse_line(line)?;
+            Some(GitAttributeEntry {
+                file: rel.to_string(),
+                line: i as u32 + 1,
+                pattern,
+                attributes,
+            })
+        })
+        .collect()
+}
+
+/// Every attribute line of the repository, grouped by file in the order git applies them.
+#[tauri::command]
+pub(crate) fn git_attributes_list(repo_path: String) -> Result<Vec<GitAttributeEntry>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    Ok(attribute_files(&repo_path)
+        .iter()
+        .flat_map(|(rel, abs)| read_entries(rel, abs))
+        .collect())
+}
+
+/// Replaces the attributes of `pattern` in `file` (the root `.gitattributes` by default),
+/// appending a line when the pattern is new. An empty `attributes` list removes the line.
+/// Attributes are written as in the file: `text`, `-diff`, `!eol`, `eol=lf`.
+#[tauri::command]
+pub(crate) fn git_attributes_set(
+    repo_path: String,
+    pattern: String,
+    attributes: Vec<String>,
+    file: Option<String>,
+) -> Result<(), String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let pattern = pattern.trim().to_string();
+    if pattern.is_empty() {
+        return Err(String::from("pattern is empty"));
+    }
+    let attributes: Vec<String> = attributes
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+    if attributes.iter().any(|a| a.contains(char::is_whitespace)) {
+        return Err(String::from("Attributes must not contain whitespace."));
+    }
+
+    let file = file
+        .map(|f| f.trim().replace('\\', "/"))
+        .filter(|f| !f.is_empty())
+        .unwrap_or_else(|| String::from(".gitattributes"));
+    let path = if file == "info/attributes" || file.ends_with("/info/attributes") {
+        super::handles::git_common_dir(&repo_path)
+            .ok_or_else(|| String::from("Failed to resolve git directory."))?
+            .join("info")
+            .join("attributes")
+    } else {
+        crate::ensure_rel_path_safe(file.as_str())?;
+        if file != ".gitattributes" && !file.ends_with("/.gitattributes") {
+            return Err(String::from("file must be a .gitattributes file."));
+        }
+        Path::new(&repo_path).join(&file)
+    };
+
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let new_line = (!attributes.is_empty()).then(|| format!("{} {}", format_pattern(&pattern), attributes.join(" ")));
+    let mut lines: Vec<String> = Vec::new();
+    let mut replaced = false;
+    for line in content.lines() {
+        let same = parse_line(line).is_some_and(|(p, _)| p == pattern);
+        if !same {
+            lines.push(line.to_string());
+        } else if !replaced {
+            replaced = true;
+            if let Some(l) = new_line.as_ref() {
+                lines.push(l.clone());
+            }
+        }
+    }
+    if !replaced {
+        match new_line {
+            Some(l) => lines.push(l),
+            None => return Ok(()),
+        }
+    }
+
+    let mut out = lines.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+    fs::write(&path, out).map_err(|e| format!("Failed to write {file}: {e}"))?;
+    Ok(())
+}
+
+/// Attributes git resolves for each path (`git check-attr -a`), after all files, macros
+/// and precedence rules are applied. Unspecified attributes are left out.
+#[tauri::command]
+pub(crate) fn git_check_attr(repo_path: String, paths: Vec<String>) -> Result<Vec<GitPathAttributes>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let paths: Vec<String> = paths
+        .into_iter()
+        .map(|p| p.trim().replace('\\', "/"))
+        .filter(|p| !p.is_empty())
+        .collect();
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut args: Vec<&str> = vec!["check-attr", "-z", "-a", "--"];
+    args.extend(paths.iter().map(|p| p.as_str()));
+    let raw = crate::run_git_stdout_raw(&repo_path, args.as_slice())?;
+
+    let mut out: Vec<GitPathAttributes> = paths
+        .iter()
+        .map(|p| GitPathAttributes {
+            path: p.clone(),
+            attributes: Vec::new(),
+        })
+        .collect();
+    let fields: Vec<&str> = raw.split('\0').collect();
+    for chunk in fields.chunks(3).filter(|c| c.len() == 3) {
+        let (path, name, info) = (chunk[0], chunk[1], chunk[2]);
+        let Some(entry) = out.iter_mut().find(|e| e.path == path) else {
+            continue;
+        };
+        entry.attributes.push(GitAttribute {
+            name: name.to_string(),
+            value: info.to_string(),
+        });
+    }
+    Ok(out)
+}
+
+fn config_has(repo_path: &str, key: &str) -> bool {
+    crate::run_git(repo_path, &["config", "--get", key]).is_ok_and(|v| !v.trim().is_empty())
+}
+
+/// Lines git ignores, values it does not understand, and drivers or filters that are not
+/// configured on this machine (so the attribute silently does nothing, or fails on checkout
+/// in the case of filters marked `required`).
+#[tauri::command]
+pub(crate) fn git_attributes_validate(repo_path: String) -> Result<Vec<GitAttributeProblem>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let mut problems: Vec<GitAttributeProblem> = Vec::new();
+    for (rel, abs) in attribute_files(&repo_path) {
+        for entry in read_entries(&rel, &abs) {
+            let mut report = |severity: &str, message: String| {
+                problems.push(GitAttributeProblem {
+                    file: entry.file.clone(),
+                    line: entry.line,
+                    pattern: entry.pattern.clone(),
+                    severity: severity.to_string(),
+                    message,
+                });
+            };
+
+            if entry.pattern.starts_with('!') {
+                report("error", String::from("Negative patterns are not allowed in attribute files; git ignores this line."));
+                continue;
+            }
+            if entry.pattern.ends_with('/') {
+                report(
+                    "warning",
+                    format!("Patterns ending in '/' never match files; use '{}**' instead.", entry.pattern),
+                );
+            }
+            if entry.attributes.is_empty() {
+                report("warning", String::from("The line sets no attributes."));
+            }
+
+            for attr in entry.attributes.iter() {
+                let value = attr.value.as_str();
+                match attr.name.as_str() {
+                    "text" if !matches!(value, "set" | "unset" | "unspecified" | "auto") => {
+                        report("error", format!("Unknown text value '{value}'; expected text, -text or text=auto."));
+                    }
+                    "eol" if !matches!(value, "lf" | "crlf" | "unspecified") => {
+                        report("error", format!("Unknown eol value '{value}'; expected eol=lf or eol=crlf."));
+                    }
+                    "diff" if !matches!(value, "set" | "unset" | "unspecified") => {
+                        let configured = config_has(&repo_path, format!("diff.{value}.command").as_str())
+                            || config_has(&repo_path, format!("diff.{value}.textconv").as_str())
+                            || config_has(&repo_path, format!("diff.{value}.xfuncname").as_str())
+                            || config_has(&repo_path, format!("diff.{value}.binary").as_str());
+                        if !BUILTIN_DIFF_DRIVERS.contains(&value) && value != LFS_DRIVER && !configured {
+                            report(
+                                "warning",
+                                format!("Diff driver '{value}' is not configured; git falls back to a plain text diff."),
+                            );
+                        }
+                    }
+                    "merge" if !matches!(value, "set" | "unset" | "unspecified") => {
+                        let configured = config_has(&repo_path, format!("merge.{value}.driver").as_str());
+                        if !BUILTIN_MERGE_DRIVERS.contains(&value) && value != LFS_DRIVER && !configured {
+                            report(
+                                "warning",
+                                format!("Merge driver '{value}' is not configured; git falls back to a text merge."),
+                            );
+                        }
+                    }
+                    "filter" if !matches!(value, "set" | "unset" | "unspecified") => {
+                        let configured = config_has(&repo_path, format!("filter.{value}.process").as_str())
+                            || config_has(&repo_path, format!("filter.{value}.clean").as_str())
+                            || config_has(&repo_path, format!("filter.{value}.smudge").as_str());
+                        if !configured {
+                            let hint = if value == LFS_DRIVER {
+                                String::from("Git LFS is not set up; run 'git lfs install' or files stay pointer files.")
+                            } else {
+                                format!("Filter '{value}' is not configured; files are stored as they are.")
+                            };
+                            report("warning", hint);
+                        }
+                    }
+                    "working-tree-encoding" if value == "set" || value == "unset" => {
+                        report("error", String::from("working-tree-encoding needs a value, e.g. working-tree-encoding=UTF-16LE."));
+                    }
+                    _ => {}
+                }
+            }
+
+            let is_binary = entry
+                .attributes
+                .iter()
+                .any(|a| (a.name == "binary" && a.value == "set") || (a.name == "text" && a.value == "unset"));
+            if is_binary && entry.attributes.iter().any(|a| a.name == "eol" && a.value != "unspecified") {
+                report("warning", String::from("eol has no effect on files that are not treated as text."));
+            }
+        }
+    }
+    Ok(problems)
+}
@@ -0,0 +1,776 @@
+// One-file repository backups: a zip holding a git bundle of every ref and a JSON
+// manifest with what a bundle cannot carry (HEAD, remote and branch config, the stash list).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+const MANIFEST_VERSION: u32 = 1;
+const BUNDLE_ENTRY: &str = "repository.bundle";
+const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// Stash entries other than the newest are only reachable from the stash reflog, which a
+/// bundle does not carry; they are bundled under these temporary refs.
+const STASH_BACKUP_PREFIX: &str = "refs/graphoria-backup/stash-";
+
+/// Config sections copied into the manifest and restored.
+const CONFIG_PREFIXES: &[&str] = &["remote.", "branch.", "user.", "submodule.", "pull.", "push."];
+
+/// Variable names that make git run a program; a backup is not trusted to set them.
+const UNSAFE_CONFIG_NAMES: &[&str] = &["uploadpack", "receivepack", "sshcommand", "proxy", "update"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupRemote {
+    name: String,
+    url: Option<String>,
+    push_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupStash {
+    oid: String,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupManifest {
+    version: u32,
+    created_at: u64,
+    source_path: String,
+    /// Symbolic ref like `refs/heads/main`, or a commit id when HEAD was detached.
+    head: String,
+    refs: Vec<String>,
+    remotes: Vec<BackupRemote>,
+    config: Vec<(String, String)>,
+    /// Newest first, as in `git stash list`.
+    stashes: Vec<BackupStash>,
+    /// Scheduled backups only carry branches with unpushed commits; their bundle needs
+    /// the remote history to be restored.
+    #[serde(default)]
+    unpushed_only: bool,
+    /// Sorted tip ids of what was bundled, to tell whether anything changed since.
+    #[serde(default)]
+    tips: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitBackupSummary {
+    path: String,
+    head: String,
+    refs: u32,
+    remotes: u32,
+    stashes: u32,
+}
+
+fn scratch_dir(what: &str) -> Result<PathBuf, String> {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let dir = std::env::temp_dir().join(format!("graphoria-{what}-{}-{stamp}", std::process::id()));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp dir: {e}"))?;
+    Ok(dir)
+}
+
+fn read_stashes(repo_path: &str) -> Vec<BackupStash> {
+    crate::run_git(repo_path, &["stash", "list", "--format=%H%x00%gs"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.split_once('\0'))
+        .map(|(oid, message)| BackupStash {
+            oid: oid.trim().to_string(),
+            message: message.to_string(),
+        })
+        .collect()
+}
+
+fn read_manifest_config(repo_path: &str) -> Vec<(String, String)> {
+    crate::run_git_stdout_raw(repo_path, &["config", "--local", "--list", "-z"])
+        .unwrap_or_default()
+        .split('\0')
+        .filter_map(|entry| entry.split_once('\n'))
+        .filter(|(key, _)| CONFIG_PREFIXES.iter().any(|p| key.starts_with(p)))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Whether a manifest config entry may be written back on restore. Remote URLs come from
+/// the manifest's remotes instead.
+fn restorable_config_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    let name = key.rsplit('.').next().unwrap_or_default();
+    CONFIG_PREFIXES.iter().any(|p| key.starts_with(p))
+        && !key.starts_with("core.")
+        && key != "credential.helper"
+        && !UNSAFE_CONFIG_NAMES.contains(&name)
+        && !(key.starts_with("remote.") && matches!(name, "url" | "pushurl"))
+}
+
+/// Remote names and URLs that can be set without git reading them as options or running
+/// a command for the `ext::` transport.
+fn restorable_remote(remote: &BackupRemote) -> bool {
+    let name_ok = !remote.name.is_empty()
+        && !remote.name.starts_with('-')
+        && !remote.name.contains(|c: char| c.is_whitespace() || c.is_control());
+    let url_ok = |url: &Option<String>| {
+        url.as_deref().is_none_or(|u| !u.starts_with('-') && !u.to_ascii_lowercase().starts_with("ext::"))
+    };
+    name_ok && url_ok(&remote.url) && url_ok(&remote.push_url)
+}
+
+fn read_remotes(repo_path: &str) -> Vec<BackupRemote> {
+    crate::run_git(repo_path, &["remote"])
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|name| BackupRemote {
+            name: name.to_string(),
+            url: crate::run_git(repo_path, &["remote", "get-url", name]).ok(),
+            push_url: crate::run_git(repo_path, &["remote", "get-url", "--push", name]).ok(),
+        })
+        .collect()
+}
+
+fn delete_stash_backup_refs(repo_path: &str) {
+    let refs = crate::run_git(repo_path, &["for-each-ref", "--format=%(refname)", "refs/graphoria-backup/"]).unwrap_or_default();
+    for r in refs.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        let _ = crate::run_git(repo_path, &["update-ref", "-d", r]);
+    }
+}
+
+fn write_backup_zip(out_file: &Path, bundle: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let file = fs::File::create(out_file).map_err(|e| format!("Failed to create backup file: {e}"))?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().large_file(true);
+
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| format!("Failed to serialize manifest: {e}"))?;
+    zip.start_file(MANIFEST_ENTRY, options)
+        .and_then(|_| zip.write_all(json.as_bytes()).map_err(Into::into))
+        .map_err(|e| format!("Failed to write backup file: {e}"))?;
+
+    // The pack inside the bundle is already compressed.
+    let mut bundle_file = fs::File::open(bundle).map_err(|e| format!("Failed to read bundle: {e}"))?;
+    zip.start_file(BUNDLE_ENTRY, options.compression_method(zip::CompressionMethod::Stored))
+        .map_err(|e| format!("Failed to write backup file: {e}"))?;
+    std::io::copy(&mut bundle_file, &mut zip).map_err(|e| format!("Failed to write backup file: {e}"))?;
+
+    zip.finish().map_err(|e| format!("Failed to write backup file: {e}"))?;
+    Ok(())
+}
+
+/// Bundles every ref, or with `branches` only those branches (and the stash list)
+/// minus anything reachable from a remote-tracking branch.
+pub(crate) fn create_backup(repo_path: &str, out_file: &Path, branches: Option<&[String]>) -> Result<GitBackupSummary, String> {
+    let head = match crate::run_git(repo_path, &["symbolic-ref", "-q", "HEAD"]) {
+        Ok(r) if !r.trim().is_empty() => r.trim().to_string(),
+        _ => crate::run_git(repo_path, &["rev-parse", "--verify", "HEAD"])
+            .map_err(|_| String::from("Repository has no commits to back up."))?,
+    };
+    let stashes = read_stashes(repo_path);
+
+    let dir = scratch_dir("backup")?;
+    let bundle = dir.join(BUNDLE_ENTRY);
+    let result = crate::with_repo_git_lock(repo_path, || {
+        for (i, s) in stashes.iter().enumerate() {
+            let r = format!("{STASH_BACKUP_PREFIX}{i}");
+            crate::run_git(repo_path, &["update-ref", r.as_str(), s.oid.as_str()])?;
+        }
+        let bundle_s = bundle.to_string_lossy().to_string();
+        let mut args: Vec<String> = vec!["bundle".into(), "create".into(), "--quiet".into(), bundle_s];
+        match branches {
+            Some(branches) => {
+                args.extend(branches.iter().map(|b| format!("refs/heads/{b}")));
+                args.extend((0..stashes.len()).map(|i| format!("{STASH_BACKUP_PREFIX}{i}")));
+                args.extend(["--not".into(), "--remotes".into()]);
+            }
+            None => args.push("--all".into()),
+        }
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+        let out = crate::run_git(repo_path, args.as_slice());
+        delete_stash_backup_refs(repo_path);
+        out
+    })
+    .and_then(|_| {
+        let heads = crate::run_git(repo_path, &["bundle", "list-heads", bundle.to_string_lossy().as_ref()])?;
+        let heads: Vec<(&str, &str)> = heads
+            .lines()
+            .filter_map(|l| l.split_once(' ').map(|(oid, r)| (oid.trim(), r.trim())))
+            .collect();
+        let refs: Vec<String> = heads
+            .iter()
+            .filter(|(_, r)| !r.starts_with(STASH_BACKUP_PREFIX))
+            .map(|(_, r)| r.to_string())
+            .collect();
+        let mut tips: Vec<String> = heads.iter().map(|(oid, _)| oid.to_string()).collect();
+        tips.sort();
+        let manifest = BackupManifest {
+            version: MANIFEST_VERSION,
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            source_path: repo_path.to_string(),
+            head: head.clone(),
+            refs,
+            remotes: read_remotes(repo_path),
+            config: read_manifest_config(repo_path),
+            stashes,
+            unpushed_only: branches.is_some(),
+            tips,
+        };
+        write_backup_zip(out_file, &bundle, &manifest)?;
+        Ok(GitBackupSummary {
+            path: out_file.to_string_lossy().to_string(),
+            head: manifest.head,
+            refs: manifest.refs.len() as u32,
+            remotes: manifest.remotes.len() as u32,
+            stashes: manifest.stashes.len() as u32,
+        })
+    });
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+/// Writes every ref (including remote-tracking branches, tags and the whole stash list)
+/// of `repo_path` into a single backup file.
+#[tauri::command]
+pub(crate) async fn backup_repository(repo_path: String, out_file: String) -> Result<GitBackupSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        let out_file = out_file.trim().to_string();
+        if out_file.is_empty() {
+            return Err(String::from("out_file is empty"));
+        }
+        create_backup(&repo_path, Path::new(&out_file), None)
+    })
+    .await
+    .map_err(|e| format!("Failed to back up repository: {e}"))?
+}
+
+fn open_backup_zip(backup_file: &Path) -> Result<ZipArchive<fs::File>, String> {
+    let file = fs::File::open(backup_file).map_err(|e| format!("Failed to open backup file: {e}"))?;
+    ZipArchive::new(file).map_err(|_| String::from("Not a Graphoria backup file."))
+}
+
+fn read_manifest(zip: &mut ZipArchive<fs::File>) -> Result<BackupManifest, String> {
+    let mut json = String::new();
+    zip.by_name(MANIFEST_ENTRY)
+        .map_err(|_| String::from("Not a Graphoria backup file."))?
+        .read_to_string(&mut json)
+        .map_err(|e| format!("Failed to read backup manifest: {e}"))?;
+    let manifest: BackupManifest = serde_json::from_str(&json).map_err(|e| format!("Invalid backup manifest: {e}"))?;
+    if manifest.version == 0 || manifest.version > MANIFEST_VERSION {
+        return Err(format!("Unsupported backup version {}.", manifest.version));
+    }
+    Ok(manifest)
+}
+
+fn read_backup_zip(backup_file: &Path, dir: &Path) -> Result<(BackupManifest, PathBuf), String> {
+    let mut zip = open_backup_zip(backup_file)?;
+    let manifest = read_manifest(&mut zip)?;
+
+    let bundle = dir.join(BUNDLE_ENTRY);
+    let mut entry = zip.by_name(BUNDLE_ENTRY).map_err(|_| String::from("The backup has no bundle."))?;
+    let mut out = fs::File::create(&bundle).map_err(|e| format!("Failed to extract bundle: {e}"))?;
+    std::io::copy(&mut entry, &mut out).map_err(|e| format!("Failed to extract bundle: {e}"))?;
+    Ok((manifest, bundle))
+}
+
+fn restore_backup(backup_file: &Path, dest: &str) -> Result<GitBackupSummary, String> {
+    let dir = scratch_dir("restore")?;
+    let result = read_backup_zip(backup_file, &dir).and_then(|(manifest, bundle)| {
+        if manifest.unpushed_only {
+            return Err(String::from(
+                "This backup only holds unpushed work. Restore it into a clone of the repository instead.",
+            ));
+        }
+        let bundle_s = bundle.to_string_lossy().to_string();
+        crate::run_git(dest, &["init", "--quiet"])?;
+        crate::run_git(dest, &["bundle", "verify", "--quiet", bundle_s.as_str()])?;
+        crate::run_git(dest, &["fetch", "--quiet", "--update-head-ok", bundle_s.as_str(), "refs/*:refs/*"])?;
+
+        // Rebuild the stash list oldest first; the fetched refs/stash only held the newest.
+        let _ = crate::run_git(dest, &["update-ref", "-d", "refs/stash"]);
+        for s in manifest.stashes.iter().rev() {
+            crate::run_git(dest, &["stash", "store", "-m", s.message.as_str(), s.oid.as_str()])?;
+        }
+        delete_stash_backup_refs(dest);
+
+        for remote in manifest.remotes.iter().filter(|r| restorable_remote(r)) {
+            let name = remote.name.as_str();
+            if let Some(url) = remote.url.as_deref() {
+                crate::run_git(dest, &["config", format!("remote.{name}.url").as_str(), url])?;
+            }
+            if let Some(push_url) = remote.push_url.as_deref().filter(|p| Some(*p) != remote.url.as_deref()) {
+                crate::run_git(dest, &["config", format!("remote.{name}.pushurl").as_str(), push_url])?;
+            }
+        }
+        for (key, value) in manifest.config.iter().filter(|(key, _)| restorable_config_key(key)) {
+            crate::run_git(dest, &["config", "--add", key.as_str(), value.as_str()])?;
+        }
+
+        if manifest.head.starts_with("refs/") {
+            crate::run_git(dest, &["symbolic-ref", "HEAD", manifest.head.as_str()])?;
+        } else {
+            crate::run_git(dest, &["update-ref", "--no-deref", "HEAD", manifest.head.as_str()])?;
+        }
+        if crate::run_git(dest, &["rev-parse", "-q", "--verify", "HEAD"]).is_ok() {
+            crate::run_git(dest, &["reset", "--hard", "--quiet", "HEAD"])?;
+        }
+
+        Ok(GitBackupSummary {
+            path: dest.to_string(),
+            head: manifest.head,
+            refs: manifest.refs.len() as u32,
+            remotes: manifest.remotes.len() as u32,
+            stashes: manifest.stashes.len() as u32,
+        })
+    });
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+/// Recreates a repository from a `backup_repository` file in `destination_path`, which
+/// must not exist yet or be empty.
+#[tauri::command]
+pub(crate) async fn restore_repository(backup_file: String, destination_path: String) -> Result<GitBackupSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let backup_file = backup_file.trim().to_string();
+        let destination_path = destination_path.trim().to_string();
+        if backup_file.is_empty() {
+            return Err(String::from("backup_file is empty"));
+        }
+        if destination_path.is_empty() {
+            return Err(String::from("destination_path is empty"));
+        }
+
+        let dest = Path::new(&destination_path);
+        let existed = dest.exists();
+        if existed && fs::read_dir(dest).map(|mut d| d.next().is_some()).unwrap_or(true) {
+            return Err(String::from("Destination folder is not empty."));
+        }
+        fs::create_dir_all(dest).map_err(|e| format!("Failed to create destination folder: {e}"))?;
+
+        let result = restore_backup(Path::new(&backup_file), &destination_path);
+        if result.is_err() {
+            // Leave nothing half-restored behind.
+            if existed {
+                if let Ok(entries) = fs::read_dir(dest) {
+                    for e in entries.flatten() {
+                        let p = e.path();
+                        let _ = if p.is_dir() { fs::remove_dir_all(&p) } else { fs::remove_file(&p) };
+                    }
+                }
+            } else {
+                let _ = fs::remove_dir_all(dest);
+            }
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("Failed to restore repository: {e}"))?
+}
+
+// Scheduled backups: while the app runs, repositories that opted in get their unpushed
+// branches and stashes bundled into a directory of their choice every so often, so work
+// that never reached a remote survives a dead disk.
+
+const SCHEDULE_FILE: &str = "graphoria-backup-schedule.json";
+const DEFAULT_INTERVAL_MINUTES: u32 = 60;
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct BackupSchedule {
+    enabled: bool,
+    directory: String,
+    interval_minutes: u32,
+    /// Number of backups to keep, 0 for no limit.
+    keep_last: u32,
+    /// Backups older than this are deleted, 0 for no limit. The newest one is always kept.
+    max_age_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ScheduledBackupInfo {
+    path: String,
+    created_at: u64,
+    size: u64,
+    branches: Vec<String>,
+    stashes: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitScheduledRestoreResult {
+    /// Local branch names the backed up branches were restored to.
+    branches: Vec<String>,
+    stashes: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScheduledBackupEvent {
+    repo_path: String,
+    path: Option<String>,
+    error: Option<String>,
+}
+
+/// Repositories the scheduler looks at, added when they are opened or their schedule is
+/// read or written, mapped to when a backup was last attempted.
+static SCHEDULED_REPOS: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+
+static SCHEDULER_STARTED: OnceLock<()> = OnceLock::new();
+
+fn scheduled_repos() -> &'static Mutex<HashMap<String, u64>> {
+    SCHEDULED_REPOS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn schedule_path(repo_path: &str) -> Option<PathBuf> {
+    Some(super::handles::git_common_dir(repo_path)?.join(SCHEDULE_FILE))
+}
+
+fn load_schedule(repo_path: &str) -> BackupSchedule {
+    schedule_path(repo_path)
+        .and_then(|p| fs::read_to_string(&p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn register_scheduled_repo(repo_path: &str) {
+    if let Ok(mut repos) = scheduled_repos().lock() {
+        repos.entry(crate::normalize_repo_path(repo_path)).or_insert(0);
+    }
+}
+
+pub(crate) fn unregister_scheduled_repo(repo_path: &str) {
+    if let Ok(mut repos) = scheduled_repos().lock() {
+        repos.remove(&crate::normalize_repo_path(repo_path));
+    }
+}
+
+/// File name prefix of a repository's scheduled backups: the folder name plus a stable
+/// hash of the path, so repositories sharing a backup directory do not mix.
+fn backup_file_prefix(repo_path: &str) -> String {
+    let repo = crate::normalize_repo_path(repo_path);
+    let name: String = Path::new(&repo)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    // FNV-1a, which unlike the std hasher is guaranteed to stay the same across builds.
+    let hash = repo
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+    format!("{name}-{:08x}-unpushed-", hash as u32)
+}
+
+/// Scheduled backups of `repo_path` in the configured directory, newest first.
+fn scheduled_backup_files(repo_path: &str, directory: &str) -> Vec<(PathBuf, u64)> {
+    let prefix = backup_file_prefix(repo_path);
+    let mut files: Vec<(PathBuf, u64)> = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    let secs = name.strip_prefix(prefix.as_str())?.strip_suffix(".zip")?.parse::<u64>().ok()?;
+                    Some((e.path(), secs))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by_key(|(_, secs)| std::cmp::Reverse(*secs));
+    files
+}
+
+/// Local branches with commits that are on no remote-tracking branch.
+fn unpushed_branches(repo_path: &str) -> Vec<String> {
+    crate::run_git(repo_path, &["for-each-ref", "--format=%(refname:short)", "refs/heads/"])
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim())
+        .filter(|b| !b.is_empty())
+        .filter(|b| {
+            let r = format!("refs/heads/{b}");
+            crate::run_git(repo_path, &["rev-list", "--count", r.as_str(), "--not", "--remotes"])
+                .is_ok_and(|n| n.trim() != "0")
+        })
+        .map(|b| b.to_string())
+        .collect()
+}
+
+/// Deletes backups beyond `keep_last` or older than `max_age_days`, never the newest.
+fn apply_retention(repo_path: &str, schedule: &BackupSchedule) {
+    let now = now_secs();
+    let max_age = u64::from(schedule.max_age_days) * 24 * 60 * 60;
+    for (i, (path, secs)) in scheduled_backup_files(repo_path, &schedule.directory).into_iter().enumerate() {
+        if i == 0 {
+            continue;
+        }
+        let too_many = schedule.keep_last > 0 && i >= schedule.keep_last as usize;
+        let too_old = max_age > 0 && now.saturating_sub(secs) > max_age;
+        if too_many || too_old {
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
+
+/// Writes a backup of the unpushed work of `repo_path`. Returns `None` when there is
+/// nothing unpushed, or nothing changed since the newest backup.
+fn run_scheduled_backup(repo_path: &str, schedule: &BackupSchedule) -> Result<Option<GitBackupSummary>, String> {
+    if schedule.directory.trim().is_empty() {
+        return Err(String::from("Backup directory is not set."));
+    }
+    fs::create_dir_all(&schedule.directory).map_err(|e| format!("Failed to create backup directory: {e}"))?;
+
+    let branches = unpushed_branches(repo_path);
+    let stashes = read_stashes(repo_path);
+    if branches.is_empty() && stashes.is_empty() {
+        return Ok(None);
+    }
+
+    let mut tips: Vec<String> = branches
+        .iter()
+        .filter_map(|b| crate::run_git(repo_path, &["rev-parse", format!("refs/heads/{b}").as_str()]).ok())
+        .chain(stashes.iter().map(|s| s.oid.clone()))
+        .collect();
+    tips.sort();
+    let newest = scheduled_backup_files(repo_path, &schedule.directory).into_iter().next();
+    if newest.is_some_and(|(p, _)| open_backup_zip(&p).and_then(|mut z| read_manifest(&mut z)).is_ok_and(|m| m.tips == tips)) {
+        return Ok(None);
+    }
+
+    let out_file = Path::new(&schedule.directory).join(format!("{}{}.zip", backup_file_prefix(repo_path), now_secs()));
+    let summary = create_backup(repo_path, &out_file, Some(branches.as_slice()));
+    if summary.is_err() {
+        let _ = fs::remove_file(&out_file);
+    }
+    let summary = summary?;
+    apply_retention(repo_path, schedule);
+    Ok(Some(summary))
+}
+
+/// Starts the thread that runs due scheduled backups. Results are reported through
+/// `scheduled_backup` events.
+pub(crate) fn start_scheduler(app: tauri::AppHandle) {
+    if SCHEDULER_STARTED.set(()).is_err() {
+        return;
+    }
+    std::thread::spawn(move || loop {
+        std::thread::sleep(SCHEDULER_TICK);
+        let repos: Vec<(String, u64)> = match scheduled_repos().lock() {
+            Ok(repos) => repos.iter().map(|(r, t)| (r.clone(), *t)).collect(),
+            Err(_) => continue,
+        };
+        for (repo_path, last_attempt) in repos {
+            if crate::ensure_is_git_worktree(&repo_path).is_err() {
+                continue;
+            }
+            let schedule = load_schedule(&repo_path);
+            if !schedule.enabled {
+                continue;
+            }
+            let interval = u64::from(match schedule.interval_minutes {
+                0 => DEFAULT_INTERVAL_MINUTES,
+                m => m,
+            }) * 60;
+            let last_backup = scheduled_backup_files(&repo_path, &schedule.directory)
+                .first()
+                .map(|(_, secs)| *secs)
+                .unwrap_or(0);
+            let now = now_secs();
+            if now.saturating_sub(last_attempt.max(last_backup)) < interval {
+                continue;
+            }
+            if let Ok(mut repos) = scheduled_repos().lock() {
+                repos.insert(repo_path.clone(), now);
+            }
+
+            let (path, error) = match run_scheduled_backup(&repo_path, &schedule) {
+                Ok(None) => continue,
+                Ok(Some(summary)) => (Some(summary.path), None),
+                Err(e) => (None, Some(e)),
+            };
+            let _ = app.emit("scheduled_backup", ScheduledBackupEvent { repo_path, path, error });
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn get_backup_schedule(repo_path: String) -> Result<BackupSchedule, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    register_scheduled_repo(&repo_path);
+    Ok(load_schedule(&repo_path))
+}
+
+#[tauri::command]
+pub(crate) fn set_backup_schedule(repo_path: String, schedule: BackupSchedule) -> Result<BackupSchedule, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let schedule = BackupSchedule {
+        directory: schedule.directory.trim().to_string(),
+        interval_minutes: match schedule.interval_minutes {
+            0 => DEFAULT_INTERVAL_MINUTES,
+            m => m,
+        },
+        ..schedule
+    };
+    if schedule.enabled {
+        if schedule.directory.is_empty() {
+            return Err(String::from("directory is empty"));
+        }
+        if !Path::new(&schedule.directory).is_absolute() {
+            return Err(String::from("Backup directory must be an absolute path."));
+        }
+        fs::create_dir_all(&schedule.directory).map_err(|e| format!("Failed to create backup directory: {e}"))?;
+    }
+
+    let path = schedule_path(&repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    let json = serde_json::to_string_pretty(&schedule).map_err(|e| format!("Failed to serialize backup schedule: {e}"))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write backup schedule: {e}"))?;
+    register_scheduled_repo(&repo_path);
+    Ok(schedule)
+}
+
+/// Runs the scheduled backup right away. `None` means there was nothing new to back up.
+#[tauri::command]
+pub(crate) async fn run_scheduled_backup_now(repo_path: String) -> Result<Option<GitBackupSummary>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        let schedule = load_schedule(&repo_path);
+        let result = run_scheduled_backup(&repo_path, &schedule)?;
+        if let Ok(mut repos) = scheduled_repos().lock() {
+            repos.insert(crate::normalize_repo_path(&repo_path), now_secs());
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Failed to back up repository: {e}"))?
+}
+
+/// Scheduled backups of `repo_path`, newest first.
+#[tauri::command]
+pub(crate) fn list_scheduled_backups(repo_path: String) -> Result<Vec<ScheduledBackupInfo>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    let schedule = load_schedule(&repo_path);
+    if schedule.directory.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(scheduled_backup_files(&repo_path, &schedule.directory)
+        .into_iter()
+        .filter_map(|(path, _)| {
+            let manifest = open_backup_zip(&path).and_then(|mut z| read_manifest(&mut z)).ok()?;
+            Some(ScheduledBackupInfo {
+                path: path.to_string_lossy().to_string(),
+                created_at: manifest.created_at,
+                size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+                branches: manifest
+                    .refs
+                    .iter()
+                    .filter_map(|r| r.strip_prefix("refs/heads/"))
+                    .map(|b| b.to_string())
+                    .collect(),
+                stashes: manifest.stashes.len() as u32,
+            })
+        })
+        .collect())
+}
+
+fn restore_unpushed(repo_path: &str, backup_file: &Path) -> Result<GitScheduledRestoreResult, String> {
+    let dir = scratch_dir("restore")?;
+    let result = read_backup_zip(backup_file, &dir).and_then(|(manifest, bundle)| {
+        let bundle_s = bundle.to_string_lossy().to_string();
+        crate::run_git(repo_path, &["bundle", "verify", "--quiet", bundle_s.as_str()]).map_err(|_| {
+            String::from("The repository lacks commits this backup builds on. Fetch from the remote first.")
+        })?;
+
+        crate::with_repo_git_lock(repo_path, || {
+            crate::run_git(
+                repo_path,
+                &["fetch", "--quiet", "--no-tags", bundle_s.as_str(), "refs/*:refs/graphoria-restore/*"],
+            )?;
+
+            // Missing branches come back under their name; a branch that moved on since
+            // gets the backed up tip next to it instead of being overwritten.
+            let mut branches: Vec<String> = Vec::new();
+            for r in manifest.refs.iter() {
+                let Some(name) = r.strip_prefix("refs/heads/") else {
+                    continue;
+                };
+                let restored = format!("refs/graphoria-restore/heads/{name}");
+                let tip = crate::run_git(repo_path, &["rev-parse", restored.as_str()])?;
+                let aside = format!("{name}-backup-{}", manifest.created_at);
+                let mut target: Option<&str> = None;
+                for candidate in [name, aside.as_str()] {
+                    let full = format!("refs/heads/{candidate}");
+                    match crate::run_git(repo_path, &["rev-parse", "-q", "--verify", full.as_str()]) {
+                        Ok(current) if current.trim() == tip.trim() => break,
+                        Ok(_) => continue,
+                        Err(_) => {
+                            target = Some(candidate);
+                            break;
+                        }
+                    }
+                }
+                let Some(target) = target.map(|t| t.to_string()) else {
+                    continue;
+                };
+                crate::run_git(repo_path, &["branch", target.as_str(), tip.trim()])?;
+                branches.push(target);
+            }
+
+            let existing: Vec<String> = read_stashes(repo_path).into_iter().map(|s| s.oid).collect();
+            let mut stashes = 0u32;
+            for s in manifest.stashes.iter().rev() {
+                if !existing.contains(&s.oid) {
+                    crate::run_git(repo_path, &["stash", "store", "-m", s.message.as_str(), s.oid.as_str()])?;
+                    stashes += 1;
+                }
+            }
+            Ok(GitScheduledRestoreResult { branches, stashes })
+        })
+    });
+
+    let refs = crate::run_git(repo_path, &["for-each-ref", "--format=%(refname)", "refs/graphoria-restore/"]).unwrap_or_default();
+    for r in refs.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        let _ = crate::run_git(repo_path, &["update-ref", "-d", r]);
+    }
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+/// Brings the branches and stashes of a scheduled backup back into `repo_path`, typically
+/// a fresh clone after the original was lost.
+#[tauri::command]
+pub(crate) async fn restore_scheduled_backup(
+    repo_path: String,
+    backup_file: String,
+) -> Result<GitScheduledRestoreResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+        let backup_file = backup_file.trim().to_string();
+        if backup_file.is_empty() {
+            return Err(String::from("backup_file is empty"));
+        }
+        restore_unpushed(&repo_path, Path::new(&backup_file))
+    })
+    .await
+    .map_err(|e| format!("Failed to restore backup: {e}"))?
+}
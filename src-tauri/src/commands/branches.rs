@@ -1,6 +1,47 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSwitchPrediction {
+    target: String,
+    can_switch: bool,
+    has_local_changes: bool,
+    overwritten_files: Vec<String>,
+    untracked_overwritten_files: Vec<String>,
+    submodule_changes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRemoteCheckoutResult {
+    /// The local branch that was checked out.
+    branch: String,
+    /// False when an existing branch already tracking the remote branch was reused.
+    created: bool,
+    #[serde(flatten)]
+    checkout: crate::PullResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitStaleBranch {
+    name: String,
+    target: String,
+    /// Unix time of the tip's committer date.
+    committer_time: i64,
+    age_days: u32,
+    last_author: String,
+    upstream: Option<String>,
+    upstream_gone: bool,
+    /// Commits on the branch that HEAD does not have; 0 when it is fully merged.
+    ahead: u32,
+    behind: u32,
+}
+
 #[tauri::command]
 pub(crate) fn git_checkout_commit(repo_path: String, commit: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let commit = commit.trim().to_string();
     if commit.is_empty() {
@@ -10,16 +51,161 @@ pub(crate) fn git_checkout_commit(repo_path: String, commit: String) -> Result<S
     crate::run_git(&repo_path, &["checkout", commit.as_str()])
 }
 
+/// Pops the autostash with `--index` so staged changes stay staged, falling back to a
+/// plain pop when git cannot restore the index (the working tree is untouched then).
+fn pop_autostash(repo_path: &str) -> Result<(bool, String, String), String> {
+    let popped = crate::run_git_status(repo_path, &["stash", "pop", "--index"])?;
+    if popped.0 || !crate::list_unmerged_files(repo_path).is_empty() {
+        return Ok(popped);
+    }
+    crate::run_git_status(repo_path, &["stash", "pop"])
+}
+
+/// Runs a checkout/switch. With `autostash`, local changes are stashed first and
+/// re-applied afterwards; a conflicting re-apply is reported as "conflicts" and the
+/// stash is kept so nothing is lost.
+fn checkout_with_autostash(repo_path: &str, args: &[&str], autostash: bool) -> Result<crate::PullResult, String> {
+    crate::with_repo_git_lock(repo_path, || {
+        let dirty = !crate::run_git(repo_path, &["status", "--porcelain", "--untracked-files=no"])?
+            .trim()
+            .is_empty();
+
+        if !autostash || !dirty {
+            let message = crate::run_git(repo_path, args)?;
+            return Ok(crate::PullResult {
+                status: String::from("ok"),
+                operation: String::from("checkout"),
+                message,
+                conflict_files: Vec::new(),
+            });
+        }
+
+        let (ok, stdout, stderr) = crate::run_git_status(
+            repo_path,
+            &["stash", "push", "-m", "Graphoria autostash before checkout"],
+        )?;
+        if !ok {
+            return Err(format!("git stash failed: {}", if !stderr.is_empty() { stderr } else { stdout }));
+        }
+
+        let (ok, stdout, stderr) = crate::run_git_status(repo_path, args)?;
+        if !ok {
+            // Checkout failed: put the changes back where they were.
+            let _ = pop_autostash(repo_path);
+            return Err(if !stderr.is_empty() { stderr } else { stdout });
+        }
+        let checkout_message = if !stdout.is_empty() { stdout } else { stderr };
+
+        let (ok, stdout, stderr) = pop_autostash(repo_path)?;
+        if ok {
+            return Ok(crate::PullResult {
+                status: String::from("ok"),
+                operation: String::from("checkout"),
+                message: checkout_message,
+                conflict_files: Vec::new(),
+            });
+        }
+
+        let pop_message = if !stderr.is_empty() { stderr } else { stdout };
+        let mut conflict_files = crate::list_unmerged_files(repo_path);
+        if conflict_files.is_empty() {
+            conflict_files = crate::parse_conflict_files(pop_message.as_str());
+        }
+
+        if !conflict_files.is_empty() {
+            return Ok(crate::PullResult {
+                status: String::from("conflicts"),
+                operation: String::from("checkout"),
+                message: format!(
+                    "Checked out, but re-applying local changes conflicted. The changes are kept in stash@{{0}}.\n{pop_message}"
+                ),
+                conflict_files,
+            });
+        }
+
+        Err(format!(
+            "Checked out, but re-applying local changes failed. The changes are kept in stash@{{0}}.\n{pop_message}"
+        ))
+    })
+}
+
 #[tauri::command]
-pub(crate) fn git_checkout_branch(repo_path: String, branch: String) -> Result<String, String> {
+pub(crate) fn git_checkout_branch(
+    repo_path: String,
+    branch: String,
+    force: Option<bool>,
+) -> Result<crate::PullResult, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let branch = branch.trim().to_string();
     if branch.is_empty() {
         return Err(String::from("branch is empty"));
     }
 
-    crate::run_git(&repo_path, &["checkout", branch.as_str()])
+    checkout_with_autostash(&repo_path, &["checkout", branch.as_str()], force.unwrap_or(false))
+}
+
+/// Time and action of a reflog line, `<old> <new> <name> <<email>> <time> <tz>\t<message>`.
+fn parse_reflog_line(line: &str) -> Option<(i64, &str)> {
+    let (who, message) = line.split_once('\t')?;
+    let mut fields = who.rsplitn(3, ' ');
+    let _tz = fields.next()?;
+    let time = fields.next()?.parse::<i64>().ok()?;
+    Some((time, message.trim()))
+}
+
+/// Last reflog activity per local branch, read from the reflog files: the branch's own
+/// latest entry, or a later checkout of it from HEAD's reflog. Branches without a reflog
+/// (e.g. with `core.logAllRefUpdates` off) are left out.
+fn local_branch_activity(repo_path: &str) -> HashMap<String, (i64, String)> {
+    let mut out: HashMap<String, (i64, String)> = HashMap::new();
+
+    if let Some(common_dir) = super::handles::git_common_dir(repo_path) {
+        let root = common_dir.join("logs").join("refs").join("heads");
+        let mut dirs: Vec<PathBuf> = vec![root.clone()];
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                let Ok(rel) = path.strip_prefix(&root) else {
+                    continue;
+                };
+                let name = rel.to_string_lossy().replace('\\', "/");
+                let Ok(raw) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                if let Some((time, message)) = raw.lines().rev().find_map(parse_reflog_line) {
+                    let action = message.split_once(':').map(|(a, _)| a).unwrap_or(message).trim();
+                    out.insert(name, (time, action.to_string()));
+                }
+            }
+        }
+    }
+
+    let head_log = super::handles::git_dir(repo_path).map(|d| d.join("logs").join("HEAD"));
+    if let Some(raw) = head_log.and_then(|p| fs::read_to_string(p).ok()) {
+        for (time, message) in raw.lines().filter_map(parse_reflog_line) {
+            let Some(moves) = message.strip_prefix("checkout: moving from ") else {
+                continue;
+            };
+            let Some((_, to)) = moves.rsplit_once(" to ") else {
+                continue;
+            };
+            match out.get_mut(to.trim()) {
+                Some(entry) if time > entry.0 => *entry = (time, String::from("checkout")),
+                _ => {}
+            }
+        }
+    }
+
+    out
 }
 
 #[tauri::command]
@@ -29,10 +215,32 @@ pub(crate) fn git_list_branches(
 ) -> Result<Vec<crate::GitBranchInfo>, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
-    let format = "%(refname:short)\x1f%(objectname)\x1f%(committerdate:iso-strict)";
+    let format = concat!(
+        "%(refname:short)\x1f%(objectname)\x1f%(committerdate:iso-strict)",
+        "\x1f%(upstream:short)\x1f%(upstream:track)",
+        "\x1f%(subject)\x1f%(authorname)\x1f%(authordate:iso-strict)\x1f%(worktreepath)",
+    );
     let local_raw = crate::run_git(&repo_path, &["for-each-ref", "--format", format, "refs/heads"])?;
     let mut out = crate::parse_for_each_ref(local_raw.as_str(), "local");
 
+    // `%(worktreepath)` also names this worktree for the current branch.
+    let canonical = |p: &str| {
+        std::fs::canonicalize(p)
+            .map(|c| crate::normalize_repo_path(&c.to_string_lossy()))
+            .unwrap_or_else(|_| crate::normalize_repo_path(p))
+    };
+    let this_worktree = canonical(&repo_path);
+    let activity = local_branch_activity(&repo_path);
+    for b in out.iter_mut() {
+        if b.checked_out_in.as_deref().is_some_and(|w| canonical(w) == this_worktree) {
+            b.checked_out_in = None;
+        }
+        if let Some((time, action)) = activity.get(&b.name) {
+            b.last_activity = Some(*time);
+            b.last_activity_action = Some(action.clone());
+        }
+    }
+
     if include_remote.unwrap_or(true) {
         let remote_raw = crate::run_git(&repo_path, &["for-each-ref", "--format", format, "refs/remotes"])?;
         out.extend(crate::parse_for_each_ref(remote_raw.as_str(), "remote"));
@@ -41,6 +249,63 @@ pub(crate) fn git_list_branches(
     Ok(out)
 }
 
+/// Local branches whose tip was committed more than `older_than_days` ago, oldest first,
+/// with ahead/behind counted against HEAD. Branches checked out in a worktree are left out
+/// since they cannot be deleted.
+#[tauri::command]
+pub(crate) fn git_stale_branches(repo_path: String, older_than_days: u32) -> Result<Vec<GitStaleBranch>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    if crate::resolve_commit(&repo_path, "HEAD").is_none() {
+        return Ok(Vec::new());
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let cutoff = now - i64::from(older_than_days) * 86_400;
+
+    let format = concat!(
+        "%(refname:short)\x1f%(objectname)\x1f%(committerdate:unix)\x1f%(authorname)",
+        "\x1f%(upstream:short)\x1f%(upstream:track)\x1f%(worktreepath)",
+    );
+    let raw = crate::run_git(&repo_path, &["for-each-ref", "--format", format, "refs/heads"])?;
+
+    let mut out: Vec<GitStaleBranch> = Vec::new();
+    for line in raw.lines() {
+        let parts: Vec<&str> = line.split('\x1f').map(|p| p.trim()).collect();
+        let [name, target, time, author, upstream, track, worktree] = parts[..] else {
+            continue;
+        };
+        let committer_time = time.parse::<i64>().unwrap_or(0);
+        if name.is_empty() || !worktree.is_empty() || committer_time > cutoff {
+            continue;
+        }
+
+        let range = format!("HEAD...{target}");
+        let counts = crate::run_git(&repo_path, &["rev-list", "--left-right", "--count", range.as_str()]).unwrap_or_default();
+        let mut counts = counts.split_whitespace().map(|c| c.parse::<u32>().unwrap_or(0));
+        let behind = counts.next().unwrap_or(0);
+        let ahead = counts.next().unwrap_or(0);
+
+        out.push(GitStaleBranch {
+            name: name.to_string(),
+            target: target.to_string(),
+            committer_time,
+            age_days: ((now - committer_time).max(0) / 86_400) as u32,
+            last_author: author.to_string(),
+            upstream: Some(upstream.to_string()).filter(|u| !u.is_empty()),
+            upstream_gone: !upstream.is_empty() && track == "[gone]",
+            ahead,
+            behind,
+        });
+    }
+
+    out.sort_by_key(|b| b.committer_time);
+    Ok(out)
+}
+
 #[tauri::command]
 pub(crate) fn git_switch(
     repo_path: String,
@@ -49,8 +314,10 @@ pub(crate) fn git_switch(
     force: Option<bool>,
     start_point: Option<String>,
     track: Option<bool>,
-) -> Result<String, String> {
+    autostash: Option<bool>,
+) -> Result<crate::PullResult, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let branch = branch.trim().to_string();
     if branch.is_empty() {
@@ -58,7 +325,9 @@ pub(crate) fn git_switch(
     }
 
     let create = create.unwrap_or(false);
+    // `force` resets an existing branch on create (-C); `autostash` carries local changes over.
     let force = force.unwrap_or(false);
+    let autostash = autostash.unwrap_or(false);
     let track = track.unwrap_or(false);
     let start_point = start_point.unwrap_or_default().trim().to_string();
 
@@ -73,19 +342,270 @@ pub(crate) fn git_switch(
         if !start_point.is_empty() {
             args.push(start_point.as_str());
         }
-        return crate::run_git(&repo_path, args.as_slice());
+        return checkout_with_autostash(&repo_path, args.as_slice(), autostash);
     }
 
-    crate::run_git(&repo_path, &["switch", branch.as_str()])
+    checkout_with_autostash(&repo_path, &["switch", branch.as_str()], autostash)
+}
+
+/// Checks out `tag`, detached or, with `create_branch`, on a new branch at the tag that
+/// tracks nothing. Tracked local changes refuse the checkout unless `autostash` is set,
+/// in which case they are stashed and re-applied as for a branch checkout.
+#[tauri::command]
+pub(crate) fn git_checkout_tag(
+    repo_path: String,
+    tag: String,
+    create_branch: Option<String>,
+    autostash: Option<bool>,
+) -> Result<crate::PullResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let tag = tag.trim().trim_start_matches("refs/tags/").to_string();
+    if tag.is_empty() {
+        return Err(String::from("tag is empty"));
+    }
+    let tag_commit = format!("refs/tags/{tag}^{{commit}}");
+    let commit = crate::run_git(&repo_path, &["rev-parse", "--verify", "--quiet", tag_commit.as_str()])
+        .map_err(|_| format!("Tag '{tag}' does not exist or does not point to a commit."))?;
+
+    let branch = create_branch.unwrap_or_default().trim().to_string();
+    if !branch.is_empty() {
+        let (ok, _, _) = crate::run_git_status(&repo_path, &["check-ref-format", "--branch", branch.as_str()])?;
+        if !ok {
+            return Err(format!("'{branch}' is not a valid branch name."));
+        }
+        if crate::run_git(&repo_path, &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{branch}")]).is_ok() {
+            return Err(format!("Branch '{branch}' already exists."));
+        }
+    }
+
+    let autostash = autostash.unwrap_or(false);
+    if !autostash {
+        let dirty = !crate::run_git(&repo_path, &["status", "--porcelain", "--untracked-files=no"])?
+            .trim()
+            .is_empty();
+        if dirty {
+            return Err(String::from(
+                "You have local changes. Commit or stash them first, or check out with autostash.",
+            ));
+        }
+    }
+
+    let undo_label = format!("checkout tag {tag}");
+    let _undo = super::undo::record_undo_point(&repo_path, undo_label.as_str(), &[]);
+
+    if branch.is_empty() {
+        checkout_with_autostash(&repo_path, &["switch", "--detach", commit.as_str()], autostash)
+    } else {
+        checkout_with_autostash(
+            &repo_path,
+            &["switch", "--no-track", "-c", branch.as_str(), commit.as_str()],
+            autostash,
+        )
+    }
+}
+
+fn local_branch_exists(repo_path: &str, branch: &str) -> bool {
+    let full_ref = format!("refs/heads/{branch}");
+    crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", full_ref.as_str()]).is_ok()
 }
 
+/// Checks out `remote_branch` (e.g. "origin/feature") on a local branch that tracks it.
+/// A local branch already tracking it is reused; otherwise one named after the remote
+/// branch is created, or `local_name` when given. When that name belongs to an unrelated
+/// branch, `<name>-<remote>` is used instead.
 #[tauri::command]
-pub(crate) fn git_rename_branch(
+pub(crate) fn git_checkout_remote_branch(
+    repo_path: String,
+    remote_branch: String,
+    local_name: Option<String>,
+    force: Option<bool>,
+) -> Result<GitRemoteCheckoutResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let remote_branch = remote_branch.trim().trim_start_matches("refs/remotes/").to_string();
+    if remote_branch.is_empty() {
+        return Err(String::from("remote_branch is empty"));
+    }
+    let remote_ref = format!("refs/remotes/{remote_branch}");
+    if crate::resolve_commit(&repo_path, remote_ref.as_str()).is_none() {
+        return Err(format!("Remote branch '{remote_branch}' does not exist."));
+    }
+
+    // Remote names may contain slashes, so take the longest remote the name starts with.
+    let remote = crate::run_git(&repo_path, &["remote"])?
+        .lines()
+        .map(|r| r.trim().to_string())
+        .filter(|r| remote_branch.starts_with(format!("{r}/").as_str()))
+        .max_by_key(|r| r.len())
+        .ok_or_else(|| format!("'{remote_branch}' does not belong to a configured remote."))?;
+    let name = remote_branch[remote.len() + 1..].to_string();
+    let force = force.unwrap_or(false);
+
+    let existing = crate::run_git(&repo_path, &["for-each-ref", "--format=%(refname:short)\x1f%(upstream)", "refs/heads"])?
+        .lines()
+        .filter_map(|l| l.split_once('\x1f'))
+        .filter(|(_, upstream)| upstream.trim() == remote_ref)
+        .map(|(branch, _)| branch.trim().to_string())
+        .collect::<Vec<String>>();
+
+    let requested = local_name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let reuse = match &requested {
+        Some(n) => existing.iter().find(|b| *b == n),
+        None => existing.iter().find(|b| **b == name).or_else(|| existing.first()),
+    };
+    if let Some(branch) = reuse.cloned() {
+        let checkout = checkout_with_autostash(&repo_path, &["checkout", branch.as_str()], force)?;
+        return Ok(GitRemoteCheckoutResult {
+            branch,
+            created: false,
+            checkout,
+        });
+    }
+
+    let branch = match requested {
+        Some(n) => {
+            if local_branch_exists(&repo_path, &n) {
+                return Err(format!("Branch '{n}' already exists."));
+            }
+            n
+        }
+        None => {
+            let mut candidate = name.clone();
+            let mut n = 1;
+            while local_branch_exists(&repo_path, &candidate) {
+                candidate = if n == 1 { format!("{name}-{remote}") } else { format!("{name}-{remote}-{n}") };
+                n += 1;
+            }
+            candidate
+        }
+    };
+    let (ok, _, _) = crate::run_git_status(&repo_path, &["check-ref-format", "--branch", branch.as_str()])?;
+    if !ok {
+        return Err(format!("'{branch}' is not a valid branch name."));
+    }
+
+    let checkout = checkout_with_autostash(
+        &repo_path,
+        &["checkout", "--track", "-b", branch.as_str(), remote_branch.as_str()],
+        force,
+    )?;
+    Ok(GitRemoteCheckoutResult {
+        branch,
+        created: true,
+        checkout,
+    })
+}
+
+/// Predicts whether switching to `branch` would succeed without touching the
+/// working tree: tracked local changes to files that differ between HEAD and the
+/// target block the switch, as do untracked files the target would create.
+#[tauri::command]
+pub(crate) fn git_switch_predict(repo_path: String, branch: String) -> Result<GitSwitchPrediction, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let branch = branch.trim().to_string();
+    if branch.is_empty() {
+        return Err(String::from("branch is empty"));
+    }
+
+    let target_spec = format!("{branch}^{{commit}}");
+    let target = crate::run_git(&repo_path, &["rev-parse", "--verify", target_spec.as_str()])
+        .map_err(|_| format!("Branch not found: {branch}"))?;
+
+    let has_head = crate::run_git(&repo_path, &["rev-parse", "--verify", "HEAD"]).is_ok();
+
+    // Files (and submodule pointers) that differ between HEAD and the target.
+    let mut changed: Vec<String> = Vec::new();
+    let mut submodule_changes: Vec<String> = Vec::new();
+    if has_head {
+        let raw = crate::run_git_stdout_raw(&repo_path, &["diff", "--raw", "-z", "--no-renames", "HEAD", target.as_str()])?;
+        let mut parts = raw.split('\0');
+        while let Some(meta) = parts.next() {
+            if meta.is_empty() {
+                continue;
+            }
+            let path = match parts.next() {
+                Some(p) if !p.is_empty() => p.to_string(),
+                _ => break,
+            };
+            // Format: :<old mode> <new mode> <old sha> <new sha> <status>
+            let modes: Vec<&str> = meta.trim_start_matches(':').split_whitespace().collect();
+            if modes.iter().take(2).any(|m| *m == "160000") {
+                submodule_changes.push(path.clone());
+            }
+            changed.push(path);
+        }
+    } else {
+        let raw = crate::run_git_stdout_raw(&repo_path, &["ls-tree", "-r", "-z", "--name-only", target.as_str()])?;
+        changed.extend(raw.split('\0').filter(|p| !p.is_empty()).map(|p| p.to_string()));
+    }
+
+    let status_raw = crate::run_git_stdout_raw(&repo_path, &["status", "--porcelain", "-z", "--untracked-files=all"])?;
+    let mut local_tracked: Vec<String> = Vec::new();
+    let mut local_untracked: Vec<String> = Vec::new();
+    let mut records = status_raw.split('\0');
+    while let Some(rec) = records.next() {
+        if rec.len() < 4 {
+            continue;
+        }
+        let code = &rec[0..2];
+        let path = rec[3..].to_string();
+        if code == "??" {
+            local_untracked.push(path);
+        } else {
+            if code.contains('R') || code.contains('C') {
+                // Renames/copies carry the original path as the next record.
+                if let Some(orig) = records.next() {
+                    local_tracked.push(orig.to_string());
+                }
+            }
+            local_tracked.push(path);
+        }
+    }
+
+    let overwritten_files: Vec<String> = local_tracked.iter().filter(|p| changed.contains(p)).cloned().collect();
+    let untracked_overwritten_files: Vec<String> =
+        local_untracked.iter().filter(|p| changed.contains(p)).cloned().collect();
+
+    Ok(GitSwitchPrediction {
+        target,
+        can_switch: overwritten_files.is_empty() && untracked_overwritten_files.is_empty(),
+        has_local_changes: !local_tracked.is_empty(),
+        overwritten_files,
+        untracked_overwritten_files,
+        submodule_changes,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn git_rename_branch(
     repo_path: String,
     old_name: String,
     new_name: String,
+    rename_on_remote: Option<bool>,
+    remote_name: Option<String>,
+    allow_protected: Option<bool>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        git_rename_branch_impl(repo_path, old_name, new_name, rename_on_remote, remote_name, allow_protected)
+    })
+    .await
+    .map_err(|e| format!("Failed to rename branch: {e}"))?
+}
+
+fn git_rename_branch_impl(
+    repo_path: String,
+    old_name: String,
+    new_name: String,
+    rename_on_remote: Option<bool>,
+    remote_name: Option<String>,
+    allow_protected: Option<bool>,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let old_name = old_name.trim().to_string();
     let new_name = new_name.trim().to_string();
@@ -96,7 +616,137 @@ pub(crate) fn git_rename_branch(
         return Err(String::from("new_name is empty"));
     }
 
-    crate::run_git(&repo_path, &["branch", "-m", old_name.as_str(), new_name.as_str()])
+    let rename_on_remote = rename_on_remote.unwrap_or(false);
+    if rename_on_remote {
+        if old_name == new_name {
+            return Err(String::from("new_name is the same as old_name"));
+        }
+        if old_name.starts_with('-') || new_name.starts_with('-') || new_name.contains(':') {
+            return Err(String::from("Invalid branch name."));
+        }
+    }
+
+    crate::with_repo_git_lock(&repo_path, || {
+        if rename_on_remote {
+            return rename_branch_on_remote(&repo_path, &old_name, &new_name, remote_name, allow_protected);
+        }
+        crate::run_git(&repo_path, &["branch", "-m", old_name.as_str(), new_name.as_str()])
+    })
+}
+
+/// Renames the branch locally, pushes it under the new name, points its tracking at the
+/// new remote branch and deletes the old one on the remote. The remote defaults to the
+/// branch's own; the old remote branch is the one it tracks there. A failing step undoes
+/// the ones before it. Deleting a protected old remote branch needs `allow_protected`.
+fn rename_branch_on_remote(
+    repo_path: &str,
+    old_name: &str,
+    new_name: &str,
+    remote_name: Option<String>,
+    allow_protected: Option<bool>,
+) -> Result<String, String> {
+    let config = |key: &str| {
+        crate::run_git(repo_path, &["config", "--get", key])
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let old_remote = config(&format!("branch.{old_name}.remote"));
+    let old_merge = config(&format!("branch.{old_name}.merge"));
+
+    let remote_name = remote_name
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .or_else(|| old_remote.clone())
+        .unwrap_or_else(|| String::from("origin"));
+    let remote_old_ref = match (&old_remote, &old_merge) {
+        (Some(r), Some(m)) if *r == remote_name && m.starts_with("refs/heads/") => m.clone(),
+        _ => format!("refs/heads/{old_name}"),
+    };
+    let remote_new_ref = format!("refs/heads/{new_name}");
+
+    let remote_new_exists = crate::run_git(repo_path, &["ls-remote", "--heads", remote_name.as_str(), remote_new_ref.as_str()])?;
+    if !remote_new_exists.trim().is_empty() {
+        return Err(format!("Branch '{new_name}' already exists on {remote_name}."));
+    }
+    let remote_old_exists = !crate::run_git(repo_path, &["ls-remote", "--heads", remote_name.as_str(), remote_old_ref.as_str()])?
+        .trim()
+        .is_empty();
+    if remote_old_exists {
+        super::protected::ensure_not_protected(repo_path, &remote_old_ref, "remote branch deletion", allow_protected)?;
+    }
+
+    crate::run_git(repo_path, &["branch", "-m", old_name, new_name])?;
+
+    // Returns the error to report, noting a pushed branch that could not be removed again.
+    let rollback = |pushed: bool, mut err: String| {
+        if pushed
+            && crate::run_git(repo_path, &["push", remote_name.as_str(), "--delete", remote_new_ref.as_str()]).is_err()
+        {
+            err.push_str(&format!("\n'{new_name}' was pushed to {remote_name} and could not be deleted again."));
+        }
+        let _ = crate::run_git(repo_path, &["branch", "-m", new_name, old_name]);
+        for (key, value) in [("remote", &old_remote), ("merge", &old_merge)] {
+            let key = format!("branch.{old_name}.{key}");
+            let _ = match value {
+                Some(v) => crate::run_git(repo_path, &["config", key.as_str(), v.as_str()]),
+                None => crate::run_git(repo_path, &["config", "--unset", key.as_str()]),
+            };
+        }
+        err
+    };
+
+    let refspec = format!("refs/heads/{new_name}:{remote_new_ref}");
+    crate::run_git(repo_path, &["push", remote_name.as_str(), refspec.as_str()]).map_err(|e| rollback(false, e))?;
+
+    crate::run_git(repo_path, &["config", &format!("branch.{new_name}.remote"), remote_name.as_str()])
+        .and_then(|_| crate::run_git(repo_path, &["config", &format!("branch.{new_name}.merge"), remote_new_ref.as_str()]))
+        .map_err(|e| rollback(true, e))?;
+
+    if remote_old_exists {
+        crate::run_git(repo_path, &["push", remote_name.as_str(), "--delete", remote_old_ref.as_str()])
+            .map_err(|e| rollback(true, e))?;
+    }
+
+    Ok(format!("Renamed '{old_name}' to '{new_name}' locally and on {remote_name}."))
+}
+
+/// Sets `branch`'s upstream to `upstream`, a remote-tracking or local branch.
+#[tauri::command]
+pub(crate) fn git_set_upstream(repo_path: String, branch: String, upstream: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let branch = branch.trim().to_string();
+    let upstream = upstream.trim().to_string();
+    if branch.is_empty() {
+        return Err(String::from("branch is empty"));
+    }
+    if upstream.is_empty() {
+        return Err(String::from("upstream is empty"));
+    }
+    if branch.starts_with('-') || upstream.starts_with('-') {
+        return Err(String::from("Invalid branch name."));
+    }
+
+    let set_upstream = format!("--set-upstream-to={upstream}");
+    crate::run_git(&repo_path, &["branch", set_upstream.as_str(), branch.as_str()])
+}
+
+#[tauri::command]
+pub(crate) fn git_unset_upstream(repo_path: String, branch: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let branch = branch.trim().to_string();
+    if branch.is_empty() {
+        return Err(String::from("branch is empty"));
+    }
+    if branch.starts_with('-') {
+        return Err(String::from("Invalid branch name."));
+    }
+
+    crate::run_git(&repo_path, &["branch", "--unset-upstream", branch.as_str()])
 }
 
 #[tauri::command]
@@ -109,6 +759,7 @@ pub(crate) fn git_create_branch_advanced(
     clear_working_tree: Option<bool>,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let branch = branch.trim().to_string();
     if branch.is_empty() {
@@ -166,14 +817,41 @@ pub(crate) fn git_create_branch_advanced(
 }
 
 #[tauri::command]
-pub(crate) fn git_reset_hard(repo_path: String) -> Result<String, String> {
+pub(crate) fn git_reset_hard(repo_path: String, dry_run: Option<bool>) -> Result<crate::GitCommandOutcome, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
-    crate::run_git(&repo_path, &["reset", "--hard"])
+    if !dry_run.unwrap_or(false) {
+        super::read_only::ensure_writable(&repo_path)?;
+    }
+
+    if dry_run.unwrap_or(false) {
+        let changed = crate::run_git_stdout_raw(&repo_path, &["diff", "HEAD", "--name-only", "-z"]).unwrap_or_default();
+        let files_changed = crate::name_only_list(changed.as_str());
+        return Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+            operation: String::from("reset --hard"),
+            ref_updates: Vec::new(),
+            files_removed: Vec::new(),
+            message: format!("Would discard local changes in {} file(s).", files_changed.len()),
+            files_changed,
+            commits_orphaned: Vec::new(),
+        }));
+    }
+
+    let _undo = super::undo::record_undo_point(&repo_path, "reset --hard", &[]);
+    crate::run_git(&repo_path, &["reset", "--hard"]).map(crate::GitCommandOutcome::Done)
 }
 
 #[tauri::command]
-pub(crate) fn git_reset(repo_path: String, mode: String, target: String) -> Result<String, String> {
+pub(crate) fn git_reset(
+    repo_path: String,
+    mode: String,
+    target: String,
+    dry_run: Option<bool>,
+    allow_protected: Option<bool>,
+) -> Result<crate::GitCommandOutcome, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    if !dry_run.unwrap_or(false) {
+        super::read_only::ensure_writable(&repo_path)?;
+    }
 
     let mode = mode.trim().to_lowercase();
     let target = target.trim().to_string();
@@ -188,7 +866,60 @@ pub(crate) fn git_reset(repo_path: String, mode: String, target: String) -> Resu
         _ => return Err(String::from("Invalid reset mode. Use: soft, mixed or hard.")),
     };
 
-    crate::run_git(&repo_path, &["reset", flag, target.as_str()])
+    if dry_run.unwrap_or(false) {
+        let target_hash = crate::resolve_commit(&repo_path, target.as_str())
+            .ok_or_else(|| format!("Revision not found: {target}"))?;
+        let head_hash = crate::resolve_commit(&repo_path, "HEAD").unwrap_or_default();
+        let head_ref = crate::run_git(&repo_path, &["symbolic-ref", "--quiet", "HEAD"]).unwrap_or_default();
+        let ref_name = if head_ref.trim().is_empty() { String::from("HEAD") } else { head_ref.trim().to_string() };
+
+        // Soft keeps index and working tree, mixed rewrites the index, hard both.
+        let files_changed = match mode.as_str() {
+            "hard" => crate::name_only_list(
+                crate::run_git_stdout_raw(&repo_path, &["diff", target_hash.as_str(), "--name-only", "-z"])
+                    .unwrap_or_default()
+                    .as_str(),
+            ),
+            "mixed" => crate::name_only_list(
+                crate::run_git_stdout_raw(&repo_path, &["diff", "--cached", target_hash.as_str(), "--name-only", "-z"])
+                    .unwrap_or_default()
+                    .as_str(),
+            ),
+            _ => Vec::new(),
+        };
+
+        let commits_orphaned = if head_hash.is_empty() {
+            Vec::new()
+        } else {
+            crate::commits_orphaned_by(&repo_path, head_hash.as_str(), &[ref_name.as_str()], &[target_hash.as_str()])
+        };
+
+        return Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+            operation: format!("reset {flag}"),
+            ref_updates: vec![crate::GitRefUpdate {
+                name: ref_name,
+                old: if head_hash.is_empty() { None } else { Some(head_hash) },
+                new: Some(target_hash),
+            }],
+            files_removed: Vec::new(),
+            message: format!(
+                "Would move the branch to {target}; {} file(s) change, {} commit(s) become unreachable.",
+                files_changed.len(),
+                commits_orphaned.len()
+            ),
+            files_changed,
+            commits_orphaned,
+        }));
+    }
+
+    // A hard reset that moves the branch drops its commits; one onto HEAD only discards changes.
+    if mode == "hard" && crate::resolve_commit(&repo_path, target.as_str()) != crate::resolve_commit(&repo_path, "HEAD") {
+        super::protected::ensure_head_not_protected(&repo_path, "reset --hard", allow_protected)?;
+    }
+
+    let undo_label = format!("reset {flag} {target}");
+    let _undo = super::undo::record_undo_point(&repo_path, undo_label.as_str(), &[]);
+    crate::run_git(&repo_path, &["reset", flag, target.as_str()]).map(crate::GitCommandOutcome::Done)
 }
 
 #[tauri::command]
@@ -233,6 +964,7 @@ pub(crate) fn git_is_ancestor(
 #[tauri::command]
 pub(crate) fn git_create_branch(repo_path: String, branch: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let branch = branch.trim().to_string();
     if branch.is_empty() {
@@ -247,22 +979,100 @@ pub(crate) fn git_delete_branch(
     repo_path: String,
     branch: String,
     force: Option<bool>,
-) -> Result<String, String> {
+    dry_run: Option<bool>,
+    allow_protected: Option<bool>,
+) -> Result<crate::GitCommandOutcome, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    if !dry_run.unwrap_or(false) {
+        super::read_only::ensure_writable(&repo_path)?;
+    }
 
     let branch = branch.trim().to_string();
     if branch.is_empty() {
         return Err(String::from("branch is empty"));
     }
 
+    if dry_run.unwrap_or(false) {
+        let full_ref = format!("refs/heads/{branch}");
+        let tip = crate::resolve_commit(&repo_path, full_ref.as_str())
+            .ok_or_else(|| format!("Branch not found: {branch}"))?;
+        let head = crate::resolve_commit(&repo_path, "HEAD").unwrap_or_default();
+        let commits_orphaned = crate::commits_orphaned_by(&repo_path, tip.as_str(), &[full_ref.as_str()], &[head.as_str()]);
+        return Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+            operation: String::from("branch delete"),
+            ref_updates: vec![crate::GitRefUpdate {
+                name: full_ref,
+                old: Some(tip),
+                new: None,
+            }],
+            files_removed: Vec::new(),
+            files_changed: Vec::new(),
+            message: if commits_orphaned.is_empty() {
+                format!("Would delete branch {branch}; all its commits stay reachable.")
+            } else {
+                format!("Would delete branch {branch}; {} commit(s) become unreachable.", commits_orphaned.len())
+            },
+            commits_orphaned,
+        }));
+    }
+
+    super::protected::ensure_not_protected(&repo_path, &branch, "branch deletion", allow_protected)?;
+
+    let undo_label = format!("delete branch {branch}");
+    let branch_ref = format!("refs/heads/{branch}");
+    let _undo = super::undo::record_undo_point(&repo_path, undo_label.as_str(), &[branch_ref.as_str()]);
+
     let force = force.unwrap_or(false);
     if force {
-        crate::run_git(&repo_path, &["branch", "-D", branch.as_str()])
+        crate::run_git(&repo_path, &["branch", "-D", branch.as_str()]).map(crate::GitCommandOutcome::Done)
     } else {
-        crate::run_git(&repo_path, &["branch", "-d", branch.as_str()])
+        crate::run_git(&repo_path, &["branch", "-d", branch.as_str()]).map(crate::GitCommandOutcome::Done)
     }
 }
 
+#[tauri::command]
+pub(crate) fn git_clean(
+    repo_path: String,
+    directories: Option<bool>,
+    ignored: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<crate::GitCommandOutcome, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    if !dry_run.unwrap_or(false) {
+        super::read_only::ensure_writable(&repo_path)?;
+    }
+
+    let mut args: Vec<&str> = vec!["clean"];
+    args.push(if dry_run.unwrap_or(false) { "-n" } else { "-f" });
+    if directories.unwrap_or(false) {
+        args.push("-d");
+    }
+    if ignored.unwrap_or(false) {
+        args.push("-x");
+    }
+
+    let out = crate::run_git(&repo_path, args.as_slice())?;
+    if !dry_run.unwrap_or(false) {
+        return Ok(crate::GitCommandOutcome::Done(out));
+    }
+
+    // Dry-run output: "Would remove <path>"
+    let files_removed: Vec<String> = out
+        .lines()
+        .filter_map(|l| l.strip_prefix("Would remove "))
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+        operation: String::from("clean"),
+        ref_updates: Vec::new(),
+        message: format!("Would remove {} untracked path(s).", files_removed.len()),
+        files_removed,
+        files_changed: Vec::new(),
+        commits_orphaned: Vec::new(),
+    }))
+}
+
 #[tauri::command]
 pub(crate) fn git_branches_points_at(repo_path: String, commit: String) -> Result<Vec<String>, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
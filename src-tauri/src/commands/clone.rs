@@ -1,10 +1,33 @@
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
+use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use std::path::Path;
-use std::process::Stdio;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitShallowInfo {
+    shallow: bool,
+    local_commits: u32,
+    boundary_commits: Vec<String>,
+}
+
+/// Progress of a fetch, pull or push (`git_transfer_progress` events).
+#[derive(Debug, Clone, Serialize)]
+struct GitTransferProgressEvent {
+    repo_path: String,
+    operation: String,
+    phase: String,
+    percent: u32,
+    objects_done: Option<u64>,
+    objects_total: Option<u64>,
+    bytes: Option<u64>,
+    message: String,
+}
 
 #[derive(Debug, Clone, Serialize)]
 struct GitCloneProgressEvent {
@@ -58,6 +81,271 @@ fn parse_git_clone_progress_line(line: &str) -> Option<(String, u32, String)> {
     Some((phase, pct, without_remote.to_string()))
 }
 
+/// Object counts and transferred size from a progress line such as
+/// `Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s`.
+fn parse_transfer_counts(message: &str) -> (Option<u64>, Option<u64>, Option<u64>) {
+    let Some(open) = message.find('(') else {
+        return (None, None, None);
+    };
+    let Some(close) = message[open..].find(')').map(|i| open + i) else {
+        return (None, None, None);
+    };
+    let (done, total) = match message[open + 1..close].split_once('/') {
+        Some((d, t)) => (d.trim().parse::<u64>().ok(), t.trim().parse::<u64>().ok()),
+        None => (None, None),
+    };
+
+    let after = message[close + 1..].trim_start_matches(',').trim();
+    let size = after.split(['|', ',']).next().unwrap_or_default().trim();
+    let mut parts = size.split_whitespace();
+    let bytes = match (parts.next().and_then(|n| n.parse::<f64>().ok()), parts.next()) {
+        (Some(n), Some(unit)) => {
+            let mult = match unit {
+                "bytes" => 1.0,
+                "KiB" => 1024.0,
+                "MiB" => 1024.0 * 1024.0,
+                "GiB" => 1024.0 * 1024.0 * 1024.0,
+                _ => 0.0,
+            };
+            (mult > 0.0).then_some((n * mult) as u64)
+        }
+        _ => None,
+    };
+
+    (done, total, bytes)
+}
+
+/// Progress callback for `run_git_with_progress` that emits `git_transfer_progress`
+/// events for `operation` ("fetch", "pull" or "push").
+pub(crate) fn transfer_progress_emitter<'a>(
+    app: &'a AppHandle,
+    repo_path: &'a str,
+    operation: &'a str,
+) -> impl FnMut(String, u32, String) + 'a {
+    move |phase, percent, message| {
+        let (objects_done, objects_total, bytes) = parse_transfer_counts(&message);
+        let _ = app.emit(
+            "git_transfer_progress",
+            GitTransferProgressEvent {
+                repo_path: repo_path.to_string(),
+                operation: operation.to_string(),
+                phase,
+                percent,
+                objects_done,
+                objects_total,
+                bytes,
+                message,
+            },
+        );
+    }
+}
+
+type RunningGitJobs = HashMap<String, (Arc<Mutex<Child>>, Arc<AtomicBool>)>;
+
+static RUNNING_GIT_JOBS: OnceLock<Mutex<RunningGitJobs>> = OnceLock::new();
+
+fn running_git_jobs() -> &'static Mutex<RunningGitJobs> {
+    RUNNING_GIT_JOBS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Kills the git process registered under `job_id`. Returns whether one was running.
+fn cancel_git_job(job_id: &str) -> bool {
+    let job = running_git_jobs().lock().ok().and_then(|g| g.get(job_id).cloned());
+    let Some((child, cancelled)) = job else {
+        return false;
+    };
+    cancelled.store(true, Ordering::SeqCst);
+    if let Ok(mut c) = child.lock() {
+        kill_process_tree(&mut c);
+    }
+    true
+}
+
+/// Git hands the transfer to helper processes (remote helpers, index-pack) that keep
+/// stderr open, so the whole tree has to go, not just the git process.
+fn kill_process_tree(child: &mut Child) {
+    let pid = child.id().to_string();
+
+    #[cfg(target_os = "windows")]
+    let killed = crate::new_command("taskkill")
+        .args(["/T", "/F", "/PID", pid.as_str()])
+        .status()
+        .is_ok_and(|s| s.success());
+
+    // The child leads its own process group (see `run_git_with_progress_as`).
+    #[cfg(not(target_os = "windows"))]
+    let killed = crate::new_command("kill")
+        .args(["-KILL", "--", format!("-{pid}").as_str()])
+        .status()
+        .is_ok_and(|s| s.success());
+
+    if !killed {
+        let _ = child.kill();
+    }
+}
+
+/// Runs a git command that reports `--progress` on stderr and calls `on_progress(phase,
+/// percent, message)` whenever the phase or percentage changes. Returns whether git
+/// succeeded together with its whole stderr.
+pub(crate) fn run_git_with_progress(
+    cmd: Command,
+    what: &str,
+    on_progress: impl FnMut(String, u32, String),
+) -> Result<(bool, String), String> {
+    run_git_status_with_progress(cmd, what, on_progress).map(|(ok, _, stderr)| (ok, stderr))
+}
+
+/// Like `run_git_with_progress`, but also returns stdout: `(ok, stdout, stderr)`.
+pub(crate) fn run_git_status_with_progress(
+    cmd: Command,
+    what: &str,
+    on_progress: impl FnMut(String, u32, String),
+) -> Result<(bool, String, String), String> {
+    run_git_with_progress_as(cmd, what, None, on_progress, |_| {})
+        .map(|r| r.unwrap_or((false, String::new(), String::new())))
+}
+
+/// Like `run_git_with_progress`, but also hands every other stderr line to `on_line` as
+/// soon as git prints it.
+pub(crate) fn run_git_with_progress_and_log(
+    cmd: Command,
+    what: &str,
+    on_progress: impl FnMut(String, u32, String),
+    on_line: impl FnMut(&str),
+) -> Result<(bool, String), String> {
+    run_git_with_progress_as(cmd, what, None, on_progress, on_line)
+        .map(|r| r.map(|(ok, _, stderr)| (ok, stderr)).unwrap_or((false, String::new())))
+}
+
+/// Like `run_git_with_progress`, but while it runs the child can be killed through
+/// `cancel_git_job(job_id)`. Returns `None` when it was cancelled.
+fn run_git_with_progress_as(
+    mut cmd: Command,
+    what: &str,
+    job_id: Option<&str>,
+    mut on_progress: impl FnMut(String, u32, String),
+    mut on_line: impl FnMut(&str),
+) -> Result<Option<(bool, String, String)>, String> {
+    super::askpass::apply_env(&mut cmd);
+
+    #[cfg(not(target_os = "windows"))]
+    if job_id.is_some() {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn {what}: {e}"))?;
+
+    // stdout is drained on its own thread so a full pipe cannot stall git.
+    let stdout_reader = child.stdout.take().map(|mut out| {
+        std::thread::spawn(move || {
+            let mut buf: Vec<u8> = Vec::new();
+            let _ = out.read_to_end(&mut buf);
+            String::from_utf8_lossy(&buf).trim_end().to_string()
+        })
+    });
+    let stderr = child.stderr.take();
+    let child = Arc::new(Mutex::new(child));
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let (Some(id), Ok(mut guard)) = (job_id, running_git_jobs().lock()) {
+        guard.insert(id.to_string(), (Arc::clone(&child), Arc::clone(&cancelled)));
+    }
+
+    let read = match stderr {
+        Some(stderr) => read_git_progress(stderr, what, &mut on_progress, &mut on_line),
+        None => Err(format!("Failed to capture {what} stderr.")),
+    };
+    let status = child
+        .lock()
+        .map_err(|_| format!("Failed to wait for {what}."))
+        .and_then(|mut c| c.wait().map_err(|e| format!("Failed to wait for {what}: {e}")));
+
+    if let (Some(id), Ok(mut guard)) = (job_id, running_git_jobs().lock()) {
+        guard.remove(id);
+    }
+
+    let stdout = stdout_reader.and_then(|h| h.join().ok()).unwrap_or_default();
+
+    if cancelled.load(Ordering::SeqCst) {
+        return Ok(None);
+    }
+    let stderr = read?;
+    Ok(Some((status?.success(), stdout, stderr)))
+}
+
+/// Reads git's stderr, reporting progress lines through `on_progress` and the others
+/// through `on_line`. Returns the rest of stderr with the progress lines left out.
+fn read_git_progress(
+    mut stderr: impl Read,
+    what: &str,
+    on_progress: &mut impl FnMut(String, u32, String),
+    on_line: &mut impl FnMut(&str),
+) -> Result<String, String> {
+    let mut kept: Vec<String> = Vec::new();
+    let mut pending: Vec<u8> = Vec::new();
+    let mut buf = [0u8; 4096];
+    let mut last_sent: Option<(String, u32)> = None;
+
+    let mut handle_line = |line: &str, last_sent: &mut Option<(String, u32)>, kept: &mut Vec<String>| {
+        let Some((phase, pct, message)) = parse_git_clone_progress_line(line) else {
+            if !line.trim().is_empty() {
+                on_line(line.trim_end());
+                kept.push(line.trim_end().to_string());
+            }
+            return;
+        };
+        let should_emit = match last_sent.as_ref() {
+            Some((p, last_pct)) => p != &phase || *last_pct != pct,
+            None => true,
+        };
+        if should_emit {
+            *last_sent = Some((phase.clone(), pct));
+            on_progress(phase, pct, message);
+        }
+    };
+
+    loop {
+        let n = stderr
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read {what} progress: {e}"))?;
+        if n == 0 {
+            break;
+        }
+
+        pending.extend_from_slice(&buf[..n]);
+
+        while let Some(pos) = pending.iter().position(|b| *b == b'\r' || *b == b'\n') {
+            let chunk: Vec<u8> = pending.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&chunk).trim_matches(&['\r', '\n'][..]).to_string();
+            handle_line(line.as_str(), &mut last_sent, &mut kept);
+        }
+    }
+
+    if !pending.is_empty() {
+        let line = String::from_utf8_lossy(&pending).to_string();
+        handle_line(line.as_str(), &mut last_sent, &mut kept);
+    }
+
+    Ok(kept.join("\n").trim().to_string())
+}
+
+/// Partial clone filters offered in the UI: `blob:none`, `tree:0`, `blob:limit=<n>[k|m|g]`.
+fn is_valid_clone_filter(filter: &str) -> bool {
+    if filter == "blob:none" || filter == "tree:0" {
+        return true;
+    }
+    let Some(limit) = filter.strip_prefix("blob:limit=") else {
+        return false;
+    };
+    let digits = limit.trim_end_matches(['k', 'm', 'g', 'K', 'M', 'G']);
+    !digits.is_empty() && digits.len() + 1 >= limit.len() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
 fn ensure_clone_destination_valid(destination_path: &str) -> Result<(), String> {
     let destination_path = destination_path.trim();
     if destination_path.is_empty() {
@@ -107,8 +395,29 @@ fn ensure_clone_destination_valid(destination_path: &str) -> Result<(), String>
     }
 }
 
+fn clone_job_id(destination_path: &str) -> String {
+    format!("clone:{}", crate::normalize_repo_path(destination_path))
+}
+
+/// Removes what a cancelled clone left behind. The destination was checked to be empty
+/// or missing before cloning, so everything in it came from the clone.
+fn remove_partial_clone(destination_path: &str, existed_before: bool) {
+    let dest = Path::new(destination_path);
+    if !existed_before {
+        let _ = fs::remove_dir_all(dest);
+        return;
+    }
+    if let Ok(rd) = fs::read_dir(dest) {
+        for entry in rd.flatten() {
+            let path = entry.path();
+            let _ = if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+        }
+    }
+}
+
+
 #[tauri::command]
-pub(crate) fn git_clone_repo(
+pub(crate) async fn git_clone_repo(
     app: AppHandle,
     repo_url: String,
     destination_path: String,
@@ -118,131 +427,88 @@ pub(crate) fn git_clone_repo(
     bare: Option<bool>,
     origin: Option<String>,
     single_branch: Option<bool>,
+    filter: Option<String>,
 ) -> Result<String, String> {
-    let repo_url = repo_url.trim().to_string();
-    let destination_path = destination_path.trim().to_string();
-    let origin = origin.unwrap_or_else(|| String::from("origin")).trim().to_string();
-    let init_submodules = init_submodules.unwrap_or(false);
-    let download_full_history = download_full_history.unwrap_or(true);
-    let bare = bare.unwrap_or(false);
-    let single_branch = single_branch.unwrap_or(false);
-
-    if repo_url.is_empty() {
-        return Err(String::from("repo_url is empty"));
-    }
-    if destination_path.is_empty() {
-        return Err(String::from("destination_path is empty"));
-    }
-    if origin.is_empty() {
-        return Err(String::from("origin is empty"));
-    }
-    if bare && init_submodules {
-        return Err(String::from("Cannot initialize submodules in a bare repository."));
-    }
-
-    ensure_clone_destination_valid(destination_path.as_str())?;
-
-    if Path::new(destination_path.as_str()).exists() {
-        crate::ensure_is_not_git_worktree(destination_path.as_str())?;
-    }
-
-    let mut args: Vec<String> = vec![String::from("clone")];
-    args.push(String::from("--progress"));
-
-    if bare {
-        args.push(String::from("--bare"));
-    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let repo_url = repo_url.trim().to_string();
+        let destination_path = destination_path.trim().to_string();
+        let origin = origin.unwrap_or_else(|| String::from("origin")).trim().to_string();
+        let init_submodules = init_submodules.unwrap_or(false);
+        let download_full_history = download_full_history.unwrap_or(true);
+        let bare = bare.unwrap_or(false);
+        let single_branch = single_branch.unwrap_or(false);
+        let filter = filter.unwrap_or_default().trim().to_string();
+
+        if repo_url.is_empty() {
+            return Err(String::from("repo_url is empty"));
+        }
+        if destination_path.is_empty() {
+            return Err(String::from("destination_path is empty"));
+        }
+        if origin.is_empty() {
+            return Err(String::from("origin is empty"));
+        }
+        if bare && init_submodules {
+            return Err(String::from("Cannot initialize submodules in a bare repository."));
+        }
 
-    args.push(String::from("--origin"));
-    args.push(origin);
+        if !filter.is_empty() && !is_valid_clone_filter(filter.as_str()) {
+            return Err(String::from("Invalid filter. Use: blob:none, tree:0 or blob:limit=<size>."));
+        }
 
-    if single_branch {
-        args.push(String::from("--single-branch"));
-    }
+        ensure_clone_destination_valid(destination_path.as_str())?;
 
-    if !download_full_history {
-        args.push(String::from("--depth"));
-        args.push(String::from("1"));
-    }
-
-    if let Some(b) = branch {
-        let b = b.trim().to_string();
-        if !b.is_empty() {
-            args.push(String::from("--branch"));
-            args.push(b);
+        let existed_before = Path::new(destination_path.as_str()).exists();
+        if existed_before {
+            crate::ensure_is_not_git_worktree(destination_path.as_str())?;
         }
-    }
 
-    args.push(repo_url);
-    args.push(destination_path.clone());
+        let job_id = clone_job_id(&destination_path);
+        if running_git_jobs().lock().is_ok_and(|g| g.contains_key(&job_id)) {
+            return Err(String::from("A clone into this folder is already running."));
+        }
 
-    let mut child = crate::new_command("git")
-        .args(args)
-        .stdout(Stdio::null())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to spawn git clone: {e}"))?;
+        let mut args: Vec<String> = vec![String::from("clone")];
+        args.push(String::from("--progress"));
 
-    let mut stderr = child
-        .stderr
-        .take()
-        .ok_or_else(|| String::from("Failed to capture git clone stderr."))?;
+        if bare {
+            args.push(String::from("--bare"));
+        }
 
-    let mut stderr_all: Vec<u8> = Vec::new();
-    let mut pending: Vec<u8> = Vec::new();
-    let mut buf = [0u8; 4096];
-    let mut last_sent: Option<(String, u32)> = None;
+        args.push(String::from("--origin"));
+        args.push(origin);
 
-    loop {
-        let n = stderr
-            .read(&mut buf)
-            .map_err(|e| format!("Failed to read git clone progress: {e}"))?;
-        if n == 0 {
-            break;
+        if single_branch {
+            args.push(String::from("--single-branch"));
         }
 
-        stderr_all.extend_from_slice(&buf[..n]);
-        pending.extend_from_slice(&buf[..n]);
+        if !filter.is_empty() {
+            args.push(format!("--filter={filter}"));
+        }
 
-        while let Some(pos) = pending.iter().position(|b| *b == b'\r' || *b == b'\n') {
-            let chunk: Vec<u8> = pending.drain(..=pos).collect();
-            let line = String::from_utf8_lossy(&chunk)
-                .trim_matches(&['\r', '\n'][..])
-                .trim()
-                .to_string();
-            if line.is_empty() {
-                continue;
-            }
+        if !download_full_history {
+            args.push(String::from("--depth"));
+            args.push(String::from("1"));
+        }
 
-            if let Some((phase, pct, message)) = parse_git_clone_progress_line(line.as_str()) {
-                let should_emit = match &last_sent {
-                    Some((p, last_pct)) => p != &phase || *last_pct != pct,
-                    None => true,
-                };
-                if should_emit {
-                    let _ = app.emit(
-                        "git_clone_progress",
-                        GitCloneProgressEvent {
-                            destination_path: destination_path.clone(),
-                            phase: Some(phase.clone()),
-                            percent: Some(pct),
-                            message,
-                        },
-                    );
-                    last_sent = Some((phase, pct));
-                }
+        if let Some(b) = branch {
+            let b = b.trim().to_string();
+            if !b.is_empty() {
+                args.push(String::from("--branch"));
+                args.push(b);
             }
         }
-    }
 
-    if !pending.is_empty() {
-        let line = String::from_utf8_lossy(&pending).trim().to_string();
-        if let Some((phase, pct, message)) = parse_git_clone_progress_line(line.as_str()) {
-            let should_emit = match &last_sent {
-                Some((p, last_pct)) => p != &phase || *last_pct != pct,
-                None => true,
-            };
-            if should_emit {
+        args.push(repo_url);
+        args.push(destination_path.clone());
+
+        let mut cmd = crate::new_command("git");
+        cmd.args(args);
+        let result = run_git_with_progress_as(
+            cmd,
+            "git clone",
+            Some(&job_id),
+            |phase, pct, message| {
                 let _ = app.emit(
                     "git_clone_progress",
                     GitCloneProgressEvent {
@@ -252,28 +518,156 @@ pub(crate) fn git_clone_repo(
                         message,
                     },
                 );
+            },
+            |_| {},
+        )?;
+        let Some((ok, _, stderr)) = result else {
+            remove_partial_clone(&destination_path, existed_before);
+            return Err(String::from("Clone cancelled."));
+        };
+
+        if !ok {
+            if !stderr.is_empty() {
+                return Err(format!("git clone failed: {stderr}"));
             }
+            return Err(String::from("git clone failed."));
         }
+
+        if init_submodules {
+            crate::run_git(
+                destination_path.as_str(),
+                &["submodule", "update", "--init", "--recursive"],
+            )?;
+        }
+
+        Ok(destination_path)
+    })
+    .await
+    .map_err(|e| format!("Failed to run git clone: {e}"))?
+}
+
+/// Stops a running `git_clone_repo` into `destination_path`; the clone then removes what
+/// it already wrote there. Returns whether a clone was running.
+#[tauri::command]
+pub(crate) fn git_clone_cancel(destination_path: String) -> Result<bool, String> {
+    let destination_path = destination_path.trim().to_string();
+    if destination_path.is_empty() {
+        return Err(String::from("destination_path is empty"));
     }
+    Ok(cancel_git_job(&clone_job_id(&destination_path)))
+}
 
-    let status = child
-        .wait()
-        .map_err(|e| format!("Failed to wait for git clone: {e}"))?;
+fn is_shallow_repository(repo_path: &str) -> bool {
+    crate::run_git(repo_path, &["rev-parse", "--is-shallow-repository"])
+        .map(|v| v.trim() == "true")
+        .unwrap_or(false)
+}
 
-    if !status.success() {
-        let stderr = String::from_utf8_lossy(stderr_all.as_slice()).trim().to_string();
-        if !stderr.is_empty() {
-            return Err(format!("git clone failed: {stderr}"));
-        }
-        return Err(String::from("git clone failed."));
+#[tauri::command]
+pub(crate) fn git_repo_shallow_info(repo_path: String) -> Result<GitShallowInfo, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let shallow = is_shallow_repository(&repo_path);
+
+    // `.git/shallow` lists the commits whose parents were cut off.
+    let mut boundary_commits: Vec<String> = Vec::new();
+    if let Some(p) = shallow
+        .then(|| crate::run_git(&repo_path, &["rev-parse", "--git-path", "shallow"]).ok())
+        .flatten()
+    {
+        let p = Path::new(p.trim());
+        let p = if p.is_absolute() { p.to_path_buf() } else { Path::new(&repo_path).join(p) };
+        boundary_commits = fs::read_to_string(p)
+            .unwrap_or_default()
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
     }
 
-    if init_submodules {
-        crate::run_git(
-            destination_path.as_str(),
-            &["submodule", "update", "--init", "--recursive"],
-        )?;
+    let local_commits = crate::run_git(&repo_path, &["rev-list", "--count", "--all"])
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+
+    Ok(GitShallowInfo {
+        shallow,
+        local_commits,
+        boundary_commits,
+    })
+}
+
+/// Fetches more history into a shallow clone: `depth` more commits behind the current
+/// boundary, or the whole history with `unshallow`. Progress goes out as
+/// `git_transfer_progress` events.
+#[tauri::command]
+pub(crate) async fn git_fetch_deepen(
+    app: AppHandle,
+    repo_path: String,
+    remote_name: Option<String>,
+    depth: Option<u32>,
+    unshallow: Option<bool>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        let remote_name = remote_name.unwrap_or_else(|| String::from("origin")).trim().to_string();
+        if remote_name.is_empty() {
+            return Err(String::from("remote_name is empty"));
+        }
+        let unshallow = unshallow.unwrap_or(false);
+        let deepen_arg = match (unshallow, depth) {
+            (true, Some(_)) => return Err(String::from("Use either depth or unshallow, not both.")),
+            (true, None) => String::from("--unshallow"),
+            (false, Some(0)) | (false, None) => return Err(String::from("depth must be greater than 0")),
+            (false, Some(n)) => format!("--deepen={n}"),
+        };
+
+        if !is_shallow_repository(&repo_path) {
+            return Err(String::from("Repository is not shallow."));
+        }
+
+        crate::with_repo_git_lock(&repo_path, || {
+            let mut cmd = crate::git_command_in_repo(&repo_path);
+            cmd.args(["fetch", "--progress", deepen_arg.as_str(), remote_name.as_str()]);
+            let (ok, stderr) =
+                run_git_with_progress(cmd, "git fetch", transfer_progress_emitter(&app, &repo_path, "fetch"))?;
+
+            if !ok {
+                if !stderr.is_empty() {
+                    return Err(format!("git fetch failed: {stderr}"));
+                }
+                return Err(String::from("git fetch failed."));
+            }
+            Ok(stderr)
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to run git fetch: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_clone_filter() {
+        for ok in ["blob:none", "tree:0", "blob:limit=1024", "blob:limit=10k", "blob:limit=5M"] {
+            assert!(is_valid_clone_filter(ok), "{ok}");
+        }
+        for bad in ["", "blob:limit=", "blob:limit=k", "blob:limit=10kk", "blob:limit=-1", "tree:1", "sparse:oid=x", "blob:none "] {
+            assert!(!is_valid_clone_filter(bad), "{bad}");
+        }
     }
 
-    Ok(destination_path)
+    #[test]
+    fn test_parse_transfer_counts() {
+        assert_eq!(
+            parse_transfer_counts("Receiving objects:  45% (450/1000), 1.50 MiB | 2.00 MiB/s"),
+            (Some(450), Some(1000), Some(1024 * 1024 * 3 / 2))
+        );
+        assert_eq!(parse_transfer_counts("Writing objects: 100% (3/3), 280 bytes | 280.00 KiB/s, done."), (Some(3), Some(3), Some(280)));
+        assert_eq!(parse_transfer_counts("Resolving deltas: 100% (12/12), done."), (Some(12), Some(12), None));
+        assert_eq!(parse_transfer_counts("Enumerating objects: 5, done."), (None, None, None));
+    }
 }
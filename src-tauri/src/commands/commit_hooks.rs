@@ -0,0 +1,179 @@
+// Commit hooks. Instead of letting `git commit` run pre-commit and commit-msg with their
+// output mixed into the commit's, they are run one by one through `git hook run` so each
+// hook's exit code and output can be reported; the commit itself then runs with
+// `--no-verify`. Gits without `git hook run` (before 2.36) fall back to a plain commit.
+
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitHookRun {
+    hook: String,
+    /// -1 when the hook was killed by a signal.
+    exit_code: i32,
+    output: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitCommitResult {
+    /// Empty when a hook stopped the commit.
+    pub(crate) commit: String,
+    /// Hooks that ran before the commit; empty with `no_verify` or when none are installed.
+    /// When `committed` is false the last one is the hook that failed.
+    hooks: Vec<GitHookRun>,
+    pub(crate) committed: bool,
+}
+
+static HOOK_RUN_SUPPORTED: OnceLock<bool> = OnceLock::new();
+
+fn hook_run_supported(repo_path: &str) -> bool {
+    *HOOK_RUN_SUPPORTED.get_or_init(|| {
+        crate::git_command_in_repo(repo_path)
+            .args(["hook", "run", "--ignore-missing", "graphoria-probe"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Whether `hook` is installed, honouring `core.hooksPath`.
+fn hook_installed(repo_path: &str, hook: &str) -> bool {
+    let rel = format!("hooks/{hook}");
+    let Ok(path) = crate::run_git(repo_path, &["rev-parse", "--git-path", rel.as_str()]) else {
+        return false;
+    };
+    let path = PathBuf::from(path.trim());
+    let path = if path.is_absolute() {
+        path
+    } else {
+        PathBuf::from(repo_path).join(path)
+    };
+    path.is_file()
+}
+
+fn run_hook(repo_path: &str, hook: &str, args: &[&str]) -> Result<Option<GitHookRun>, String> {
+    if !hook_installed(repo_path, hook) {
+        return Ok(None);
+    }
+
+    let mut cmd = crate::git_command_in_repo(repo_path);
+    cmd.args(["hook", "run", "--ignore-missing", hook, "--"]).args(args);
+    // Like `git commit -m`, hooks are told there is no editor to open.
+    cmd.env("GIT_EDITOR", ":");
+    let out = cmd
+        .output()
+        .map_err(|e| format!("Failed to spawn git hook run: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let output = [stdout.trim_end(), stderr.trim_end()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(Some(GitHookRun {
+        hook: hook.to_string(),
+        exit_code: out.status.code().unwrap_or(-1),
+        output,
+    }))
+}
+
+/// Commits the index with `message`, amending HEAD when `amend` is set. Unless `no_verify`
+/// is set, pre-commit and commit-msg run first; a failing hook stops the commit and is
+/// returned with `committed: false`. commit-msg may rewrite the message.
+pub(crate) fn commit_with_hooks(
+    repo_path: &str,
+    message: &str,
+    amend: bool,
+    no_verify: bool,
+) -> Result<GitCommitResult, String> {
+    let git_dir =
+        super::handles::git_dir(repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    let msg_path = git_dir.join("COMMIT_EDITMSG");
+    let mut message = message.to_string();
+    if !message.ends_with('\n') {
+        message.push('\n');
+    }
+    fs::write(&msg_path, message).map_err(|e| format!("Failed to write commit message: {e}"))?;
+    let msg_arg = msg_path.to_string_lossy().to_string();
+
+    let mut hooks: Vec<GitHookRun> = Vec::new();
+    let run_hooks = !no_verify && hook_run_supported(repo_path);
+    if run_hooks {
+        for (hook, args) in [("pre-commit", vec![]), ("commit-msg", vec![msg_arg.as_str()])] {
+            if let Some(run) = run_hook(repo_path, hook, &args)? {
+                let failed = run.exit_code != 0;
+                hooks.push(run);
+                if failed {
+                    return Ok(GitCommitResult { commit: String::new(), hooks, committed: false });
+                }
+            }
+        }
+        let edited = fs::read_to_string(&msg_path).map_err(|e| format!("Failed to read commit message: {e}"))?;
+        if edited.trim().is_empty() {
+            return Err(String::from("Commit message is empty after running the commit-msg hook."));
+        }
+    }
+
+    let mut args: Vec<&str> = vec!["commit", "-F", msg_arg.as_str()];
+    if amend {
+        args.push("--amend");
+    }
+    if no_verify || run_hooks {
+        args.push("--no-verify");
+    }
+    let out = crate::git_command_in_repo(repo_path)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to spawn git commit: {e}"))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(format!("git commit failed: {stderr}"));
+    }
+
+    let commit = crate::run_git(repo_path, &["rev-parse", "HEAD"]).unwrap_or_default();
+    Ok(GitCommitResult { commit, hooks, committed: true })
+}
+
+/// Amends HEAD with the staged changes plus `paths`, keeping its message unless a new one
+/// is given.
+#[tauri::command]
+pub(crate) fn git_commit_amend(
+    repo_path: String,
+    message: Option<String>,
+    paths: Option<Vec<String>>,
+    no_verify: Option<bool>,
+) -> Result<GitCommitResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    crate::run_git(&repo_path, &["rev-parse", "--verify", "HEAD"])
+        .map_err(|_| String::from("There is no commit to amend."))?;
+
+    let message = match message.filter(|m| !m.trim().is_empty()) {
+        Some(m) => m,
+        None => crate::run_git(&repo_path, &["log", "-1", "--format=%B", "HEAD"])?,
+    };
+
+    let paths: Vec<String> = paths
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if !paths.is_empty() {
+        for p in &paths {
+            crate::ensure_rel_path_safe(p)?;
+        }
+        let mut add_args: Vec<&str> = vec!["add", "--"];
+        add_args.extend(paths.iter().map(|p| p.as_str()));
+        let (ok, _, stderr) = crate::run_git_status(&repo_path, &add_args)?;
+        if !ok {
+            return Err(format!("git add failed: {stderr}"));
+        }
+    }
+
+    commit_with_hooks(&repo_path, &message, true, no_verify.unwrap_or(false))
+}
@@ -0,0 +1,460 @@
+// Conventional Commits validation for the commit box. The rules start from
+// `@commitlint/config-conventional`, are overridden by a JSON commitlint config in the
+// repository (`.commitlintrc.json` / `.commitlintrc`) and then by the caller's config.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const CONFIG_FILES: &[&str] = &[".commitlintrc.json", ".commitlintrc"];
+
+const DEFAULT_TYPES: &[&str] = &[
+    "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
+];
+
+/// Explicit settings from the UI; every field left out keeps the repository's or the
+/// default rule.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct CommitLintConfig {
+    types: Option<Vec<String>>,
+    /// Allowed scopes; an empty list allows any scope.
+    scopes: Option<Vec<String>>,
+    scope_required: Option<bool>,
+    header_max_length: Option<usize>,
+    body_max_line_length: Option<usize>,
+    /// Read the commitlint config from the repository (default true).
+    use_repo_config: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CommitLintViolation {
+    /// commitlint rule name, e.g. `type-enum` or `header-max-length`.
+    rule: String,
+    level: String, // "error" | "warning"
+    message: String,
+    /// 1-based line of the message the violation is on.
+    line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CommitLintReport {
+    /// No error-level violations (warnings are allowed).
+    valid: bool,
+    /// Merge, revert and fixup!/squash! messages are not checked, like commitlint does.
+    ignored: bool,
+    commit_type: Option<String>,
+    scope: Option<String>,
+    breaking: bool,
+    subject: Option<String>,
+    violations: Vec<CommitLintViolation>,
+    /// The repository config file that was applied.
+    config_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Warning,
+    Error,
+}
+
+type Rule<T> = Option<(Level, T)>;
+
+struct Rules {
+    type_enum: Rule<Vec<String>>,
+    type_empty: Rule<()>,
+    scope_enum: Rule<Vec<String>>,
+    /// `true`: a scope is required, `false`: scopes are not allowed.
+    scope_empty: Rule<bool>,
+    subject_empty: Rule<()>,
+    subject_full_stop: Rule<String>,
+    header_max_length: Rule<usize>,
+    body_leading_blank: Rule<()>,
+    body_max_line_length: Rule<usize>,
+    footer_leading_blank: Rule<()>,
+    footer_max_line_length: Rule<usize>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            type_enum: Some((Level::Error, DEFAULT_TYPES.iter().map(|t| t.to_string()).collect())),
+            type_empty: Some((Level::Error, ())),
+            scope_enum: None,
+            scope_empty: None,
+            subject_empty: Some((Level::Error, ())),
+            subject_full_stop: Some((Level::Error, String::from("."))),
+            header_max_length: Some((Level::Error, 100)),
+            body_leading_blank: Some((Level::Warning, ())),
+            body_max_line_length: Some((Level::Error, 100)),
+            footer_leading_blank: Some((Level::Warning, ())),
+            footer_max_line_length: Some((Level::Error, 100)),
+        }
+    }
+}
+
+/// A commitlint rule value, `[level, "always" | "never", value]`. Level 0 disables it.
+fn rule_parts(v: &serde_json::Value) -> Option<(Option<Level>, bool, Option<&serde_json::Value>)> {
+    let arr = v.as_array()?;
+    let level = match arr.first()?.as_u64()? {
+        0 => None,
+        1 => Some(Level::Warning),
+        _ => Some(Level::Error),
+    };
+    let always = arr.get(1).and_then(|a| a.as_str()).unwrap_or("always") != "never";
+    Some((level, always, arr.get(2)))
+}
+
+fn string_list(v: Option<&serde_json::Value>) -> Vec<String> {
+    v.and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+/// Applies the rules Graphoria understands; other rules and `extends` are ignored.
+fn apply_commitlint_rules(rules: &mut Rules, config: &serde_json::Value) {
+    let Some(map) = config.get("rules").and_then(|r| r.as_object()) else {
+        return;
+    };
+    for (name, v) in map {
+        let Some((level, always, value)) = rule_parts(v) else {
+            continue;
+        };
+        let number = value.and_then(|v| v.as_u64()).map(|n| n as usize);
+        match name.as_str() {
+            "type-enum" => rules.type_enum = level.filter(|_| always).map(|l| (l, string_list(value))),
+            "type-empty" => rules.type_empty = level.filter(|_| !always).map(|l| (l, ())),
+            "scope-enum" => rules.scope_enum = level.filter(|_| always).map(|l| (l, string_list(value))),
+            "scope-empty" => rules.scope_empty = level.map(|l| (l, !always)),
+            "subject-empty" => rules.subject_empty = level.filter(|_| !always).map(|l| (l, ())),
+            "subject-full-stop" => {
+                let stop = value.and_then(|v| v.as_str()).unwrap_or(".").to_string();
+                rules.subject_full_stop = level.filter(|_| !always).map(|l| (l, stop));
+            }
+            "header-max-length" => rules.header_max_length = level.zip(number),
+            "body-leading-blank" => rules.body_leading_blank = level.filter(|_| always).map(|l| (l, ())),
+            "body-max-line-length" => rules.body_max_line_length = level.zip(number),
+            "footer-leading-blank" => rules.footer_leading_blank = level.filter(|_| always).map(|l| (l, ())),
+            "footer-max-line-length" => rules.footer_max_line_length = level.zip(number),
+            _ => {}
+        }
+    }
+}
+
+fn read_repo_config(repo_path: &str) -> Option<(String, serde_json::Value)> {
+    CONFIG_FILES.iter().find_map(|name| {
+        let path = Path::new(repo_path).join(name);
+        let raw = fs::read_to_string(&path).ok()?;
+        let value = serde_json::from_str(&raw).ok()?;
+        Some((path.to_string_lossy().to_string(), value))
+    })
+}
+
+/// Level of a rule the caller overrides; rules that were off become errors.
+fn level<T>(rule: &Rule<T>) -> Level {
+    rule.as_ref().map(|(l, _)| *l).unwrap_or(Level::Error)
+}
+
+fn apply_config(rules: &mut Rules, config: &CommitLintConfig) {
+    if let Some(types) = &config.types {
+        rules.type_enum = (!types.is_empty()).then(|| (level(&rules.type_enum), types.clone()));
+    }
+    if let Some(scopes) = &config.scopes {
+        rules.scope_enum = (!scopes.is_empty()).then(|| (level(&rules.scope_enum), scopes.clone()));
+    }
+    if let Some(required) = config.scope_required {
+        rules.scope_empty = required.then_some((Level::Error, true));
+    }
+    if let Some(n) = config.header_max_length {
+        rules.header_max_length = (n > 0).then(|| (level(&rules.header_max_length), n));
+    }
+    if let Some(n) = config.body_max_line_length {
+        rules.body_max_line_length = (n > 0).then(|| (level(&rules.body_max_line_length), n));
+    }
+}
+
+pub(crate) struct Header {
+    pub(crate) commit_type: String,
+    scope: Option<String>,
+    pub(crate) breaking: bool,
+    subject: String,
+}
+
+/// `type(scope)!: subject`; `None` when the header does not have that shape.
+pub(crate) fn parse_header(header: &str) -> Option<Header> {
+    let (prefix, subject) = header.split_once(':')?;
+    let (prefix, breaking) = match prefix.strip_suffix('!') {
+        Some(p) => (p, true),
+        None => (prefix, false),
+    };
+    let (commit_type, scope) = match prefix.split_once('(') {
+        Some((t, rest)) => (t, Some(rest.strip_suffix(')')?.to_string())),
+        None => (prefix, None),
+    };
+    if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return None;
+    }
+    Some(Header {
+        commit_type: commit_type.to_string(),
+        scope,
+        breaking,
+        subject: subject.trim().to_string(),
+    })
+}
+
+fn is_footer_line(line: &str) -> bool {
+    if line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:") {
+        return true;
+    }
+    let token = line.split_once(": ").or_else(|| line.split_once(" #")).map(|(t, _)| t);
+    token.is_some_and(|t| !t.is_empty() && t.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+}
+
+fn lint(message: &str, rules: &Rules) -> CommitLintReport {
+    let lines: Vec<&str> = message
+        .lines()
+        .map(|l| l.trim_end())
+        .filter(|l| !l.starts_with('#'))
+        .collect();
+    let first = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(0);
+    let lines = &lines[first..];
+    let header = lines.first().copied().unwrap_or("");
+
+    let mut report = CommitLintReport {
+        valid: true,
+        ignored: false,
+        commit_type: None,
+        scope: None,
+        breaking: false,
+        subject: None,
+        violations: Vec::new(),
+        config_file: None,
+    };
+    if ["Merge ", "Revert ", "fixup! ", "squash! ", "amend! "].iter().any(|p| header.starts_with(p)) {
+        report.ignored = true;
+        return report;
+    }
+
+    let mut violations: Vec<CommitLintViolation> = Vec::new();
+    let mut push = |rule: &str, level: Level, message: String, line: Option<usize>| {
+        violations.push(CommitLintViolation {
+            rule: rule.to_string(),
+            level: String::from(if level == Level::Error { "error" } else { "warning" }),
+            message,
+            line: line.map(|l| (l + first + 1) as u32),
+        });
+    };
+
+    if let Some((level, max)) = rules.header_max_length.filter(|(_, max)| header.chars().count() > *max) {
+        push("header-max-length", level, format!("Header is longer than {max} characters."), Some(0));
+    }
+
+    match parse_header(header) {
+        None => {
+            if let Some((level, _)) = rules.type_empty {
+                push("type-empty", level, String::from("Header must start with a type, e.g. \"feat: ...\"."), Some(0));
+            }
+            if let Some((level, _)) = rules.subject_empty {
+                push("subject-empty", level, String::from("Subject may not be empty."), Some(0));
+            }
+        }
+        Some(h) => {
+            match &rules.type_enum {
+                Some((level, types)) if !types.is_empty() && !types.contains(&h.commit_type) => push(
+                    "type-enum",
+                    *level,
+                    format!("Type \"{}\" is not one of: {}.", h.commit_type, types.join(", ")),
+                    Some(0),
+                ),
+                _ => {}
+            }
+            match (&h.scope, rules.scope_empty) {
+                (None, Some((level, true))) => push("scope-empty", level, String::from("Scope is required."), Some(0)),
+                (Some(_), Some((level, false))) => push("scope-empty", level, String::from("Scope is not allowed."), Some(0)),
+                _ => {}
+            }
+            match (&h.scope, &rules.scope_enum) {
+                (Some(scope), Some((level, scopes)))
+                    if !scopes.is_empty() && !scope.split([',', '/']).all(|s| scopes.iter().any(|a| a == s.trim())) =>
+                {
+                    push("scope-enum", *level, format!("Scope \"{scope}\" is not one of: {}.", scopes.join(", ")), Some(0))
+                }
+                _ => {}
+            }
+            match (&rules.subject_empty, &rules.subject_full_stop) {
+                (Some((level, _)), _) if h.subject.is_empty() => {
+                    push("subject-empty", *level, String::from("Subject may not be empty."), Some(0))
+                }
+                (_, Some((level, stop))) if !h.subject.is_empty() && h.subject.ends_with(stop.as_str()) => {
+                    push("subject-full-stop", *level, format!("Subject may not end with \"{stop}\"."), Some(0))
+                }
+                _ => {}
+            }
+            report.commit_type = Some(h.commit_type);
+            report.scope = h.scope;
+            report.breaking = h.breaking;
+            report.subject = Some(h.subject);
+        }
+    }
+
+    if lines.len() > 1 {
+        match rules.body_leading_blank {
+            Some((level, _)) if !lines[1].trim().is_empty() => push(
+                "body-leading-blank",
+                level,
+                String::from("Body must be separated from the header by a blank line."),
+                Some(1),
+            ),
+            _ => {}
+        }
+
+        // The footer is the last paragraph when all its lines are trailers.
+        let rest = &lines[1..];
+        let footer_start = rest
+            .iter()
+            .rposition(|l| l.trim().is_empty())
+            .map(|i| i + 1)
+            .filter(|&i| i < rest.len() && rest[i..].iter().all(|l| is_footer_line(l) || l.starts_with(' ')))
+            .or_else(|| (rest.len() == 1 && is_footer_line(rest[0])).then_some(0));
+        let body_end = footer_start.unwrap_or(rest.len());
+
+        if let Some((level, max)) = rules.body_max_line_length {
+            for (i, l) in rest[..body_end].iter().enumerate() {
+                if l.chars().count() > max {
+                    push("body-max-line-length", level, format!("Body line is longer than {max} characters."), Some(i + 1));
+                }
+            }
+        }
+        if let Some(start) = footer_start {
+            match rules.footer_leading_blank {
+                Some((level, _)) if start > 0 && !rest[start - 1].trim().is_empty() => push(
+                    "footer-leading-blank",
+                    level,
+                    String::from("Footer must be separated by a blank line."),
+                    Some(start + 1),
+                ),
+                _ => {}
+            }
+            for (i, l) in rest[start..].iter().enumerate() {
+                if l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:") {
+                    report.breaking = true;
+                }
+                if let Some((level, max)) = rules.footer_max_line_length.filter(|(_, max)| l.chars().count() > *max) {
+                    push(
+                        "footer-max-line-length",
+                        level,
+                        format!("Footer line is longer than {max} characters."),
+                        Some(start + i + 1),
+                    );
+                }
+            }
+        }
+    }
+
+    report.valid = !violations.iter().any(|v| v.level == "error");
+    report.violations = violations;
+    report
+}
+
+/// Checks `message` against Conventional Commits: type, scope, subject, header length and
+/// body/footer wrapping. With `repo_path`, the repository's commitlint JSON config applies.
+#[tauri::command]
+pub(crate) fn validate_commit_message(
+    repo_path: Option<String>,
+    message: String,
+    config: Option<CommitLintConfig>,
+) -> Result<CommitLintReport, String> {
+    let config = config.unwrap_or_default();
+    let mut rules = Rules::default();
+
+    let mut config_file: Option<String> = None;
+    let repo_path = repo_path.filter(|p| !p.trim().is_empty() && config.use_repo_config.unwrap_or(true));
+    if let Some(repo_path) = repo_path {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        if let Some((file, value)) = read_repo_config(&repo_path) {
+            apply_commitlint_rules(&mut rules, &value);
+            config_file = Some(file);
+        }
+    }
+    apply_config(&mut rules, &config);
+
+    let mut report = lint(&message, &rules);
+    report.config_file = config_file;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_of(report: &CommitLintReport) -> Vec<&str> {
+        report.violations.iter().map(|v| v.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_header_reads_type_scope_and_breaking_marker() {
+        let h = parse_header("feat(api)!: add endpoint").unwrap();
+        assert_eq!(h.commit_type, "feat");
+        assert_eq!(h.scope.as_deref(), Some("api"));
+        assert!(h.breaking);
+        assert_eq!(h.subject, "add endpoint");
+
+        let h = parse_header("fix: typo").unwrap();
+        assert_eq!(h.scope, None);
+        assert!(!h.breaking);
+
+        assert!(parse_header("Update readme").is_none());
+        assert!(parse_header("feat(api: missing paren").is_none());
+        assert!(parse_header("two words: nope").is_none());
+    }
+
+    #[test]
+    fn test_lint_accepts_conventional_message_with_breaking_footer() {
+        let report = lint("feat(ui): add dark mode\n\nLong explanation.\n\nBREAKING CHANGE: themes moved\nRefs: #12\n", &Rules::default());
+        assert!(report.valid, "{:?}", rules_of(&report));
+        assert!(report.violations.is_empty());
+        assert!(report.breaking);
+        assert_eq!(report.commit_type.as_deref(), Some("feat"));
+    }
+
+    #[test]
+    fn test_lint_reports_type_subject_and_body_violations() {
+        let report = lint("feature: done.\nno blank line", &Rules::default());
+        assert!(!report.valid);
+        assert_eq!(rules_of(&report), ["type-enum", "subject-full-stop", "body-leading-blank"]);
+        let blank = report.violations.iter().find(|v| v.rule == "body-leading-blank").unwrap();
+        assert_eq!(blank.level, "warning");
+        assert_eq!(blank.line, Some(2));
+
+        let report = lint("just words", &Rules::default());
+        assert_eq!(rules_of(&report), ["type-empty", "subject-empty"]);
+    }
+
+    #[test]
+    fn test_lint_skips_merge_and_fixup_messages() {
+        assert!(lint("Merge branch 'main'", &Rules::default()).ignored);
+        assert!(lint("fixup! feat: x", &Rules::default()).ignored);
+        assert!(!lint("feat: x", &Rules::default()).ignored);
+    }
+
+    #[test]
+    fn test_commitlint_rules_and_ui_config_override_defaults() {
+        let mut rules = Rules::default();
+        let config = serde_json::json!({
+            "rules": {
+                "type-enum": [2, "always", ["feat", "wip"]],
+                "scope-enum": [1, "always", ["core"]],
+                "header-max-length": [0, "always", 72],
+            }
+        });
+        apply_commitlint_rules(&mut rules, &config);
+        assert!(rules.header_max_length.is_none());
+
+        let report = lint("wip(docs): draft", &rules);
+        assert!(report.valid);
+        assert_eq!(rules_of(&report), ["scope-enum"]);
+        assert_eq!(report.violations[0].level, "warning");
+
+        apply_config(&mut rules, &CommitLintConfig { scope_required: Some(true), ..Default::default() });
+        assert_eq!(rules_of(&lint("wip: draft", &rules)), ["scope-empty"]);
+    }
+}
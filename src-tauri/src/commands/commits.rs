@@ -1,13 +1,337 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRepoAuthor {
+    name: String,
+    email: String,
+    commit_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitCommitSignature {
+    /// "good" | "untrusted" | "unsigned" | "bad" | "expired" | "revoked" | "unchecked"
+    status: String,
+    signer: Option<String>,
+    key: Option<String>,
+    problem: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitCommitDetails {
+    hash: String,
+    short_hash: String,
+    parents: Vec<String>,
+    author_name: String,
+    author_email: String,
+    author_date: String,
+    committer_name: String,
+    committer_email: String,
+    committer_date: String,
+    subject: String,
+    /// The message after the subject line.
+    body: String,
+    /// Branches and tags pointing at the commit, e.g. "HEAD -> main", "tag: v1.0".
+    refs: Vec<String>,
+    signature: GitCommitSignature,
+    /// Against the first parent.
+    files_changed: u32,
+    insertions: u32,
+    deletions: u32,
+}
+
+// Cached authors per repo, with a fingerprint of the ref tips they were read from.
+type RepoAuthorsCache = HashMap<String, (String, Vec<GitRepoAuthor>)>;
+
+static REPO_AUTHORS_CACHE: OnceLock<Mutex<RepoAuthorsCache>> = OnceLock::new();
+
+fn repo_authors_cache() -> &'static Mutex<RepoAuthorsCache> {
+    REPO_AUTHORS_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cached_repo_authors(key: &str, fingerprint: &str) -> Option<Vec<GitRepoAuthor>> {
+    let guard = repo_authors_cache().lock().ok()?;
+    guard
+        .get(key)
+        .filter(|(fp, _)| fp == fingerprint)
+        .map(|(_, authors)| authors.clone())
+}
+
+fn authors_fingerprint(repo_path: &str) -> String {
+    let mut fp = crate::run_git(
+        repo_path,
+        &["for-each-ref", "--format=%(objectname)", "refs/heads", "refs/remotes", "refs/tags"],
+    )
+    .unwrap_or_default();
+
+    // Editing .mailmap changes the result without moving any ref.
+    let mailmap_mtime = std::fs::metadata(std::path::Path::new(repo_path).join(".mailmap"))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    fp.push_str(format!("\nmailmap {mailmap_mtime}").as_str());
+    fp
+}
+
+fn read_repo_authors(repo_path: &str) -> Vec<GitRepoAuthor> {
+    // shortlog applies .mailmap when printing emails.
+    let raw = crate::run_git(repo_path, &["shortlog", "-sne", "--branches", "--tags", "--remotes"]).unwrap_or_default();
+
+    let mut out: Vec<GitRepoAuthor> = Vec::new();
+    for line in raw.lines() {
+        let Some((count, ident)) = line.trim().split_once('\t') else {
+            continue;
+        };
+        let commit_count = count.trim().parse::<u32>().unwrap_or(0);
+        let ident = ident.trim();
+        let (name, email) = match ident.rfind(" <") {
+            Some(i) if ident.ends_with('>') => (ident[..i].trim(), &ident[i + 2..ident.len() - 1]),
+            _ => (ident, ""),
+        };
+        if name.is_empty() && email.is_empty() {
+            continue;
+        }
+        out.push(GitRepoAuthor {
+            name: name.to_string(),
+            email: email.to_string(),
+            commit_count,
+        });
+    }
+
+    out.sort_by(|a, b| b.commit_count.cmp(&a.commit_count).then_with(|| a.name.cmp(&b.name)));
+    out
+}
+
+#[tauri::command]
+pub(crate) fn list_repo_authors(repo_path: String, refresh: Option<bool>) -> Result<Vec<GitRepoAuthor>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let key = crate::normalize_repo_path(&repo_path);
+    let fingerprint = authors_fingerprint(&repo_path);
+
+    let cached = if refresh.unwrap_or(false) {
+        None
+    } else {
+        cached_repo_authors(key.as_str(), fingerprint.as_str())
+    };
+    if let Some(authors) = cached {
+        return Ok(authors);
+    }
+
+    let authors = read_repo_authors(&repo_path);
+    if let Ok(mut guard) = repo_authors_cache().lock() {
+        guard.insert(key, (fingerprint, authors.clone()));
+    }
+    Ok(authors)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitCoAuthorSuggestion {
+    name: String,
+    email: String,
+    /// `Name <email>`, ready to pass as a co-author.
+    co_author: String,
+    commit_count: u32,
+    /// Unix time of the most recent commit they authored or co-authored.
+    last_seen: i64,
+}
+
+/// Splits `Name <email>` into its parts.
+fn split_co_author(raw: &str) -> Option<(&str, &str)> {
+    raw.trim()
+        .strip_suffix('>')
+        .and_then(|r| r.rsplit_once('<'))
+        .map(|(name, email)| (name.trim(), email.trim()))
+        .filter(|(name, email)| !name.is_empty() && email.contains('@') && !email.contains(char::is_whitespace))
+}
+
+/// Normalizes `Name <email>`; anything else is rejected so trailers stay parseable.
+fn parse_co_author(raw: &str) -> Result<String, String> {
+    match split_co_author(raw) {
+        Some((name, email)) => Ok(format!("{name} <{email}>")),
+        None => Err(format!("Invalid co-author: {}. Expected \"Name <email>\".", raw.trim())),
+    }
+}
+
+/// Appends a `Co-authored-by:` trailer per co-author to `message`, skipping ones already
+/// present. `git interpret-trailers` decides where the trailer block goes.
+pub(crate) fn add_co_author_trailers(repo_path: &str, message: &str, co_authors: Option<&[String]>) -> Result<String, String> {
+    let co_authors: Vec<String> = co_authors
+        .unwrap_or_default()
+        .iter()
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| parse_co_author(c))
+        .collect::<Result<_, _>>()?;
+    if co_authors.is_empty() {
+        return Ok(message.to_string());
+    }
+
+    let trailers: Vec<String> = co_authors.iter().map(|c| format!("Co-authored-by: {c}")).collect();
+    let mut args: Vec<&str> = vec!["interpret-trailers", "--if-exists", "addIfDifferent"];
+    for t in &trailers {
+        args.push("--trailer");
+        args.push(t.as_str());
+    }
+    // Without a final newline the trailer is glued to the last line.
+    let mut input = message.trim_end().to_string();
+    input.push('\n');
+    let mut out = crate::run_git_with_stdin(repo_path, &args, &input)?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// People who recently authored or co-authored commits, most recent first, for the
+/// co-author picker. The current user is left out.
+#[tauri::command]
+pub(crate) fn git_suggest_co_authors(
+    repo_path: String,
+    query: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<GitCoAuthorSuggestion>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let raw = crate::run_git(
+        &repo_path,
+        &[
+            "log",
+            "--branches",
+            "-n",
+            "500",
+            "--format=%aN%x00%aE%x00%at%x00%(trailers:key=Co-authored-by,valueonly,separator=%x01)%x1e",
+        ],
+    )
+    .unwrap_or_default();
+    let me = crate::run_git(&repo_path, &["config", "--get", "user.email"])
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+
+    let mut by_email: HashMap<String, GitCoAuthorSuggestion> = HashMap::new();
+    let mut add = |name: &str, email: &str, at: i64| {
+        let key = email.trim().to_lowercase();
+        if key.is_empty() || key == me {
+            return;
+        }
+        let entry = by_email.entry(key).or_insert_with(|| GitCoAuthorSuggestion {
+            name: name.trim().to_string(),
+            email: email.trim().to_string(),
+            co_author: format!("{} <{}>", name.trim(), email.trim()),
+            commit_count: 0,
+            last_seen: at,
+        });
+        entry.commit_count += 1;
+        entry.last_seen = entry.last_seen.max(at);
+    };
+    for record in raw.split('\x1e') {
+        let f: Vec<&str> = record.trim_start_matches('\n').split('\0').collect();
+        if f.len() < 4 {
+            continue;
+        }
+        let at = f[2].trim().parse::<i64>().unwrap_or(0);
+        add(f[0], f[1], at);
+        for (name, email) in f[3].split('\x01').filter_map(split_co_author) {
+            add(name, email, at);
+        }
+    }
+
+    let query = query.unwrap_or_default().trim().to_lowercase();
+    let mut out: Vec<GitCoAuthorSuggestion> = by_email
+        .into_values()
+        .filter(|s| query.is_empty() || s.name.to_lowercase().contains(&query) || s.email.to_lowercase().contains(&query))
+        .collect();
+    out.sort_by(|a, b| b.last_seen.cmp(&a.last_seen).then_with(|| b.commit_count.cmp(&a.commit_count)));
+    out.truncate(limit.unwrap_or(10) as usize);
+    Ok(out)
+}
+
+const WIP_NODE_ID: &str = "wip:uncommitted";
+
+/// Synthetic "Uncommitted changes" node on top of HEAD, or `None` for a clean worktree.
+/// Staged, unstaged and untracked counts come from one porcelain status pass and are
+/// carried in `wip` as well as in the subject.
+fn wip_commit(repo_path: &str) -> Option<crate::GitCommit> {
+    let raw = crate::run_git_stdout_raw(repo_path, &["status", "--porcelain=v1", "-z"]).ok()?;
+
+    let mut staged = 0u32;
+    let mut unstaged = 0u32;
+    let mut untracked = 0u32;
+    let mut records = raw.split('\0');
+    while let Some(rec) = records.next() {
+        let mut chars = rec.chars();
+        let (Some(x), Some(y)) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        if x == 'R' || x == 'C' {
+            // Renames and copies are followed by their source path.
+            records.next();
+        }
+        if x == '?' {
+            untracked += 1;
+            continue;
+        }
+
+        let unmerged = x == 'U' || y == 'U' || (x == 'A' && y == 'A') || (x == 'D' && y == 'D');
+        if !unmerged && x != ' ' {
+            staged += 1;
+        }
+        if unmerged || y != ' ' {
+            unstaged += 1;
+        }
+    }
+
+    if staged == 0 && unstaged == 0 && untracked == 0 {
+        return None;
+    }
+
+    let head = crate::resolve_commit(repo_path, "HEAD");
+    Some(crate::GitCommit {
+        hash: String::from(WIP_NODE_ID),
+        parents: head.into_iter().collect(),
+        author: String::from("(wip)"),
+        author_email: String::new(),
+        date: String::new(),
+        committer_date: String::new(),
+        subject: format!("Uncommitted changes ({staged} staged, {unstaged} unstaged, {untracked} untracked)"),
+        refs: String::new(),
+        is_head: false,
+        is_pushed: false,
+        wip: Some(crate::GitWipCounts {
+            staged,
+            unstaged,
+            untracked,
+        }),
+    })
+}
+
+fn with_wip_commit(repo_path: &str, include_wip: Option<bool>, mut commits: Vec<crate::GitCommit>) -> Vec<crate::GitCommit> {
+    if let Some(wip) = include_wip.unwrap_or(false).then(|| wip_commit(repo_path)).flatten() {
+        commits.insert(0, wip);
+    }
+    commits
+}
+
 #[tauri::command]
 pub(crate) fn list_commits(
     repo_path: String,
     max_count: Option<u32>,
     only_head: Option<bool>,
     history_order: Option<String>,
+    first_parent: Option<bool>,
+    include_wip: Option<bool>,
 ) -> Result<Vec<crate::GitCommit>, String> {
     let max_count = max_count.unwrap_or(200).min(2001);
     let history_order = history_order.unwrap_or_else(|| String::from("topo"));
-    crate::list_commits_impl_v2(&repo_path, Some(max_count), only_head.unwrap_or(false), &history_order)
+    let commits = crate::list_commits_impl_v2(
+        &repo_path,
+        Some(max_count),
+        only_head.unwrap_or(false),
+        &history_order,
+        first_parent.unwrap_or(false),
+    )?;
+    Ok(with_wip_commit(&repo_path, include_wip, commits))
 }
 
 #[tauri::command]
@@ -15,7 +339,123 @@ pub(crate) fn list_commits_full(
     repo_path: String,
     only_head: Option<bool>,
     history_order: Option<String>,
+    first_parent: Option<bool>,
+    include_wip: Option<bool>,
 ) -> Result<Vec<crate::GitCommit>, String> {
     let history_order = history_order.unwrap_or_else(|| String::from("topo"));
-    crate::list_commits_impl_v2(&repo_path, None, only_head.unwrap_or(false), &history_order)
+    let commits = crate::list_commits_impl_v2(
+        &repo_path,
+        None,
+        only_head.unwrap_or(false),
+        &history_order,
+        first_parent.unwrap_or(false),
+    )?;
+    Ok(with_wip_commit(&repo_path, include_wip, commits))
+}
+
+/// Commits a merge brought in from its side branches, i.e. the ones a first-parent graph
+/// collapses into the merge node.
+#[tauri::command]
+pub(crate) fn list_merge_side_commits(
+    repo_path: String,
+    merge: String,
+    max_count: Option<u32>,
+) -> Result<Vec<crate::GitCommit>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let merge = merge.trim().to_string();
+    if merge.is_empty() {
+        return Err(String::from("merge is empty"));
+    }
+
+    let parents_spec = format!("{merge}^@");
+    let parents_raw = crate::run_git(&repo_path, &["rev-parse", parents_spec.as_str()])?;
+    let parents: Vec<&str> = parents_raw.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+    let Some((mainline, side)) = parents.split_first().filter(|(_, side)| !side.is_empty()) else {
+        return Err(String::from("Commit is not a merge."));
+    };
+
+    let mut revs: Vec<String> = vec![format!("^{mainline}")];
+    revs.extend(side.iter().map(|p| p.to_string()));
+    crate::git_log_commits_decorated(&repo_path, &revs, max_count.unwrap_or(200).min(2001))
+}
+
+/// Counts from a `--shortstat` line: " 3 files changed, 10 insertions(+), 2 deletions(-)".
+fn parse_shortstat(line: &str) -> (u32, u32, u32) {
+    let (mut files, mut insertions, mut deletions) = (0, 0, 0);
+    for part in line.split(',') {
+        let mut words = part.split_whitespace();
+        let n = words.next().and_then(|n| n.parse::<u32>().ok()).unwrap_or(0);
+        match words.next() {
+            Some(w) if w.starts_with("file") => files = n,
+            Some(w) if w.starts_with("insertion") => insertions = n,
+            Some(w) if w.starts_with("deletion") => deletions = n,
+            _ => {}
+        }
+    }
+    (files, insertions, deletions)
+}
+
+/// Everything the commit panel shows about one commit: the full message, author and
+/// committer, parents, refs, signature and the size of the change against the first
+/// parent.
+#[tauri::command]
+pub(crate) fn git_commit_details(repo_path: String, hash: String) -> Result<GitCommitDetails, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let hash = hash.trim().to_string();
+    if hash.is_empty() {
+        return Err(String::from("hash is empty"));
+    }
+    let commit = crate::resolve_commit(&repo_path, hash.as_str()).ok_or_else(|| format!("Commit not found: {hash}"))?;
+
+    let raw = crate::run_git_stdout_raw(
+        &repo_path,
+        &[
+            "log",
+            "-1",
+            "--no-color",
+            "--format=%H%x00%h%x00%P%x00%an%x00%ae%x00%aI%x00%cn%x00%ce%x00%cI%x00%D%x00%G?%x00%GS%x00%GK%x00%s%x00%b",
+            commit.as_str(),
+        ],
+    )?;
+    let f: Vec<&str> = raw.splitn(15, '\0').collect();
+    if f.len() < 15 {
+        return Err(format!("Failed to read commit {hash}."));
+    }
+
+    let parents: Vec<String> = f[2].split_whitespace().map(String::from).collect();
+    let stat_args: Vec<&str> = match parents.first() {
+        Some(parent) => vec!["diff-tree", "-r", "--shortstat", parent.as_str(), commit.as_str()],
+        None => vec!["diff-tree", "-r", "--root", "--no-commit-id", "--shortstat", commit.as_str()],
+    };
+    let stat = crate::run_git(&repo_path, &stat_args).unwrap_or_default();
+    let (files_changed, insertions, deletions) = parse_shortstat(stat.trim());
+
+    let (status, problem) = super::signing::signature_status(f[10].trim());
+    let optional = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+
+    Ok(GitCommitDetails {
+        hash: f[0].trim().to_string(),
+        short_hash: f[1].trim().to_string(),
+        parents,
+        author_name: f[3].to_string(),
+        author_email: f[4].to_string(),
+        author_date: f[5].to_string(),
+        committer_name: f[6].to_string(),
+        committer_email: f[7].to_string(),
+        committer_date: f[8].to_string(),
+        refs: f[9].split(", ").map(|r| r.trim().to_string()).filter(|r| !r.is_empty()).collect(),
+        signature: GitCommitSignature {
+            status: status.to_string(),
+            signer: optional(f[11]),
+            key: optional(f[12]),
+            problem: problem.map(String::from),
+        },
+        subject: f[13].to_string(),
+        body: f[14].trim_end().to_string(),
+        files_changed,
+        insertions,
+        deletions,
+    })
 }
@@ -0,0 +1,493 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitConfigEntry {
+    key: String,
+    value: String,
+    /// `system`, `global`, `local`, `worktree` or `command`.
+    scope: String,
+    /// Where git read the value from, as printed by `--show-origin` (`file:<path>`,
+    /// `command line:` ...).
+    origin: String,
+    /// Absolute path of the config file for `file:` origins.
+    file: Option<String>,
+}
+
+/// `None` reads the effective configuration; writes always need an explicit scope.
+fn scope_flag(scope: Option<&str>) -> Result<Option<&'static str>, String> {
+    match scope.map(|s| s.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("all") => Ok(None),
+        Some("system") => Ok(Some("--system")),
+        Some("global") => Ok(Some("--global")),
+        Some("local") | Some("repo") => Ok(Some("--local")),
+        Some("worktree") => Ok(Some("--worktree")),
+        Some(other) => Err(format!("Invalid config scope: {other}")),
+    }
+}
+
+/// `git config` in the repository, or outside of any repository when there is none.
+fn git_config_command(repo_path: Option<&str>) -> Command {
+    let mut cmd = match repo_path {
+        Some(repo_path) => crate::git_command_in_repo(repo_path),
+        None => crate::new_command("git"),
+    };
+    cmd.arg("config");
+    cmd
+}
+
+/// A git command for the repository, or for the user's config when there is none.
+fn config_command(repo_path: Option<&str>, scope: Option<&str>) -> Result<Command, String> {
+    let needs_repo = matches!(scope, Some("--local") | Some("--worktree"));
+    match repo_path {
+        Some(repo_path) => crate::ensure_is_git_worktree(repo_path)?,
+        None if needs_repo => return Err(String::from("repo_path is required for this scope.")),
+        None => {}
+    }
+    let mut cmd = git_config_command(repo_path);
+    if let Some(flag) = scope {
+        cmd.arg(flag);
+    }
+    Ok(cmd)
+}
+
+fn run_config(mut cmd: Command) -> Result<(bool, i32, String, String), String> {
+    let out = cmd.output().map_err(|e| format!("Failed to spawn git config: {e}"))?;
+    Ok((
+        out.status.success(),
+        out.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&out.stdout).to_string(),
+        String::from_utf8_lossy(&out.stderr).trim_end().to_string(),
+    ))
+}
+
+fn config_error(stderr: String) -> String {
+    if !stderr.is_empty() {
+        format!("git config failed: {stderr}")
+    } else {
+        String::from("git config failed.")
+    }
+}
+
+/// Runs `git config <args>` with the repository's effective config, or on the global
+/// config when `repo_path` is None. Returns stdout without the trailing newline.
+pub(crate) fn run_git_config(repo_path: Option<&str>, args: &[&str]) -> Result<String, String> {
+    let mut cmd = git_config_command(repo_path);
+    if repo_path.is_none() {
+        cmd.arg("--global");
+    }
+    cmd.args(args);
+    let (ok, _, stdout, stderr) = run_config(cmd)?;
+    if !ok {
+        return Err(config_error(stderr));
+    }
+    Ok(stdout.trim_end().to_string())
+}
+
+/// The value of `key`, or None when it is unset or empty.
+pub(crate) fn git_config_value(repo_path: Option<&str>, key: &str) -> Option<String> {
+    let value = run_git_config(repo_path, &["--get", key]).ok()?;
+    let value = value.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Writes to the repository's own config are refused in read-only mode; global and
+/// system config stay writable.
+fn ensure_scope_writable(repo_path: Option<&str>, flag: &str) -> Result<(), String> {
+    match repo_path {
+        Some(repo_path) if matches!(flag, "--local" | "--worktree") => super::read_only::ensure_writable(repo_path),
+        _ => Ok(()),
+    }
+}
+
+fn normalize_repo(repo_path: Option<String>) -> Option<String> {
+    repo_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty())
+}
+
+fn normalize_key(key: String) -> Result<String, String> {
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return Err(String::from("key is empty"));
+    }
+    Ok(key)
+}
+
+/// Parses `--show-scope --show-origin -z` output: `scope NUL origin NUL key LF value NUL`,
+/// where a key without a value has no LF. With `--get-all` only the value is printed, so
+/// the key is passed in as `get_key`.
+fn parse_config_entries(raw: &str, repo_path: Option<&str>, get_key: Option<&str>) -> Vec<GitConfigEntry> {
+    let mut out: Vec<GitConfigEntry> = Vec::new();
+    let mut fields = raw.split('\0');
+    while let (Some(scope), Some(origin), Some(kv)) = (fields.next(), fields.next(), fields.next()) {
+        if scope.is_empty() {
+            break;
+        }
+        let (key, value) = match get_key {
+            Some(key) => (key, kv),
+            None => kv.split_once('\n').unwrap_or((kv, "")),
+        };
+        let file = origin.strip_prefix("file:").map(|f| {
+            let p = PathBuf::from(f);
+            let p = match repo_path {
+                Some(repo) if p.is_relative() => Path::new(repo).join(p),
+                _ => p,
+            };
+            p.to_string_lossy().to_string()
+        });
+        out.push(GitConfigEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+            scope: scope.to_string(),
+            origin: origin.to_string(),
+            file,
+        });
+    }
+    out
+}
+
+#[tauri::command]
+pub(crate) fn git_config_list(repo_path: Option<String>, scope: Option<String>) -> Result<Vec<GitConfigEntry>, String> {
+    let repo_path = normalize_repo(repo_path);
+    let flag = scope_flag(scope.as_deref())?;
+    let mut cmd = config_command(repo_path.as_deref(), flag)?;
+    cmd.args(["--list", "--show-scope", "--show-origin", "-z"]);
+    let (ok, _, stdout, stderr) = run_config(cmd)?;
+    if !ok {
+        // A scope whose file does not exist yet simply has no entries.
+        if stderr.contains("unable to read config file") || stderr.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        return Err(config_error(stderr));
+    }
+    Ok(parse_config_entries(stdout.as_str(), repo_path.as_deref(), None))
+}
+
+/// All values of `key` in the scope, in the order git reads them (the last one wins for
+/// single-valued keys). Empty when the key is not set.
+#[tauri::command]
+pub(crate) fn git_config_get(
+    repo_path: Option<String>,
+    key: String,
+    scope: Option<String>,
+) -> Result<Vec<GitConfigEntry>, String> {
+    let repo_path = normalize_repo(repo_path);
+    let key = normalize_key(key)?;
+    let flag = scope_flag(scope.as_deref())?;
+    let mut cmd = config_command(repo_path.as_deref(), flag)?;
+    cmd.args(["--show-scope", "--show-origin", "-z", "--get-all", "--", key.as_str()]);
+    let (ok, code, stdout, stderr) = run_config(cmd)?;
+    if !ok {
+        // Exit code 1 means the key is not set.
+        if code == 1 {
+            return Ok(Vec::new());
+        }
+        return Err(config_error(stderr));
+    }
+    Ok(parse_config_entries(stdout.as_str(), repo_path.as_deref(), Some(key.as_str())))
+}
+
+/// Sets `key` in `scope`. `add` appends another value to a multi-valued key and
+/// `replace_all` replaces every existing value with this one.
+#[tauri::command]
+pub(crate) fn git_config_set(
+    repo_path: Option<String>,
+    key: String,
+    value: String,
+    scope: String,
+    add: Option<bool>,
+    replace_all: Option<bool>,
+) -> Result<(), String> {
+    let repo_path = normalize_repo(repo_path);
+    let key = normalize_key(key)?;
+    let flag = scope_flag(Some(scope.as_str()))?.ok_or_else(|| String::from("scope is required to write config."))?;
+    let mut cmd = config_command(repo_path.as_deref(), Some(flag))?;
+    ensure_scope_writable(repo_path.as_deref(), flag)?;
+    if add.unwrap_or(false) {
+        cmd.arg("--add");
+    } else if replace_all.unwrap_or(false) {
+        cmd.arg("--replace-all");
+    }
+    // `--` keeps values starting with `-` (common in aliases) from being read as options.
+    cmd.args(["--", key.as_str(), value.as_str()]);
+    let (ok, _, _, stderr) = run_config(cmd)?;
+    if !ok {
+        return Err(config_error(stderr));
+    }
+    Ok(())
+}
+
+/// Removes `key` from `scope`; `all` removes every value of a multi-valued key. Unsetting
+/// a key that is not set is not an error.
+#[tauri::command]
+pub(crate) fn git_config_unset(
+    repo_path: Option<String>,
+    key: String,
+    scope: String,
+    all: Option<bool>,
+) -> Result<(), String> {
+    let repo_path = normalize_repo(repo_path);
+    let key = normalize_key(key)?;
+    let flag = scope_flag(Some(scope.as_str()))?.ok_or_else(|| String::from("scope is required to write config."))?;
+    let mut cmd = config_command(repo_path.as_deref(), Some(flag))?;
+    ensure_scope_writable(repo_path.as_deref(), flag)?;
+    cmd.arg(if all.unwrap_or(false) { "--unset-all" } else { "--unset" });
+    cmd.args(["--", key.as_str()]);
+    let (ok, code, _, stderr) = run_config(cmd)?;
+    // Exit code 5 is "key not set".
+    if !ok && code != 5 {
+        return Err(config_error(stderr));
+    }
+    Ok(())
+}
+
+/// A config change that resolves a `GitConfigProblem`, applied with `git_config_apply_fix`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GitConfigFix {
+    /// "set" | "unset"
+    action: String,
+    key: String,
+    value: Option<String>,
+    scope: String,
+    description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitConfigProblem {
+    key: String,
+    value: String,
+    scope: String,
+    file: Option<String>,
+    /// "error" when Graphoria cannot work correctly, "warning" when it may misbehave.
+    severity: String,
+    message: String,
+    fixes: Vec<GitConfigFix>,
+}
+
+/// The value git uses for `key` (the last one read), with where it came from.
+fn effective_entry(repo_path: Option<&str>, key: &str) -> Option<GitConfigEntry> {
+    git_config_get(repo_path.map(String::from), key.to_string(), None)
+        .ok()
+        .and_then(|mut entries| entries.pop())
+}
+
+fn is_true(value: &str) -> bool {
+    // A key without a value (`[diff] noprefix`) is true as well.
+    matches!(value.trim().to_lowercase().as_str(), "" | "true" | "yes" | "on" | "1")
+}
+
+/// Values set with `-c` (by Graphoria or in GIT_CONFIG_PARAMETERS) cannot be changed in
+/// a file, so they get no fixes.
+fn writable_scope(scope: &str) -> bool {
+    matches!(scope, "system" | "global" | "local" | "worktree")
+}
+
+fn problem(entry: GitConfigEntry, severity: &str, message: &str, fixes: Vec<GitConfigFix>) -> GitConfigProblem {
+    let fixes = if writable_scope(&entry.scope) { fixes } else { Vec::new() };
+    GitConfigProblem {
+        key: entry.key,
+        value: entry.value,
+        scope: entry.scope,
+        file: entry.file,
+        severity: severity.to_string(),
+        message: message.to_string(),
+        fixes,
+    }
+}
+
+fn unset_fix(entry: &GitConfigEntry, description: &str) -> GitConfigFix {
+    GitConfigFix {
+        action: String::from("unset"),
+        key: entry.key.clone(),
+        value: None,
+        scope: entry.scope.clone(),
+        description: description.to_string(),
+    }
+}
+
+fn set_fix(entry: &GitConfigEntry, value: &str, description: &str) -> GitConfigFix {
+    GitConfigFix {
+        action: String::from("set"),
+        key: entry.key.clone(),
+        value: Some(value.to_string()),
+        scope: entry.scope.clone(),
+        description: description.to_string(),
+    }
+}
+
+/// Whether gpg has a secret key for `email`, which is what git signs with when
+/// `user.signingkey` is not set.
+fn gpg_has_secret_key(repo_path: Option<&str>, email: &str) -> bool {
+    let program = effective_entry(repo_path, "gpg.program")
+        .map(|e| e.value)
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| String::from("gpg"));
+    crate::new_command(program.trim())
+        .args(["--batch", "--list-secret-keys", "--with-colons", "--", email])
+        .output()
+        .map(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).lines().any(|l| l.starts_with("sec")))
+        .unwrap_or(false)
+}
+
+/// Looks for config settings that break how Graphoria reads git's output or runs its
+/// flows. Each problem names the scope the setting comes from and, when it can be changed
+/// there, fixes for it.
+#[tauri::command]
+pub(crate) fn git_config_check(repo_path: Option<String>) -> Result<Vec<GitConfigProblem>, String> {
+    let repo_path = normalize_repo(repo_path);
+    if let Some(repo) = repo_path.as_deref() {
+        crate::ensure_is_git_worktree(repo)?;
+    }
+    let repo = repo_path.as_deref();
+    let mut out: Vec<GitConfigProblem> = Vec::new();
+
+    if let Some(e) = effective_entry(repo, "core.pager") {
+        let program = e.value.split_whitespace().next().unwrap_or_default().to_lowercase();
+        let program = program.rsplit(['/', '\\']).next().unwrap_or_default().trim_end_matches(".exe").to_string();
+        if !matches!(program.as_str(), "" | "less" | "more" | "cat") {
+            let fixes = vec![unset_fix(&e, "Use the default pager.")];
+            out.push(problem(
+                e,
+                "warning",
+                "core.pager runs a program that rewrites git's output, which cannot be parsed when git pages it.",
+                fixes,
+            ));
+        }
+    }
+
+    for key in ["color.ui", "color.diff", "color.status", "color.branch"] {
+        let Some(e) = effective_entry(repo, key) else {
+            continue;
+        };
+        if e.value.trim().eq_ignore_ascii_case("always") {
+            let fixes = vec![set_fix(&e, "auto", "Only color output for terminals.")];
+            out.push(problem(
+                e,
+                "error",
+                "Color set to \"always\" adds escape codes to output Graphoria parses.",
+                fixes,
+            ));
+        }
+    }
+
+    if let Some(e) = effective_entry(repo, "status.relativePaths").filter(|e| !is_true(&e.value)) {
+        let fixes = vec![unset_fix(&e, "Use the default (paths relative to the current directory).")];
+        out.push(problem(
+            e,
+            "warning",
+            "status.relativePaths is turned off, which changes the paths status reports outside the repository root.",
+            fixes,
+        ));
+    }
+
+    for key in ["diff.noprefix", "diff.mnemonicPrefix"] {
+        let Some(e) = effective_entry(repo, key).filter(|e| is_true(&e.value)) else {
+            continue;
+        };
+        let fixes = vec![unset_fix(&e, "Use the standard a/ and b/ prefixes.")];
+        out.push(problem(
+            e,
+            "error",
+            "Diffs without the standard a/ and b/ path prefixes cannot be parsed, so diffs and patches show wrong file names.",
+            fixes,
+        ));
+    }
+
+    if let Some(e) = effective_entry(repo, "commit.gpgsign").filter(|e| is_true(&e.value)) {
+        let signing_key = effective_entry(repo, "user.signingkey")
+            .map(|k| k.value)
+            .filter(|v| !v.trim().is_empty());
+        let format = effective_entry(repo, "gpg.format")
+            .map(|f| f.value.trim().to_lowercase())
+            .unwrap_or_else(|| String::from("openpgp"));
+        let email = effective_entry(repo, "user.email").map(|m| m.value).unwrap_or_default();
+        let missing = match format.as_str() {
+            // SSH and X.509 signing cannot fall back to the committer identity.
+            "ssh" | "x509" => signing_key.is_none(),
+            _ => signing_key.is_none() && (email.trim().is_empty() || !gpg_has_secret_key(repo, email.trim())),
+        };
+        if missing {
+            let fixes = vec![set_fix(&e, "false", "Stop signing commits until a signing key is set up.")];
+            out.push(problem(
+                e,
+                "error",
+                "commit.gpgsign is on but no signing key is configured, so every commit will fail.",
+                fixes,
+            ));
+        }
+    }
+
+    if let Some(e) = effective_entry(repo, "core.autocrlf") {
+        let value = e.value.trim().to_lowercase();
+        if cfg!(windows) && value == "input" {
+            let fixes = vec![set_fix(&e, "true", "Check out files with CRLF line endings.")];
+            out.push(problem(
+                e,
+                "warning",
+                "core.autocrlf=input on Windows keeps LF line endings in the working tree, so editors that write CRLF make every line show as changed.",
+                fixes,
+            ));
+        } else if !cfg!(windows) && is_true(&value) {
+            let fixes = vec![set_fix(&e, "input", "Keep LF line endings in the working tree.")];
+            out.push(problem(
+                e,
+                "warning",
+                "core.autocrlf=true outside Windows checks files out with CRLF line endings, so files can show as modified right after checkout.",
+                fixes,
+            ));
+        }
+    }
+
+    Ok(out)
+}
+
+/// Applies one of the fixes offered by `git_config_check`.
+#[tauri::command]
+pub(crate) fn git_config_apply_fix(repo_path: Option<String>, fix: GitConfigFix) -> Result<(), String> {
+    match fix.action.as_str() {
+        "set" => {
+            let value = fix.value.ok_or_else(|| String::from("value is required to set config."))?;
+            git_config_set(repo_path, fix.key, value, fix.scope, None, Some(true))
+        }
+        "unset" => git_config_unset(repo_path, fix.key, fix.scope, Some(true)),
+        other => Err(format!("Invalid config fix action: {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_entries_list_output() {
+        let raw = "global\0file:/home/me/.gitconfig\0user.name\nMe Myself\0\
+                   local\0file:.git/config\0core.bare\nfalse\0\
+                   local\0file:.git/config\0remote.origin.mirror\0\
+                   command\0command line:\0color.ui\nnever\0";
+        let entries = parse_config_entries(raw, Some("/work/repo"), None);
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!((entries[0].key.as_str(), entries[0].value.as_str()), ("user.name", "Me Myself"));
+        assert_eq!(entries[0].scope, "global");
+        assert_eq!(entries[0].file.as_deref(), Some("/home/me/.gitconfig"));
+
+        // Relative origins are relative to the repository.
+        assert_eq!(entries[1].file, Some(Path::new("/work/repo").join(".git/config").to_string_lossy().to_string()));
+
+        // A key without a value has no LF.
+        assert_eq!((entries[2].key.as_str(), entries[2].value.as_str()), ("remote.origin.mirror", ""));
+
+        assert_eq!((entries[3].origin.as_str(), entries[3].file.as_deref()), ("command line:", None));
+    }
+
+    #[test]
+    fn test_parse_config_entries_get_all_output() {
+        let raw = "local\0file:.git/config\0+refs/heads/*:refs/remotes/origin/*\0\
+                   local\0file:.git/config\0+refs/tags/*:refs/tags/*\0";
+        let entries = parse_config_entries(raw, None, Some("remote.origin.fetch"));
+        let values: Vec<&str> = entries.iter().map(|e| e.value.as_str()).collect();
+        assert_eq!(values, ["+refs/heads/*:refs/remotes/origin/*", "+refs/tags/*:refs/tags/*"]);
+        assert!(entries.iter().all(|e| e.key == "remote.origin.fetch"));
+        assert_eq!(entries[0].file.as_deref(), Some(".git/config"));
+    }
+}
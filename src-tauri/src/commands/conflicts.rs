@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::process::Stdio;
@@ -14,7 +14,7 @@ fn rev_exists(repo_path: &str, rev: &str) -> bool {
         .is_ok_and(|s| s.success())
 }
 
-fn is_am_in_progress(repo_path: &str) -> bool {
+pub(crate) fn is_am_in_progress(repo_path: &str) -> bool {
     // `git am` uses `.git/rebase-apply` and creates an `applying` file.
     let apply_dir = resolve_git_path(repo_path, "rebase-apply").ok().flatten();
     if let Some(dir) = apply_dir {
@@ -26,6 +26,7 @@ fn is_am_in_progress(repo_path: &str) -> bool {
 #[tauri::command]
 pub(crate) fn git_am_abort(repo_path: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     if !is_am_in_progress(&repo_path) {
         return Err(String::from("No git am in progress."));
     }
@@ -35,6 +36,7 @@ pub(crate) fn git_am_abort(repo_path: String) -> Result<String, String> {
 #[tauri::command]
 pub(crate) fn git_am_continue_with_message(repo_path: String, message: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     if !is_am_in_progress(&repo_path) {
         return Err(String::from("No git am in progress."));
     }
@@ -50,6 +52,7 @@ pub(crate) fn git_am_continue_with_message(repo_path: String, message: String) -
 #[tauri::command]
 pub(crate) fn git_conflict_resolve_rename(repo_path: String, path: String, keep_name: String, keep_content: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let path = path.trim().to_string();
     if path.is_empty() {
@@ -68,63 +71,65 @@ pub(crate) fn git_conflict_resolve_rename(repo_path: String, path: String, keep_
     let ours_path = path.clone();
     let _ = crate::safe_repo_join(&repo_path, ours_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
 
-    crate::with_repo_git_lock(&repo_path, || {
-        let theirs_ref = detect_theirs_ref(&repo_path).ok_or_else(|| String::from("Failed to detect their ref (MERGE_HEAD/REBASE_HEAD)."))?;
-        let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
-        let theirs_path = renames
-            .get(ours_path.as_str())
-            .cloned()
-            .ok_or_else(|| String::from("Failed to detect rename target for this conflict."))?;
+    resolving_conflict(&repo_path, path.as_str(), keep_content.as_str(), || {
+        super::index::with_index_snapshot(&repo_path, || {
+            let theirs_ref = detect_theirs_ref(&repo_path).ok_or_else(|| String::from("Failed to detect their ref (MERGE_HEAD/REBASE_HEAD)."))?;
+            let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
+            let theirs_path = renames
+                .get(ours_path.as_str())
+                .cloned()
+                .ok_or_else(|| String::from("Failed to detect rename target for this conflict."))?;
 
-        let _ = crate::safe_repo_join(&repo_path, theirs_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+            let _ = crate::safe_repo_join(&repo_path, theirs_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
 
-        let content_bytes = if keep_content == "ours" {
-            crate::git_show_path_bytes_or_empty(&repo_path, ":2", ours_path.as_str())?
-        } else {
-            let b = crate::git_show_path_bytes_or_empty(&repo_path, ":3", ours_path.as_str())?;
-            if !b.is_empty() {
-                b
+            let content_bytes = if keep_content == "ours" {
+                crate::git_show_path_bytes_or_empty(&repo_path, ":2", ours_path.as_str())?
             } else {
-                crate::git_show_path_bytes_or_empty(&repo_path, theirs_ref.as_str(), theirs_path.as_str())?
-            }
-        };
+                let b = crate::git_show_path_bytes_or_empty(&repo_path, ":3", ours_path.as_str())?;
+                if !b.is_empty() {
+                    b
+                } else {
+                    crate::git_show_path_bytes_or_empty(&repo_path, theirs_ref.as_str(), theirs_path.as_str())?
+                }
+            };
 
-        if content_bytes.is_empty() {
-            return Err(String::from("Failed to load selected content for rename conflict."));
-        }
-        let content_text = bytes_to_text_or_err(content_bytes.as_slice())?;
+            if content_bytes.is_empty() {
+                return Err(String::from("Failed to load selected content for rename conflict."));
+            }
+            let content_text = bytes_to_text_or_err(content_bytes.as_slice())?;
 
-        let final_path = if keep_name == "ours" {
-            ours_path.clone()
-        } else {
-            theirs_path.clone()
-        };
-        let remove_path = if final_path == ours_path {
-            theirs_path.clone()
-        } else {
-            ours_path.clone()
-        };
+            let final_path = if keep_name == "ours" {
+                ours_path.clone()
+            } else {
+                theirs_path.clone()
+            };
+            let remove_path = if final_path == ours_path {
+                theirs_path.clone()
+            } else {
+                ours_path.clone()
+            };
 
-        let full_final = crate::safe_repo_join(&repo_path, final_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
-        if let Some(parent) = full_final.parent() {
-            fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {e}"))?;
-        }
-        fs::write(&full_final, content_text.as_bytes()).map_err(|e| format!("Failed to write file: {e}"))?;
+            let full_final = crate::safe_repo_join(&repo_path, final_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+            if let Some(parent) = full_final.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {e}"))?;
+            }
+            fs::write(&full_final, content_text.as_bytes()).map_err(|e| format!("Failed to write file: {e}"))?;
 
-        crate::run_git(&repo_path, &["add", "-A", "--", final_path.as_str()])?;
+            crate::run_git(&repo_path, &["add", "-A", "--", final_path.as_str()])?;
 
-        crate::run_git(&repo_path, &["rm", "-f", "--ignore-unmatch", "--", remove_path.as_str()])?;
+            crate::run_git(&repo_path, &["rm", "-f", "--ignore-unmatch", "--", remove_path.as_str()])?;
 
-        let full_remove = crate::safe_repo_join(&repo_path, remove_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
-        if full_remove.exists() {
-            if full_remove.is_dir() {
-                let _ = fs::remove_dir_all(&full_remove);
-            } else {
-                let _ = fs::remove_file(&full_remove);
+            let full_remove = crate::safe_repo_join(&repo_path, remove_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+            if full_remove.exists() {
+                if full_remove.is_dir() {
+                    let _ = fs::remove_dir_all(&full_remove);
+                } else {
+                    let _ = fs::remove_file(&full_remove);
+                }
             }
-        }
 
-        Ok(String::from("ok"))
+            Ok(String::from("ok"))
+        })
     })
 }
 
@@ -136,6 +141,7 @@ pub(crate) fn git_conflict_resolve_rename_with_content(
     content: String,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let path = path.trim().to_string();
     if path.is_empty() {
@@ -149,56 +155,58 @@ pub(crate) fn git_conflict_resolve_rename_with_content(
     let ours_path = path.clone();
     let _ = crate::safe_repo_join(&repo_path, ours_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
 
-    crate::with_repo_git_lock(&repo_path, || {
-        let theirs_ref = detect_theirs_ref(&repo_path).ok_or_else(|| String::from("Failed to detect their ref (MERGE_HEAD/REBASE_HEAD)."))?;
-        let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
-        let theirs_path = renames
-            .get(ours_path.as_str())
-            .cloned()
-            .ok_or_else(|| String::from("Failed to detect rename target for this conflict."))?;
-
-        let _ = crate::safe_repo_join(&repo_path, theirs_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
-
-        let content = content;
-        if content.trim().is_empty() {
-            return Err(String::from("Content is empty."));
-        }
-
-        let final_path = if keep_name == "ours" {
-            ours_path.clone()
-        } else {
-            theirs_path.clone()
-        };
-        let remove_path = if final_path == ours_path {
-            theirs_path.clone()
-        } else {
-            ours_path.clone()
-        };
+    resolving_conflict(&repo_path, path.as_str(), "manual", || {
+        super::index::with_index_snapshot(&repo_path, || {
+            let theirs_ref = detect_theirs_ref(&repo_path).ok_or_else(|| String::from("Failed to detect their ref (MERGE_HEAD/REBASE_HEAD)."))?;
+            let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
+            let theirs_path = renames
+                .get(ours_path.as_str())
+                .cloned()
+                .ok_or_else(|| String::from("Failed to detect rename target for this conflict."))?;
 
-        let full_final = crate::safe_repo_join(&repo_path, final_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
-        if let Some(parent) = full_final.parent() {
-            fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {e}"))?;
-        }
-        fs::write(&full_final, content.as_bytes()).map_err(|e| format!("Failed to write file: {e}"))?;
+            let _ = crate::safe_repo_join(&repo_path, theirs_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
 
-        crate::run_git(&repo_path, &["add", "-A", "--", final_path.as_str()])?;
-        crate::run_git(&repo_path, &["rm", "-f", "--ignore-unmatch", "--", remove_path.as_str()])?;
+            let content = content;
+            if content.trim().is_empty() {
+                return Err(String::from("Content is empty."));
+            }
 
-        let full_remove = crate::safe_repo_join(&repo_path, remove_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
-        if full_remove.exists() {
-            if full_remove.is_dir() {
-                let _ = fs::remove_dir_all(&full_remove);
+            let final_path = if keep_name == "ours" {
+                ours_path.clone()
+            } else {
+                theirs_path.clone()
+            };
+            let remove_path = if final_path == ours_path {
+                theirs_path.clone()
             } else {
-                let _ = fs::remove_file(&full_remove);
+                ours_path.clone()
+            };
+
+            let full_final = crate::safe_repo_join(&repo_path, final_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+            if let Some(parent) = full_final.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {e}"))?;
             }
-        }
+            fs::write(&full_final, content.as_bytes()).map_err(|e| format!("Failed to write file: {e}"))?;
 
-        Ok(String::from("ok"))
+            crate::run_git(&repo_path, &["add", "-A", "--", final_path.as_str()])?;
+            crate::run_git(&repo_path, &["rm", "-f", "--ignore-unmatch", "--", remove_path.as_str()])?;
+
+            let full_remove = crate::safe_repo_join(&repo_path, remove_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+            if full_remove.exists() {
+                if full_remove.is_dir() {
+                    let _ = fs::remove_dir_all(&full_remove);
+                } else {
+                    let _ = fs::remove_file(&full_remove);
+                }
+            }
+
+            Ok(String::from("ok"))
+        })
     })
 }
 
 fn detect_theirs_ref(repo_path: &str) -> Option<String> {
-    for r in ["MERGE_HEAD", "CHERRY_PICK_HEAD", "REBASE_HEAD"] {
+    for r in ["MERGE_HEAD", "CHERRY_PICK_HEAD", "REVERT_HEAD", "REBASE_HEAD"] {
         if rev_exists(repo_path, r) {
             return Some(r.to_string());
         }
@@ -296,22 +304,124 @@ pub(crate) struct GitConflictFileEntry {
     stages: Vec<u8>,
 }
 
+const SCISSORS_LINE: &str = "------------------------ >8 ------------------------";
+
+/// Effective `commit.cleanup` mode. The continue commands hand git a finished message
+/// rather than opening an editor, so `default` (or nothing configured) means `whitespace`.
+fn commit_cleanup_mode(repo_path: &str) -> String {
+    let mode = crate::run_git(repo_path, &["config", "--get", "commit.cleanup"])
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    match mode.as_str() {
+        "strip" | "whitespace" | "verbatim" | "scissors" => mode,
+        _ => String::from("whitespace"),
+    }
+}
+
+/// `-c` value that makes a continue commit clean up its message exactly as reported by
+/// `commit_cleanup_mode`, whether or not git thinks the message was edited.
+fn cleanup_config(repo_path: &str) -> String {
+    format!("commit.cleanup={}", commit_cleanup_mode(repo_path))
+}
+
+fn commit_comment_char(repo_path: &str) -> String {
+    let c = crate::run_git(repo_path, &["config", "--get", "core.commentChar"]).unwrap_or_default();
+    let c = c.trim();
+    if c.is_empty() || c.eq_ignore_ascii_case("auto") {
+        String::from("#")
+    } else {
+        c.to_string()
+    }
+}
+
+/// Applies Git's commit message cleanup (`commit.cleanup`) to `text`.
+fn cleanup_commit_message(text: &str, mode: &str, comment_char: &str) -> String {
+    let text = text.replace("\r\n", "\n");
+    if mode == "verbatim" {
+        return text;
+    }
+
+    let scissors = format!("{comment_char} {SCISSORS_LINE}");
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        if mode == "scissors" && line == scissors {
+            break;
+        }
+        if mode == "strip" && line.starts_with(comment_char) {
+            continue;
+        }
+        lines.push(line.trim_end());
+    }
+
+    // Collapse consecutive blank lines and trim leading/trailing ones.
+    let mut out = String::new();
+    let mut pending_blank = false;
+    for line in lines {
+        if line.is_empty() {
+            pending_blank = !out.is_empty();
+            continue;
+        }
+        if pending_blank {
+            out.push('\n');
+            pending_blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Standard merge summary (as with `merge.log`): subjects of the merged commits.
+fn merge_log_summary(repo_path: &str, message: &str) -> Option<String> {
+    const MAX_ENTRIES: usize = 20;
+
+    let raw = crate::run_git(
+        repo_path,
+        &["log", "--no-merges", "--format=%s", "-n", "21", "HEAD..MERGE_HEAD"],
+    )
+    .ok()?;
+    let subjects: Vec<&str> = raw.lines().filter(|l| !l.trim().is_empty()).collect();
+    if subjects.is_empty() {
+        return None;
+    }
+
+    let first = message.lines().next().unwrap_or("");
+    let source = first
+        .split('\'')
+        .nth(1)
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| crate::run_git(repo_path, &["rev-parse", "--short", "MERGE_HEAD"]).unwrap_or_default());
+
+    let mut s = format!("* {source}:\n");
+    for subject in subjects.iter().take(MAX_ENTRIES) {
+        s.push_str(format!("  {subject}\n").as_str());
+    }
+    if subjects.len() > MAX_ENTRIES {
+        s.push_str("  ...\n");
+    }
+    Some(s)
+}
+
 #[tauri::command]
-pub(crate) fn git_continue_info(repo_path: String) -> Result<GitContinueInfo, String> {
+pub(crate) fn git_continue_info(repo_path: String, strip_comments: Option<bool>) -> Result<GitContinueInfo, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
     let merge = crate::is_merge_in_progress(&repo_path);
     let rebase = crate::is_rebase_in_progress(&repo_path);
     let cherry = crate::is_cherry_pick_in_progress(&repo_path);
+    let revert = crate::is_revert_in_progress(&repo_path);
     let am = is_am_in_progress(&repo_path);
-    if !merge && !rebase && !cherry && !am {
-        return Err(String::from("No merge/rebase/cherry-pick/am in progress."));
+    if !merge && !rebase && !cherry && !revert && !am {
+        return Err(String::from("No merge/rebase/cherry-pick/revert/am in progress."));
     }
 
     let operation = if merge {
         "merge"
     } else if cherry {
         "cherry-pick"
+    } else if revert {
+        "revert"
     } else if am {
         "am"
     } else {
@@ -332,6 +442,14 @@ pub(crate) fn git_continue_info(repo_path: String) -> Result<GitContinueInfo, St
         } else {
             m
         }
+    } else if operation == "revert" {
+        // `git revert` prepares its message in MERGE_MSG.
+        let m = read_git_path_text(&repo_path, "MERGE_MSG")?;
+        if m.trim().is_empty() {
+            String::from("Revert")
+        } else {
+            m
+        }
     } else if operation == "am" {
         // `git am` stores message in `rebase-apply/msg`.
         let m = read_git_path_text(&repo_path, "rebase-apply/msg")?;
@@ -355,54 +473,98 @@ pub(crate) fn git_continue_info(repo_path: String) -> Result<GitContinueInfo, St
     };
 
     let files = staged_name_status(&repo_path).unwrap_or_default();
+    let cleanup_mode = commit_cleanup_mode(&repo_path);
+    let cc = commit_comment_char(&repo_path);
 
     let mut s = message.replace("\r\n", "\n");
     if !s.ends_with('\n') {
         s.push('\n');
     }
-    s.push('\n');
-    s.push_str("# Please enter the commit message for your changes. Lines starting\n");
-    s.push_str("# with '#' will be ignored, and an empty message aborts the commit.\n");
-    s.push_str("#\n");
-
-    if operation == "merge" || operation == "cherry-pick" || operation == "am" {
-        let conflicts = crate::list_unmerged_files(&repo_path);
-        if !conflicts.is_empty() {
-            s.push_str("# Conflicts:\n");
-            for p in conflicts.iter() {
-                s.push_str(format!("#\t{}\n", p).as_str());
+
+    let merge_summary = if operation == "merge" && !s.lines().any(|l| l.starts_with("* ")) {
+        merge_log_summary(&repo_path, s.as_str())
+    } else {
+        None
+    };
+    if let Some(summary) = merge_summary {
+        // Keep the summary above any comment lines Git already put into MERGE_MSG.
+        let mut body = String::new();
+        let mut rest = String::new();
+        for line in s.lines() {
+            if rest.is_empty() && !line.starts_with(cc.as_str()) {
+                body.push_str(line);
+                body.push('\n');
+            } else {
+                rest.push_str(line);
+                rest.push('\n');
             }
-            s.push_str("#\n");
         }
-    }
-
-    if let Ok(status_text) = git_status_text(&repo_path) {
-        for line in status_text.replace("\r\n", "\n").lines() {
-            s.push_str("# ");
-            s.push_str(line);
+        s = format!("{}\n\n{summary}", body.trim_end());
+        if !rest.is_empty() {
             s.push('\n');
+            s.push_str(rest.as_str());
         }
     }
 
-    if !files.is_empty() {
-        s.push_str("#\n");
-        s.push_str("# Staged changes:\n");
-        for f in files.iter() {
-            s.push_str(format!("# {} {}\n", f.status, f.path).as_str());
+    // Comments are only added when the configured cleanup will remove them again.
+    if cleanup_mode == "strip" || cleanup_mode == "scissors" {
+        s.push('\n');
+        if cleanup_mode == "scissors" {
+            s.push_str(format!("{cc} {SCISSORS_LINE}\n").as_str());
+            s.push_str(format!("{cc} Do not modify or remove the line above.\n").as_str());
+            s.push_str(format!("{cc} Everything below it will be ignored.\n").as_str());
+        } else {
+            s.push_str(format!("{cc} Please enter the commit message for your changes. Lines starting\n").as_str());
+            s.push_str(format!("{cc} with '{cc}' will be ignored, and an empty message aborts the commit.\n").as_str());
+        }
+        s.push_str(format!("{cc}\n").as_str());
+
+        if operation == "merge" || operation == "cherry-pick" || operation == "revert" || operation == "am" {
+            let conflicts = crate::list_unmerged_files(&repo_path);
+            if !conflicts.is_empty() {
+                s.push_str(format!("{cc} Conflicts:\n").as_str());
+                for p in conflicts.iter() {
+                    s.push_str(format!("{cc}\t{}\n", p).as_str());
+                }
+                s.push_str(format!("{cc}\n").as_str());
+            }
+        }
+
+        if let Ok(status_text) = git_status_text(&repo_path) {
+            for line in status_text.replace("\r\n", "\n").lines() {
+                s.push_str(format!("{cc} ").as_str());
+                s.push_str(line);
+                s.push('\n');
+            }
+        }
+
+        if !files.is_empty() {
+            s.push_str(format!("{cc}\n").as_str());
+            s.push_str(format!("{cc} Staged changes:\n").as_str());
+            for f in files.iter() {
+                s.push_str(format!("{cc} {} {}\n", f.status, f.path).as_str());
+            }
         }
     }
 
-    message = s;
+    message = if strip_comments.unwrap_or(false) {
+        cleanup_commit_message(s.as_str(), cleanup_mode.as_str(), cc.as_str())
+    } else {
+        s
+    };
     Ok(GitContinueInfo {
         operation: operation.to_string(),
         message,
         files,
+        cleanup_mode,
+        comment_char: cc,
     })
 }
 
 #[tauri::command]
 pub(crate) fn git_cherry_pick_abort(repo_path: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     if !crate::is_cherry_pick_in_progress(&repo_path) {
         return Err(String::from("No cherry-pick in progress."));
     }
@@ -410,11 +572,17 @@ pub(crate) fn git_cherry_pick_abort(repo_path: String) -> Result<String, String>
 }
 
 #[tauri::command]
-pub(crate) fn git_cherry_pick_continue_with_message(repo_path: String, message: String) -> Result<String, String> {
+pub(crate) fn git_cherry_pick_continue_with_message(
+    repo_path: String,
+    message: String,
+    co_authors: Option<Vec<String>>,
+) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     if !crate::is_cherry_pick_in_progress(&repo_path) {
         return Err(String::from("No cherry-pick in progress."));
     }
+    let message = super::commits::add_co_author_trailers(&repo_path, &message, co_authors.as_deref())?;
 
     // Keep message in sync with what Git may use during cherry-pick continue.
     // Depending on Git version/flow this can be CHERRY_PICK_MSG, MERGE_MSG,
@@ -424,10 +592,11 @@ pub(crate) fn git_cherry_pick_continue_with_message(repo_path: String, message:
     let _ = write_git_path_text(&repo_path, "sequencer/message", message.as_str());
 
     // Continue without launching editor, but allow Git to use CHERRY_PICK_MSG.
+    let cleanup = cleanup_config(&repo_path);
     let mut cmd = crate::git_command_in_repo(&repo_path);
     no_editor_env(&mut cmd);
     let out = cmd
-        .args(["cherry-pick", "--continue"])
+        .args(["-c", cleanup.as_str(), "cherry-pick", "--continue"])
         .output()
         .map_err(|e| format!("Failed to spawn git cherry-pick --continue: {e}"))?;
 
@@ -440,6 +609,47 @@ pub(crate) fn git_cherry_pick_continue_with_message(repo_path: String, message:
     }
 }
 
+#[tauri::command]
+pub(crate) fn git_revert_abort(repo_path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+    if !crate::is_revert_in_progress(&repo_path) {
+        return Err(String::from("No revert in progress."));
+    }
+    crate::run_git(&repo_path, &["revert", "--abort"])
+}
+
+#[tauri::command]
+pub(crate) fn git_revert_continue(repo_path: String, message: Option<String>) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+    if !crate::is_revert_in_progress(&repo_path) {
+        return Err(String::from("No revert in progress."));
+    }
+
+    // `git revert --continue` commits using MERGE_MSG (no editor is launched).
+    let message = message.unwrap_or_default();
+    if !message.trim().is_empty() {
+        write_git_path_text(&repo_path, "MERGE_MSG", message.as_str())?;
+    }
+
+    let cleanup = cleanup_config(&repo_path);
+    let mut cmd = crate::git_command_in_repo(&repo_path);
+    no_editor_env(&mut cmd);
+    let out = cmd
+        .args(["-c", cleanup.as_str(), "revert", "--continue"])
+        .output()
+        .map_err(|e| format!("Failed to spawn git revert --continue: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&out.stdout).trim_end().to_string();
+    let stderr = String::from_utf8_lossy(&out.stderr).trim_end().to_string();
+    if out.status.success() {
+        Ok(if !stdout.is_empty() { stdout } else { stderr })
+    } else {
+        Err(if !stderr.is_empty() { stderr } else { stdout })
+    }
+}
+
 #[tauri::command]
 pub(crate) fn git_continue_file_diff(repo_path: String, path: String, unified: u32) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
@@ -493,19 +703,26 @@ pub(crate) fn git_continue_rename_diff(
 }
 
 #[tauri::command]
-pub(crate) fn git_merge_continue_with_message(repo_path: String, message: String) -> Result<String, String> {
+pub(crate) fn git_merge_continue_with_message(
+    repo_path: String,
+    message: String,
+    co_authors: Option<Vec<String>>,
+) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     if !crate::is_merge_in_progress(&repo_path) {
         return Err(String::from("No merge in progress."));
     }
 
-    let mut msg = message.replace("\r\n", "\n");
+    let message = message.replace("\r\n", "\n");
+    let mut msg = super::commits::add_co_author_trailers(&repo_path, &message, co_authors.as_deref())?;
     if !msg.ends_with('\n') {
         msg.push('\n');
     }
 
+    let cleanup = cleanup_config(&repo_path);
     let mut child = crate::git_command_in_repo(&repo_path)
-        .args(["commit", "-F", "-"])
+        .args(["-c", cleanup.as_str(), "commit", "-F", "-"])
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -532,11 +749,17 @@ pub(crate) fn git_merge_continue_with_message(repo_path: String, message: String
 }
 
 #[tauri::command]
-pub(crate) fn git_rebase_continue_with_message(repo_path: String, message: String) -> Result<String, String> {
+pub(crate) fn git_rebase_continue_with_message(
+    repo_path: String,
+    message: String,
+    co_authors: Option<Vec<String>>,
+) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     if !crate::is_rebase_in_progress(&repo_path) {
         return Err(String::from("No rebase in progress."));
     }
+    let message = super::commits::add_co_author_trailers(&repo_path, &message, co_authors.as_deref())?;
 
     let merge_dir = resolve_git_path(&repo_path, "rebase-merge")?;
     let apply_dir = resolve_git_path(&repo_path, "rebase-apply")?;
@@ -551,10 +774,11 @@ pub(crate) fn git_rebase_continue_with_message(repo_path: String, message: Strin
         let _ = write_git_path_text(&repo_path, "rebase-apply/message", message.as_str());
     }
 
+    let cleanup = cleanup_config(&repo_path);
     let mut cmd = crate::git_command_in_repo(&repo_path);
     no_editor_env(&mut cmd);
     let out = cmd
-        .args(["rebase", "--continue", "--no-edit"])
+        .args(["-c", cleanup.as_str(), "rebase", "--continue", "--no-edit"])
         .output()
         .map_err(|e| format!("Failed to spawn git rebase --continue: {e}"))?;
 
@@ -574,7 +798,7 @@ pub(crate) fn git_rebase_continue_with_message(repo_path: String, message: Strin
         let mut cmd2 = crate::git_command_in_repo(&repo_path);
         no_editor_env(&mut cmd2);
         let out2 = cmd2
-            .args(["rebase", "--continue"])
+            .args(["-c", cleanup.as_str(), "rebase", "--continue"])
             .output()
             .map_err(|e| format!("Failed to spawn git rebase --continue: {e}"))?;
 
@@ -622,6 +846,8 @@ pub(crate) struct GitContinueInfo {
     operation: String,
     message: String,
     files: Vec<GitContinueFileEntry>,
+    cleanup_mode: String,
+    comment_char: String,
 }
 
 fn parse_status_porcelain_z(stdout: &[u8]) -> HashMap<String, String> {
@@ -873,6 +1099,7 @@ pub(crate) fn git_conflict_state(repo_path: String) -> Result<GitConflictState,
         let merge_in_progress = crate::is_merge_in_progress(&repo_path);
         let rebase_in_progress = crate::is_rebase_in_progress(&repo_path);
         let cherry_in_progress = crate::is_cherry_pick_in_progress(&repo_path);
+        let revert_in_progress = crate::is_revert_in_progress(&repo_path);
         let am_in_progress = is_am_in_progress(&repo_path);
 
         let operation = if am_in_progress {
@@ -883,11 +1110,14 @@ pub(crate) fn git_conflict_state(repo_path: String) -> Result<GitConflictState,
             String::from("merge")
         } else if cherry_in_progress {
             String::from("cherry-pick")
+        } else if revert_in_progress {
+            String::from("revert")
         } else {
             String::new()
         };
 
-        let in_progress = merge_in_progress || rebase_in_progress || cherry_in_progress || am_in_progress;
+        let in_progress =
+            merge_in_progress || rebase_in_progress || cherry_in_progress || revert_in_progress || am_in_progress;
 
         let files = crate::list_unmerged_files(&repo_path);
 
@@ -1032,6 +1262,7 @@ pub(crate) fn git_conflict_file_versions(repo_path: String, path: String) -> Res
 #[tauri::command]
 pub(crate) fn git_conflict_take_ours(repo_path: String, path: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let path = path.trim().to_string();
     if path.is_empty() {
@@ -1040,36 +1271,40 @@ pub(crate) fn git_conflict_take_ours(repo_path: String, path: String) -> Result<
 
     let _ = crate::safe_repo_join(&repo_path, path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
 
-    crate::with_repo_git_lock(&repo_path, || {
-        let ours_bytes = crate::git_show_path_bytes_or_empty(&repo_path, ":2", path.as_str())?;
-        if ours_bytes.is_empty() {
-            crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
-            return Ok(String::from("ok"));
-        }
+    resolving_conflict(&repo_path, path.as_str(), "ours", || {
+        super::index::with_index_snapshot(&repo_path, || {
+            let ours_bytes = crate::git_show_path_bytes_or_empty(&repo_path, ":2", path.as_str())?;
+            if ours_bytes.is_empty() {
+                crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
+                return Ok(String::from("ok"));
+            }
 
-        let theirs_ref = detect_theirs_ref(&repo_path);
-        if let Some(theirs_ref) = theirs_ref {
-            let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
-            if let Some(new_path) = renames.get(path.as_str()) {
-                crate::run_git(&repo_path, &["rm", "-f", "--", new_path.as_str()])?;
+            let theirs_ref = detect_theirs_ref(&repo_path);
+            if let Some(theirs_ref) = theirs_ref {
+                let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
+                if let Some(new_path) = renames.get(path.as_str()) {
+                    crate::run_git(&repo_path, &["rm", "-f", "--", new_path.as_str()])?;
+                }
             }
-        }
 
-        crate::run_git(&repo_path, &["checkout", "--ours", "--", path.as_str()])?;
-        crate::run_git(&repo_path, &["add", "--", path.as_str()])?;
-        Ok(String::from("ok"))
+            crate::run_git(&repo_path, &["checkout", "--ours", "--", path.as_str()])?;
+            crate::run_git(&repo_path, &["add", "--", path.as_str()])?;
+            Ok(String::from("ok"))
+        })
     })
 }
 
 #[tauri::command]
 pub(crate) fn git_rebase_skip(repo_path: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     crate::run_git(&repo_path, &["rebase", "--skip"])
 }
 
 #[tauri::command]
 pub(crate) fn git_conflict_take_theirs(repo_path: String, path: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let path = path.trim().to_string();
     if path.is_empty() {
@@ -1078,48 +1313,51 @@ pub(crate) fn git_conflict_take_theirs(repo_path: String, path: String) -> Resul
 
     let _ = crate::safe_repo_join(&repo_path, path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
 
-    crate::with_repo_git_lock(&repo_path, || {
-        let theirs_bytes = crate::git_show_path_bytes_or_empty(&repo_path, ":3", path.as_str())?;
-        if !theirs_bytes.is_empty() {
-            crate::run_git(&repo_path, &["checkout", "--theirs", "--", path.as_str()])?;
-            crate::run_git(&repo_path, &["add", "--", path.as_str()])?;
-            return Ok(String::from("ok"));
-        }
+    resolving_conflict(&repo_path, path.as_str(), "theirs", || {
+        super::index::with_index_snapshot(&repo_path, || {
+            let theirs_bytes = crate::git_show_path_bytes_or_empty(&repo_path, ":3", path.as_str())?;
+            if !theirs_bytes.is_empty() {
+                crate::run_git(&repo_path, &["checkout", "--theirs", "--", path.as_str()])?;
+                crate::run_git(&repo_path, &["add", "--", path.as_str()])?;
+                return Ok(String::from("ok"));
+            }
 
-        if let Some(theirs_ref) = detect_theirs_ref(&repo_path) {
-            let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
-            if let Some(new_path) = renames.get(path.as_str()) {
-                let full_new = crate::safe_repo_join(&repo_path, new_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
-                if let Some(parent) = full_new.parent() {
-                    fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {e}"))?;
-                }
+            if let Some(theirs_ref) = detect_theirs_ref(&repo_path) {
+                let renames = detect_renames_against_theirs(&repo_path, theirs_ref.as_str());
+                if let Some(new_path) = renames.get(path.as_str()) {
+                    let full_new = crate::safe_repo_join(&repo_path, new_path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+                    if let Some(parent) = full_new.parent() {
+                        fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {e}"))?;
+                    }
+
+                    let theirs_new_bytes = crate::git_show_path_bytes_or_empty(&repo_path, theirs_ref.as_str(), new_path.as_str())?;
+                    if theirs_new_bytes.is_empty() {
+                        crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
+                        return Ok(String::from("ok"));
+                    }
 
-                let theirs_new_bytes = crate::git_show_path_bytes_or_empty(&repo_path, theirs_ref.as_str(), new_path.as_str())?;
-                if theirs_new_bytes.is_empty() {
+                    fs::write(&full_new, theirs_new_bytes.as_slice()).map_err(|e| format!("Failed to write file: {e}"))?;
+                    crate::run_git(&repo_path, &["add", "-A", "--", new_path.as_str()])?;
                     crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
                     return Ok(String::from("ok"));
                 }
 
-                fs::write(&full_new, theirs_new_bytes.as_slice()).map_err(|e| format!("Failed to write file: {e}"))?;
-                crate::run_git(&repo_path, &["add", "-A", "--", new_path.as_str()])?;
-                crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
-                return Ok(String::from("ok"));
-            }
-
-            if !git_file_exists_at_rev(&repo_path, theirs_ref.as_str(), path.as_str()) {
-                crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
-                return Ok(String::from("ok"));
+                if !git_file_exists_at_rev(&repo_path, theirs_ref.as_str(), path.as_str()) {
+                    crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
+                    return Ok(String::from("ok"));
+                }
             }
-        }
 
-        crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
-        Ok(String::from("ok"))
+            crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
+            Ok(String::from("ok"))
+        })
     })
 }
 
 #[tauri::command]
 pub(crate) fn git_conflict_apply_and_stage(repo_path: String, path: String, content: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let path = path.trim().to_string();
     if path.is_empty() {
@@ -1128,7 +1366,7 @@ pub(crate) fn git_conflict_apply_and_stage(repo_path: String, path: String, cont
 
     let full = crate::safe_repo_join(&repo_path, path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
 
-    crate::with_repo_git_lock(&repo_path, || {
+    resolving_conflict(&repo_path, path.as_str(), "manual", || {
         if let Some(parent) = full.parent() {
             fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {e}"))?;
         }
@@ -1142,6 +1380,7 @@ pub(crate) fn git_conflict_apply_and_stage(repo_path: String, path: String, cont
 #[allow(dead_code)]
 pub(crate) fn git_conflict_apply(repo_path: String, path: String, content: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let path = path.trim().to_string();
     if path.is_empty() {
@@ -1158,3 +1397,225 @@ pub(crate) fn git_conflict_apply(repo_path: String, path: String, content: Strin
         Ok(String::from("ok"))
     })
 }
+
+// Resolutions made through the conflict UI are logged in `.git/graphoria/` for the
+// operation in progress, together with the conflicted index stages they replaced, so a
+// resolved file can be listed and put back into conflict until the operation finishes.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GitConflictResolution {
+    path: String,
+    /// "ours" | "theirs" | "manual"
+    resolution: String,
+    resolved_at: u64,
+    /// The unmerged index entries (`<mode> <oid> <stage>\t<path>`) before resolving.
+    stages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ConflictResolutionLog {
+    /// The operation the resolutions belong to, e.g. `MERGE_HEAD <oid>`.
+    operation: String,
+    resolutions: Vec<GitConflictResolution>,
+}
+
+fn resolution_log_path(repo_path: &str) -> Option<PathBuf> {
+    Some(super::handles::git_dir(repo_path)?.join("graphoria").join("conflict-resolutions.json"))
+}
+
+/// Identifies the conflicting operation (or rebase step) in progress.
+fn conflict_operation_key(repo_path: &str) -> Option<String> {
+    ["MERGE_HEAD", "CHERRY_PICK_HEAD", "REVERT_HEAD", "REBASE_HEAD"]
+        .into_iter()
+        .find_map(|head| {
+            crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", head])
+                .ok()
+                .map(|oid| format!("{head} {}", oid.trim()))
+        })
+}
+
+/// The log for the operation in progress; resolutions from an earlier one are dropped.
+fn load_resolution_log(repo_path: &str) -> ConflictResolutionLog {
+    let Some(operation) = conflict_operation_key(repo_path) else {
+        return ConflictResolutionLog::default();
+    };
+    let log: ConflictResolutionLog = resolution_log_path(repo_path)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    if log.operation == operation {
+        log
+    } else {
+        ConflictResolutionLog {
+            operation,
+            resolutions: Vec::new(),
+        }
+    }
+}
+
+fn save_resolution_log(repo_path: &str, log: &ConflictResolutionLog) -> Result<(), String> {
+    let path = resolution_log_path(repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(log).map_err(|e| format!("Failed to serialize resolutions: {e}"))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+fn unmerged_index_entries(repo_path: &str, path: &str) -> Vec<String> {
+    crate::run_git_stdout_raw(repo_path, &["ls-files", "-u", "-z", "--", path])
+        .unwrap_or_default()
+        .split('\0')
+        .filter(|e| !e.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn entry_stage(entry: &str) -> Option<&str> {
+    entry.split('\t').next()?.split_whitespace().nth(2)
+}
+
+/// Runs a resolution of `path` under the repository lock and logs it when `path` was
+/// actually conflicted.
+fn resolving_conflict(
+    repo_path: &str,
+    path: &str,
+    resolution: &str,
+    f: impl FnOnce() -> Result<String, String>,
+) -> Result<String, String> {
+    crate::with_repo_git_lock(repo_path, || {
+        let stages = unmerged_index_entries(repo_path, path);
+        let out = f()?;
+        if !stages.is_empty() && conflict_operation_key(repo_path).is_some() {
+            let resolved_at = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let mut log = load_resolution_log(repo_path);
+            log.resolutions.retain(|r| r.path != path);
+            log.resolutions.push(GitConflictResolution {
+                path: path.to_string(),
+                resolution: resolution.to_string(),
+                resolved_at,
+                stages,
+            });
+            // The resolution itself succeeded; failing to log it only loses the re-open.
+            let _ = save_resolution_log(repo_path, &log);
+        }
+        Ok(out)
+    })
+}
+
+/// Files resolved through the conflict UI during the operation in progress, oldest first.
+#[tauri::command]
+pub(crate) fn git_conflict_resolutions(repo_path: String) -> Result<Vec<GitConflictResolution>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let unmerged: HashSet<String> = crate::list_unmerged_files(&repo_path).into_iter().collect();
+    Ok(load_resolution_log(&repo_path)
+        .resolutions
+        .into_iter()
+        .filter(|r| !unmerged.contains(&r.path))
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitConflictReopenResult {
+    path: String,
+    /// Blob holding the working file as it was before the re-open rewrote it; restore it
+    /// with `git cat-file blob <oid>`.
+    saved_blob: Option<String>,
+    message: String,
+}
+
+/// Puts a resolved file back into conflict: restores its unmerged index stages and, when
+/// both sides exist, rewrites the working file with conflict markers. The resolved working
+/// file is written to the object database first so the manual resolution is not lost.
+#[tauri::command]
+pub(crate) fn git_conflict_reopen(repo_path: String, path: String) -> Result<GitConflictReopenResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let path = path.trim().to_string();
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    let full_path = crate::safe_repo_join(&repo_path, path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+
+    crate::with_repo_git_lock(&repo_path, || {
+        let mut log = load_resolution_log(&repo_path);
+        let idx = log
+            .resolutions
+            .iter()
+            .position(|r| r.path == path)
+            .ok_or_else(|| format!("No recorded resolution for '{path}' in the operation in progress."))?;
+        if !unmerged_index_entries(&repo_path, &path).is_empty() {
+            return Err(format!("'{path}' is already conflicted."));
+        }
+        let record = log.resolutions.remove(idx);
+
+        let saved_blob = if full_path.is_file() {
+            let oid = crate::run_git(&repo_path, &["hash-object", "-w", "--", path.as_str()])
+                .map_err(|e| format!("Failed to save the resolved '{path}': {e}"))?;
+            Some(oid.trim().to_string())
+        } else {
+            None
+        };
+
+        // A mode 0 entry drops the resolved stage 0 before the conflict stages go back in.
+        let oid_len = record
+            .stages
+            .first()
+            .and_then(|e| e.split_whitespace().nth(1))
+            .map(|oid| oid.len())
+            .unwrap_or(40);
+        let mut info = format!("0 {}\t{path}\0", "0".repeat(oid_len));
+        for entry in &record.stages {
+            info.push_str(entry);
+            info.push('\0');
+        }
+        crate::run_git_with_stdin(&repo_path, &["update-index", "-z", "--index-info"], info.as_str())?;
+
+        let has_stage = |stage: &str| record.stages.iter().any(|e| entry_stage(e) == Some(stage));
+        if has_stage("2") && has_stage("3") {
+            crate::run_git(&repo_path, &["checkout", "-m", "--", path.as_str()])?;
+        } else if has_stage("2") {
+            crate::run_git(&repo_path, &["checkout", "--ours", "--", path.as_str()])?;
+        } else if has_stage("3") {
+            crate::run_git(&repo_path, &["checkout", "--theirs", "--", path.as_str()])?;
+        }
+
+        save_resolution_log(&repo_path, &log)?;
+        let message = match &saved_blob {
+            Some(oid) => format!("Re-opened '{path}'. The resolved version is saved as blob {oid}."),
+            None => format!("Re-opened '{path}'."),
+        };
+        Ok(GitConflictReopenResult {
+            path: path.clone(),
+            saved_blob,
+            message,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "\n\nSubject  \r\n\n\n\nBody line\n# a comment\n";
+
+    #[test]
+    fn test_cleanup_commit_message_strip_and_whitespace() {
+        assert_eq!(cleanup_commit_message(MESSAGE, "strip", "#"), "Subject\n\nBody line\n");
+        assert_eq!(cleanup_commit_message(MESSAGE, "whitespace", "#"), "Subject\n\nBody line\n# a comment\n");
+        assert_eq!(cleanup_commit_message("; note\nSubject\n", "strip", ";"), "Subject\n");
+        assert_eq!(cleanup_commit_message("# only comments\n\n", "strip", "#"), "");
+    }
+
+    #[test]
+    fn test_cleanup_commit_message_scissors_and_verbatim() {
+        let text = format!("Subject\n# kept\n# {SCISSORS_LINE}\ndiff --git a/x b/x\n");
+        assert_eq!(cleanup_commit_message(&text, "scissors", "#"), "Subject\n# kept\n");
+        assert_eq!(cleanup_commit_message(MESSAGE, "verbatim", "#"), MESSAGE.replace("\r\n", "\n"));
+    }
+}
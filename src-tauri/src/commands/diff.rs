@@ -1,13 +1,66 @@
 use base64::Engine;
 use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
 use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+use super::diff_cache::{self, DiffCacheKey};
+use super::diff_parse::{paginate_diff, parse_hunk_header, parse_unified_diff, GitDiffPage, GitDiffPageOptions};
+use super::status::non_zero_oid;
+
+const COMMIT_FILE_DIFF_OPTIONS: &str = "patch";
+
+/// Default and upper bound for how much of a diff is sent to the frontend.
+const DEFAULT_DIFF_MAX_BYTES: u64 = 16 * 1024 * 1024;
+const DIFF_MAX_BYTES_LIMIT: u64 = 256 * 1024 * 1024;
+
+/// Streamed diffs are cut at line ends; a chunk is flushed once it reaches this size.
+const DIFF_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Images larger than this are not previewed.
+const MAX_IMAGE_PREVIEW_BYTES: u64 = 10_000_000;
+
+/// Running diff streams by id, with whether they were asked to stop.
+static ACTIVE_DIFF_STREAMS: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
+
+fn active_diff_streams() -> &'static Mutex<HashMap<String, bool>> {
+    ACTIVE_DIFF_STREAMS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The commits a submodule pointer moved between.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSubmoduleChange {
+    /// None when the submodule was added.
+    old_commit: Option<String>,
+    /// None when the submodule was removed.
+    new_commit: Option<String>,
+}
 
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct GitChangeEntry {
     status: String,
     path: String,
     old_path: Option<String>,
+    /// Set for submodule (gitlink) entries of `--raw` output.
+    submodule: Option<GitSubmoduleChange>,
+}
+
+impl GitChangeEntry {
+    /// An untracked file, with the `??` status `git status` gives it.
+    pub(crate) fn untracked(path: String) -> Self {
+        GitChangeEntry {
+            status: String::from("??"),
+            path,
+            old_path: None,
+            submodule: None,
+        }
+    }
 }
 
 #[tauri::command]
@@ -34,7 +87,8 @@ pub(crate) fn git_commit_changes(repo_path: String, commit: String) -> Result<Ve
             crate::git_command_in_repo(&repo_path)
                 .args([
                     "diff",
-                    "--name-status",
+                    "--raw",
+                    "--no-abbrev",
                     "-z",
                     "-M",
                     p1,
@@ -44,13 +98,13 @@ pub(crate) fn git_commit_changes(repo_path: String, commit: String) -> Result<Ve
                 .map_err(|e| format!("Failed to spawn git: {e}"))?
         } else {
             crate::git_command_in_repo(&repo_path)
-                .args(["show", "--name-status", "-z", "--pretty=format:", commit.as_str()])
+                .args(["show", "--raw", "--no-abbrev", "-z", "--pretty=format:", commit.as_str()])
                 .output()
                 .map_err(|e| format!("Failed to spawn git: {e}"))?
         }
     } else {
         crate::git_command_in_repo(&repo_path)
-            .args(["show", "--name-status", "-z", "--pretty=format:", commit.as_str()])
+            .args(["show", "--raw", "--no-abbrev", "-z", "--pretty=format:", commit.as_str()])
             .output()
             .map_err(|e| format!("Failed to spawn git: {e}"))?
     };
@@ -60,9 +114,15 @@ pub(crate) fn git_commit_changes(repo_path: String, commit: String) -> Result<Ve
         return Err(format!("git command failed: {stderr}"));
     }
 
+    Ok(parse_name_status_z(&out_bytes.stdout))
+}
+
+/// Parses `--name-status -z` or `--raw -z` output; renames and copies carry their old
+/// path, and with `--raw` submodule entries carry the commits they moved between.
+pub(crate) fn parse_name_status_z(stdout: &[u8]) -> Vec<GitChangeEntry> {
     let mut out: Vec<GitChangeEntry> = Vec::new();
     let mut tokens: Vec<String> = Vec::new();
-    for t in out_bytes.stdout.split(|c| *c == 0) {
+    for t in stdout.split(|c| *c == 0) {
         if t.is_empty() {
             continue;
         }
@@ -74,8 +134,22 @@ pub(crate) fn git_commit_changes(repo_path: String, commit: String) -> Result<Ve
 
     let mut i: usize = 0;
     while i < tokens.len() {
-        let status = tokens[i].trim().to_string();
+        let token = tokens[i].trim();
         i += 1;
+        // `--raw`: ":<old mode> <new mode> <old oid> <new oid> <status>"
+        let (status, submodule) = match token.strip_prefix(':') {
+            Some(raw) => match raw.split_whitespace().collect::<Vec<&str>>()[..] {
+                [old_mode, new_mode, old_oid, new_oid, status] => {
+                    let submodule = (old_mode == "160000" || new_mode == "160000").then(|| GitSubmoduleChange {
+                        old_commit: (old_mode == "160000").then(|| non_zero_oid(old_oid)).flatten(),
+                        new_commit: (new_mode == "160000").then(|| non_zero_oid(new_oid)).flatten(),
+                    });
+                    (status.to_string(), submodule)
+                }
+                _ => continue,
+            },
+            None => (token.to_string(), None),
+        };
         if status.is_empty() {
             continue;
         }
@@ -98,6 +172,7 @@ pub(crate) fn git_commit_changes(repo_path: String, commit: String) -> Result<Ve
                     } else {
                         Some(old_path)
                     },
+                    submodule,
                 });
             }
         } else {
@@ -111,16 +186,104 @@ pub(crate) fn git_commit_changes(repo_path: String, commit: String) -> Result<Ve
                     status,
                     path,
                     old_path: None,
+                    submodule,
                 });
             }
         }
     }
 
-    Ok(out)
+    out
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitNumstatEntry {
+    pub(crate) path: String,
+    pub(crate) old_path: Option<String>,
+    pub(crate) additions: u64,
+    pub(crate) deletions: u64,
+    /// Git reports no line counts for binary files; both counts are 0 then.
+    pub(crate) binary: bool,
 }
 
+/// Per-file added and removed line counts for `commit`, against its first parent for
+/// merges (like `git_commit_changes`).
 #[tauri::command]
-pub(crate) fn git_commit_file_diff(repo_path: String, commit: String, path: String) -> Result<String, String> {
+pub(crate) fn git_commit_numstat(repo_path: String, commit: String) -> Result<Vec<GitNumstatEntry>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let commit = commit.trim().to_string();
+    if commit.is_empty() {
+        return Err(String::from("commit is empty"));
+    }
+
+    let parents_line = crate::run_git(&repo_path, &["rev-list", "--parents", "-n", "1", commit.as_str()])
+        .unwrap_or_default();
+    let parents: Vec<&str> = parents_line.split_whitespace().skip(1).collect();
+
+    let out = if parents.len() > 1 {
+        crate::run_git_stdout_raw(&repo_path, &["diff", "--numstat", "-z", "-M", parents[0], commit.as_str()])?
+    } else {
+        crate::run_git_stdout_raw(
+            &repo_path,
+            &["show", "--numstat", "-z", "-M", "--pretty=format:", commit.as_str()],
+        )?
+    };
+    Ok(parse_numstat_z(&out))
+}
+
+/// Parses `--numstat -z` output: `"<add>\t<del>\t<path>\0"`, or
+/// `"<add>\t<del>\t\0<old>\0<new>\0"` for renames and copies; binary files have `-` counts.
+pub(crate) fn parse_numstat_z(stdout: &str) -> Vec<GitNumstatEntry> {
+    let mut out: Vec<GitNumstatEntry> = Vec::new();
+    let mut fields = stdout.split('\0');
+    while let Some(entry) = fields.next() {
+        let mut parts = entry.trim_start_matches('\n').splitn(3, '\t');
+        let (Some(add), Some(del), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let (path, old_path) = if path.is_empty() {
+            let old = fields.next().unwrap_or_default();
+            (fields.next().unwrap_or_default(), Some(old.to_string()))
+        } else {
+            (path, None)
+        };
+        if path.is_empty() {
+            continue;
+        }
+        let binary = add == "-" || del == "-";
+        out.push(GitNumstatEntry {
+            path: path.to_string(),
+            old_path,
+            additions: add.parse().unwrap_or(0),
+            deletions: del.parse().unwrap_or(0),
+            binary,
+        });
+    }
+    out
+}
+
+/// With `function_context`, hunks are widened to the whole enclosing function (`-W`).
+/// The text is cut at `max_bytes` (default `DEFAULT_DIFF_MAX_BYTES`) and `max_lines`, see
+/// `limit_diff_text`.
+#[tauri::command]
+pub(crate) fn git_commit_file_diff(
+    repo_path: String,
+    commit: String,
+    path: String,
+    function_context: Option<bool>,
+    max_bytes: Option<u64>,
+    max_lines: Option<u32>,
+) -> Result<String, String> {
+    let diff = commit_file_diff(repo_path, commit, path, function_context)?;
+    Ok(limit_diff_text(diff, max_bytes, max_lines))
+}
+
+fn commit_file_diff(
+    repo_path: String,
+    commit: String,
+    path: String,
+    function_context: Option<bool>,
+) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
     let commit = commit.trim().to_string();
@@ -133,46 +296,633 @@ pub(crate) fn git_commit_file_diff(repo_path: String, commit: String, path: Stri
         return Err(String::from("path is empty"));
     }
 
-    let parents_line = crate::run_git(
-        &repo_path,
-        &["rev-list", "--parents", "-n", "1", commit.as_str()],
-    )
-    .unwrap_or_default();
+    let function_context = function_context.unwrap_or(false);
+    let options = commit_file_diff_options(function_context);
+    if let Some(diff) = diff_cache::get_for_commit(&repo_path, &commit, &path, options) {
+        return Ok(diff);
+    }
+
+    let merge_base = merge_diff_base(&repo_path, commit.as_str());
+    let key = commit_file_blob_pair(&repo_path, commit.as_str(), merge_base.as_deref(), path.as_str())
+        .map(|(old, new)| DiffCacheKey::new(&repo_path, &path, &old, &new, options));
+    if let Some(diff) = key.as_ref().and_then(diff_cache::get) {
+        if let Some(key) = key {
+            diff_cache::remember_commit(&repo_path, &commit, &path, options, key);
+        }
+        return Ok(diff);
+    }
+
+    let args = commit_diff_args_from(commit.as_str(), merge_base, Some(path.as_str()), function_context);
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    let diff = crate::run_git_stdout_raw(&repo_path, args.as_slice())?;
+    if let Some(key) = key {
+        diff_cache::remember_commit(&repo_path, &commit, &path, options, key.clone());
+        diff_cache::put(key, diff.clone());
+    }
+    Ok(diff)
+}
+
+/// `git_commit_file_diff` parsed into hunks and lines, one `page` at a time.
+#[tauri::command]
+pub(crate) fn git_commit_file_diff_structured(
+    repo_path: String,
+    commit: String,
+    path: String,
+    function_context: Option<bool>,
+    page: Option<GitDiffPageOptions>,
+) -> Result<GitDiffPage, String> {
+    let diff = commit_file_diff(repo_path, commit, path, function_context)?;
+    paginate_diff(parse_unified_diff(&diff), page)
+}
+
+/// The first parent of `commit` when it is a merge; merges are diffed against it, as in
+/// the commit details view. Other commits are diffed against their parent by `git show`.
+fn merge_diff_base(repo_path: &str, commit: &str) -> Option<String> {
+    let parents_line = crate::run_git(repo_path, &["rev-list", "--parents", "-n", "1", commit]).unwrap_or_default();
     let mut parents_it = parents_line.split_whitespace();
     let _self_hash = parents_it.next();
     let first_parent = parents_it.next().map(|s| s.to_string());
     let is_merge_commit = parents_it.next().is_some();
+    first_parent.filter(|p| is_merge_commit && !p.trim().is_empty())
+}
 
-    if is_merge_commit {
-        if let Some(p1) = first_parent.as_ref().map(|s| s.trim()).filter(|s| !s.is_empty()) {
-            return crate::run_git_stdout_raw(
-                &repo_path,
-                &[
-                    "diff",
-                    "--no-color",
-                    "-M",
-                    "--patch",
-                    p1,
-                    commit.as_str(),
-                    "--",
-                    path.as_str(),
-                ],
-            );
+/// Blob ids `(old, new)` of `path` on both sides of the commit diff, `-` for a side where
+/// the file does not exist or the path is unchanged.
+fn commit_file_blob_pair(repo_path: &str, commit: &str, merge_base: Option<&str>, path: &str) -> Option<(String, String)> {
+    let mut args: Vec<&str> = vec!["diff-tree", "-r", "--no-commit-id", "--root"];
+    if let Some(base) = merge_base {
+        args.push(base);
+    }
+    args.extend([commit, "--", path]);
+    let raw = crate::run_git(repo_path, args.as_slice()).ok()?;
+    let Some(line) = raw.lines().find(|l| l.starts_with(':')) else {
+        return Some((String::from("-"), String::from("-")));
+    };
+    let mut fields = line.split_whitespace();
+    let (_, _, old, new) = (fields.next()?, fields.next()?, fields.next()?, fields.next()?);
+    Some((old.to_string(), new.to_string()))
+}
+
+/// Patch arguments for `commit`, optionally limited to one path.
+fn commit_diff_args(repo_path: &str, commit: &str, path: Option<&str>, function_context: bool) -> Vec<String> {
+    commit_diff_args_from(commit, merge_diff_base(repo_path, commit), path, function_context)
+}
+
+/// Cache options of a commit file diff.
+fn commit_file_diff_options(function_context: bool) -> &'static str {
+    if function_context {
+        "patch --function-context"
+    } else {
+        COMMIT_FILE_DIFF_OPTIONS
+    }
+}
+
+fn commit_diff_args_from(
+    commit: &str,
+    merge_base: Option<String>,
+    path: Option<&str>,
+    function_context: bool,
+) -> Vec<String> {
+    let mut args: Vec<String> = match merge_base {
+        Some(p1) => vec![
+            String::from("diff"),
+            String::from("--no-color"),
+            String::from("-M"),
+            String::from("--patch"),
+            p1,
+            commit.to_string(),
+        ],
+        None => vec![
+            String::from("show"),
+            String::from("--no-color"),
+            String::from("--pretty=format:"),
+            String::from("--patch"),
+            commit.to_string(),
+        ],
+    };
+    if function_context {
+        args.push(String::from("--function-context"));
+    }
+    if let Some(path) = path {
+        args.push(String::from("--"));
+        args.push(path.to_string());
+    }
+    args
+}
+
+/// Resolves both ends of a two-revision diff to commit hashes. With `three_dot` the
+/// diff starts at the merge base instead (`from...to`), showing only what `to` added.
+fn resolve_rev_pair(repo_path: &str, from_rev: &str, to_rev: &str, three_dot: bool) -> Result<(String, String), String> {
+    let resolve = |rev: &str| -> Result<String, String> {
+        let rev = rev.trim();
+        if rev.is_empty() {
+            return Err(String::from("revision is empty"));
+        }
+        if rev.starts_with('-') {
+            return Err(format!("Invalid revision: {rev}"));
         }
+        crate::resolve_commit(repo_path, rev).ok_or_else(|| format!("Unknown revision: {rev}"))
+    };
+    let (from, to) = (resolve(from_rev)?, resolve(to_rev)?);
+    if !three_dot {
+        return Ok((from, to));
     }
+    let base = crate::run_git(repo_path, &["merge-base", from.as_str(), to.as_str()])
+        .map(|b| b.trim().to_string())
+        .unwrap_or_default();
+    if base.is_empty() {
+        return Err(format!("{} and {} have no common ancestor.", from_rev.trim(), to_rev.trim()));
+    }
+    Ok((base, to))
+}
 
-    crate::run_git_stdout_raw(
+/// Files that differ between two revisions (commits, branches or tags), with renames.
+#[tauri::command]
+pub(crate) fn git_range_changes(
+    repo_path: String,
+    from_rev: String,
+    to_rev: String,
+    three_dot: Option<bool>,
+) -> Result<Vec<GitChangeEntry>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let (from, to) = resolve_rev_pair(&repo_path, &from_rev, &to_rev, three_dot.unwrap_or(false))?;
+    let out = crate::run_git_stdout_raw(
         &repo_path,
-        &[
-            "show",
-            "--no-color",
-            "--pretty=format:",
-            "--patch",
-            commit.as_str(),
-            "--",
-            path.as_str(),
-        ],
-    )
+        &["diff", "--raw", "--no-abbrev", "-z", "-M", from.as_str(), to.as_str()],
+    )?;
+    Ok(parse_name_status_z(out.as_bytes()))
+}
+
+/// The patch of one file between two revisions, cut like `git_commit_file_diff`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn git_range_file_diff(
+    repo_path: String,
+    from_rev: String,
+    to_rev: String,
+    path: String,
+    three_dot: Option<bool>,
+    function_context: Option<bool>,
+    max_bytes: Option<u64>,
+    max_lines: Option<u32>,
+) -> Result<String, String> {
+    let diff = range_file_diff(repo_path, from_rev, to_rev, path, three_dot, function_context)?;
+    Ok(limit_diff_text(diff, max_bytes, max_lines))
+}
+
+fn range_file_diff(
+    repo_path: String,
+    from_rev: String,
+    to_rev: String,
+    path: String,
+    three_dot: Option<bool>,
+    function_context: Option<bool>,
+) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let path = path.trim().to_string();
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    let (from, to) = resolve_rev_pair(&repo_path, &from_rev, &to_rev, three_dot.unwrap_or(false))?;
+    let function_context = function_context.unwrap_or(false);
+
+    let key = commit_file_blob_pair(&repo_path, to.as_str(), Some(from.as_str()), path.as_str())
+        .map(|(old, new)| DiffCacheKey::new(&repo_path, &path, &old, &new, commit_file_diff_options(function_context)));
+    if let Some(diff) = key.as_ref().and_then(diff_cache::get) {
+        return Ok(diff);
+    }
+
+    let args = commit_diff_args_from(to.as_str(), Some(from), Some(path.as_str()), function_context);
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    let diff = crate::run_git_stdout_raw(&repo_path, args.as_slice())?;
+    if let Some(key) = key {
+        diff_cache::put(key, diff.clone());
+    }
+    Ok(diff)
+}
+
+/// `git_range_file_diff` parsed into hunks and lines, one `page` at a time.
+#[tauri::command]
+pub(crate) fn git_range_file_diff_structured(
+    repo_path: String,
+    from_rev: String,
+    to_rev: String,
+    path: String,
+    three_dot: Option<bool>,
+    function_context: Option<bool>,
+    page: Option<GitDiffPageOptions>,
+) -> Result<GitDiffPage, String> {
+    let diff = range_file_diff(repo_path, from_rev, to_rev, path, three_dot, function_context)?;
+    paginate_diff(parse_unified_diff(&diff), page)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitWordDiffSegment {
+    /// "context" | "added" | "removed"
+    kind: &'static str,
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitWordDiffHunk {
+    header: String,
+    old_start: u32,
+    old_lines: u32,
+    new_start: u32,
+    new_lines: u32,
+    /// One entry per line, each a run of segments; the old line is the context and removed
+    /// segments, the new line the context and added ones.
+    lines: Vec<Vec<GitWordDiffSegment>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitWordDiff {
+    path: String,
+    binary: bool,
+    hunks: Vec<GitWordDiffHunk>,
+}
+
+/// Intra-line diff of one file, from `--word-diff=porcelain`. The file is compared like
+/// the other diff views: between `from_rev` and `to_rev` when both are given, for `commit`
+/// (merges against their first parent), or else the working tree against HEAD.
+/// `granularity` is "word" (default) or "char".
+#[tauri::command]
+pub(crate) fn git_file_diff_words(
+    repo_path: String,
+    path: String,
+    commit: Option<String>,
+    from_rev: Option<String>,
+    to_rev: Option<String>,
+    granularity: Option<String>,
+) -> Result<GitWordDiff, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let path = path.trim().to_string();
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+
+    let mut args: Vec<String> = match (from_rev, to_rev, commit.map(|c| c.trim().to_string()).filter(|c| !c.is_empty())) {
+        (Some(from_rev), Some(to_rev), _) => {
+            let (from, to) = resolve_rev_pair(&repo_path, &from_rev, &to_rev, false)?;
+            vec![String::from("diff"), String::from("--no-color"), String::from("-M"), from, to]
+        }
+        (_, _, Some(commit)) => commit_diff_args(&repo_path, commit.as_str(), None, false),
+        _ => vec![String::from("diff"), String::from("--no-color"), String::from("HEAD")],
+    };
+    args.push(String::from("--word-diff=porcelain"));
+    match granularity.as_deref().map(str::trim).unwrap_or("word") {
+        "word" | "" => {}
+        "char" => args.push(String::from("--word-diff-regex=.")),
+        other => return Err(format!("Unknown granularity: {other}")),
+    }
+    args.push(String::from("--"));
+    args.push(path.clone());
+
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    let out = crate::run_git_stdout_raw(&repo_path, args.as_slice())?;
+    Ok(parse_word_diff_porcelain(path, &out))
+}
+
+/// Parses `--word-diff=porcelain` output for a single file. Each line of it is one
+/// segment, prefixed with ' ', '+' or '-'; a line holding just '~' ends a line of the file.
+fn parse_word_diff_porcelain(path: String, out: &str) -> GitWordDiff {
+    let mut diff = GitWordDiff {
+        path,
+        binary: false,
+        hunks: Vec::new(),
+    };
+    let mut line: Vec<GitWordDiffSegment> = Vec::new();
+    for raw in out.lines() {
+        if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(raw) {
+            diff.hunks.push(GitWordDiffHunk {
+                header: raw.to_string(),
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        let Some(hunk) = diff.hunks.last_mut() else {
+            if raw.starts_with("Binary files ") {
+                diff.binary = true;
+            }
+            continue;
+        };
+        let kind = match raw.chars().next() {
+            Some('~') => {
+                hunk.lines.push(std::mem::take(&mut line));
+                continue;
+            }
+            Some(' ') => "context",
+            Some('+') => "added",
+            Some('-') => "removed",
+            _ => continue,
+        };
+        line.push(GitWordDiffSegment {
+            kind,
+            text: raw[1..].to_string(),
+        });
+    }
+    if let Some(hunk) = diff.hunks.last_mut().filter(|_| !line.is_empty()) {
+        hunk.lines.push(line);
+    }
+    diff
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitLimitedDiff {
+    diff: String,
+    truncated: bool,
+    /// Size of the whole diff; None when it was cut off, since git is stopped at the limit.
+    total_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitDiffStreamChunk {
+    stream_id: String,
+    index: u32,
+    data: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitDiffStreamResult {
+    stream_id: String,
+    chunks: u32,
+    bytes: u64,
+    truncated: bool,
+    cancelled: bool,
+}
+
+fn diff_max_bytes(max_bytes: Option<u64>) -> u64 {
+    max_bytes.unwrap_or(DEFAULT_DIFF_MAX_BYTES).clamp(1, DIFF_MAX_BYTES_LIMIT)
+}
+
+/// Cuts a diff at a line break so that it stays within `max_bytes` (default
+/// `DEFAULT_DIFF_MAX_BYTES`) and `max_lines`, and notes the cut in a trailing `\ ` line,
+/// which diff viewers show like git's "No newline at end of file". Use the structured
+/// commands to page through the rest.
+fn limit_diff_text(diff: String, max_bytes: Option<u64>, max_lines: Option<u32>) -> String {
+    let max_bytes = diff_max_bytes(max_bytes) as usize;
+    let max_lines = max_lines.filter(|n| *n > 0).map(|n| n as usize).unwrap_or(usize::MAX);
+    let (mut end, mut lines) = (0usize, 0usize);
+    for line in diff.split_inclusive('\n') {
+        if lines == max_lines || end + line.len() > max_bytes {
+            break;
+        }
+        end += line.len();
+        lines += 1;
+    }
+    if end == diff.len() {
+        return diff;
+    }
+    let total_lines = diff.split_inclusive('\n').count();
+    let mut out = diff[..end].to_string();
+    out.push_str(&format!("\\ Diff truncated: {lines} of {total_lines} lines shown.\n"));
+    out
+}
+
+/// Where to end a chunk of `data` so that a multi-byte UTF-8 character is not split: before
+/// the last character when it is incomplete, otherwise at the end.
+fn utf8_chunk_end(data: &[u8]) -> usize {
+    let Some(start) = data.iter().rposition(|b| (*b & 0xC0) != 0x80) else {
+        return data.len();
+    };
+    let width = match data[start] {
+        b if b >= 0xF0 => 4,
+        b if b >= 0xE0 => 3,
+        b if b >= 0xC0 => 2,
+        _ => 1,
+    };
+    if start + width > data.len() && start > 0 {
+        start
+    } else {
+        data.len()
+    }
+}
+
+/// Runs git and hands its stdout to `on_data` in pieces that end at a line break (unless
+/// a single line is longer than a chunk, then at a character boundary). `on_data` returns
+/// false to stop reading, which kills git. Returns whether git ran to completion.
+fn read_git_output_chunked(
+    repo_path: &str,
+    args: &[String],
+    mut on_data: impl FnMut(&[u8]) -> bool,
+) -> Result<bool, String> {
+    let mut child = crate::git_command_in_repo(repo_path)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn git: {e}"))?;
+
+    let stderr_reader = child.stderr.take().map(|mut err| {
+        std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = err.read_to_string(&mut buf);
+            buf
+        })
+    });
+    let mut stdout = child.stdout.take().ok_or_else(|| String::from("Failed to capture git output."))?;
+
+    let mut pending: Vec<u8> = Vec::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut stopped = false;
+    loop {
+        let n = stdout.read(&mut buf).map_err(|e| format!("Failed to read git output: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        pending.extend_from_slice(&buf[..n]);
+        if pending.len() < DIFF_CHUNK_BYTES {
+            continue;
+        }
+        let cut = pending
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map(|i| i + 1)
+            .unwrap_or_else(|| utf8_chunk_end(&pending));
+        let rest = pending.split_off(cut);
+        if !on_data(&pending) {
+            stopped = true;
+            break;
+        }
+        pending = rest;
+    }
+    if !stopped && !pending.is_empty() && !on_data(&pending) {
+        stopped = true;
+    }
+
+    if stopped {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Ok(false);
+    }
+
+    let status = child.wait().map_err(|e| format!("Failed to wait for git: {e}"))?;
+    if !status.success() {
+        let stderr = stderr_reader.and_then(|h| h.join().ok()).unwrap_or_default();
+        return Err(format!("git command failed: {}", stderr.trim_end()));
+    }
+    Ok(true)
+}
+
+/// Lazily loads one file's diff from a commit, cut off at `max_bytes`.
+#[tauri::command]
+pub(crate) fn git_commit_file_diff_limited(
+    repo_path: String,
+    commit: String,
+    path: String,
+    max_bytes: Option<u64>,
+    function_context: Option<bool>,
+) -> Result<GitLimitedDiff, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let commit = commit.trim().to_string();
+    let path = path.trim().to_string();
+    if commit.is_empty() {
+        return Err(String::from("commit is empty"));
+    }
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+
+    let max_bytes = diff_max_bytes(max_bytes);
+    let args = commit_diff_args(&repo_path, commit.as_str(), Some(path.as_str()), function_context.unwrap_or(false));
+    let mut kept: Vec<u8> = Vec::new();
+    let mut truncated = false;
+    read_git_output_chunked(&repo_path, &args, |data| {
+        let room = (max_bytes as usize).saturating_sub(kept.len());
+        if data.len() > room {
+            truncated = true;
+        }
+        kept.extend_from_slice(&data[..data.len().min(room)]);
+        !truncated
+    })?;
+
+    let total_bytes = (!truncated).then_some(kept.len() as u64);
+    if truncated {
+        // Do not end on half a line, or on half a character when there is no line break.
+        let cut = kept
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map(|i| i + 1)
+            .unwrap_or_else(|| utf8_chunk_end(&kept));
+        kept.truncate(cut);
+    }
+    Ok(GitLimitedDiff {
+        diff: String::from_utf8_lossy(&kept).to_string(),
+        truncated,
+        total_bytes,
+    })
+}
+
+/// Streams the patch of `commit` (or of one `path` in it) as `git_diff_stream_chunk`
+/// events tagged with `stream_id`, stopping after `max_bytes`. The result arrives after
+/// the last chunk.
+#[tauri::command]
+pub(crate) async fn git_commit_diff_stream(
+    app: tauri::AppHandle,
+    repo_path: String,
+    commit: String,
+    stream_id: String,
+    path: Option<String>,
+    max_bytes: Option<u64>,
+) -> Result<GitDiffStreamResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        let commit = commit.trim().to_string();
+        let stream_id = stream_id.trim().to_string();
+        if commit.is_empty() {
+            return Err(String::from("commit is empty"));
+        }
+        if stream_id.is_empty() {
+            return Err(String::from("stream_id is empty"));
+        }
+        let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+
+        let max_bytes = diff_max_bytes(max_bytes);
+        let args = commit_diff_args(&repo_path, commit.as_str(), path.as_deref(), false);
+
+        {
+            let mut g = active_diff_streams()
+                .lock()
+                .map_err(|_| String::from("Failed to lock diff streams."))?;
+            if g.contains_key(&stream_id) {
+                return Err(String::from("A diff stream with this id is already running."));
+            }
+            g.insert(stream_id.clone(), false);
+        }
+
+        let mut result = GitDiffStreamResult {
+            stream_id: stream_id.clone(),
+            chunks: 0,
+            bytes: 0,
+            truncated: false,
+            cancelled: false,
+        };
+        let outcome = read_git_output_chunked(&repo_path, &args, |data| {
+            if active_diff_streams().lock().map(|g| g.get(&stream_id) == Some(&true)).unwrap_or(false) {
+                result.cancelled = true;
+                return false;
+            }
+            let room = max_bytes - result.bytes;
+            let mut data = data;
+            if data.len() as u64 > room {
+                result.truncated = true;
+                data = &data[..room as usize];
+                if let Some(i) = data.iter().rposition(|b| *b == b'\n') {
+                    data = &data[..i + 1];
+                }
+            }
+            if !data.is_empty() {
+                let _ = app.emit(
+                    "git_diff_stream_chunk",
+                    GitDiffStreamChunk {
+                        stream_id: stream_id.clone(),
+                        index: result.chunks,
+                        data: String::from_utf8_lossy(data).to_string(),
+                    },
+                );
+                result.chunks += 1;
+                result.bytes += data.len() as u64;
+            }
+            !result.truncated
+        });
+
+        if let Ok(mut g) = active_diff_streams().lock() {
+            g.remove(&stream_id);
+        }
+        outcome?;
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Failed to stream diff: {e}"))?
+}
+
+/// Stops a running `git_commit_diff_stream`; its result then has `cancelled` set.
+/// Returns whether such a stream was running.
+#[tauri::command]
+pub(crate) fn git_commit_diff_stream_cancel(stream_id: String) -> Result<bool, String> {
+    let stream_id = stream_id.trim().to_string();
+    if stream_id.is_empty() {
+        return Err(String::from("stream_id is empty"));
+    }
+    let mut g = active_diff_streams()
+        .lock()
+        .map_err(|_| String::from("Failed to lock diff streams."))?;
+    Ok(match g.get_mut(&stream_id) {
+        Some(cancelled) => {
+            *cancelled = true;
+            true
+        }
+        None => false,
+    })
 }
 
 #[tauri::command]
@@ -192,8 +942,15 @@ pub(crate) fn git_commit_file_content(repo_path: String, commit: String, path: S
     crate::run_git_stdout_raw(&repo_path, &["show", spec.as_str()])
 }
 
+/// The working tree file against HEAD, cut like `git_commit_file_diff`.
 #[tauri::command]
-pub(crate) fn git_working_file_diff(repo_path: String, path: String) -> Result<String, String> {
+pub(crate) fn git_working_file_diff(
+    repo_path: String,
+    path: String,
+    function_context: Option<bool>,
+    max_bytes: Option<u64>,
+    max_lines: Option<u32>,
+) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
     let path = path.trim().to_string();
@@ -201,14 +958,17 @@ pub(crate) fn git_working_file_diff(repo_path: String, path: String) -> Result<S
         return Err(String::from("path is empty"));
     }
 
-    crate::run_git(
-        &repo_path,
-        &["diff", "--no-color", "--unified=3", "HEAD", "--", path.as_str()],
-    )
+    let diff = working_file_diff_cached(&repo_path, &path, 3, function_context.unwrap_or(false))?;
+    Ok(limit_diff_text(diff, max_bytes, max_lines))
 }
 
 #[tauri::command]
-pub(crate) fn git_working_file_diff_unified(repo_path: String, path: String, unified: u32) -> Result<String, String> {
+pub(crate) fn git_working_file_diff_unified(
+    repo_path: String,
+    path: String,
+    unified: u32,
+    function_context: Option<bool>,
+) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
     let path = path.trim().to_string();
@@ -216,12 +976,58 @@ pub(crate) fn git_working_file_diff_unified(repo_path: String, path: String, uni
         return Err(String::from("path is empty"));
     }
 
-    let u = unified.min(50);
-    let unified_arg = format!("--unified={u}");
-    crate::run_git(
-        &repo_path,
-        &["diff", "--no-color", unified_arg.as_str(), "HEAD", "--", path.as_str()],
-    )
+    working_file_diff_cached(&repo_path, &path, unified.min(50), function_context.unwrap_or(false))
+}
+
+/// The working tree file against HEAD, parsed into hunks and lines, one `page` at a time.
+#[tauri::command]
+pub(crate) fn git_working_file_diff_structured(
+    repo_path: String,
+    path: String,
+    unified: Option<u32>,
+    function_context: Option<bool>,
+    page: Option<GitDiffPageOptions>,
+) -> Result<GitDiffPage, String> {
+    let diff = git_working_file_diff_unified(repo_path, path, unified.unwrap_or(3), function_context)?;
+    paginate_diff(parse_unified_diff(&diff), page)
+}
+
+/// Cache key of a HEAD-vs-working-tree diff: the HEAD blob against the file's size and
+/// content hash.
+fn working_diff_key(repo_path: &str, path: &str, options: &str) -> Option<DiffCacheKey> {
+    let old = crate::run_git(repo_path, &["rev-parse", "-q", "--verify", format!("HEAD:{path}").as_str()])
+        .unwrap_or_else(|_| String::from("-"));
+    // Hashing costs far less than the diff and, unlike an mtime, catches same-size edits.
+    let new = match fs::read(Path::new(repo_path).join(path)) {
+        Ok(bytes) => {
+            let mut hasher = DefaultHasher::new();
+            bytes.hash(&mut hasher);
+            format!("worktree:{}:{:016x}", bytes.len(), hasher.finish())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::from("worktree:missing"),
+        // A directory (submodule) or unreadable file: not cached.
+        Err(_) => return None,
+    };
+    Some(DiffCacheKey::new(repo_path, path, old.trim(), &new, options))
+}
+
+fn working_file_diff_cached(repo_path: &str, path: &str, unified: u32, function_context: bool) -> Result<String, String> {
+    let unified_arg = format!("--unified={unified}");
+    let mut args: Vec<&str> = vec!["diff", "--no-color", unified_arg.as_str()];
+    if function_context {
+        args.push("--function-context");
+    }
+    let key = working_diff_key(repo_path, path, args[2..].join(" ").as_str());
+    if let Some(diff) = key.as_ref().and_then(diff_cache::get) {
+        return Ok(diff);
+    }
+
+    args.extend(["HEAD", "--", path]);
+    let diff = crate::run_git(repo_path, args.as_slice())?;
+    if let Some(key) = key {
+        diff_cache::put(key, diff.clone());
+    }
+    Ok(diff)
 }
 
 #[tauri::command]
@@ -249,6 +1055,54 @@ pub(crate) fn git_working_file_content(repo_path: String, path: String) -> Resul
     Ok(String::from_utf8_lossy(bytes.as_slice()).to_string())
 }
 
+/// Upper bound on the lines one `git_diff_context_lines` call returns.
+const MAX_CONTEXT_LINES: u32 = 10_000;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitContextLines {
+    /// 1-based number of the first line returned.
+    start_line: u32,
+    lines: Vec<String>,
+    /// Lines in the whole file, so the viewer knows when there is nothing left to expand.
+    total_lines: u32,
+}
+
+/// `count` lines of `path` from `start_line` (1-based), as stored in `rev`, or in the
+/// working tree when `rev` is None. Lets the diff viewer expand the context around a hunk
+/// without fetching the diff again with a larger `--unified`.
+#[tauri::command]
+pub(crate) fn git_diff_context_lines(
+    repo_path: String,
+    rev: Option<String>,
+    path: String,
+    start_line: u32,
+    count: u32,
+) -> Result<GitContextLines, String> {
+    let content = match rev.map(|r| r.trim().to_string()).filter(|r| !r.is_empty()) {
+        Some(rev) if rev.starts_with('-') => return Err(format!("Invalid revision: {rev}")),
+        Some(rev) => git_commit_file_content(repo_path, rev, path)?,
+        None => git_working_file_content(repo_path, path)?,
+    };
+    if content.contains('\0') {
+        return Err(String::from("Binary file preview is not supported."));
+    }
+
+    // Split on \n only, so CRLF files keep their \r as they do in the diff.
+    let all: Vec<&str> = content.split_terminator('\n').collect();
+    let start_line = start_line.max(1);
+    let lines: Vec<String> = all
+        .iter()
+        .skip(start_line as usize - 1)
+        .take(count.min(MAX_CONTEXT_LINES) as usize)
+        .map(|l| l.to_string())
+        .collect();
+    Ok(GitContextLines {
+        start_line,
+        lines,
+        total_lines: all.len() as u32,
+    })
+}
+
 #[tauri::command]
 pub(crate) fn write_text_file(path: String, content: String) -> Result<(), String> {
     let path = path.trim().to_string();
@@ -436,14 +1290,273 @@ pub(crate) fn git_working_file_image_base64(repo_path: String, path: String) ->
             return Err(format!("Failed to read file: {e}"));
         }
     };
-    if bytes.len() > 10_000_000 {
+    if bytes.len() as u64 > MAX_IMAGE_PREVIEW_BYTES {
         return Err(String::from("Image is too large to preview."));
     }
     Ok(base64::engine::general_purpose::STANDARD.encode(bytes.as_slice()))
 }
 
+/// Base64 of the blob `rev:path`, for image previews.
+fn blob_image_base64(repo_path: &str, rev: &str, path: &str) -> Result<String, String> {
+    let spec = format!("{rev}:{path}");
+    let size = crate::run_git(repo_path, &["cat-file", "-s", spec.as_str()])
+        .map_err(|_| format!("{path} does not exist in {rev}."))?;
+    if size.trim().parse::<u64>().unwrap_or(u64::MAX) > MAX_IMAGE_PREVIEW_BYTES {
+        return Err(String::from("Image is too large to preview."));
+    }
+    let out = crate::git_command_in_repo(repo_path)
+        .args(["cat-file", "blob", spec.as_str()])
+        .output()
+        .map_err(|e| format!("Failed to spawn git: {e}"))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(format!("git cat-file failed: {}", stderr.trim_end()));
+    }
+    Ok(base64::engine::general_purpose::STANDARD.encode(out.stdout.as_slice()))
+}
+
+/// An image as stored in `commit`, the new side of the commit's image diff.
+#[tauri::command]
+pub(crate) fn git_commit_file_image_base64(repo_path: String, commit: String, path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let commit = commit.trim().to_string();
+    let path = path.trim().to_string();
+    if commit.is_empty() {
+        return Err(String::from("commit is empty"));
+    }
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    blob_image_base64(&repo_path, commit.as_str(), path.as_str())
+}
+
+/// The old side of a commit's image diff: the image in the commit's first parent, under
+/// `old_path` when the commit renamed it.
+#[tauri::command]
+pub(crate) fn git_commit_parent_file_image_base64(
+    repo_path: String,
+    commit: String,
+    path: String,
+    old_path: Option<String>,
+) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let commit = commit.trim().to_string();
+    let path = old_path
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| path.trim().to_string());
+    if commit.is_empty() {
+        return Err(String::from("commit is empty"));
+    }
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    let parent = format!("{commit}^");
+    if crate::resolve_commit(&repo_path, parent.as_str()).is_none() {
+        return Err(String::from("The commit has no parent."));
+    }
+    blob_image_base64(&repo_path, parent.as_str(), path.as_str())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitBinaryFileInfo {
+    path: String,
+    old_path: Option<String>,
+    /// None when the file does not exist on that side.
+    old_size: Option<u64>,
+    new_size: Option<u64>,
+    /// Git file modes, e.g. "100644", "100755", "120000".
+    old_mode: Option<String>,
+    new_mode: Option<String>,
+    /// Sniffed from the file's first bytes, falling back to its extension.
+    mime: Option<String>,
+}
+
+/// Mode and size of `path` in `rev`.
+fn tree_entry_info(repo_path: &str, rev: &str, path: &str) -> Option<(String, u64)> {
+    let out = crate::run_git(repo_path, &["ls-tree", "-l", "--full-tree", rev, "--", path]).ok()?;
+    let line = out.lines().next()?;
+    let (meta, _) = line.split_once('\t')?;
+    let mut fields = meta.split_whitespace();
+    let mode = fields.next()?.to_string();
+    let size = fields.nth(2)?.parse::<u64>().unwrap_or(0);
+    Some((mode, size))
+}
+
+/// Mode and size of `path` in the working tree, in git's terms.
+fn worktree_entry_info(repo_path: &str, path: &str) -> Option<(String, u64)> {
+    let full = crate::safe_repo_join(repo_path, path).ok()?;
+    let meta = fs::symlink_metadata(full).ok()?;
+    if meta.file_type().is_symlink() {
+        return Some((String::from("120000"), meta.len()));
+    }
+    if !meta.is_file() {
+        return None;
+    }
+    #[cfg(unix)]
+    let executable = {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o111 != 0
+    };
+    #[cfg(not(unix))]
+    let executable = false;
+    let mode = if executable { "100755" } else { "100644" };
+    Some((mode.to_string(), meta.len()))
+}
+
+/// The first bytes of `path` in `rev`, or in the working tree when `rev` is None.
+fn file_head_bytes(repo_path: &str, rev: Option<&str>, path: &str) -> Vec<u8> {
+    let mut buf: Vec<u8> = Vec::new();
+    match rev {
+        Some(rev) => {
+            let spec = format!("{rev}:{path}");
+            let Ok(mut child) = crate::git_command_in_repo(repo_path)
+                .args(["cat-file", "blob", spec.as_str()])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+            else {
+                return buf;
+            };
+            if let Some(stdout) = child.stdout.take() {
+                let _ = stdout.take(16).read_to_end(&mut buf);
+            }
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        None => {
+            let file = crate::safe_repo_join(repo_path, path).ok().and_then(|p| fs::File::open(p).ok());
+            if let Some(file) = file {
+                let _ = file.take(16).read_to_end(&mut buf);
+            }
+        }
+    }
+    buf
+}
+
+/// Mime type from a file's magic number, or from its extension when that says nothing.
+fn sniff_mime(head: &[u8], path: &str) -> Option<&'static str> {
+    let magic: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF8", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"%PDF-", "application/pdf"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+        (b"\x00asm", "application/wasm"),
+        (b"\x7fELF", "application/x-executable"),
+        (b"MZ", "application/vnd.microsoft.portable-executable"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+        (b"II*\x00", "image/tiff"),
+        (b"MM\x00*", "image/tiff"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (b"ID3", "audio/mpeg"),
+    ];
+    if let Some((_, mime)) = magic.iter().find(|(m, _)| head.starts_with(m)) {
+        return Some(mime);
+    }
+    let ext = crate::file_extension_lower(path);
+    if head.len() >= 12 && head.starts_with(b"RIFF") {
+        match &head[8..12] {
+            b"WEBP" => return Some("image/webp"),
+            b"WAVE" => return Some("audio/wav"),
+            _ => {}
+        }
+    }
+    if head.len() >= 12 && &head[4..8] == b"ftyp" {
+        return Some(if ext == "mov" { "video/quicktime" } else { "video/mp4" });
+    }
+    if head.starts_with(b"PK\x03\x04") {
+        // Office documents, jars and the like are zip archives.
+        return Some(match ext.as_str() {
+            "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            "jar" => "application/java-archive",
+            _ => "application/zip",
+        });
+    }
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "bmp" => Some("image/bmp"),
+        "svg" => Some("image/svg+xml"),
+        "pdf" => Some("application/pdf"),
+        "zip" => Some("application/zip"),
+        _ => None,
+    }
+}
+
+/// Sizes, modes and mime type of a binary file on both sides of a change, for the diff
+/// viewer to describe what it cannot show. `rev` is a commit, compared against its first
+/// parent; without it the working tree file is compared against HEAD.
 #[tauri::command]
-pub(crate) fn git_head_vs_working_diff(repo_path: String, path: String, unified: u32) -> Result<String, String> {
+pub(crate) fn git_file_binary_info(
+    repo_path: String,
+    rev: Option<String>,
+    path: String,
+    old_path: Option<String>,
+) -> Result<GitBinaryFileInfo, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let path = path.trim().to_string();
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    let old_path = old_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty() && *p != path);
+    let rev = rev.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+
+    let (old_rev, new_side) = match rev.as_deref() {
+        Some(rev) => {
+            if rev.starts_with('-') {
+                return Err(format!("Invalid revision: {rev}"));
+            }
+            let commit = crate::resolve_commit(&repo_path, rev).ok_or_else(|| format!("Unknown revision: {rev}"))?;
+            let parent = crate::resolve_commit(&repo_path, format!("{commit}^").as_str());
+            let new_side = tree_entry_info(&repo_path, commit.as_str(), path.as_str());
+            (parent, new_side)
+        }
+        None => (
+            crate::resolve_commit(&repo_path, "HEAD"),
+            worktree_entry_info(&repo_path, path.as_str()),
+        ),
+    };
+    let old_side = old_rev
+        .as_deref()
+        .and_then(|r| tree_entry_info(&repo_path, r, old_path.as_deref().unwrap_or(path.as_str())));
+
+    let head = match (&new_side, rev.as_deref()) {
+        (Some(_), rev) => file_head_bytes(&repo_path, rev, path.as_str()),
+        (None, _) => match old_rev.as_deref() {
+            Some(r) if old_side.is_some() => file_head_bytes(&repo_path, Some(r), old_path.as_deref().unwrap_or(path.as_str())),
+            _ => Vec::new(),
+        },
+    };
+
+    Ok(GitBinaryFileInfo {
+        mime: sniff_mime(&head, path.as_str()).map(str::to_string),
+        old_size: old_side.as_ref().map(|(_, size)| *size),
+        old_mode: old_side.map(|(mode, _)| mode),
+        new_size: new_side.as_ref().map(|(_, size)| *size),
+        new_mode: new_side.map(|(mode, _)| mode),
+        path,
+        old_path,
+    })
+}
+
+#[tauri::command]
+pub(crate) fn git_head_vs_working_diff(
+    repo_path: String,
+    path: String,
+    unified: u32,
+    function_context: Option<bool>,
+) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
     let path = path.trim().to_string();
@@ -484,16 +1597,13 @@ pub(crate) fn git_head_vs_working_diff(repo_path: String, path: String, unified:
 
     let u = unified.min(50);
     let unified_arg = format!("--unified={u}");
-    let out = crate::new_command("git")
-        .args([
-            "diff",
-            "--no-index",
-            "--no-color",
-            unified_arg.as_str(),
-            "--",
-            left.to_string_lossy().as_ref(),
-            right.to_string_lossy().as_ref(),
-        ])
+    let mut cmd = crate::new_command("git");
+    cmd.args(["diff", "--no-index", "--no-color", unified_arg.as_str()]);
+    if function_context.unwrap_or(false) {
+        cmd.arg("--function-context");
+    }
+    let out = cmd
+        .args(["--", left.to_string_lossy().as_ref(), right.to_string_lossy().as_ref()])
         .output()
         .map_err(|e| format!("Failed to spawn git: {e}"))?;
 
@@ -0,0 +1,272 @@
+// In-memory LRU of rendered diffs keyed by the blob pair they compare, so re-selecting a
+// commit or file that was already viewed does not run git again.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+
+const MAX_ENTRIES: usize = 512;
+const MAX_TOTAL_BYTES: usize = 64 * 1024 * 1024;
+
+/// Commit diffs larger than this are not worth keeping around.
+const MAX_ENTRY_BYTES: usize = 8 * 1024 * 1024;
+
+/// What a diff compares. `old`/`new` are blob ids; for working-tree files `new` is a
+/// `worktree:` stamp of size and content hash, so such entries go stale on their own and
+/// can be dropped early through `invalidate_worktree`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct DiffCacheKey {
+    repo: String,
+    path: String,
+    old: String,
+    new: String,
+    options: String,
+}
+
+impl DiffCacheKey {
+    pub(crate) fn new(repo_path: &str, path: &str, old: &str, new: &str, options: &str) -> Self {
+        DiffCacheKey {
+            repo: crate::normalize_repo_path(repo_path),
+            path: path.to_string(),
+            old: old.to_string(),
+            new: new.to_string(),
+            options: options.to_string(),
+        }
+    }
+
+    fn is_worktree(&self) -> bool {
+        self.new.starts_with("worktree:")
+    }
+}
+
+/// Commit + path lookups resolved to their blob pair; commits never change, so a hit
+/// here needs no git call at all.
+type CommitKey = (String, String, String, String);
+
+#[derive(Default)]
+struct DiffCache {
+    /// Diff and the tick it was last used at.
+    entries: HashMap<DiffCacheKey, (String, u64)>,
+    /// Entries by last use, oldest first.
+    lru: BTreeMap<u64, DiffCacheKey>,
+    commits: HashMap<CommitKey, DiffCacheKey>,
+    /// The commit lookups leading to each entry, so dropping one needs no scan.
+    commits_by_entry: HashMap<DiffCacheKey, Vec<CommitKey>>,
+    total_bytes: usize,
+    tick: u64,
+}
+
+impl DiffCache {
+    fn get(&mut self, key: &DiffCacheKey) -> Option<String> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (diff, used) = self.entries.get_mut(key)?;
+        self.lru.remove(used);
+        *used = tick;
+        self.lru.insert(tick, key.clone());
+        Some(diff.clone())
+    }
+
+    fn put(&mut self, key: DiffCacheKey, diff: String) {
+        if diff.len() > MAX_ENTRY_BYTES {
+            return;
+        }
+        self.tick += 1;
+        self.total_bytes += diff.len();
+        self.lru.insert(self.tick, key.clone());
+        if let Some((old, used)) = self.entries.insert(key, (diff, self.tick)) {
+            self.total_bytes -= old.len();
+            self.lru.remove(&used);
+        }
+        while self.entries.len() > MAX_ENTRIES || self.total_bytes > MAX_TOTAL_BYTES {
+            let Some((_, oldest)) = self.lru.pop_first() else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+    }
+
+    fn remove(&mut self, key: &DiffCacheKey) {
+        if let Some((diff, used)) = self.entries.remove(key) {
+            self.total_bytes -= diff.len();
+            self.lru.remove(&used);
+        }
+        for commit in self.commits_by_entry.remove(key).unwrap_or_default() {
+            if self.commits.get(&commit) == Some(key) {
+                self.commits.remove(&commit);
+            }
+        }
+    }
+
+    fn remember_commit(&mut self, commit: CommitKey, key: DiffCacheKey) {
+        if self.commits.len() >= MAX_ENTRIES * 4 {
+            self.commits.clear();
+            self.commits_by_entry.clear();
+        }
+        self.commits_by_entry.entry(key.clone()).or_default().push(commit.clone());
+        self.commits.insert(commit, key);
+    }
+}
+
+static DIFF_CACHE: OnceLock<Mutex<DiffCache>> = OnceLock::new();
+
+fn diff_cache() -> &'static Mutex<DiffCache> {
+    DIFF_CACHE.get_or_init(|| Mutex::new(DiffCache::default()))
+}
+
+pub(crate) fn get(key: &DiffCacheKey) -> Option<String> {
+    diff_cache().lock().ok()?.get(key)
+}
+
+pub(crate) fn put(key: DiffCacheKey, diff: String) {
+    if let Ok(mut cache) = diff_cache().lock() {
+        cache.put(key, diff);
+    }
+}
+
+fn commit_key(repo_path: &str, commit: &str, path: &str, options: &str) -> CommitKey {
+    (
+        crate::normalize_repo_path(repo_path),
+        commit.to_string(),
+        path.to_string(),
+        options.to_string(),
+    )
+}
+
+/// Cached diff of `path` in `commit`, found without running git. Only full object ids
+/// are looked up, since anything else (branch names, `HEAD~1`) can move.
+pub(crate) fn get_for_commit(repo_path: &str, commit: &str, path: &str, options: &str) -> Option<String> {
+    if !is_full_object_id(commit) {
+        return None;
+    }
+    let mut cache = diff_cache().lock().ok()?;
+    let key = cache.commits.get(&commit_key(repo_path, commit, path, options))?.clone();
+    cache.get(&key)
+}
+
+pub(crate) fn remember_commit(repo_path: &str, commit: &str, path: &str, options: &str, key: DiffCacheKey) {
+    if !is_full_object_id(commit) {
+        return;
+    }
+    if let Ok(mut cache) = diff_cache().lock() {
+        cache.remember_commit(commit_key(repo_path, commit, path, options), key);
+    }
+}
+
+fn is_full_object_id(s: &str) -> bool {
+    (s.len() == 40 || s.len() == 64) && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Drops cached working-tree diffs of a repository, either all of them or only those of
+/// `paths`.
+pub(crate) fn invalidate_worktree(repo_path: &str, paths: Option<&[String]>) {
+    let repo = crate::normalize_repo_path(repo_path);
+    let Ok(mut cache) = diff_cache().lock() else {
+        return;
+    };
+    let stale: Vec<DiffCacheKey> = cache
+        .entries
+        .keys()
+        .filter(|k| k.repo == repo && k.is_worktree())
+        .filter(|k| paths.is_none_or(|ps| ps.iter().any(|p| p == &k.path)))
+        .cloned()
+        .collect();
+    for k in stale {
+        cache.remove(&k);
+    }
+}
+
+/// Drops everything cached for a repository, e.g. when it is closed.
+pub(crate) fn invalidate_repo(repo_path: &str) {
+    let repo = crate::normalize_repo_path(repo_path);
+    let Ok(mut cache) = diff_cache().lock() else {
+        return;
+    };
+    let stale: Vec<DiffCacheKey> = cache.entries.keys().filter(|k| k.repo == repo).cloned().collect();
+    for k in stale {
+        cache.remove(&k);
+    }
+    cache.commits.retain(|(r, _, _, _), _| *r != repo);
+    cache.commits_by_entry.retain(|k, _| k.repo != repo);
+}
+
+/// Called when the working tree of `repo_path` changed, e.g. from a file watcher event.
+#[tauri::command]
+pub(crate) fn git_diff_cache_invalidate(repo_path: String, paths: Option<Vec<String>>) -> Result<(), String> {
+    let paths: Option<Vec<String>> = paths.map(|ps| {
+        ps.into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect()
+    });
+    invalidate_worktree(&repo_path, paths.as_deref());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(path: &str) -> DiffCacheKey {
+        DiffCacheKey::new("/repo", path, "old", "new", "")
+    }
+
+    fn commit(c: &str, path: &str) -> CommitKey {
+        commit_key("/repo", c, path, "")
+    }
+
+    #[test]
+    fn test_diff_cache_evicts_least_recently_used_entry() {
+        let mut cache = DiffCache::default();
+        for i in 0..MAX_ENTRIES {
+            cache.put(key(&format!("f{i}")), format!("diff {i}"));
+        }
+        // Using the oldest entry makes the second one the eviction candidate.
+        assert_eq!(cache.get(&key("f0")).as_deref(), Some("diff 0"));
+        cache.put(key("extra"), String::from("extra"));
+
+        assert_eq!(cache.entries.len(), MAX_ENTRIES);
+        assert_eq!(cache.lru.len(), MAX_ENTRIES);
+        assert!(cache.get(&key("f0")).is_some());
+        assert!(cache.get(&key("f1")).is_none());
+        assert!(cache.get(&key("extra")).is_some());
+    }
+
+    #[test]
+    fn test_diff_cache_tracks_bytes_and_skips_oversized_diffs() {
+        let mut cache = DiffCache::default();
+        cache.put(key("huge"), "x".repeat(MAX_ENTRY_BYTES + 1));
+        assert!(cache.entries.is_empty());
+
+        cache.put(key("a"), String::from("12345"));
+        cache.put(key("a"), String::from("123"));
+        assert_eq!((cache.entries.len(), cache.lru.len(), cache.total_bytes), (1, 1, 3));
+
+        let big = "y".repeat(MAX_ENTRY_BYTES);
+        for i in 0..MAX_TOTAL_BYTES / MAX_ENTRY_BYTES {
+            cache.put(key(&format!("big{i}")), big.clone());
+        }
+        assert!(cache.total_bytes <= MAX_TOTAL_BYTES);
+        assert!(cache.get(&key("a")).is_none());
+        assert!(cache.get(&key("big0")).is_some());
+    }
+
+    #[test]
+    fn test_diff_cache_remove_drops_only_its_commit_lookups() {
+        let mut cache = DiffCache::default();
+        cache.put(key("a"), String::from("a"));
+        cache.put(key("b"), String::from("b"));
+        cache.remember_commit(commit("c1", "a"), key("a"));
+        cache.remember_commit(commit("c2", "a"), key("a"));
+        cache.remember_commit(commit("c3", "b"), key("b"));
+        // A lookup that moved to another entry stays when the first one goes.
+        cache.remember_commit(commit("c4", "x"), key("a"));
+        cache.remember_commit(commit("c4", "x"), key("b"));
+
+        cache.remove(&key("a"));
+        assert!(!cache.commits.contains_key(&commit("c1", "a")));
+        assert!(!cache.commits.contains_key(&commit("c2", "a")));
+        assert_eq!(cache.commits.get(&commit("c3", "b")), Some(&key("b")));
+        assert_eq!(cache.commits.get(&commit("c4", "x")), Some(&key("b")));
+        assert!(cache.lru.values().all(|k| *k == key("b")));
+    }
+}
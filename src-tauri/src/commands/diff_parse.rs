@@ -0,0 +1,472 @@
+// Unified diff parsing. Git's patch output is turned into files -> hunks -> lines with
+// old and new line numbers, so the frontend does not have to parse patch text itself.
+// Large diffs are sent a page of whole hunks at a time; each page carries a continuation
+// token for the next one.
+
+use serde::{Deserialize, Serialize};
+
+/// Page size when the caller sets no limit.
+const DEFAULT_PAGE_MAX_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitDiffLine {
+    /// "context" | "added" | "removed"
+    kind: &'static str,
+    old_line: Option<u32>,
+    new_line: Option<u32>,
+    content: String,
+    /// Followed by "\ No newline at end of file".
+    no_newline: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitDiffHunk {
+    header: String,
+    old_start: u32,
+    old_lines: u32,
+    new_start: u32,
+    new_lines: u32,
+    lines: Vec<GitDiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitDiffFile {
+    /// None for an added file.
+    old_path: Option<String>,
+    /// None for a deleted file.
+    new_path: Option<String>,
+    /// "added" | "deleted" | "renamed" | "copied" | "modified"
+    status: &'static str,
+    binary: bool,
+    hunks: Vec<GitDiffHunk>,
+}
+
+/// Limits of one page of a structured diff. Without `max_bytes` and `max_lines` a page
+/// holds about `DEFAULT_PAGE_MAX_BYTES` of line content.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct GitDiffPageOptions {
+    max_bytes: Option<u64>,
+    max_lines: Option<u32>,
+    /// `continuation` of the previous page.
+    continuation: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitDiffPage {
+    /// A file whose hunks span pages is repeated on each with the hunks of that page.
+    files: Vec<GitDiffFile>,
+    total_files: u32,
+    total_hunks: u32,
+    /// Index of the page's first hunk, counting across files.
+    first_hunk: u32,
+    truncated: bool,
+    /// Passed back to fetch the next page; None on the last one.
+    continuation: Option<String>,
+}
+
+impl GitDiffFile {
+    fn new(old_path: Option<String>, new_path: Option<String>) -> Self {
+        GitDiffFile {
+            old_path,
+            new_path,
+            status: "modified",
+            binary: false,
+            hunks: Vec::new(),
+        }
+    }
+}
+
+/// Start and length of both sides from a `@@ -a,b +c,d @@` hunk header; a missing
+/// length means 1.
+pub(crate) fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let range = |r: &str| -> Option<(u32, u32)> {
+        match r.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((r.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_lines) = range(old)?;
+    let (new_start, new_lines) = range(new)?;
+    Some((old_start, old_lines, new_start, new_lines))
+}
+
+/// Undoes git's C-style quoting of a path (`"a\tb"`); unquoted paths are returned as is.
+fn unquote_path(path: &str) -> String {
+    let Some(inner) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) else {
+        return path.to_string();
+    };
+    let mut bytes: Vec<u8> = Vec::with_capacity(inner.len());
+    let mut it = inner.bytes().peekable();
+    while let Some(b) = it.next() {
+        if b != b'\\' {
+            bytes.push(b);
+            continue;
+        }
+        match it.next() {
+            Some(b'n') => bytes.push(b'\n'),
+            Some(b't') => bytes.push(b'\t'),
+            Some(b'r') => bytes.push(b'\r'),
+            Some(b'a') => bytes.push(0x07),
+            Some(b'b') => bytes.push(0x08),
+            Some(b'f') => bytes.push(0x0c),
+            Some(b'v') => bytes.push(0x0b),
+            Some(d @ b'0'..=b'7') => {
+                let mut value = u32::from(d - b'0');
+                for _ in 0..2 {
+                    match it.peek() {
+                        Some(d @ b'0'..=b'7') => {
+                            value = value * 8 + u32::from(d - b'0');
+                            it.next();
+                        }
+                        _ => break,
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => bytes.push(other),
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+/// Path of a `---`/`+++` line without its `a/`/`b/` prefix; None for `/dev/null`.
+fn marker_path(rest: &str) -> Option<String> {
+    // Git appends a tab when the path contains a space.
+    let rest = rest.strip_suffix('\t').unwrap_or(rest);
+    if rest == "/dev/null" {
+        return None;
+    }
+    let path = unquote_path(rest);
+    Some(
+        path.strip_prefix("a/")
+            .or_else(|| path.strip_prefix("b/"))
+            .map(str::to_string)
+            .unwrap_or(path),
+    )
+}
+
+/// Both paths from a `diff --git a/x b/y` line. Unquoted paths with spaces are ambiguous;
+/// the common case of an unchanged path is split in the middle.
+fn diff_git_paths(rest: &str) -> (Option<String>, Option<String>) {
+    let quoted_end = rest.strip_prefix('"').and_then(|r| r.find("\" ")).map(|i| i + 1);
+    if let Some(end) = quoted_end {
+        return (marker_path(&rest[..=end]), marker_path(&rest[end + 2..]));
+    }
+    if rest.len() % 2 == 1 {
+        let half = rest.len() / 2;
+        if rest.is_char_boundary(half) && rest[..half].get(2..) == rest[half + 1..].get(2..) {
+            return (marker_path(&rest[..half]), marker_path(&rest[half + 1..]));
+        }
+    }
+    match rest.split_once(" b/") {
+        Some((a, b)) => (marker_path(a), Some(b.to_string())),
+        None => (None, None),
+    }
+}
+
+/// Parses unified diff output (`git diff`, `git show`, `git diff --no-index`) into files.
+/// Text before the first file header, such as a commit message, is skipped.
+pub(crate) fn parse_unified_diff(text: &str) -> Vec<GitDiffFile> {
+    let mut files: Vec<GitDiffFile> = Vec::new();
+    // Lines each side of the current hunk still expects.
+    let mut old_left: u32 = 0;
+    let mut new_left: u32 = 0;
+    let mut old_line: u32 = 0;
+    let mut new_line: u32 = 0;
+
+    for line in text.lines() {
+        let in_hunk = old_left > 0 || new_left > 0;
+        if in_hunk {
+            if let Some(hunk) = files.last_mut().and_then(|f| f.hunks.last_mut()) {
+                let (kind, old, new) = match line.chars().next() {
+                    Some('+') if new_left > 0 => ("added", None, Some(new_line)),
+                    Some('-') if old_left > 0 => ("removed", Some(old_line), None),
+                    // An empty line is a context line whose leading space was stripped.
+                    Some(' ') | None if old_left > 0 && new_left > 0 => ("context", Some(old_line), Some(new_line)),
+                    Some('\\') => {
+                        if let Some(last) = hunk.lines.last_mut() {
+                            last.no_newline = true;
+                        }
+                        continue;
+                    }
+                    _ => ("", None, None),
+                };
+                if !kind.is_empty() {
+                    if old.is_some() {
+                        old_line += 1;
+                        old_left -= 1;
+                    }
+                    if new.is_some() {
+                        new_line += 1;
+                        new_left -= 1;
+                    }
+                    hunk.lines.push(GitDiffLine {
+                        kind,
+                        old_line: old,
+                        new_line: new,
+                        content: line.get(1..).unwrap_or_default().to_string(),
+                        no_newline: false,
+                    });
+                    continue;
+                }
+            }
+            // Not a hunk line after all: the hunk was cut short.
+            old_left = 0;
+            new_left = 0;
+        }
+
+        if line.starts_with('\\') {
+            // "\ No newline at end of file" after the last line of a hunk.
+            if let Some(last) = files.last_mut().and_then(|f| f.hunks.last_mut()).and_then(|h| h.lines.last_mut()) {
+                last.no_newline = true;
+            }
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let (old, new) = diff_git_paths(rest);
+            files.push(GitDiffFile::new(old, new));
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            // `--no-index` and some patches have no `diff --git` line.
+            if let Some(rest) = line.strip_prefix("--- ") {
+                files.push(GitDiffFile::new(marker_path(rest), None));
+            }
+            continue;
+        };
+        if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) {
+            file.hunks.push(GitDiffHunk {
+                header: line.to_string(),
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                lines: Vec::new(),
+            });
+            old_left = old_lines;
+            new_left = new_lines;
+            old_line = old_start;
+            new_line = new_start;
+        } else if !file.hunks.is_empty() {
+            // A file without a `diff --git` line, after the previous one's hunks.
+            if let Some(rest) = line.strip_prefix("--- ") {
+                files.push(GitDiffFile::new(marker_path(rest), None));
+            }
+        } else if let Some(rest) = line.strip_prefix("--- ") {
+            file.old_path = marker_path(rest);
+        } else if let Some(rest) = line.strip_prefix("+++ ") {
+            file.new_path = marker_path(rest);
+        } else if line.starts_with("new file mode") {
+            file.status = "added";
+        } else if line.starts_with("deleted file mode") {
+            file.status = "deleted";
+        } else if let Some(rest) = line.strip_prefix("rename from ") {
+            file.old_path = Some(unquote_path(rest));
+            file.status = "renamed";
+        } else if let Some(rest) = line.strip_prefix("rename to ") {
+            file.new_path = Some(unquote_path(rest));
+        } else if let Some(rest) = line.strip_prefix("copy from ") {
+            file.old_path = Some(unquote_path(rest));
+            file.status = "copied";
+        } else if let Some(rest) = line.strip_prefix("copy to ") {
+            file.new_path = Some(unquote_path(rest));
+        } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+            file.binary = true;
+        }
+    }
+
+    for file in &mut files {
+        match file.status {
+            "added" => file.old_path = None,
+            "deleted" => file.new_path = None,
+            _ if file.old_path.is_none() && file.new_path.is_some() => file.status = "added",
+            _ if file.new_path.is_none() && file.old_path.is_some() => file.status = "deleted",
+            _ => {}
+        }
+    }
+    files
+}
+
+/// `file:hunk` position of a continuation token.
+fn parse_continuation(token: &str) -> Option<(usize, usize)> {
+    let (file, hunk) = token.split_once(':')?;
+    Some((file.parse().ok()?, hunk.parse().ok()?))
+}
+
+/// Cuts parsed files down to one page of whole hunks. A page always holds at least one
+/// hunk, so a single hunk larger than the limits still comes through.
+pub(crate) fn paginate_diff(files: Vec<GitDiffFile>, page: Option<GitDiffPageOptions>) -> Result<GitDiffPage, String> {
+    let page = page.unwrap_or_default();
+    let max_lines = page.max_lines.filter(|n| *n > 0).map(u64::from);
+    let max_bytes = match page.max_bytes.filter(|n| *n > 0) {
+        Some(n) => Some(n),
+        None if max_lines.is_none() => Some(DEFAULT_PAGE_MAX_BYTES),
+        None => None,
+    };
+
+    let (start_file, start_hunk) = match page.continuation.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(token) => parse_continuation(token)
+            .filter(|(f, h)| files.get(*f).is_some_and(|file| *h < file.hunks.len().max(1)))
+            .ok_or_else(|| format!("Invalid continuation token: {token}"))?,
+        None => (0, 0),
+    };
+    let total_files = files.len() as u32;
+    let total_hunks = files.iter().map(|f| f.hunks.len() as u32).sum();
+    let first_hunk = files[..start_file.min(files.len())].iter().map(|f| f.hunks.len() as u32).sum::<u32>() + start_hunk as u32;
+
+    let mut out: Vec<GitDiffFile> = Vec::new();
+    let mut continuation: Option<String> = None;
+    let (mut bytes, mut lines, mut taken) = (0u64, 0u64, 0u32);
+    for (file_index, mut file) in files.into_iter().enumerate().skip(start_file) {
+        if continuation.is_some() {
+            break;
+        }
+        let hunks = std::mem::take(&mut file.hunks);
+        if hunks.is_empty() {
+            // Binary files and pure renames have nothing to page.
+            out.push(file);
+            continue;
+        }
+        let skip = if file_index == start_file { start_hunk } else { 0 };
+        for (hunk_index, hunk) in hunks.into_iter().enumerate().skip(skip) {
+            let hunk_lines = hunk.lines.len() as u64 + 1;
+            let hunk_bytes = hunk.header.len() as u64 + 1 + hunk.lines.iter().map(|l| l.content.len() as u64 + 2).sum::<u64>();
+            let over = max_bytes.is_some_and(|m| bytes + hunk_bytes > m) || max_lines.is_some_and(|m| lines + hunk_lines > m);
+            if over && taken > 0 {
+                continuation = Some(format!("{file_index}:{hunk_index}"));
+                break;
+            }
+            bytes += hunk_bytes;
+            lines += hunk_lines;
+            taken += 1;
+            file.hunks.push(hunk);
+        }
+        if !file.hunks.is_empty() {
+            out.push(file);
+        }
+    }
+
+    Ok(GitDiffPage {
+        files: out,
+        total_files,
+        total_hunks,
+        first_hunk,
+        truncated: continuation.is_some(),
+        continuation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_FILES: &str = "diff --git a/src/a.rs b/src/a.rs
+index 1111111..2222222 100644
+--- a/src/a.rs
++++ b/src/a.rs
+@@ -1,3 +1,3 @@ fn main() {
+ one
+-two
++deux
+
+diff --git a/new.txt b/new.txt
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1 @@
++hello
+\\ No newline at end of file
+";
+
+    #[test]
+    fn test_parse_hunk_header_defaults_missing_lengths_to_one() {
+        assert_eq!(parse_hunk_header("@@ -3,4 +5,6 @@ fn x()"), Some((3, 4, 5, 6)));
+        assert_eq!(parse_hunk_header("@@ -0,0 +1 @@"), Some((0, 0, 1, 1)));
+        assert_eq!(parse_hunk_header("@@ -1 +1 @@"), Some((1, 1, 1, 1)));
+        assert_eq!(parse_hunk_header("not a hunk"), None);
+    }
+
+    #[test]
+    fn test_unquote_path_handles_escapes_and_octal_utf8() {
+        assert_eq!(unquote_path("plain.txt"), "plain.txt");
+        assert_eq!(unquote_path("\"a\\tb\""), "a\tb");
+        assert_eq!(unquote_path("\"\\303\\251t\\303\\251.txt\""), "été.txt");
+        assert_eq!(unquote_path("\"quote\\\"d\""), "quote\"d");
+    }
+
+    #[test]
+    fn test_diff_git_paths_splits_unquoted_paths_with_spaces() {
+        assert_eq!(
+            diff_git_paths("a/my file.txt b/my file.txt"),
+            (Some(String::from("my file.txt")), Some(String::from("my file.txt")))
+        );
+        assert_eq!(
+            diff_git_paths("\"a/x\\ty\" \"b/x\\ty\""),
+            (Some(String::from("x\ty")), Some(String::from("x\ty")))
+        );
+    }
+
+    #[test]
+    fn test_parse_unified_diff_numbers_lines_and_detects_added_files() {
+        let files = parse_unified_diff(TWO_FILES);
+        assert_eq!(files.len(), 2);
+
+        let a = &files[0];
+        assert_eq!(a.status, "modified");
+        assert_eq!(a.old_path.as_deref(), Some("src/a.rs"));
+        let lines = &a.hunks[0].lines;
+        let kinds: Vec<&str> = lines.iter().map(|l| l.kind).collect();
+        assert_eq!(kinds, ["context", "removed", "added", "context"]);
+        assert_eq!((lines[1].old_line, lines[1].new_line), (Some(2), None));
+        assert_eq!((lines[2].old_line, lines[2].new_line), (None, Some(2)));
+        // The stripped leading space of an empty context line.
+        assert_eq!((lines[3].old_line, lines[3].new_line, lines[3].content.as_str()), (Some(3), Some(3), ""));
+
+        let new = &files[1];
+        assert_eq!(new.status, "added");
+        assert_eq!(new.old_path, None);
+        assert_eq!(new.new_path.as_deref(), Some("new.txt"));
+        assert!(new.hunks[0].lines[0].no_newline);
+    }
+
+    #[test]
+    fn test_parse_unified_diff_reads_renames_and_binary_files() {
+        let text = "diff --git a/old.png b/new.png
+similarity index 90%
+rename from old.png
+rename to new.png
+Binary files a/old.png and b/new.png differ
+";
+        let files = parse_unified_diff(text);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].status, "renamed");
+        assert_eq!(files[0].old_path.as_deref(), Some("old.png"));
+        assert_eq!(files[0].new_path.as_deref(), Some("new.png"));
+        assert!(files[0].binary);
+    }
+
+    #[test]
+    fn test_paginate_diff_continues_at_the_next_hunk() {
+        let options = |continuation: Option<String>| GitDiffPageOptions {
+            max_bytes: None,
+            max_lines: Some(1),
+            continuation,
+        };
+        let first = paginate_diff(parse_unified_diff(TWO_FILES), Some(options(None))).unwrap();
+        assert_eq!((first.total_files, first.total_hunks, first.first_hunk), (2, 2, 0));
+        assert!(first.truncated);
+        assert_eq!(first.continuation.as_deref(), Some("1:0"));
+
+        let second = paginate_diff(parse_unified_diff(TWO_FILES), Some(options(first.continuation))).unwrap();
+        assert_eq!(second.first_hunk, 1);
+        assert_eq!(second.files.len(), 1);
+        assert!(!second.truncated);
+
+        assert!(paginate_diff(parse_unified_diff(TWO_FILES), Some(options(Some(String::from("9:0"))))).is_err());
+    }
+}
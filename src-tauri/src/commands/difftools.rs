@@ -0,0 +1,176 @@
+use serde::Serialize;
+use std::path::PathBuf;
+
+use super::config::git_config_value;
+
+/// An installed external diff/merge tool with a command template in the format
+/// `expand_external_diff_command` understands (`$LOCAL`, `$REMOTE`, `$BASE`).
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ExternalToolInfo {
+    id: String,
+    name: String,
+    path: String,
+    diff_command: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ExternalToolCatalog {
+    tools: Vec<ExternalToolInfo>,
+    diff_tool: Option<String>,
+    merge_tool: Option<String>,
+}
+
+struct KnownTool {
+    /// Tool name as used by `git difftool --tool=<id>`.
+    id: &'static str,
+    name: &'static str,
+    /// Executable names looked up on PATH.
+    executables: &'static [&'static str],
+    /// Install locations relative to a Windows program directory.
+    windows_dirs: &'static [&'static str],
+    /// Absolute install locations on macOS.
+    mac_paths: &'static [&'static str],
+    diff_args: &'static str,
+}
+
+const KNOWN_TOOLS: &[KnownTool] = &[
+    KnownTool {
+        id: "vscode",
+        name: "Visual Studio Code",
+        executables: &["code", "code.cmd"],
+        windows_dirs: &["Microsoft VS Code\\bin\\code.cmd", "Programs\\Microsoft VS Code\\bin\\code.cmd"],
+        mac_paths: &["/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code"],
+        diff_args: "--wait --diff \"$LOCAL\" \"$REMOTE\"",
+    },
+    KnownTool {
+        id: "bc",
+        name: "Beyond Compare",
+        executables: &["bcompare", "bcomp.exe"],
+        windows_dirs: &["Beyond Compare 5\\BComp.exe", "Beyond Compare 4\\BComp.exe", "Beyond Compare 3\\BComp.exe"],
+        mac_paths: &["/Applications/Beyond Compare.app/Contents/MacOS/bcomp"],
+        diff_args: "\"$LOCAL\" \"$REMOTE\"",
+    },
+    KnownTool {
+        id: "kdiff3",
+        name: "KDiff3",
+        executables: &["kdiff3", "kdiff3.exe"],
+        windows_dirs: &["KDiff3\\kdiff3.exe", "KDiff3\\bin\\kdiff3.exe"],
+        mac_paths: &["/Applications/kdiff3.app/Contents/MacOS/kdiff3"],
+        diff_args: "\"$LOCAL\" \"$REMOTE\"",
+    },
+    KnownTool {
+        id: "meld",
+        name: "Meld",
+        executables: &["meld", "meld.exe"],
+        windows_dirs: &["Meld\\Meld.exe"],
+        mac_paths: &["/Applications/Meld.app/Contents/MacOS/Meld"],
+        diff_args: "\"$LOCAL\" \"$REMOTE\"",
+    },
+    KnownTool {
+        id: "araxis",
+        name: "Araxis Merge",
+        // Not looked up on PATH: `compare` there is usually ImageMagick.
+        executables: &[],
+        windows_dirs: &["Araxis\\Araxis Merge\\Compare.exe"],
+        mac_paths: &["/Applications/Araxis Merge.app/Contents/Utilities/compare"],
+        diff_args: "-wait -2 \"$LOCAL\" \"$REMOTE\"",
+    },
+    KnownTool {
+        id: "winmerge",
+        name: "WinMerge",
+        executables: &["winmergeu.exe"],
+        windows_dirs: &["WinMerge\\WinMergeU.exe"],
+        mac_paths: &[],
+        diff_args: "-e -u \"$LOCAL\" \"$REMOTE\"",
+    },
+];
+
+fn find_in_path(executable: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(executable))
+        .find(|p| p.is_file())
+}
+
+fn windows_program_dirs() -> Vec<PathBuf> {
+    ["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn locate_tool(tool: &KnownTool) -> Option<PathBuf> {
+    if let Some(p) = tool.executables.iter().find_map(|e| find_in_path(e)) {
+        return Some(p);
+    }
+    if cfg!(target_os = "windows") {
+        for dir in windows_program_dirs() {
+            if let Some(p) = tool.windows_dirs.iter().map(|rel| dir.join(rel)).find(|p| p.is_file()) {
+                return Some(p);
+            }
+        }
+    }
+    if cfg!(target_os = "macos") {
+        return tool.mac_paths.iter().map(PathBuf::from).find(|p| p.is_file());
+    }
+    None
+}
+
+/// A tool configured in gitconfig with its own `difftool.<name>.cmd`, which is already in
+/// the `$LOCAL`/`$REMOTE` form the launcher expects.
+fn configured_custom_tool(repo_path: Option<&str>, id: &str) -> Option<ExternalToolInfo> {
+    let diff_command = git_config_value(repo_path, format!("difftool.{id}.cmd").as_str())?;
+    let path = git_config_value(repo_path, format!("difftool.{id}.path").as_str()).unwrap_or_default();
+    Some(ExternalToolInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        path,
+        diff_command,
+    })
+}
+
+#[tauri::command]
+pub(crate) fn detect_external_diff_tools(repo_path: Option<String>) -> Result<ExternalToolCatalog, String> {
+    let repo_path = repo_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(repo_path) = repo_path.as_deref() {
+        crate::ensure_is_git_worktree(repo_path)?;
+    }
+    let repo_path = repo_path.as_deref();
+
+    let diff_tool = git_config_value(repo_path, "diff.tool");
+    let merge_tool = git_config_value(repo_path, "merge.tool");
+
+    let mut tools: Vec<ExternalToolInfo> = Vec::new();
+    for tool in KNOWN_TOOLS {
+        // An explicit `difftool.<id>.path` wins over detection.
+        let configured = git_config_value(repo_path, format!("difftool.{}.path", tool.id).as_str())
+            .map(PathBuf::from)
+            .filter(|p| p.is_file());
+        let Some(path) = configured.or_else(|| locate_tool(tool)) else {
+            continue;
+        };
+        let path = path.to_string_lossy().to_string();
+        tools.push(ExternalToolInfo {
+            id: tool.id.to_string(),
+            name: tool.name.to_string(),
+            diff_command: format!("\"{path}\" {}", tool.diff_args),
+            path,
+        });
+    }
+
+    for id in [diff_tool.as_deref(), merge_tool.as_deref()].into_iter().flatten() {
+        if tools.iter().any(|t| t.id == id) {
+            continue;
+        }
+        if let Some(t) = configured_custom_tool(repo_path, id) {
+            tools.push(t);
+        }
+    }
+
+    Ok(ExternalToolCatalog {
+        tools,
+        diff_tool,
+        merge_tool,
+    })
+}
@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use crate::{ensure_is_git_worktree, git_command_in_repo, run_git, GitCommit};
+use crate::{ensure_is_git_worktree, git_command_in_repo, mark_pushed_commits, run_git, GitCommit};
 
 #[derive(Debug, Deserialize)]
 pub struct GitLogSearchParams {
@@ -34,7 +34,7 @@ pub struct GitLogSearchParams {
 pub fn git_log_search(repo_path: String, params: GitLogSearchParams) -> Result<Vec<GitCommit>, String> {
     ensure_is_git_worktree(&repo_path)?;
 
-    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%s\x1f%D\x1e";
+    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%cd\x1f%s\x1f%D\x1e";
     let pretty = format!("--pretty=format:{format}");
 
     let mut args: Vec<String> = vec![
@@ -43,6 +43,8 @@ pub fn git_log_search(repo_path: String, params: GitLogSearchParams) -> Result<V
     ];
 
     if params.all.unwrap_or(false) {
+        // Internal snapshot refs (undo points, index snapshots) are not history.
+        args.push(String::from("--exclude=refs/graphoria/*"));
         args.push(String::from("--all"));
     }
 
@@ -227,6 +229,7 @@ pub fn git_log_search(repo_path: String, params: GitLogSearchParams) -> Result<V
         let author = parts.next().unwrap_or_default().to_string();
         let author_email = parts.next().unwrap_or_default().to_string();
         let date = parts.next().unwrap_or_default().to_string();
+        let committer_date = parts.next().unwrap_or_default().to_string();
         let subject = parts.next().unwrap_or_default().to_string();
         let decorations = parts.next().unwrap_or_default().trim().to_string();
 
@@ -246,9 +249,12 @@ pub fn git_log_search(repo_path: String, params: GitLogSearchParams) -> Result<V
             author,
             author_email,
             date,
+            committer_date,
             subject,
             refs: decorations,
             is_head: head == hash,
+            is_pushed: false,
+            wip: None,
         });
     }
 
@@ -281,5 +287,6 @@ pub fn git_log_search(repo_path: String, params: GitLogSearchParams) -> Result<V
         }
     }
 
+    mark_pushed_commits(&repo_path, &mut commits);
     Ok(commits)
 }
@@ -0,0 +1,482 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+
+/// Per-repo graph filter, stored as JSON in the repository's git directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct GraphFilterSettings {
+    hidden_remotes: Vec<String>,
+    hidden_branch_patterns: Vec<String>,
+    show_tags: bool,
+}
+
+impl Default for GraphFilterSettings {
+    fn default() -> Self {
+        GraphFilterSettings {
+            hidden_remotes: Vec::new(),
+            hidden_branch_patterns: Vec::new(),
+            show_tags: true,
+        }
+    }
+}
+
+impl GraphFilterSettings {
+    fn is_empty(&self) -> bool {
+        self.hidden_remotes.is_empty() && self.hidden_branch_patterns.is_empty() && self.show_tags
+    }
+
+    /// Hidden branches relative to `refs/heads/`.
+    fn excluded_heads(&self) -> Vec<String> {
+        self.hidden_branch_patterns.clone()
+    }
+
+    /// Hidden remote-tracking branches relative to `refs/remotes/`.
+    fn excluded_remotes(&self) -> Vec<String> {
+        let mut out: Vec<String> = self.hidden_remotes.iter().map(|r| format!("{r}/*")).collect();
+        out.extend(self.hidden_branch_patterns.iter().map(|p| format!("*/{p}")));
+        out
+    }
+
+    /// Rev selection for the graph: `--branches`, `--tags` and `--remotes`, each preceded
+    /// by the `--exclude` patterns that apply to it (git resets them after every group and
+    /// matches them against names relative to the group's namespace).
+    pub(crate) fn rev_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = Vec::new();
+        for p in self.excluded_heads() {
+            args.push(format!("--exclude={p}"));
+        }
+        args.push(String::from("--branches"));
+        if self.show_tags {
+            args.push(String::from("--tags"));
+        }
+        for p in self.excluded_remotes() {
+            args.push(format!("--exclude={p}"));
+        }
+        args.push(String::from("--remotes"));
+        args
+    }
+
+    /// Keeps hidden refs out of the `%D` labels of commits that stay visible.
+    pub(crate) fn decorate_args(&self) -> Vec<String> {
+        let mut patterns: Vec<String> = self.excluded_heads().iter().map(|p| format!("refs/heads/{p}")).collect();
+        patterns.extend(self.excluded_remotes().iter().map(|p| format!("refs/remotes/{p}")));
+        if !self.show_tags {
+            patterns.push(String::from("refs/tags/*"));
+        }
+        patterns
+            .into_iter()
+            .map(|p| format!("--decorate-refs-exclude={p}"))
+            .collect()
+    }
+}
+
+fn graph_filter_path(repo_path: &str) -> Option<PathBuf> {
+    Some(super::handles::git_common_dir(repo_path)?.join("graphoria-graph-filter.json"))
+}
+
+pub(crate) fn load_graph_filter(repo_path: &str) -> GraphFilterSettings {
+    graph_filter_path(repo_path)
+        .and_then(|p| fs::read_to_string(&p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn normalize_list(values: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for v in values {
+        let v = v.trim().trim_matches('/').to_string();
+        if !v.is_empty() && !out.contains(&v) {
+            out.push(v);
+        }
+    }
+    out
+}
+
+#[tauri::command]
+pub(crate) fn get_graph_filter(repo_path: String) -> Result<GraphFilterSettings, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    Ok(load_graph_filter(&repo_path))
+}
+
+#[tauri::command]
+pub(crate) fn set_graph_filter(repo_path: String, filter: GraphFilterSettings) -> Result<GraphFilterSettings, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let filter = GraphFilterSettings {
+        hidden_remotes: normalize_list(filter.hidden_remotes),
+        hidden_branch_patterns: normalize_list(filter.hidden_branch_patterns),
+        show_tags: filter.show_tags,
+    };
+    let path = graph_filter_path(&repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    if filter.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove graph filter: {e}"))?;
+        }
+    } else {
+        let json = serde_json::to_string_pretty(&filter).map_err(|e| format!("Failed to serialize graph filter: {e}"))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write graph filter: {e}"))?;
+    }
+    Ok(filter)
+}
+
+// Graph snapshots: the ref tips the graph was rendered from, kept in memory under a
+// token so a later `graph_delta` can report only what changed since then.
+
+const MAX_SNAPSHOTS_PER_REPO: usize = 16;
+const MAX_DELTA_COMMITS: u32 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct GraphRefState {
+    head: String,
+    refs: BTreeMap<String, String>,
+}
+
+type GraphSnapshots = HashMap<String, VecDeque<(String, GraphRefState)>>;
+
+static GRAPH_SNAPSHOTS: OnceLock<Mutex<GraphSnapshots>> = OnceLock::new();
+
+fn graph_snapshots() -> &'static Mutex<GraphSnapshots> {
+    GRAPH_SNAPSHOTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitGraphDelta {
+    snapshot_id: String,
+    full_reload: bool,
+    head: String,
+    added_commits: Vec<crate::GitCommit>,
+    removed_commits: Vec<String>,
+    changed_commits: Vec<crate::GitCommit>,
+    ref_updates: Vec<crate::GitRefUpdate>,
+}
+
+fn read_ref_state(repo_path: &str) -> GraphRefState {
+    let head = crate::resolve_commit(repo_path, "HEAD").unwrap_or_default();
+
+    // Tags are peeled so every value is a commit the graph can show.
+    let raw = crate::run_git(
+        repo_path,
+        &[
+            "for-each-ref",
+            "--format=%(refname)%09%(objectname)%09%(*objectname)",
+            "refs/heads",
+            "refs/tags",
+            "refs/remotes",
+        ],
+    )
+    .unwrap_or_default();
+
+    // Refs hidden by the graph filter are left out so deltas match the rendered graph.
+    let filter = load_graph_filter(repo_path);
+    let visible: Option<HashSet<String>> = if filter.is_empty() {
+        None
+    } else {
+        let mut args: Vec<String> = vec![String::from("rev-parse"), String::from("--symbolic-full-name")];
+        args.extend(filter.rev_args());
+        let args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        let listed = crate::run_git(repo_path, args.as_slice()).unwrap_or_default();
+        Some(listed.lines().map(|l| l.trim().to_string()).collect())
+    };
+
+    let mut refs: BTreeMap<String, String> = BTreeMap::new();
+    for line in raw.lines() {
+        let mut parts = line.split('\t');
+        let name = parts.next().unwrap_or_default().trim();
+        let object = parts.next().unwrap_or_default().trim();
+        let peeled = parts.next().unwrap_or_default().trim();
+        if name.is_empty() || object.is_empty() {
+            continue;
+        }
+        if visible.as_ref().is_some_and(|v| !v.contains(name)) {
+            continue;
+        }
+        let target = if peeled.is_empty() { object } else { peeled };
+        refs.insert(name.to_string(), target.to_string());
+    }
+
+    GraphRefState { head, refs }
+}
+
+fn remember_snapshot(repo_path: &str, state: &GraphRefState) -> String {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    let id = format!("{:016x}", hasher.finish());
+
+    if let Ok(mut guard) = graph_snapshots().lock() {
+        let list = guard.entry(crate::normalize_repo_path(repo_path)).or_default();
+        if !list.iter().any(|(sid, _)| *sid == id) {
+            list.push_back((id.clone(), state.clone()));
+            while list.len() > MAX_SNAPSHOTS_PER_REPO {
+                list.pop_front();
+            }
+        }
+    }
+    id
+}
+
+fn find_snapshot(repo_path: &str, id: &str) -> Option<GraphRefState> {
+    let guard = graph_snapshots().lock().ok()?;
+    guard
+        .get(&crate::normalize_repo_path(repo_path))?
+        .iter()
+        .find(|(sid, _)| sid == id)
+        .map(|(_, state)| state.clone())
+}
+
+/// Drops the snapshots of a repository that was closed.
+pub(crate) fn forget_snapshots(repo_path: &str) {
+    if let Ok(mut guard) = graph_snapshots().lock() {
+        guard.remove(&crate::normalize_repo_path(repo_path));
+    }
+}
+
+fn tips_with_exclusions(include: &GraphRefState, exclude: &GraphRefState) -> Vec<String> {
+    let mut revs: Vec<String> = Vec::new();
+    for t in include.refs.values().chain(std::iter::once(&include.head)) {
+        if !t.is_empty() && !revs.contains(t) {
+            revs.push(t.clone());
+        }
+    }
+    for t in exclude.refs.values().chain(std::iter::once(&exclude.head)) {
+        let not = format!("^{t}");
+        if !t.is_empty() && !revs.contains(&not) {
+            revs.push(not);
+        }
+    }
+    revs
+}
+
+fn full_reload(snapshot_id: String, head: String) -> GitGraphDelta {
+    GitGraphDelta {
+        snapshot_id,
+        full_reload: true,
+        head,
+        added_commits: Vec::new(),
+        removed_commits: Vec::new(),
+        changed_commits: Vec::new(),
+        ref_updates: Vec::new(),
+    }
+}
+
+/// Token for the ref state the graph is currently rendered from.
+#[tauri::command]
+pub(crate) fn graph_snapshot(repo_path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    let state = read_ref_state(&repo_path);
+    Ok(remember_snapshot(&repo_path, &state))
+}
+
+/// Commits and refs added or removed since `since_snapshot_id`. `full_reload` is set
+/// when the snapshot is unknown or the change is too large to patch incrementally.
+#[tauri::command]
+pub(crate) fn graph_delta(repo_path: String, since_snapshot_id: String) -> Result<GitGraphDelta, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let new_state = read_ref_state(&repo_path);
+    let snapshot_id = remember_snapshot(&repo_path, &new_state);
+    let head = new_state.head.clone();
+
+    let Some(old_state) = find_snapshot(&repo_path, since_snapshot_id.trim()) else {
+        return Ok(full_reload(snapshot_id, head));
+    };
+
+    let mut ref_updates: Vec<crate::GitRefUpdate> = Vec::new();
+    for (name, old) in &old_state.refs {
+        match new_state.refs.get(name) {
+            Some(new) if new == old => {}
+            new => ref_updates.push(crate::GitRefUpdate {
+                name: name.clone(),
+                old: Some(old.clone()),
+                new: new.cloned(),
+            }),
+        }
+    }
+    for (name, new) in &new_state.refs {
+        if !old_state.refs.contains_key(name) {
+            ref_updates.push(crate::GitRefUpdate {
+                name: name.clone(),
+                old: None,
+                new: Some(new.clone()),
+            });
+        }
+    }
+    if old_state.head != new_state.head {
+        ref_updates.push(crate::GitRefUpdate {
+            name: String::from("HEAD"),
+            old: Some(old_state.head.clone()).filter(|h| !h.is_empty()),
+            new: Some(new_state.head.clone()).filter(|h| !h.is_empty()),
+        });
+    }
+
+    if ref_updates.is_empty() {
+        return Ok(GitGraphDelta {
+            snapshot_id,
+            full_reload: false,
+            head,
+            added_commits: Vec::new(),
+            removed_commits: Vec::new(),
+            changed_commits: Vec::new(),
+            ref_updates,
+        });
+    }
+
+    // Old tips may already be pruned; a failing walk means the caller must reload.
+    let added_revs = tips_with_exclusions(&new_state, &old_state);
+    let Ok(added_commits) = crate::git_log_commits_decorated(&repo_path, &added_revs, MAX_DELTA_COMMITS + 1) else {
+        return Ok(full_reload(snapshot_id, head));
+    };
+
+    let mut removed_args: Vec<String> = vec![
+        String::from("rev-list"),
+        String::from("-n"),
+        (MAX_DELTA_COMMITS + 1).to_string(),
+    ];
+    removed_args.extend(tips_with_exclusions(&old_state, &new_state));
+    let removed_args: Vec<&str> = removed_args.iter().map(|s| s.as_str()).collect();
+    let Ok(removed_raw) = crate::run_git(&repo_path, removed_args.as_slice()) else {
+        return Ok(full_reload(snapshot_id, head));
+    };
+    let removed_commits: Vec<String> = removed_raw
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    if added_commits.len() as u32 > MAX_DELTA_COMMITS || removed_commits.len() as u32 > MAX_DELTA_COMMITS {
+        return Ok(full_reload(snapshot_id, head));
+    }
+
+    // Commits that stay in the graph but whose ref labels changed.
+    let mut changed_revs: Vec<String> = Vec::new();
+    for u in &ref_updates {
+        for t in [u.old.as_ref(), u.new.as_ref()].into_iter().flatten() {
+            let known = added_commits.iter().any(|c| c.hash == *t) || removed_commits.contains(t);
+            if !known && !changed_revs.contains(t) {
+                changed_revs.push(t.clone());
+            }
+        }
+    }
+    let mut changed_commits: Vec<crate::GitCommit> = Vec::new();
+    for rev in &changed_revs {
+        if let Ok(mut c) = crate::git_log_commits_decorated(&repo_path, std::slice::from_ref(rev), 1) {
+            changed_commits.append(&mut c);
+        }
+    }
+
+    Ok(GitGraphDelta {
+        snapshot_id,
+        full_reload: false,
+        head,
+        added_commits,
+        removed_commits,
+        changed_commits,
+        ref_updates,
+    })
+}
+
+// Search-and-jump: finds a commit anywhere in the history the graph can load and reports
+// its position, so the UI can load enough commits to reach it and scroll there.
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GraphSearchHit {
+    hash: String,
+    subject: String,
+    refs: String,
+    /// Index of the commit in the graph order (without a WIP row); pass it back as
+    /// `after_cursor` to find the next match.
+    cursor: u32,
+    /// `hash`, `ref` or `subject`.
+    matched: String,
+}
+
+fn graph_search_match(query: &str, hash: &str, subject: &str, refs: &str) -> Option<&'static str> {
+    if query.len() >= 4 && query.bytes().all(|b| b.is_ascii_hexdigit()) && hash.starts_with(query) {
+        return Some("hash");
+    }
+    let ref_hit = refs
+        .split(", ")
+        .map(|r| r.trim_start_matches("HEAD -> ").trim_start_matches("tag: "))
+        .any(|r| r.to_lowercase().contains(query));
+    if ref_hit {
+        return Some("ref");
+    }
+    subject.to_lowercase().contains(query).then_some("subject")
+}
+
+/// First commit after `after_cursor` (or from the top) whose hash starts with `query`, or
+/// whose refs or subject contain it, case-insensitively. The graph options must match the
+/// ones the graph was listed with, so positions line up.
+#[tauri::command]
+pub(crate) fn find_commit_in_graph(
+    repo_path: String,
+    query: String,
+    after_cursor: Option<u32>,
+    only_head: Option<bool>,
+    history_order: Option<String>,
+    first_parent: Option<bool>,
+) -> Result<Option<GraphSearchHit>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Err(String::from("query is empty"));
+    }
+    let history_order = history_order.unwrap_or_else(|| String::from("topo"));
+    let args = crate::graph_log_args(
+        &repo_path,
+        "%H\x1f%s\x1f%D\x1e",
+        None,
+        only_head.unwrap_or(false),
+        &history_order,
+        first_parent.unwrap_or(false),
+    );
+
+    let mut child = crate::git_command_in_repo(&repo_path)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn git log: {e}"))?;
+    let stdout = child.stdout.take().ok_or_else(|| String::from("Failed to read git log output."))?;
+
+    // Read record by record and stop at the first hit instead of listing all history.
+    let mut hit: Option<GraphSearchHit> = None;
+    for (i, record) in BufReader::new(stdout).split(b'\x1e').enumerate() {
+        let Ok(record) = record else {
+            break;
+        };
+        let cursor = i as u32;
+        if after_cursor.is_some_and(|after| cursor <= after) {
+            continue;
+        }
+        let record = String::from_utf8_lossy(&record);
+        let mut parts = record.trim_start_matches('\n').split('\x1f');
+        let hash = parts.next().unwrap_or_default();
+        let subject = parts.next().unwrap_or_default();
+        let refs = parts.next().unwrap_or_default();
+        if hash.is_empty() {
+            continue;
+        }
+        if let Some(matched) = graph_search_match(query.as_str(), hash, subject, refs) {
+            hit = Some(GraphSearchHit {
+                hash: hash.to_string(),
+                subject: subject.to_string(),
+                refs: refs.to_string(),
+                cursor,
+                matched: matched.to_string(),
+            });
+            break;
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+    Ok(hit)
+}
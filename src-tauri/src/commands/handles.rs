@@ -0,0 +1,199 @@
+// Open repositories. `open_repository` validates a repository once and keeps what every
+// command would otherwise re-derive (root, git directories) together with the per-repo
+// services (ref watcher, backup scheduling); `close_repository` tears them down again.
+// The handle key is the normalized root path `open_repository` returns; commands take it
+// as their `repo_path` and resolve an open repository from its handle instead of
+// validating the path with git on every call.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Debug, Clone)]
+struct RepoHandle {
+    root: String,
+    git_dir: PathBuf,
+    common_dir: PathBuf,
+    /// Windows or tabs that opened the repository; it is closed when the last one goes.
+    open_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct RepoHandleInfo {
+    key: String,
+    root: String,
+    git_dir: String,
+    common_dir: String,
+    /// Whether this is a linked worktree of another repository.
+    linked_worktree: bool,
+    open_count: u32,
+    graph_filter: super::graph::GraphFilterSettings,
+}
+
+static REPO_HANDLES: OnceLock<Mutex<HashMap<String, RepoHandle>>> = OnceLock::new();
+
+fn repo_handles() -> &'static Mutex<HashMap<String, RepoHandle>> {
+    REPO_HANDLES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn handle(repo_path: &str) -> Option<RepoHandle> {
+    repo_handles()
+        .lock()
+        .ok()?
+        .get(&crate::normalize_repo_path(repo_path))
+        .cloned()
+}
+
+/// Whether `repo_path` is an open repository whose root still leads to the git directory
+/// resolved when it was opened, in which case it needs no further validation.
+pub(crate) fn is_open(repo_path: &str) -> bool {
+    handle(repo_path).is_some_and(|h| {
+        let opened = fs::canonicalize(&h.git_dir).ok();
+        opened.is_some() && root_git_dir(&h.root) == opened
+    })
+}
+
+/// The git directory `root/.git` leads to: the directory itself, or the `gitdir:` target
+/// of a linked worktree's or submodule's `.git` file.
+fn root_git_dir(root: &str) -> Option<PathBuf> {
+    let dot_git = Path::new(root).join(".git");
+    if dot_git.is_dir() {
+        return fs::canonicalize(dot_git).ok();
+    }
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let target = content.lines().find_map(|l| l.strip_prefix("gitdir:"))?.trim();
+    fs::canonicalize(resolve_dir(root, target)?).ok()
+}
+
+fn resolve_dir(repo_path: &str, dir: &str) -> Option<PathBuf> {
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return None;
+    }
+    let p = PathBuf::from(dir);
+    Some(if p.is_absolute() { p } else { Path::new(repo_path).join(p) })
+}
+
+/// The repository's own git directory (`.git`, or `.git/worktrees/<name>` for a linked
+/// worktree).
+pub(crate) fn git_dir(repo_path: &str) -> Option<PathBuf> {
+    if let Some(h) = handle(repo_path) {
+        return Some(h.git_dir);
+    }
+    resolve_dir(repo_path, crate::run_git(repo_path, &["rev-parse", "--git-dir"]).ok()?.as_str())
+}
+
+/// The git directory shared by all worktrees, where refs and config live.
+pub(crate) fn git_common_dir(repo_path: &str) -> Option<PathBuf> {
+    if let Some(h) = handle(repo_path) {
+        return Some(h.common_dir);
+    }
+    resolve_dir(repo_path, crate::run_git(repo_path, &["rev-parse", "--git-common-dir"]).ok()?.as_str())
+}
+
+/// Must not be called with the handle map locked; reading settings looks the handle up.
+fn handle_info(key: &str, h: &RepoHandle) -> RepoHandleInfo {
+    RepoHandleInfo {
+        key: key.to_string(),
+        root: h.root.clone(),
+        git_dir: h.git_dir.to_string_lossy().to_string(),
+        common_dir: h.common_dir.to_string_lossy().to_string(),
+        linked_worktree: h.git_dir != h.common_dir,
+        open_count: h.open_count,
+        graph_filter: super::graph::load_graph_filter(&h.root),
+    }
+}
+
+/// Opens a repository: validates it, caches its layout and starts its ref watcher and
+/// scheduled backups. Opening an open repository again only counts another user.
+#[tauri::command]
+pub(crate) fn open_repository(app: tauri::AppHandle, repo_path: String) -> Result<RepoHandleInfo, String> {
+    let repo_path = repo_path.trim().to_string();
+    let key = crate::normalize_repo_path(&repo_path);
+    let reopened = repo_handles()
+        .lock()
+        .map_err(|_| String::from("Failed to lock repository handles."))?
+        .get_mut(&key)
+        .map(|h| {
+            h.open_count += 1;
+            h.clone()
+        });
+    if let Some(h) = reopened {
+        return Ok(handle_info(&key, &h));
+    }
+
+    crate::ensure_is_git_worktree(&repo_path)?;
+    let dirs = crate::run_git(&repo_path, &["rev-parse", "--git-dir", "--git-common-dir"])?;
+    let mut lines = dirs.lines();
+    let git_dir = lines
+        .next()
+        .and_then(|d| resolve_dir(&repo_path, d))
+        .ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    let common_dir = lines
+        .next()
+        .and_then(|d| resolve_dir(&repo_path, d))
+        .unwrap_or_else(|| git_dir.clone());
+
+    let h = RepoHandle {
+        root: repo_path.clone(),
+        git_dir,
+        common_dir,
+        open_count: 1,
+    };
+    let info = handle_info(&key, &h);
+    repo_handles()
+        .lock()
+        .map_err(|_| String::from("Failed to lock repository handles."))?
+        .entry(key)
+        .and_modify(|existing| existing.open_count += 1)
+        .or_insert(h);
+
+    super::recovery::remember_repo(&app, &repo_path);
+    super::watcher::watch_repo_refs(app, repo_path.clone())?;
+    super::backup::register_scheduled_repo(&repo_path);
+    Ok(info)
+}
+
+/// Releases one `open_repository`. When the last user closes it, the watcher stops,
+/// scheduled backups pause and cached diffs and graph snapshots are dropped. Returns
+/// whether the repository was closed for good.
+#[tauri::command]
+pub(crate) fn close_repository(repo_path: String) -> Result<bool, String> {
+    let key = crate::normalize_repo_path(&repo_path);
+    {
+        let mut handles = repo_handles()
+            .lock()
+            .map_err(|_| String::from("Failed to lock repository handles."))?;
+        let Some(h) = handles.get_mut(&key) else {
+            return Ok(false);
+        };
+        h.open_count = h.open_count.saturating_sub(1);
+        if h.open_count > 0 {
+            return Ok(false);
+        }
+        handles.remove(&key);
+    }
+
+    super::watcher::unwatch_repo_refs(key.clone())?;
+    super::backup::unregister_scheduled_repo(&key);
+    super::diff_cache::invalidate_repo(&key);
+    super::graph::forget_snapshots(&key);
+    crate::forget_repo_git_lock(&key);
+    Ok(true)
+}
+
+/// Repositories that are currently open.
+#[tauri::command]
+pub(crate) fn list_open_repositories() -> Result<Vec<RepoHandleInfo>, String> {
+    let handles: Vec<(String, RepoHandle)> = repo_handles()
+        .lock()
+        .map_err(|_| String::from("Failed to lock repository handles."))?
+        .iter()
+        .map(|(k, h)| (k.clone(), h.clone()))
+        .collect();
+    let mut out: Vec<RepoHandleInfo> = handles.iter().map(|(k, h)| handle_info(k, h)).collect();
+    out.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(out)
+}
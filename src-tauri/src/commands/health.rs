@@ -0,0 +1,480 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitUnreachableSummary {
+    commits: u32,
+    objects: u32,
+    reflog_only_commits: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitReflogExpiry {
+    reflog_expire: String,
+    reflog_expire_unreachable: String,
+    prune_expire: String,
+}
+
+/// The object store as `git count-objects -v` reports it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct GitObjectCounts {
+    loose_objects: u64,
+    loose_size_kib: u64,
+    packs: u64,
+    pack_size_kib: u64,
+    garbage: u64,
+}
+
+impl GitObjectCounts {
+    pub(crate) fn total_kib(&self) -> u64 {
+        self.loose_size_kib + self.pack_size_kib
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRepoHealth {
+    #[serde(flatten)]
+    objects: GitObjectCounts,
+    unreachable: GitUnreachableSummary,
+    expiry: GitReflogExpiry,
+}
+
+pub(crate) fn count_objects(repo_path: &str) -> Result<GitObjectCounts, String> {
+    let raw = crate::run_git(repo_path, &["count-objects", "-v"])?;
+    let mut counts = GitObjectCounts::default();
+    for line in raw.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().parse::<u64>().unwrap_or(0);
+        match key.trim() {
+            "count" => counts.loose_objects = value,
+            "size" => counts.loose_size_kib = value,
+            "packs" => counts.packs = value,
+            "size-pack" => counts.pack_size_kib = value,
+            "garbage" => counts.garbage = value,
+            _ => {}
+        }
+    }
+    Ok(counts)
+}
+
+pub(crate) fn config_or(repo_path: &str, key: &str, default: &str) -> String {
+    crate::run_git(repo_path, &["config", "--get", key])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Counts `unreachable <type> <id>` lines of a connectivity-only fsck, which skips
+/// blob content checks and stays fast on large repositories. Reflog entries count as
+/// references, so only objects nothing points at are reported.
+fn count_unreachable(repo_path: &str) -> Result<(u32, u32), String> {
+    let raw = crate::run_git(repo_path, &["fsck", "--unreachable", "--connectivity-only", "--no-progress"])?;
+
+    let mut commits = 0u32;
+    let mut objects = 0u32;
+    for line in raw.lines() {
+        let Some(rest) = line.trim().strip_prefix("unreachable ") else {
+            continue;
+        };
+        objects += 1;
+        if rest.starts_with("commit ") {
+            commits += 1;
+        }
+    }
+    Ok((commits, objects))
+}
+
+fn unreachable_summary(repo_path: &str) -> Result<GitUnreachableSummary, String> {
+    let (commits, objects) = count_unreachable(repo_path)?;
+    // Commits only kept alive by reflog entries become unreachable once those expire.
+    let reflog_only = crate::run_git(repo_path, &["rev-list", "--count", "--reflog", "--not", "--all"])?;
+
+    Ok(GitUnreachableSummary {
+        commits,
+        objects,
+        reflog_only_commits: reflog_only.trim().parse::<u32>().unwrap_or(0),
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn git_repo_health(repo_path: String) -> Result<GitRepoHealth, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        Ok(GitRepoHealth {
+            objects: count_objects(&repo_path)?,
+            unreachable: unreachable_summary(&repo_path)?,
+            expiry: GitReflogExpiry {
+                reflog_expire: config_or(&repo_path, "gc.reflogExpire", "90.days.ago"),
+                reflog_expire_unreachable: config_or(&repo_path, "gc.reflogExpireUnreachable", "30.days.ago"),
+                prune_expire: config_or(&repo_path, "gc.pruneExpire", "2.weeks.ago"),
+            },
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to check repository health: {e}"))?
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitFsckIssue {
+    /// "dangling" | "missing" | "broken_link" | "corrupt" | "error" | "warning" | "notice"
+    kind: String,
+    /// "info" | "warning" | "error"
+    severity: String,
+    object_type: Option<String>,
+    object: Option<String>,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitFsckSuggestion {
+    /// "gc" | "backup" | "refetch" | "reclone"
+    action: String,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitFsckReport {
+    /// False when fsck stopped early on an object it could not read.
+    complete: bool,
+    errors: u32,
+    warnings: u32,
+    dangling: u32,
+    issues: Vec<GitFsckIssue>,
+    suggestions: Vec<GitFsckSuggestion>,
+}
+
+fn fsck_issue(kind: &str, severity: &str, object_type: Option<&str>, object: Option<&str>, message: &str) -> GitFsckIssue {
+    GitFsckIssue {
+        kind: kind.to_string(),
+        severity: severity.to_string(),
+        object_type: object_type.map(str::to_string),
+        object: object.map(str::to_string),
+        message: message.to_string(),
+    }
+}
+
+/// Parses `<type> <id>` at the start of `s`.
+fn typed_object(s: &str) -> Option<(&str, &str)> {
+    let mut parts = s.split_whitespace();
+    let object_type = parts.next()?;
+    let object = parts.next()?.trim_end_matches(':');
+    matches!(object_type, "commit" | "tree" | "blob" | "tag").then_some((object_type, object))
+}
+
+/// Turns fsck's report (stdout and stderr) into issues.
+fn parse_fsck_output(stdout: &str, stderr: &str) -> Vec<GitFsckIssue> {
+    let mut issues: Vec<GitFsckIssue> = Vec::new();
+
+    let mut lines = stdout.lines().map(str::trim).filter(|l| !l.is_empty()).peekable();
+    while let Some(line) = lines.next() {
+        if let Some(rest) = line.strip_prefix("broken link from") {
+            // The target is on the next line: `to    blob <id>`.
+            let from = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+            let to = match lines.peek().and_then(|l| l.strip_prefix("to")) {
+                Some(to) => {
+                    let to = to.split_whitespace().collect::<Vec<_>>().join(" ");
+                    lines.next();
+                    to
+                }
+                None => String::new(),
+            };
+            let (object_type, object) = typed_object(&to).unzip();
+            let message = format!("broken link from {from} to {to}");
+            issues.push(fsck_issue("broken_link", "error", object_type, object, &message));
+            continue;
+        }
+        if let Some(notice) = line.strip_prefix("notice:") {
+            issues.push(fsck_issue("notice", "info", None, None, notice.trim()));
+            continue;
+        }
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let severity = match kind {
+            "dangling" | "unreachable" => "info",
+            "missing" => "error",
+            _ => {
+                issues.push(fsck_issue("error", "error", None, None, line));
+                continue;
+            }
+        };
+        let (object_type, object) = typed_object(rest).unzip();
+        issues.push(fsck_issue(kind, severity, object_type, object, line));
+    }
+
+    let mut seen: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    for line in stderr.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if !seen.insert(line) {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("warning in ") {
+            let (object_type, object) = typed_object(rest).unzip();
+            issues.push(fsck_issue("warning", "warning", object_type, object, line));
+        } else if let Some(rest) = line.strip_prefix("error in ") {
+            let (object_type, object) = typed_object(rest).unzip();
+            issues.push(fsck_issue("error", "error", object_type, object, line));
+        } else if let Some(rest) = line.strip_prefix("error: ").filter(|r| r.contains("object corrupt or missing")) {
+            let object = rest.split(':').next().map(str::trim);
+            issues.push(fsck_issue("corrupt", "error", None, object, line));
+        } else if line.starts_with("warning:") {
+            issues.push(fsck_issue("warning", "warning", None, None, line));
+        } else {
+            issues.push(fsck_issue("error", "error", None, None, line));
+        }
+    }
+    issues
+}
+
+fn fsck_suggestions(repo_path: &str, issues: &[GitFsckIssue], complete: bool) -> Vec<GitFsckSuggestion> {
+    let suggestion = |action: &str, message: String| GitFsckSuggestion {
+        action: action.to_string(),
+        message,
+    };
+    let mut suggestions: Vec<GitFsckSuggestion> = Vec::new();
+
+    let damaged = !complete || issues.iter().any(|i| i.severity == "error");
+    if damaged {
+        suggestions.push(suggestion(
+            "backup",
+            String::from("Copy the whole repository folder before trying to repair it."),
+        ));
+        let remotes = crate::run_git(repo_path, &["remote"]).unwrap_or_default();
+        match remotes.lines().map(str::trim).find(|r| !r.is_empty()) {
+            Some(remote) => suggestions.push(suggestion(
+                "refetch",
+                format!(
+                    "Fetch everything from '{remote}' again (git fetch --refetch {remote}) to restore missing or damaged objects that were pushed."
+                ),
+            )),
+            None => suggestions.push(suggestion(
+                "reclone",
+                String::from("There is no remote to restore objects from. Recover what you can into a fresh clone or from a backup."),
+            )),
+        }
+    }
+    if issues.iter().any(|i| i.kind == "dangling") {
+        suggestions.push(suggestion(
+            "gc",
+            String::from("Dangling objects are left over from rewritten or deleted history; git gc removes them once they expire."),
+        ));
+    }
+    suggestions
+}
+
+/// Runs a full `git fsck` and reports what it found, with suggested remedies. Warnings
+/// are objects git accepts but would not write today and usually need no action.
+#[tauri::command]
+pub(crate) async fn git_fsck(repo_path: String) -> Result<GitFsckReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        let out = crate::git_command_in_repo(&repo_path)
+            .args(["fsck", "--full", "--no-progress"])
+            .output()
+            .map_err(|e| format!("Failed to spawn git fsck: {e}"))?;
+        let stdout = String::from_utf8_lossy(&out.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&out.stderr).to_string();
+
+        // fsck exits with a bit mask of what it found; 128 means it gave up.
+        let complete = out.status.code().is_some_and(|c| c < 128);
+        let issues = parse_fsck_output(&stdout, &stderr);
+        let count = |severity: &str| issues.iter().filter(|i| i.severity == severity).count() as u32;
+        let errors = count("error");
+        let warnings = count("warning");
+        let dangling = issues.iter().filter(|i| i.kind == "dangling").count() as u32;
+        let suggestions = fsck_suggestions(&repo_path, &issues, complete);
+
+        Ok(GitFsckReport {
+            complete,
+            errors,
+            warnings,
+            dangling,
+            issues,
+            suggestions,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to run git fsck: {e}"))?
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitPackSize {
+    name: String,
+    bytes: u64,
+    /// Pack marked with a `.keep` file, which repacks leave alone.
+    kept: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitDirSize {
+    /// Path relative to the working tree root, `/`-separated.
+    path: String,
+    is_dir: bool,
+    bytes: u64,
+    files: u64,
+    ignored: bool,
+    /// Entries of a directory, largest first; only filled for the first levels.
+    children: Vec<GitDirSize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRepoSizeStats {
+    objects: GitObjectCounts,
+    packs: Vec<GitPackSize>,
+    /// Everything under the git directory, objects included.
+    git_dir_bytes: u64,
+    lfs_objects: u64,
+    lfs_bytes: u64,
+    /// The working tree without the git directory.
+    worktree_bytes: u64,
+    worktree_files: u64,
+    /// Entries of the working tree two levels deep, largest first.
+    entries: Vec<GitDirSize>,
+}
+
+/// Total size and file count below `path`. Symlinks are counted, not followed.
+fn disk_usage(path: &Path) -> (u64, u64) {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return (0, 0);
+    };
+    if !meta.is_dir() {
+        return (meta.len(), 1);
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return (0, 0);
+    };
+    entries.flatten().fold((0, 0), |(bytes, files), entry| {
+        let (b, f) = disk_usage(&entry.path());
+        (bytes + b, files + f)
+    })
+}
+
+fn pack_sizes(common_dir: &Path) -> Vec<GitPackSize> {
+    let pack_dir = common_dir.join("objects").join("pack");
+    let Ok(entries) = fs::read_dir(&pack_dir) else {
+        return Vec::new();
+    };
+    let mut packs: Vec<GitPackSize> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stem = name.strip_suffix(".pack")?;
+            Some(GitPackSize {
+                bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                kept: pack_dir.join(format!("{stem}.keep")).exists(),
+                name,
+            })
+        })
+        .collect();
+    packs.sort_by_key(|p| std::cmp::Reverse(p.bytes));
+    packs
+}
+
+/// How many directory levels of the working tree are broken down.
+const BREAKDOWN_DEPTH: usize = 2;
+
+/// Measures the entries of `dir`, keeping the entries of subdirectories down to `depth`
+/// levels. `rel` is `dir` relative to the working tree root.
+fn dir_entries(dir: &Path, rel: &str, depth: usize) -> Vec<GitDirSize> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut sizes: Vec<GitDirSize> = entries
+        .flatten()
+        .filter(|entry| !(rel.is_empty() && entry.file_name() == ".git"))
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = if rel.is_empty() { name } else { format!("{rel}/{name}") };
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+            let (bytes, files, children) = if is_dir && depth > 1 {
+                let children = dir_entries(&entry.path(), &path, depth - 1);
+                let bytes = children.iter().map(|c| c.bytes).sum();
+                let files = children.iter().map(|c| c.files).sum();
+                (bytes, files, children)
+            } else {
+                let (bytes, files) = disk_usage(&entry.path());
+                (bytes, files, Vec::new())
+            };
+            GitDirSize {
+                path,
+                is_dir,
+                bytes,
+                files,
+                ignored: false,
+                children,
+            }
+        })
+        .collect();
+    sizes.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+    sizes
+}
+
+fn collect_paths<'a>(entries: &'a [GitDirSize], out: &mut Vec<&'a GitDirSize>) {
+    for entry in entries {
+        out.push(entry);
+        collect_paths(&entry.children, out);
+    }
+}
+
+fn mark_ignored(entries: &mut [GitDirSize], ignored: &std::collections::HashSet<String>, parent_ignored: bool) {
+    for entry in entries {
+        entry.ignored = parent_ignored || ignored.contains(&entry.path);
+        mark_ignored(&mut entry.children, ignored, entry.ignored);
+    }
+}
+
+fn worktree_entries(repo_path: &str) -> Vec<GitDirSize> {
+    let mut entries = dir_entries(Path::new(repo_path), "", BREAKDOWN_DEPTH);
+
+    let mut all: Vec<&GitDirSize> = Vec::new();
+    collect_paths(&entries, &mut all);
+    let input: String = all
+        .iter()
+        .map(|e| if e.is_dir { format!("{}/\0", e.path) } else { format!("{}\0", e.path) })
+        .collect();
+    // check-ignore exits with 1 when nothing is ignored.
+    let ignored: std::collections::HashSet<String> =
+        crate::run_git_with_stdin(repo_path, &["check-ignore", "-z", "--stdin"], &input)
+            .unwrap_or_default()
+            .split('\0')
+            .map(|p| p.trim_end_matches('/').to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+    mark_ignored(&mut entries, &ignored, false);
+    entries
+}
+
+/// Sizes of the object store, its packs, Git LFS objects and the working tree, with the
+/// working tree broken down by top-level entry so large (often ignored) folders stand out.
+#[tauri::command]
+pub(crate) async fn git_repo_size_stats(repo_path: String) -> Result<GitRepoSizeStats, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        let objects = count_objects(&repo_path)?;
+        let common_dir =
+            super::handles::git_common_dir(&repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+        let (git_dir_bytes, _) = disk_usage(&common_dir);
+        let (lfs_bytes, lfs_objects) = disk_usage(&common_dir.join("lfs").join("objects"));
+
+        let entries = worktree_entries(&repo_path);
+        let worktree_bytes = entries.iter().map(|e| e.bytes).sum();
+        let worktree_files = entries.iter().map(|e| e.files).sum();
+
+        Ok(GitRepoSizeStats {
+            objects,
+            packs: pack_sizes(&common_dir),
+            git_dir_bytes,
+            lfs_objects,
+            lfs_bytes,
+            worktree_bytes,
+            worktree_files,
+            entries,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to measure repository size: {e}"))?
+}
@@ -0,0 +1,526 @@
+// History cleanup. Finding what makes a repository big is the first step before rewriting
+// it: `git_largest_blobs` lists the biggest files ever committed, by path, with the commit
+// that brought each one in. `git_history_remove_paths` then rewrites every ref without
+// them, with `git filter-repo` when it is installed and `git filter-branch` otherwise,
+// after writing a full backup the rewrite can be undone from.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const DEFAULT_TOP_N: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitBlobCommit {
+    hash: String,
+    short_hash: String,
+    subject: String,
+    author: String,
+    /// Committer date, unix seconds.
+    date: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitLargeBlob {
+    path: String,
+    /// The largest version of the file.
+    blob: String,
+    bytes: u64,
+    /// Size of that version in the object store, after compression and deltas.
+    disk_bytes: u64,
+    /// Number of distinct versions of the file in history.
+    versions: u32,
+    /// All versions together.
+    total_bytes: u64,
+    /// Whether the largest version is in HEAD's tree; if not, only history keeps it.
+    in_head: bool,
+    introduced_by: Option<GitBlobCommit>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitLargestBlobsReport {
+    blobs_scanned: u64,
+    blobs: Vec<GitLargeBlob>,
+}
+
+/// `(blob, path, size, disk size)` of every blob reachable from any ref, found by piping
+/// `rev-list --objects --all` into `cat-file --batch-check`.
+fn reachable_blobs(repo_path: &str) -> Result<Vec<(String, String, u64, u64)>, String> {
+    let mut rev_list = crate::git_command_in_repo(repo_path)
+        .args(["rev-list", "--objects", "--all"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn git rev-list: {e}"))?;
+    let objects = rev_list
+        .stdout
+        .take()
+        .ok_or_else(|| String::from("Failed to read git rev-list output."))?;
+
+    let out = crate::git_command_in_repo(repo_path)
+        .args([
+            "cat-file",
+            "--batch-check=%(objecttype) %(objectname) %(objectsize) %(objectsize:disk) %(rest)",
+        ])
+        .stdin(Stdio::from(objects))
+        .output()
+        .map_err(|e| format!("Failed to spawn git cat-file: {e}"))?;
+    let status = rev_list.wait().map_err(|e| format!("Failed to wait for git rev-list: {e}"))?;
+    if !status.success() {
+        return Err(String::from("git rev-list failed."));
+    }
+    if !out.status.success() {
+        return Err(format!("git cat-file failed: {}", String::from_utf8_lossy(&out.stderr).trim_end()));
+    }
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let blobs = stdout
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("blob ")?;
+            let mut parts = rest.splitn(4, ' ');
+            let oid = parts.next()?.to_string();
+            let size = parts.next()?.parse::<u64>().ok()?;
+            let disk = parts.next()?.parse::<u64>().ok()?;
+            let path = parts.next().unwrap_or_default().to_string();
+            Some((oid, path, size, disk))
+        })
+        .collect();
+    Ok(blobs)
+}
+
+/// The oldest commit adding each of `blobs`, from one pass over the history's diffs.
+fn introducing_commits(repo_path: &str, blobs: &HashSet<&str>) -> HashMap<String, GitBlobCommit> {
+    let mut found: HashMap<String, GitBlobCommit> = HashMap::new();
+    if blobs.is_empty() {
+        return found;
+    }
+    let Ok(raw) = crate::run_git(
+        repo_path,
+        &[
+            "log",
+            "--all",
+            "--raw",
+            "--no-abbrev",
+            "--no-renames",
+            "--diff-filter=AMT",
+            "--format=%x1e%H%x1f%s%x1f%an%x1f%ct",
+        ],
+    ) else {
+        return found;
+    };
+
+    // Newest first, so the last commit seen for a blob is the one that introduced it.
+    for record in raw.split('\x1e').filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines();
+        let Some(header) = lines.next() else {
+            continue;
+        };
+        let mut fields = header.split('\x1f');
+        let commit = GitBlobCommit {
+            hash: fields.next().unwrap_or_default().to_string(),
+            short_hash: String::new(),
+            subject: fields.next().unwrap_or_default().to_string(),
+            author: fields.next().unwrap_or_default().to_string(),
+            date: fields.next().and_then(|d| d.trim().parse::<i64>().ok()).unwrap_or(0),
+        };
+        // `:100644 100644 <old> <new> M\t<path>`
+        for line in lines {
+            let Some(meta) = line.strip_prefix(':') else {
+                continue;
+            };
+            let meta = meta.split('\t').next().unwrap_or_default();
+            let Some(new) = meta.split_whitespace().nth(3) else {
+                continue;
+            };
+            if blobs.contains(new) {
+                found.insert(new.to_string(), commit.clone());
+            }
+        }
+    }
+
+    // `--no-abbrev` also applies to `%h`, so the few commits found are shortened here.
+    let mut args: Vec<&str> = vec!["log", "--no-walk=unsorted", "--format=%H %h"];
+    args.extend(found.values().map(|c| c.hash.as_str()));
+    let short: HashMap<String, String> = crate::run_git(repo_path, &args)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(hash, short)| (hash.to_string(), short.trim().to_string()))
+        .collect();
+    for commit in found.values_mut() {
+        commit.short_hash = short.get(&commit.hash).cloned().unwrap_or_else(|| commit.hash.clone());
+    }
+    found
+}
+
+/// The `top_n` (default 20) largest files in the history of all refs, one entry per path,
+/// ordered by the size of their largest version.
+#[tauri::command]
+pub(crate) async fn git_largest_blobs(repo_path: String, top_n: Option<usize>) -> Result<GitLargestBlobsReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        let top_n = top_n.filter(|n| *n > 0).unwrap_or(DEFAULT_TOP_N);
+        let blobs = reachable_blobs(&repo_path)?;
+        let blobs_scanned = blobs.len() as u64;
+
+        let mut by_path: HashMap<String, GitLargeBlob> = HashMap::new();
+        for (oid, path, bytes, disk_bytes) in blobs {
+            let entry = by_path.entry(path.clone()).or_insert_with(|| GitLargeBlob {
+                path,
+                blob: oid.clone(),
+                bytes,
+                disk_bytes,
+                versions: 0,
+                total_bytes: 0,
+                in_head: false,
+                introduced_by: None,
+            });
+            entry.versions += 1;
+            entry.total_bytes += bytes;
+            if bytes > entry.bytes {
+                entry.blob = oid;
+                entry.bytes = bytes;
+                entry.disk_bytes = disk_bytes;
+            }
+        }
+
+        let mut largest: Vec<GitLargeBlob> = by_path.into_values().collect();
+        largest.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+        largest.truncate(top_n);
+
+        let head_blobs: HashSet<String> = crate::run_git(&repo_path, &["ls-tree", "-r", "-z", "HEAD"])
+            .unwrap_or_default()
+            .split('\0')
+            .filter_map(|entry| entry.split('\t').next()?.split_whitespace().nth(2).map(str::to_string))
+            .collect();
+        let wanted: HashSet<&str> = largest.iter().map(|b| b.blob.as_str()).collect();
+        let mut introduced = introducing_commits(&repo_path, &wanted);
+        for blob in largest.iter_mut() {
+            blob.in_head = head_blobs.contains(&blob.blob);
+            blob.introduced_by = introduced.remove(&blob.blob);
+        }
+
+        Ok(GitLargestBlobsReport { blobs_scanned, blobs: largest })
+    })
+    .await
+    .map_err(|e| format!("Failed to list the largest blobs: {e}"))?
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct HistoryRewriteProgressEvent {
+    repo_path: String,
+    /// "filter-repo" | "filter-branch"
+    engine: String,
+    commits_done: Option<u64>,
+    commits_total: Option<u64>,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRewrittenRef {
+    name: String,
+    old: String,
+    /// None when the rewrite left the ref with no commits and deleted it.
+    new: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitHistoryRewriteResult {
+    engine: String,
+    backup: super::backup::GitBackupSummary,
+    paths: Vec<String>,
+    rewritten_refs: Vec<GitRewrittenRef>,
+    message: String,
+}
+
+/// A history rewrite's result, or with `dry_run` a report of what it would touch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub(crate) enum GitHistoryRewriteOutcome {
+    Done(GitHistoryRewriteResult),
+    DryRun(crate::DryRunReport),
+}
+
+/// Every ref with the commit it points to, leaving out filter-branch's own backups.
+fn ref_snapshot(repo_path: &str) -> HashMap<String, String> {
+    crate::run_git(repo_path, &["for-each-ref", "--format=%(objectname) %(refname)"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .filter(|(_, name)| !name.starts_with("refs/original/"))
+        .map(|(oid, name)| (name.to_string(), oid.to_string()))
+        .collect()
+}
+
+fn has_filter_repo(repo_path: &str) -> bool {
+    crate::run_git_status(repo_path, &["filter-repo", "--version"]).is_ok_and(|(ok, _, _)| ok)
+}
+
+/// Quotes `s` for the POSIX shell filter-branch runs its filters in.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Commits done (and the total, when the line says) from a progress line:
+/// filter-branch's `Rewrite <id> (3/10) ...` or filter-repo's `Parsed 3 commits`.
+fn commit_progress(line: &str) -> Option<(u64, Option<u64>)> {
+    if let Some(rest) = line.strip_prefix("Rewrite ") {
+        let counts = rest.split('(').nth(1)?.split(')').next()?;
+        let (done, total) = counts.split_once('/')?;
+        return Some((done.trim().parse().ok()?, total.trim().parse().ok()));
+    }
+    let count = line.strip_prefix("Parsed ")?.split_whitespace().next()?;
+    Some((count.parse().ok()?, None))
+}
+
+/// What removing `paths` from history would do: the refs whose history has them, the
+/// commits changing them (which are replaced along with all their descendants) and the
+/// files leaving the working tree.
+fn remove_paths_dry_run(repo_path: &str, paths: &[String]) -> crate::DryRunReport {
+    let mut ref_updates: Vec<crate::GitRefUpdate> = Vec::new();
+    for (name, old) in ref_snapshot(repo_path) {
+        let mut args: Vec<&str> = vec!["rev-list", "-1", old.as_str(), "--"];
+        args.extend(paths.iter().map(|p| p.as_str()));
+        if !crate::run_git(repo_path, &args).unwrap_or_default().trim().is_empty() {
+            // The rewritten ids are only known once the rewrite has run.
+            ref_updates.push(crate::GitRefUpdate { name, old: Some(old), new: None });
+        }
+    }
+    ref_updates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut args: Vec<&str> = vec!["rev-list", "--all", "-n", "500", "--"];
+    args.extend(paths.iter().map(|p| p.as_str()));
+    let commits_orphaned: Vec<String> = crate::run_git(repo_path, &args)
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    let mut args: Vec<&str> = vec!["ls-files", "-z", "--"];
+    args.extend(paths.iter().map(|p| p.as_str()));
+    let files_removed = crate::name_only_list(&crate::run_git(repo_path, &args).unwrap_or_default());
+
+    crate::DryRunReport {
+        operation: String::from("history remove paths"),
+        message: format!(
+            "Would rewrite {} ref(s); {} commit(s) change the paths and would be replaced with all their descendants.",
+            ref_updates.len(),
+            commits_orphaned.len()
+        ),
+        ref_updates,
+        files_removed,
+        files_changed: Vec::new(),
+        commits_orphaned,
+    }
+}
+
+/// Runs a rewrite, handing each line it prints on stdout to `on_line` as it comes;
+/// both tools redraw their progress line with `\r`. Returns whether it succeeded and
+/// its stderr.
+fn run_rewrite(mut cmd: Command, mut on_line: impl FnMut(&str)) -> Result<(bool, String), String> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn the history rewrite: {e}"))?;
+
+    let stderr_reader = child.stderr.take().map(|mut err| {
+        std::thread::spawn(move || {
+            let mut buf: Vec<u8> = Vec::new();
+            let _ = err.read_to_end(&mut buf);
+            String::from_utf8_lossy(&buf).trim().to_string()
+        })
+    });
+
+    if let Some(mut stdout) = child.stdout.take() {
+        let mut pending: Vec<u8> = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = stdout.read(&mut buf).map_err(|e| format!("Failed to read the rewrite output: {e}"))?;
+            if n == 0 {
+                break;
+            }
+            pending.extend_from_slice(&buf[..n]);
+            while let Some(pos) = pending.iter().position(|b| *b == b'\r' || *b == b'\n') {
+                let chunk: Vec<u8> = pending.drain(..=pos).collect();
+                let line = String::from_utf8_lossy(&chunk).trim().to_string();
+                if !line.is_empty() {
+                    on_line(&line);
+                }
+            }
+        }
+        let line = String::from_utf8_lossy(&pending).trim().to_string();
+        if !line.is_empty() {
+            on_line(&line);
+        }
+    }
+
+    let status = child.wait().map_err(|e| format!("Failed to wait for the history rewrite: {e}"))?;
+    let stderr = stderr_reader.and_then(|h| h.join().ok()).unwrap_or_default();
+    Ok((status.success(), stderr))
+}
+
+/// Rewrites the history of every ref without `paths` (files or directories). A full
+/// backup is written to `backup_file` first and is the only way back: afterwards the old
+/// commits are expired from the reflogs and pruned. The paths also disappear from the
+/// working tree. Rewritten branches have to be force-pushed. With `dry_run` nothing is
+/// written and the refs, commits and files it would touch are reported instead.
+#[tauri::command]
+pub(crate) async fn git_history_remove_paths(
+    app: AppHandle,
+    repo_path: String,
+    paths: Vec<String>,
+    backup_file: String,
+    dry_run: Option<bool>,
+) -> Result<GitHistoryRewriteOutcome, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        if !dry_run.unwrap_or(false) {
+            super::read_only::ensure_writable(&repo_path)?;
+        }
+
+        let mut clean_paths: Vec<String> = Vec::new();
+        for p in paths.iter().map(|p| p.trim().trim_end_matches('/')).filter(|p| !p.is_empty()) {
+            crate::ensure_rel_path_safe(p)?;
+            if !clean_paths.iter().any(|c| c == p) {
+                clean_paths.push(p.to_string());
+            }
+        }
+        if clean_paths.is_empty() {
+            return Err(String::from("No paths to remove."));
+        }
+        for p in clean_paths.iter() {
+            let found = crate::run_git(&repo_path, &["log", "--all", "-1", "--format=%H", "--", p.as_str()]).unwrap_or_default();
+            if found.trim().is_empty() {
+                return Err(format!("'{p}' is not in the history of any ref."));
+            }
+        }
+        if dry_run.unwrap_or(false) {
+            return Ok(GitHistoryRewriteOutcome::DryRun(remove_paths_dry_run(&repo_path, &clean_paths)));
+        }
+
+        if crate::is_merge_in_progress(&repo_path)
+            || crate::is_rebase_in_progress(&repo_path)
+            || crate::is_cherry_pick_in_progress(&repo_path)
+            || crate::is_revert_in_progress(&repo_path)
+            || super::conflicts::is_am_in_progress(&repo_path)
+        {
+            return Err(String::from("An operation is in progress. Finish or abort it first."));
+        }
+        let changes = crate::run_git(&repo_path, &["status", "--porcelain", "--untracked-files=no"])?;
+        if !changes.trim().is_empty() {
+            return Err(String::from("The working tree has uncommitted changes. Commit or stash them first."));
+        }
+        if crate::run_git(&repo_path, &["rev-parse", "--verify", "--quiet", "refs/stash"]).is_ok() {
+            return Err(String::from(
+                "The repository has stashes, which a history rewrite cannot carry over. Apply or drop them first.",
+            ));
+        }
+
+        let backup_file = backup_file.trim().to_string();
+        if backup_file.is_empty() {
+            return Err(String::from("A backup file is required before rewriting history."));
+        }
+        let backup = super::backup::create_backup(&repo_path, Path::new(&backup_file), None)?;
+
+        let engine = if has_filter_repo(&repo_path) { "filter-repo" } else { "filter-branch" };
+        let commits_total = crate::run_git(&repo_path, &["rev-list", "--all", "--count"])
+            .ok()
+            .and_then(|c| c.trim().parse::<u64>().ok());
+        let emit = |commits_done: Option<u64>, commits_total: Option<u64>, message: String| {
+            let _ = app.emit(
+                "history_rewrite_progress",
+                HistoryRewriteProgressEvent {
+                    repo_path: repo_path.clone(),
+                    engine: engine.to_string(),
+                    commits_done,
+                    commits_total,
+                    message,
+                },
+            );
+        };
+
+        crate::with_repo_git_lock(&repo_path, || {
+            let before = ref_snapshot(&repo_path);
+
+            let mut cmd = crate::git_command_in_repo(&repo_path);
+            if engine == "filter-repo" {
+                // `--partial` keeps the remotes, which a full run deletes; reflogs and
+                // old objects are cleaned up below for both engines.
+                cmd.args(["filter-repo", "--force", "--partial", "--invert-paths"]);
+                for p in clean_paths.iter() {
+                    cmd.args(["--path", p.as_str()]);
+                }
+            } else {
+                let quoted: Vec<String> = clean_paths.iter().map(|p| shell_quote(p)).collect();
+                let index_filter = format!("git rm -r -q --cached --ignore-unmatch -- {}", quoted.join(" "));
+                cmd.env("FILTER_BRANCH_SQUELCH_WARNING", "1").args([
+                    "filter-branch",
+                    "--force",
+                    "--index-filter",
+                    index_filter.as_str(),
+                    "--prune-empty",
+                    "--tag-name-filter",
+                    "cat",
+                    "--",
+                    "--all",
+                ]);
+            }
+
+            emit(Some(0), commits_total, format!("Rewriting history with git {engine}"));
+            let (ok, stderr) = run_rewrite(cmd, |line| match commit_progress(line) {
+                Some((done, total)) => emit(Some(done), total.or(commits_total), line.to_string()),
+                None => emit(None, None, line.to_string()),
+            })?;
+            if !ok {
+                return Err(format!(
+                    "git {engine} failed: {}. The backup is in {backup_file}.",
+                    if stderr.is_empty() { "no output" } else { stderr.as_str() }
+                ));
+            }
+
+            emit(None, None, String::from("Removing the old history"));
+            let originals =
+                crate::run_git(&repo_path, &["for-each-ref", "--format=%(refname)", "refs/original/"]).unwrap_or_default();
+            for r in originals.lines().map(str::trim).filter(|r| !r.is_empty()) {
+                let _ = crate::run_git(&repo_path, &["update-ref", "-d", r]);
+            }
+            let _ = crate::run_git(&repo_path, &["reflog", "expire", "--expire=now", "--all"]);
+            let _ = crate::run_git(&repo_path, &["gc", "--prune=now", "--quiet"]);
+
+            let after = ref_snapshot(&repo_path);
+            let mut rewritten_refs: Vec<GitRewrittenRef> = before
+                .into_iter()
+                .filter_map(|(name, old)| {
+                    let new = after.get(&name).cloned();
+                    (new.as_deref() != Some(old.as_str())).then_some(GitRewrittenRef { name, old, new })
+                })
+                .collect();
+            rewritten_refs.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let message = format!(
+                "Removed {} path(s) from history; {} ref(s) rewritten. Force-push the rewritten branches; the old history is in {}.",
+                clean_paths.len(),
+                rewritten_refs.len(),
+                backup_file
+            );
+            emit(commits_total, commits_total, message.clone());
+            Ok(GitHistoryRewriteOutcome::Done(GitHistoryRewriteResult {
+                engine: engine.to_string(),
+                backup: backup.clone(),
+                paths: clean_paths.clone(),
+                rewritten_refs,
+                message,
+            }))
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to rewrite history: {e}"))?
+}
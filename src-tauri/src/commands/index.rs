@@ -0,0 +1,149 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Index snapshots: before a multi-step operation rewrites the index, the index
+// is saved twice: as a tree (`git write-tree`, kept under SNAPSHOT_REF) and as
+// a byte copy of the index file. The byte copy also covers unmerged entries,
+// which `write-tree` refuses to store.
+
+const SNAPSHOT_REF: &str = "refs/graphoria/index-snapshot";
+const SNAPSHOT_FILE: &str = "graphoria/index-snapshot";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitIndexSnapshotInfo {
+    exists: bool,
+    tree: Option<String>,
+    has_conflicts: bool,
+    created_at: Option<u64>,
+}
+
+fn resolve_git_path(repo_path: &str, git_path: &str) -> Result<Option<PathBuf>, String> {
+    let full = crate::run_git(repo_path, &["rev-parse", "--git-path", git_path]).unwrap_or_default();
+    let full = full.trim();
+    if full.is_empty() {
+        return Ok(None);
+    }
+
+    let p = PathBuf::from(full);
+    if p.is_absolute() {
+        Ok(Some(p))
+    } else {
+        Ok(Some(Path::new(repo_path).join(p)))
+    }
+}
+
+/// Saves the current index. Returns the snapshot tree, or `None` when the index
+/// has unmerged entries (only the byte copy is kept then).
+pub(crate) fn snapshot_index(repo_path: &str) -> Result<Option<String>, String> {
+    let index_file = resolve_git_path(repo_path, "index")?.ok_or_else(|| String::from("Failed to resolve index path."))?;
+    let snapshot_file =
+        resolve_git_path(repo_path, SNAPSHOT_FILE)?.ok_or_else(|| String::from("Failed to resolve snapshot path."))?;
+
+    let tree = crate::run_git(repo_path, &["write-tree"]).ok().filter(|t| !t.trim().is_empty());
+    match tree.as_ref() {
+        Some(t) => {
+            crate::run_git(repo_path, &["update-ref", SNAPSHOT_REF, t.as_str()])?;
+        }
+        None => {
+            let _ = crate::run_git(repo_path, &["update-ref", "-d", SNAPSHOT_REF]);
+        }
+    }
+
+    if index_file.exists() {
+        if let Some(parent) = snapshot_file.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create snapshot directory: {e}"))?;
+        }
+        fs::copy(&index_file, &snapshot_file).map_err(|e| format!("Failed to snapshot index: {e}"))?;
+    } else if snapshot_file.exists() {
+        let _ = fs::remove_file(&snapshot_file);
+    }
+
+    Ok(tree)
+}
+
+/// Restores the index saved by `snapshot_index`. The working tree is not touched.
+pub(crate) fn restore_index(repo_path: &str) -> Result<(), String> {
+    let index_file = resolve_git_path(repo_path, "index")?.ok_or_else(|| String::from("Failed to resolve index path."))?;
+    let snapshot_file =
+        resolve_git_path(repo_path, SNAPSHOT_FILE)?.ok_or_else(|| String::from("Failed to resolve snapshot path."))?;
+
+    if snapshot_file.exists() {
+        // Copy next to the index first so the final replace is a single rename.
+        let tmp = index_file.with_file_name("index.graphoria-restore");
+        fs::copy(&snapshot_file, &tmp).map_err(|e| format!("Failed to restore index: {e}"))?;
+        fs::rename(&tmp, &index_file).map_err(|e| {
+            let _ = fs::remove_file(&tmp);
+            format!("Failed to restore index: {e}")
+        })?;
+        return Ok(());
+    }
+
+    let tree = crate::run_git(repo_path, &["rev-parse", "-q", "--verify", SNAPSHOT_REF]).unwrap_or_default();
+    if tree.trim().is_empty() {
+        return Err(String::from("No index snapshot available."));
+    }
+    crate::run_git(repo_path, &["read-tree", tree.trim()])?;
+    Ok(())
+}
+
+/// Runs `f` with an index snapshot taken beforehand; if `f` fails, the index is
+/// rolled back so a half-finished operation does not leave a half-staged state.
+pub(crate) fn with_index_snapshot<T>(repo_path: &str, f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    snapshot_index(repo_path)?;
+    let res = f();
+    if res.is_err() {
+        let _ = restore_index(repo_path);
+    }
+    res
+}
+
+fn snapshot_info(repo_path: &str) -> Result<GitIndexSnapshotInfo, String> {
+    let snapshot_file = resolve_git_path(repo_path, SNAPSHOT_FILE)?;
+    let created_at = snapshot_file
+        .as_ref()
+        .and_then(|p| fs::metadata(p).ok())
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+
+    let tree = crate::run_git(repo_path, &["rev-parse", "-q", "--verify", SNAPSHOT_REF])
+        .ok()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+
+    Ok(GitIndexSnapshotInfo {
+        exists: created_at.is_some() || tree.is_some(),
+        has_conflicts: created_at.is_some() && tree.is_none(),
+        tree,
+        created_at,
+    })
+}
+
+#[tauri::command]
+pub(crate) fn git_index_snapshot(repo_path: String) -> Result<GitIndexSnapshotInfo, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    crate::with_repo_git_lock(&repo_path, || {
+        snapshot_index(&repo_path)?;
+        snapshot_info(&repo_path)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn git_index_snapshot_info(repo_path: String) -> Result<GitIndexSnapshotInfo, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    snapshot_info(&repo_path)
+}
+
+#[tauri::command]
+pub(crate) fn git_index_restore(repo_path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    crate::with_repo_git_lock(&repo_path, || {
+        restore_index(&repo_path)?;
+        Ok(String::from("ok"))
+    })
+}
@@ -9,7 +9,7 @@ use std::path::{Path, PathBuf};
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub(crate) struct InteractiveRebaseTodoEntry {
-    pub action: String, // pick | reword | edit | squash | fixup | drop
+    pub action: String, // pick | reword | edit | squash | fixup | fixup -C | drop
     pub hash: String,
     pub short_hash: Option<String>,
     pub original_message: Option<String>,
@@ -82,12 +82,7 @@ fn read_rebase_file(repo_path: &str, name: &str) -> Option<String> {
 type RewordMap = std::collections::HashMap<String, (Option<String>, Option<String>)>;
 
 fn graphoria_reword_map_path(repo_path: &str) -> Option<PathBuf> {
-    let git_dir = crate::run_git(repo_path, &["rev-parse", "--git-dir"]).ok()?;
-    let git_dir = git_dir.trim();
-    if git_dir.is_empty() { return None; }
-    let p = PathBuf::from(git_dir);
-    let p = if p.is_absolute() { p } else { Path::new(repo_path).join(p) };
-    Some(p.join("graphoria-reword-map.json"))
+    Some(super::handles::git_dir(repo_path)?.join("graphoria-reword-map.json"))
 }
 
 fn save_reword_map(repo_path: &str, map: &RewordMap) {
@@ -143,6 +138,93 @@ fn get_head_author(repo_path: &str) -> (Option<String>, Option<String>) {
     }
 }
 
+/// Reorders the todo list the way `git rebase --autosquash` would: every
+/// `fixup! `/`squash! `/`amend! ` commit is moved right after the commit it
+/// targets (matched by subject or hash prefix) and its action is adjusted.
+fn autosquash_todo_entries(
+    repo_path: &str,
+    entries: Vec<InteractiveRebaseTodoEntry>,
+) -> Vec<InteractiveRebaseTodoEntry> {
+    let subjects: Vec<String> = entries
+        .iter()
+        .map(|e| {
+            let msg = e.original_message.as_deref().unwrap_or("").trim();
+            if !msg.is_empty() {
+                msg.lines().next().unwrap_or("").trim().to_string()
+            } else {
+                crate::run_git(repo_path, &["log", "-1", "--format=%s", e.hash.trim()]).unwrap_or_default()
+            }
+        })
+        .collect();
+
+    // For each entry: index of the entry it folds into, plus the action to use.
+    let mut targets: Vec<Option<(usize, &'static str)>> = vec![None; entries.len()];
+    for (i, subject) in subjects.iter().enumerate() {
+        let (action, mut rest) = if let Some(r) = subject.strip_prefix("fixup! ") {
+            ("fixup", r)
+        } else if let Some(r) = subject.strip_prefix("squash! ") {
+            ("squash", r)
+        } else if let Some(r) = subject.strip_prefix("amend! ") {
+            // Folds the changes in and takes the amend! commit's message.
+            ("fixup -C", r)
+        } else {
+            continue;
+        };
+        while let Some(r) = rest
+            .strip_prefix("fixup! ")
+            .or_else(|| rest.strip_prefix("squash! "))
+            .or_else(|| rest.strip_prefix("amend! "))
+        {
+            rest = r;
+        }
+        let rest = rest.trim();
+        if rest.is_empty() {
+            continue;
+        }
+
+        let found = (0..i)
+            .find(|&j| subjects[j] == rest)
+            .or_else(|| (0..i).find(|&j| rest.len() >= 4 && entries[j].hash.trim().starts_with(rest)))
+            .or_else(|| (0..i).find(|&j| subjects[j].starts_with(rest)));
+
+        if let Some(mut j) = found {
+            // Fold into the final target when the matched commit is itself a fixup.
+            while let Some((t, _)) = targets[j] {
+                j = t;
+            }
+            targets[i] = Some((j, action));
+        }
+    }
+
+    let mut order: Vec<usize> = Vec::new();
+    for (i, target) in targets.iter().enumerate() {
+        if target.is_some() {
+            continue;
+        }
+        order.push(i);
+        for (k, t) in targets.iter().enumerate() {
+            if matches!(t, Some((j, _)) if *j == i) {
+                order.push(k);
+            }
+        }
+    }
+
+    let mut slots: Vec<Option<InteractiveRebaseTodoEntry>> = entries.into_iter().map(Some).collect();
+    let mut out = Vec::new();
+    for i in order {
+        if let Some(mut entry) = slots[i].take() {
+            match targets[i] {
+                Some((_, action)) if entry.action.trim().eq_ignore_ascii_case("pick") => {
+                    entry.action = action.to_string();
+                }
+                _ => {}
+            }
+            out.push(entry);
+        }
+    }
+    out
+}
+
 fn detect_rebase_state(repo_path: &str) -> InteractiveRebaseResult {
     let in_progress = crate::is_rebase_in_progress(repo_path);
     if !in_progress {
@@ -353,6 +435,9 @@ fn get_pushed_commits(repo_path: &str, base_ref: &str) -> std::collections::Hash
 /// `base` is the commit to rebase onto (exclusive).
 /// `todo_entries` is the list of commits with their desired actions.
 ///
+/// When `autosquash` is set, `fixup!`/`squash!` commits are first moved next
+/// to their targets (see `autosquash_todo_entries`).
+///
 /// The function handles `reword` entries by converting them to `edit` and
 /// auto-amending with the new message. It returns when the rebase either
 /// completes, stops at a real `edit`, or hits conflicts.
@@ -361,13 +446,22 @@ pub(crate) fn git_interactive_rebase_start(
     repo_path: String,
     base: String,
     todo_entries: Vec<InteractiveRebaseTodoEntry>,
+    autosquash: Option<bool>,
+    allow_protected: Option<bool>,
 ) -> Result<InteractiveRebaseResult, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     if todo_entries.is_empty() {
         return Err(String::from("No commits selected for rebase."));
     }
 
+    let todo_entries = if autosquash.unwrap_or(false) {
+        autosquash_todo_entries(&repo_path, todo_entries)
+    } else {
+        todo_entries
+    };
+
     // Check that no rebase/merge is already in progress
     if crate::is_rebase_in_progress(&repo_path) {
         return Err(String::from("A rebase is already in progress."));
@@ -375,8 +469,12 @@ pub(crate) fn git_interactive_rebase_start(
     if crate::is_merge_in_progress(&repo_path) {
         return Err(String::from("A merge is in progress. Resolve it first."));
     }
+    super::protected::ensure_head_not_protected(&repo_path, "interactive rebase", allow_protected)?;
 
     crate::with_repo_git_lock(&repo_path, || {
+        let undo_label = format!("interactive rebase onto {}", base.trim());
+        let _undo = crate::commands::undo::record_undo_point(&repo_path, undo_label.as_str(), &[]);
+
         // Build the todo content.
         // Convert `reword` → `edit` so we can auto-amend with the new message.
         // Keep track of which entries are actually reword/author-change so we can auto-handle them.
@@ -425,6 +523,10 @@ pub(crate) fn git_interactive_rebase_start(
                     let msg = entry.original_message.as_deref().unwrap_or("");
                     todo_lines.push(format!("fixup {} {}", hash, msg));
                 }
+                "fixup -c" => {
+                    let msg = entry.original_message.as_deref().unwrap_or("");
+                    todo_lines.push(format!("fixup -C {} {}", hash, msg));
+                }
                 _ => {
                     // pick (default)
                     let msg = entry.original_message.as_deref().unwrap_or("");
@@ -675,6 +777,7 @@ pub(crate) fn git_interactive_rebase_amend(
     author: Option<String>,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     // Verify we're in a rebase
     let dir = rebase_merge_dir(&repo_path);
@@ -731,6 +834,7 @@ pub(crate) fn git_interactive_rebase_continue(
     repo_path: String,
 ) -> Result<InteractiveRebaseResult, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     crate::with_repo_git_lock(&repo_path, || {
         let mut cmd = crate::git_command_in_repo(&repo_path);
@@ -811,6 +915,267 @@ pub(crate) fn git_interactive_rebase_status(
     })
 }
 
+/// Fetches the current branch's upstream and rebases onto it with `--autostash`, i.e. the
+/// whole "update my branch" action. Conflicts leave the rebase in progress and are
+/// reported like an interactive rebase, so it continues through the same commands.
+#[tauri::command]
+pub(crate) async fn git_rebase_upstream(
+    app: tauri::AppHandle,
+    repo_path: String,
+) -> Result<InteractiveRebaseResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+
+        if crate::is_rebase_in_progress(&repo_path) || rebase_merge_dir(&repo_path).is_some() {
+            return Err(String::from("A rebase is already in progress."));
+        }
+        if crate::is_merge_in_progress(&repo_path) {
+            return Err(String::from("A merge is in progress. Resolve it first."));
+        }
+
+        crate::with_repo_git_lock(&repo_path, || {
+            let branch = crate::run_git(&repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+                .map_err(|_| String::from("Cannot rebase a detached HEAD onto an upstream."))?;
+            let upstream = crate::run_git(&repo_path, &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{upstream}"])
+                .map_err(|_| format!("Branch '{branch}' has no upstream configured."))?;
+
+            // A local upstream ("." as remote) has nothing to fetch.
+            let remote_key = format!("branch.{branch}.remote");
+            let remote = crate::run_git(&repo_path, &["config", "--get", remote_key.as_str()]).unwrap_or_default();
+            if !remote.is_empty() && remote != "." {
+                let mut cmd = crate::git_command_in_repo(&repo_path);
+                cmd.args(["fetch", "--progress", remote.as_str()]);
+                let on_progress = super::clone::transfer_progress_emitter(&app, &repo_path, "fetch");
+                let (ok, _, stderr) = super::clone::run_git_status_with_progress(cmd, "git fetch", on_progress)?;
+                if !ok {
+                    return Err(format!("git fetch failed: {stderr}"));
+                }
+            }
+
+            let undo_label = format!("rebase onto {upstream}");
+            let _undo = crate::commands::undo::record_undo_point(&repo_path, undo_label.as_str(), &[]);
+
+            let mut cmd = crate::git_command_in_repo(&repo_path);
+            no_editor_env(&mut cmd);
+            let out = cmd
+                .args(["rebase", "--autostash", upstream.as_str()])
+                .output()
+                .map_err(|e| format!("Failed to start rebase: {e}"))?;
+
+            let stdout = String::from_utf8_lossy(&out.stdout).trim_end().to_string();
+            let stderr = String::from_utf8_lossy(&out.stderr).trim_end().to_string();
+
+            let still_in_progress = rebase_merge_dir(&repo_path).is_some() || crate::is_rebase_in_progress(&repo_path);
+            if !still_in_progress {
+                if !out.status.success() {
+                    return Err(if !stderr.is_empty() { stderr } else { stdout });
+                }
+                // Re-applying the autostash can conflict after the rebase itself finished;
+                // git keeps the changes in the stash and says so on stderr.
+                let message = [stdout.as_str(), stderr.as_str()]
+                    .into_iter()
+                    .flat_map(|s| s.split(['\r', '\n']))
+                    .map(|l| l.trim_start_matches("\u{1b}[K").trim())
+                    .filter(|l| !l.is_empty() && !l.starts_with("Rebasing ("))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                return Ok(InteractiveRebaseResult {
+                    status: String::from("completed"),
+                    message,
+                    current_step: None,
+                    total_steps: None,
+                    stopped_commit_hash: None,
+                    stopped_commit_message: None,
+                    stopped_commit_author_name: None,
+                    stopped_commit_author_email: None,
+                    conflict_files: Vec::new(),
+                });
+            }
+
+            Ok(detect_rebase_state(&repo_path))
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to run rebase: {e}"))?
+}
+
+// ---------------------------------------------------------------------------
+// Authorship rewrite
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitAuthorRewriteCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub subject: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// False for the commits after the range, which are only replayed.
+    pub rewrite: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitAuthorRewritePreview {
+    /// Parent of the oldest rewritten commit; the rebase starts here.
+    pub base: String,
+    /// Oldest first, up to HEAD.
+    pub commits: Vec<GitAuthorRewriteCommit>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitAuthorRewriteResult {
+    /// Ref keeping the branch as it was before the rewrite.
+    pub backup_ref: String,
+    pub rebase: InteractiveRebaseResult,
+}
+
+/// Works out which commits a rewrite of `from..=to` touches and refuses ranges that are
+/// not safe to rewrite: pushed commits, merges, or a range that does not end on HEAD's
+/// history.
+fn author_rewrite_plan(repo_path: &str, from: &str, to: Option<&str>) -> Result<GitAuthorRewritePreview, String> {
+    let from = from.trim();
+    if from.is_empty() {
+        return Err(String::from("from is empty"));
+    }
+    let to = to.map(|t| t.trim()).filter(|t| !t.is_empty()).unwrap_or("HEAD");
+    if from.starts_with('-') || to.starts_with('-') {
+        return Err(String::from("Invalid commit range."));
+    }
+
+    let verify = |rev: &str| {
+        let spec = format!("{rev}^{{commit}}");
+        crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", spec.as_str()])
+            .map(|s| s.trim().to_string())
+            .map_err(|_| format!("Commit '{rev}' not found."))
+    };
+    let from_hash = verify(from)?;
+    let to_hash = verify(to)?;
+    let base = crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", &format!("{from_hash}^")])
+        .map(|s| s.trim().to_string())
+        .map_err(|_| String::from("The root commit cannot be rewritten."))?;
+
+    let (from_in_range, _, _) = crate::run_git_status(repo_path, &["merge-base", "--is-ancestor", &from_hash, &to_hash])?;
+    let (to_on_head, _, _) = crate::run_git_status(repo_path, &["merge-base", "--is-ancestor", &to_hash, "HEAD"])?;
+    if !from_in_range || !to_on_head {
+        return Err(String::from("The commits must be a range on the current branch's history."));
+    }
+
+    let range = format!("{base}..HEAD");
+    let raw = crate::run_git(
+        repo_path,
+        &["log", "--reverse", "--format=%H%x1f%h%x1f%P%x1f%an%x1f%ae%x1f%s", range.as_str()],
+    )?;
+    let to_rewrite: std::collections::HashSet<String> =
+        crate::run_git(repo_path, &["rev-list", &format!("{base}..{to_hash}")])?
+            .lines()
+            .map(|l| l.trim().to_string())
+            .collect();
+    let unpushed: std::collections::HashSet<String> =
+        crate::run_git(repo_path, &["rev-list", range.as_str(), "--not", "--remotes"])?
+            .lines()
+            .map(|l| l.trim().to_string())
+            .collect();
+
+    let mut commits: Vec<GitAuthorRewriteCommit> = Vec::new();
+    for line in raw.lines() {
+        let parts: Vec<&str> = line.splitn(6, '\x1f').collect();
+        if parts.len() < 6 {
+            continue;
+        }
+        if parts[2].split_whitespace().count() > 1 {
+            return Err(format!("Commit {} is a merge; ranges with merges cannot be rewritten.", parts[1]));
+        }
+        if !unpushed.contains(parts[0]) {
+            return Err(format!(
+                "Commit {} is already pushed. Only unpushed commits can be rewritten.",
+                parts[1]
+            ));
+        }
+        commits.push(GitAuthorRewriteCommit {
+            hash: parts[0].to_string(),
+            short_hash: parts[1].to_string(),
+            author_name: parts[3].to_string(),
+            author_email: parts[4].to_string(),
+            subject: parts[5].to_string(),
+            rewrite: to_rewrite.contains(parts[0]),
+        });
+    }
+
+    Ok(GitAuthorRewritePreview { base, commits })
+}
+
+/// The commits `git_rewrite_author` would touch for `from..=to` (`to` defaults to HEAD),
+/// or why the range cannot be rewritten.
+#[tauri::command]
+pub(crate) fn git_rewrite_author_preview(
+    repo_path: String,
+    from: String,
+    to: Option<String>,
+) -> Result<GitAuthorRewritePreview, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    author_rewrite_plan(&repo_path, &from, to.as_deref())
+}
+
+/// Sets the author of the unpushed commits `from..=to` to `author_name <author_email>`
+/// (author dates are kept) by rebasing them with the interactive rebase machinery. The
+/// branch is first saved under `refs/graphoria/backup/`, and commits after `to` are
+/// replayed unchanged.
+#[tauri::command]
+pub(crate) fn git_rewrite_author(
+    repo_path: String,
+    from: String,
+    to: Option<String>,
+    author_name: String,
+    author_email: String,
+) -> Result<GitAuthorRewriteResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let author_name = author_name.trim().to_string();
+    let author_email = author_email.trim().to_string();
+    if author_name.is_empty() || author_email.is_empty() {
+        return Err(String::from("Author name and email are required."));
+    }
+    if [&author_name, &author_email].iter().any(|s| s.contains(['<', '>', '\n'])) {
+        return Err(String::from("Author name and email cannot contain '<', '>' or line breaks."));
+    }
+    if crate::is_rebase_in_progress(&repo_path) || rebase_merge_dir(&repo_path).is_some() {
+        return Err(String::from("A rebase is already in progress."));
+    }
+
+    let plan = crate::with_repo_git_lock(&repo_path, || {
+        let plan = author_rewrite_plan(&repo_path, &from, to.as_deref())?;
+        let head = crate::run_git(&repo_path, &["rev-parse", "HEAD"])?;
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let backup_ref = format!("refs/graphoria/backup/author-rewrite-{stamp}");
+        crate::run_git(&repo_path, &["update-ref", "-m", "graphoria: before author rewrite", backup_ref.as_str(), head.trim()])?;
+        Ok((plan, backup_ref))
+    })?;
+    let (plan, backup_ref) = plan;
+
+    let author = format!("{author_name} <{author_email}>");
+    let todo_entries: Vec<InteractiveRebaseTodoEntry> = plan
+        .commits
+        .iter()
+        .map(|c| InteractiveRebaseTodoEntry {
+            action: String::from("pick"),
+            hash: c.hash.clone(),
+            short_hash: Some(c.short_hash.clone()),
+            original_message: Some(c.subject.clone()),
+            new_message: None,
+            new_author: if c.rewrite { Some(author.clone()) } else { None },
+        })
+        .collect();
+
+    // Only unpushed commits are rewritten, so branch protection does not apply.
+    let rebase = git_interactive_rebase_start(repo_path, plan.base, todo_entries, None, Some(true))?;
+    Ok(GitAuthorRewriteResult { backup_ref, rebase })
+}
+
 // ---------------------------------------------------------------------------
 // Edit-stop file operations
 // ---------------------------------------------------------------------------
@@ -828,6 +1193,7 @@ pub(crate) fn git_interactive_rebase_edit_files(
     repo_path: String,
 ) -> Result<Vec<EditStopFileEntry>, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let out = crate::git_command_in_repo(&repo_path)
         .args(["diff-tree", "--no-commit-id", "-r", "--name-status", "HEAD"])
@@ -880,6 +1246,7 @@ pub(crate) fn git_write_working_file(
     content: String,
 ) -> Result<(), String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     let full = std::path::Path::new(&repo_path).join(&path);
     // Ensure parent dir exists
     if let Some(parent) = full.parent() {
@@ -897,6 +1264,7 @@ pub(crate) fn git_rename_working_file(
     new_path: String,
 ) -> Result<(), String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     crate::run_git(&repo_path, &["mv", &old_path, &new_path])?;
     Ok(())
 }
@@ -908,6 +1276,7 @@ pub(crate) fn git_delete_working_file(
     path: String,
 ) -> Result<(), String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     crate::run_git(&repo_path, &["rm", "-f", &path])?;
     Ok(())
 }
@@ -919,6 +1288,76 @@ pub(crate) fn git_restore_working_file(
     path: String,
 ) -> Result<(), String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     crate::run_git(&repo_path, &["checkout", "HEAD", "--", &path])?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(hash: &str, message: &str) -> InteractiveRebaseTodoEntry {
+        InteractiveRebaseTodoEntry {
+            action: String::from("pick"),
+            hash: hash.to_string(),
+            short_hash: None,
+            original_message: Some(message.to_string()),
+            new_message: None,
+            new_author: None,
+        }
+    }
+
+    fn plan(entries: &[InteractiveRebaseTodoEntry]) -> Vec<(String, String)> {
+        entries.iter().map(|e| (e.action.clone(), e.hash.clone())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, h)| (a.to_string(), h.to_string())).collect()
+    }
+
+    #[test]
+    fn test_autosquash_moves_fixups_after_their_target() {
+        let entries = vec![
+            entry("aaaa1111", "feat: one"),
+            entry("bbbb2222", "feat: two"),
+            entry("cccc3333", "fixup! feat: one"),
+            entry("dddd4444", "squash! feat: two\n\nmore words"),
+            entry("eeee5555", "amend! feat: one\n\nfeat: one, reworded"),
+        ];
+        let out = autosquash_todo_entries("", entries);
+        assert_eq!(
+            plan(&out),
+            pairs(&[
+                ("pick", "aaaa1111"),
+                ("fixup", "cccc3333"),
+                ("fixup -C", "eeee5555"),
+                ("pick", "bbbb2222"),
+                ("squash", "dddd4444"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_autosquash_matches_hash_prefix_and_chained_fixups() {
+        let entries = vec![
+            entry("abcdef12", "feat: base"),
+            entry("11112222", "fixup! abcdef"),
+            entry("33334444", "fixup! fixup! feat: base"),
+            entry("55556666", "fixup! feat: not in this rebase"),
+        ];
+        let out = autosquash_todo_entries("", entries);
+        assert_eq!(
+            plan(&out),
+            pairs(&[("pick", "abcdef12"), ("fixup", "11112222"), ("fixup", "33334444"), ("pick", "55556666")])
+        );
+    }
+
+    #[test]
+    fn test_autosquash_keeps_explicit_actions() {
+        let mut dropped = entry("22223333", "fixup! feat: base");
+        dropped.action = String::from("drop");
+        let out = autosquash_todo_entries("", vec![entry("11112222", "feat: base"), dropped]);
+        assert_eq!(plan(&out), pairs(&[("pick", "11112222"), ("drop", "22223333")]));
+    }
+}
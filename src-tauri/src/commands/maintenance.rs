@@ -0,0 +1,236 @@
+// Repository maintenance. `git gc`, `git prune` and `git repack` run in the background;
+// their output is streamed through `maintenance_progress` events and the result compares
+// the object store before and after. Git only prints gc and repack progress to a
+// terminal, so for those the events carry the log lines and the start and end.
+// `git_enable_perf_features` sets big repositories up with a commit-graph.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use std::process::Stdio;
+use std::time::Instant;
+
+use super::health::GitObjectCounts;
+
+#[derive(Debug, Clone, Serialize)]
+struct MaintenanceProgressEvent {
+    repo_path: String,
+    /// "gc" | "prune" | "repack" | "commit-graph"
+    operation: String,
+    phase: Option<String>,
+    percent: Option<u32>,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitMaintenanceResult {
+    operation: String,
+    before: GitObjectCounts,
+    after: GitObjectCounts,
+    /// Negative when the object store grew.
+    reclaimed_kib: i64,
+    /// Git's output without the progress lines.
+    output: String,
+}
+
+/// Emits `maintenance_progress` events for `operation`: `(phase, percent, message)`.
+fn progress_emitter<'a>(
+    app: &'a AppHandle,
+    repo_path: &'a str,
+    operation: &'a str,
+) -> impl Fn(Option<String>, Option<u32>, String) + 'a {
+    move |phase, percent, message| {
+        let _ = app.emit(
+            "maintenance_progress",
+            MaintenanceProgressEvent {
+                repo_path: repo_path.to_string(),
+                operation: operation.to_string(),
+                phase,
+                percent,
+                message,
+            },
+        );
+    }
+}
+
+fn run_maintenance(app: &AppHandle, repo_path: &str, operation: &str, args: &[&str]) -> Result<GitMaintenanceResult, String> {
+    let emit = progress_emitter(app, repo_path, operation);
+
+    crate::with_repo_git_lock(repo_path, || {
+        let before = super::health::count_objects(repo_path)?;
+        emit(None, Some(0), format!("Running git {operation}"));
+
+        let mut cmd = crate::git_command_in_repo(repo_path);
+        cmd.args(args);
+        let what = format!("git {operation}");
+        let (ok, stderr) = super::clone::run_git_with_progress_and_log(
+            cmd,
+            what.as_str(),
+            |phase, pct, message| emit(Some(phase), Some(pct), message),
+            |line| emit(None, None, line.to_string()),
+        )?;
+        if !ok {
+            let err = if stderr.is_empty() { format!("{what} failed.") } else { format!("{what} failed: {stderr}") };
+            emit(None, None, err.clone());
+            return Err(err);
+        }
+
+        let after = super::health::count_objects(repo_path)?;
+        let reclaimed_kib = before.total_kib() as i64 - after.total_kib() as i64;
+        emit(None, Some(100), format!("git {operation} finished"));
+        Ok(GitMaintenanceResult {
+            operation: operation.to_string(),
+            before,
+            after,
+            reclaimed_kib,
+            output: stderr,
+        })
+    })
+}
+
+/// Runs `git gc`, optionally with `--aggressive`, which recomputes all deltas and takes
+/// much longer.
+#[tauri::command]
+pub(crate) async fn git_gc(app: AppHandle, repo_path: String, aggressive: Option<bool>) -> Result<GitMaintenanceResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+
+        let mut args: Vec<&str> = vec!["gc"];
+        if aggressive.unwrap_or(false) {
+            args.push("--aggressive");
+        }
+        run_maintenance(&app, &repo_path, "gc", &args)
+    })
+    .await
+    .map_err(|e| format!("Failed to run git gc: {e}"))?
+}
+
+/// Deletes unreachable loose objects older than `expire` (default: `gc.pruneExpire`, or
+/// two weeks), so objects a running command just wrote are left alone.
+#[tauri::command]
+pub(crate) async fn git_prune(app: AppHandle, repo_path: String, expire: Option<String>) -> Result<GitMaintenanceResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+
+        let expire = match expire.map(|e| e.trim().to_string()).filter(|e| !e.is_empty()) {
+            Some(e) => e,
+            None => super::health::config_or(&repo_path, "gc.pruneExpire", "2.weeks.ago"),
+        };
+        if expire.starts_with('-') {
+            return Err(format!("Invalid expiry: {expire}"));
+        }
+        let expire_arg = format!("--expire={expire}");
+        run_maintenance(&app, &repo_path, "prune", &["prune", "--progress", expire_arg.as_str()])
+    })
+    .await
+    .map_err(|e| format!("Failed to run git prune: {e}"))?
+}
+
+/// Packs all reachable objects into a single pack and removes the packs and loose
+/// objects that became redundant (`git repack -a -d`).
+#[tauri::command]
+pub(crate) async fn git_repack(app: AppHandle, repo_path: String) -> Result<GitMaintenanceResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+        run_maintenance(&app, &repo_path, "repack", &["repack", "-a", "-d"])
+    })
+    .await
+    .map_err(|e| format!("Failed to run git repack: {e}"))?
+}
+
+/// Config keys `git_enable_perf_features` turns on.
+const PERF_CONFIG: &[&str] = &["core.commitGraph", "fetch.writeCommitGraph"];
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitPerfFeaturesResult {
+    /// Config keys that were not already `true`.
+    enabled: Vec<String>,
+    commits: u64,
+    /// The git log behind the full history graph, timed before and after.
+    sample: String,
+    before_ms: u64,
+    after_ms: u64,
+}
+
+/// Best of two runs of the log the history graph loads, with its output discarded.
+fn time_sample_log(repo_path: &str, args: &[&str]) -> u64 {
+    (0..2)
+        .map(|_| {
+            let started = Instant::now();
+            let _ = crate::git_command_in_repo(repo_path)
+                .args(args)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+            started.elapsed().as_millis() as u64
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Writes a commit-graph for every reachable commit and turns on `core.commitGraph` and
+/// `fetch.writeCommitGraph` so it is used and kept up to date by fetches. On large
+/// histories this makes the topological walk behind the graph view much faster; the
+/// result times that walk before and after.
+#[tauri::command]
+pub(crate) async fn git_enable_perf_features(app: AppHandle, repo_path: String) -> Result<GitPerfFeaturesResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+
+        let sample_args = ["log", "--all", "--topo-order", "--format=%H %P"];
+        let sample = format!("git {}", sample_args.join(" "));
+        let commits = crate::run_git(&repo_path, &["rev-list", "--all", "--count"])
+            .ok()
+            .and_then(|c| c.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        let before_ms = time_sample_log(&repo_path, &sample_args);
+
+        let emit = progress_emitter(&app, &repo_path, "commit-graph");
+        crate::with_repo_git_lock(&repo_path, || {
+            emit(None, Some(0), String::from("Writing the commit-graph"));
+            let mut cmd = crate::git_command_in_repo(&repo_path);
+            cmd.args(["commit-graph", "write", "--reachable", "--progress"]);
+            let (ok, stderr) = super::clone::run_git_with_progress_and_log(
+                cmd,
+                "git commit-graph write",
+                |phase, pct, message| emit(Some(phase), Some(pct), message),
+                |line| emit(None, None, line.to_string()),
+            )?;
+            if !ok {
+                let err = if stderr.is_empty() {
+                    String::from("git commit-graph write failed.")
+                } else {
+                    format!("git commit-graph write failed: {stderr}")
+                };
+                emit(None, None, err.clone());
+                return Err(err);
+            }
+            Ok(())
+        })?;
+
+        let mut enabled: Vec<String> = Vec::new();
+        for key in PERF_CONFIG {
+            let current = crate::run_git(&repo_path, &["config", "--type=bool", "--get", key]).unwrap_or_default();
+            if current.trim() != "true" {
+                crate::run_git(&repo_path, &["config", "--local", key, "true"])?;
+                enabled.push(key.to_string());
+            }
+        }
+
+        let after_ms = time_sample_log(&repo_path, &sample_args);
+        emit(None, Some(100), format!("git log: {before_ms} ms before, {after_ms} ms after"));
+        Ok(GitPerfFeaturesResult {
+            enabled,
+            commits,
+            sample,
+            before_ms,
+            after_ms,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to enable performance features: {e}"))?
+}
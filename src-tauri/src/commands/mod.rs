@@ -2,28 +2,80 @@ pub(crate) mod terminal;
 
 pub(crate) mod clone;
 
+pub(crate) mod askpass;
+
+pub(crate) mod submodules;
+
 pub(crate) mod repo;
 
+pub(crate) mod remotes;
+
 pub(crate) mod commits;
 
+pub(crate) mod graph;
+
+pub(crate) mod profile;
+
 pub(crate) mod status;
 
+pub(crate) mod sparse;
+
 pub(crate) mod branches;
 
 pub(crate) mod stashes;
 
 pub(crate) mod diff;
 
+pub(crate) mod diff_cache;
+
+pub(crate) mod diff_parse;
+
+pub(crate) mod difftools;
+
 pub(crate) mod reflog;
 
 pub(crate) mod tags;
 
 pub(crate) mod conflicts;
 
+pub(crate) mod index;
+
+pub(crate) mod undo;
+
 pub(crate) mod patches;
 
 pub(crate) mod interactive_rebase;
 
+pub(crate) mod health;
+
 pub(crate) mod startup;
 
 pub(crate) mod gitlog;
+
+pub(crate) mod signing;
+
+pub(crate) mod backup;
+
+pub(crate) mod config;
+
+pub(crate) mod attributes;
+
+pub(crate) mod watcher;
+
+pub(crate) mod handles;
+
+pub(crate) mod commit_hooks;
+
+pub(crate) mod sync;
+
+pub(crate) mod commit_lint;
+
+pub(crate) mod upstream;
+
+pub(crate) mod archive;
+
+pub(crate) mod read_only;
+pub(crate) mod recovery;
+pub(crate) mod maintenance;
+pub(crate) mod history_cleanup;
+pub(crate) mod protected;
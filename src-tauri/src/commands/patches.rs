@@ -16,14 +16,18 @@ pub(crate) struct GitCommit {
     author: String,
     author_email: String,
     date: String,
+    committer_date: String,
     subject: String,
     refs: String,
     is_head: bool,
 }
 
+/// A predicted graph: synthetic `predict:*` nodes on top of the commits they build on.
 #[derive(Debug, Clone, Serialize)]
-pub(crate) struct GitPatchPredictGraphResult {
+pub(crate) struct GitPredictGraphResult {
     ok: bool,
+    /// "apply" | "am" for patches; "up-to-date" | "fast-forward" | "merge-commit" for merges.
+    action: String,
     message: String,
     conflict_files: Vec<String>,
     touched_files: Vec<String>,
@@ -32,13 +36,53 @@ pub(crate) struct GitPatchPredictGraphResult {
     head_name: String,
 }
 
+/// What `predict_graph` predicts.
+enum PredictMode {
+    /// Applying the patch file with `git apply` ("apply") or `git am` ("am").
+    Patch { patch_path: String, method: String },
+    /// Merging `source` into `target`.
+    Merge { source: String, target: String },
+}
+
+/// The mode-specific part of a prediction.
+struct Prediction {
+    ok: bool,
+    action: String,
+    message: String,
+    conflict_files: Vec<String>,
+    touched_files: Vec<String>,
+    head_name: String,
+    /// Synthetic `predict:*` nodes.
+    nodes: Vec<GitCommit>,
+    /// Existing commits the graph is built from.
+    tips: Vec<String>,
+    /// The commit the predicted HEAD (or target) points at.
+    predicted_head: String,
+    /// Refs shown on commits, by hash.
+    labels: Vec<(String, String)>,
+}
+
+fn predicted_node(id: &str, parents: Vec<String>, subject: String) -> GitCommit {
+    GitCommit {
+        hash: id.to_string(),
+        parents,
+        author: String::from("(predict)"),
+        author_email: String::new(),
+        date: String::new(),
+        committer_date: String::new(),
+        subject,
+        refs: String::new(),
+        is_head: false,
+    }
+}
+
 #[tauri::command]
 pub(crate) fn git_predict_patch_graph(
     repo_path: String,
     patch_path: String,
     method: String,
     max_commits: Option<u32>,
-) -> Result<GitPatchPredictGraphResult, String> {
+) -> Result<GitPredictGraphResult, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
     let patch_path = patch_path.trim().to_string();
@@ -51,115 +95,211 @@ pub(crate) fn git_predict_patch_graph(
         return Err(String::from("method must be 'apply' or 'am'"));
     }
 
-    crate::with_repo_git_lock(&repo_path, || {
-        let max_commits = max_commits.unwrap_or(60).max(10).min(200);
-
-        let bytes = fs::read(&patch_path).map_err(|e| format!("Failed to read patch file: {e}"))?;
-        let text = String::from_utf8_lossy(&bytes).to_string();
-        let touched_files = parse_touched_files_from_patch_text(text.as_str());
-        let subjects = parse_patch_subjects(text.as_str(), 12);
-
-        let diff_part = if method == "am" {
-            extract_diff_part_for_apply_check(text.as_str())
-        } else {
-            text
-        };
-
-        let args: [&str; 4] = ["apply", "--check", "--", "-"];
-        let res = crate::run_git_with_stdin(&repo_path, &args, diff_part.as_str());
+    predict_graph(&repo_path, PredictMode::Patch { patch_path, method }, max_commits)
+}
 
-        let (ok, message) = match res {
-            Ok(msg) => (true, if msg.trim().is_empty() { String::from("ok") } else { msg }),
-            Err(e) => (false, e),
-        };
-        let conflict_files = if ok {
-            Vec::new()
-        } else {
-            parse_conflict_files_from_apply_check_message(message.as_str())
-        };
+/// Predicts merging `source` into `target` (defaults to HEAD) without touching the
+/// repository: conflicts come from `merge-tree`, and the graph contains a synthetic
+/// merge commit on top of both tips.
+#[tauri::command]
+pub(crate) fn git_predict_merge_graph(
+    repo_path: String,
+    source: String,
+    target: Option<String>,
+    max_commits: Option<u32>,
+) -> Result<GitPredictGraphResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
 
-        let head_name = crate::run_git(&repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"]).unwrap_or_else(|_| {
-            String::from("(detached)")
-        });
-        let head_name = head_name.trim().to_string();
+    let source = source.trim().to_string();
+    if source.is_empty() {
+        return Err(String::from("source is empty"));
+    }
+    let target = target.unwrap_or_default().trim().to_string();
+    let target = if target.is_empty() { String::from("HEAD") } else { target };
 
-        let local_head = crate::run_git(&repo_path, &["rev-parse", "HEAD"]).unwrap_or_default().trim().to_string();
+    predict_graph(&repo_path, PredictMode::Merge { source, target }, max_commits)
+}
 
-        let mut created_node_ids: Vec<String> = Vec::new();
-        let mut graph_commits: Vec<GitCommit> = Vec::new();
-        let mut predicted_head_id = local_head.clone();
+fn predict_graph(repo_path: &str, mode: PredictMode, max_commits: Option<u32>) -> Result<GitPredictGraphResult, String> {
+    crate::with_repo_git_lock(repo_path, || {
+        let max_commits = max_commits.unwrap_or(60).clamp(10, 200);
 
-        if !local_head.trim().is_empty() {
-            if method == "am" {
-                let mut last_parent = local_head.clone();
-                let subs = if subjects.is_empty() {
-                    vec![String::from("Apply patch (am)")]
-                } else {
-                    subjects
-                };
-
-                for (i, subj) in subs.iter().enumerate() {
-                    let id = format!("predict:am:{}", i + 1);
-                    created_node_ids.push(id.clone());
-                    graph_commits.push(GitCommit {
-                        hash: id.clone(),
-                        parents: if last_parent.trim().is_empty() { vec![] } else { vec![last_parent.clone()] },
-                        author: String::from("(predict)"),
-                        author_email: String::new(),
-                        date: String::new(),
-                        subject: subj.clone(),
-                        refs: String::new(),
-                        is_head: false,
-                    });
-                    last_parent = id.clone();
-                    predicted_head_id = id;
-                }
-            } else {
-                let id = String::from("predict:apply");
-                created_node_ids.push(id.clone());
-                predicted_head_id = id.clone();
-                graph_commits.push(GitCommit {
-                    hash: id,
-                    parents: vec![local_head.clone()],
-                    author: String::from("(predict)"),
-                    author_email: String::new(),
-                    date: String::new(),
-                    subject: String::from("Apply patch (working tree)"),
-                    refs: String::new(),
-                    is_head: false,
-                });
-            }
+        let prediction = match &mode {
+            PredictMode::Patch { patch_path, method } => predict_patch(repo_path, patch_path, method)?,
+            PredictMode::Merge { source, target } => predict_merge(repo_path, source, target)?,
+        };
 
+        let created_node_ids: Vec<String> = prediction.nodes.iter().map(|c| c.hash.clone()).collect();
+        let mut graph_commits = prediction.nodes;
+        if !prediction.tips.is_empty() {
             let remaining = max_commits.saturating_sub(graph_commits.len() as u32);
-            let mut commits = git_log_commits_multi(&repo_path, &[String::from("HEAD")], remaining)?;
+            let mut commits = git_log_commits_multi(repo_path, &prediction.tips, remaining)?;
             graph_commits.append(&mut commits);
         }
 
         for c in graph_commits.iter_mut() {
-            c.is_head = c.hash == predicted_head_id;
-            if c.is_head {
-                c.refs = if head_name.trim().is_empty() {
-                    String::from("HEAD")
-                } else {
-                    format!("HEAD -> {}", head_name)
-                };
-            } else {
-                c.refs = String::new();
-            }
+            c.is_head = c.hash == prediction.predicted_head;
+            c.refs = prediction
+                .labels
+                .iter()
+                .find(|(hash, _)| *hash == c.hash)
+                .map(|(_, label)| label.clone())
+                .unwrap_or_default();
         }
 
-        Ok(GitPatchPredictGraphResult {
-            ok,
-            message,
-            conflict_files,
-            touched_files,
+        Ok(GitPredictGraphResult {
+            ok: prediction.ok,
+            action: prediction.action,
+            message: prediction.message,
+            conflict_files: prediction.conflict_files,
+            touched_files: prediction.touched_files,
             graph_commits,
             created_node_ids,
-            head_name,
+            head_name: prediction.head_name,
         })
     })
 }
 
+fn predict_patch(repo_path: &str, patch_path: &str, method: &str) -> Result<Prediction, String> {
+    let bytes = fs::read(patch_path).map_err(|e| format!("Failed to read patch file: {e}"))?;
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    let touched_files = parse_touched_files_from_patch_text(text.as_str());
+    let subjects = parse_patch_subjects(text.as_str(), 12);
+
+    let diff_part = if method == "am" {
+        extract_diff_part_for_apply_check(text.as_str())
+    } else {
+        text
+    };
+
+    let args: [&str; 4] = ["apply", "--check", "--", "-"];
+    let res = crate::run_git_with_stdin(repo_path, &args, diff_part.as_str());
+
+    let (ok, message) = match res {
+        Ok(msg) => (true, if msg.trim().is_empty() { String::from("ok") } else { msg }),
+        Err(e) => (false, e),
+    };
+    let conflict_files = if ok {
+        Vec::new()
+    } else {
+        parse_conflict_files_from_apply_check_message(message.as_str())
+    };
+
+    let head_name = crate::run_git(repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"]).unwrap_or_else(|_| {
+        String::from("(detached)")
+    });
+    let head_name = head_name.trim().to_string();
+
+    let local_head = crate::run_git(repo_path, &["rev-parse", "HEAD"]).unwrap_or_default().trim().to_string();
+
+    let mut nodes: Vec<GitCommit> = Vec::new();
+    let mut predicted_head = local_head.clone();
+    if !local_head.is_empty() {
+        if method == "am" {
+            let subs = if subjects.is_empty() {
+                vec![String::from("Apply patch (am)")]
+            } else {
+                subjects
+            };
+            for (i, subj) in subs.into_iter().enumerate() {
+                let id = format!("predict:am:{}", i + 1);
+                nodes.push(predicted_node(&id, vec![predicted_head.clone()], subj));
+                predicted_head = id;
+            }
+        } else {
+            let id = String::from("predict:apply");
+            nodes.push(predicted_node(&id, vec![local_head.clone()], String::from("Apply patch (working tree)")));
+            predicted_head = id;
+        }
+    }
+
+    let head_label = if head_name.is_empty() {
+        String::from("HEAD")
+    } else {
+        format!("HEAD -> {head_name}")
+    };
+    Ok(Prediction {
+        ok,
+        action: method.to_string(),
+        message,
+        conflict_files,
+        touched_files,
+        head_name,
+        nodes,
+        tips: if local_head.is_empty() { Vec::new() } else { vec![String::from("HEAD")] },
+        labels: vec![(predicted_head.clone(), head_label)],
+        predicted_head,
+    })
+}
+
+fn predict_merge(repo_path: &str, source: &str, target: &str) -> Result<Prediction, String> {
+    let source_spec = format!("{source}^{{commit}}");
+    let source_head = crate::run_git(repo_path, &["rev-parse", "--verify", source_spec.as_str()])
+        .map_err(|_| format!("Revision not found: {source}"))?;
+    let target_spec = format!("{target}^{{commit}}");
+    let target_head = crate::run_git(repo_path, &["rev-parse", "--verify", target_spec.as_str()])
+        .map_err(|_| format!("Revision not found: {target}"))?;
+
+    let is_ancestor = |a: &str, b: &str| {
+        crate::git_command_in_repo(repo_path)
+            .args(["merge-base", "--is-ancestor", a, b])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    };
+
+    let action = if is_ancestor(source_head.as_str(), target_head.as_str()) {
+        String::from("up-to-date")
+    } else if is_ancestor(target_head.as_str(), source_head.as_str()) {
+        String::from("fast-forward")
+    } else {
+        String::from("merge-commit")
+    };
+
+    let conflict_files = if action == "merge-commit" {
+        crate::predict_merge_conflicts_between(repo_path, target_head.as_str(), source_head.as_str())
+    } else {
+        Vec::new()
+    };
+    let ok = conflict_files.is_empty();
+    let message = match action.as_str() {
+        "up-to-date" => format!("{target} already contains {source}."),
+        "fast-forward" => format!("{target} can be fast-forwarded to {source}."),
+        _ if ok => format!("{source} merges cleanly into {target}."),
+        _ => format!("Merging {source} into {target} would conflict in {} file(s).", conflict_files.len()),
+    };
+
+    let range = format!("{target_head}...{source_head}");
+    let touched_files = crate::run_git(repo_path, &["diff", "--name-only", range.as_str()])
+        .map(|out| out.lines().map(|l| l.to_string()).filter(|l| !l.is_empty()).collect())
+        .unwrap_or_default();
+
+    let mut nodes: Vec<GitCommit> = Vec::new();
+    let predicted_head = match action.as_str() {
+        "up-to-date" => target_head.clone(),
+        "fast-forward" => source_head.clone(),
+        _ => {
+            let id = String::from("predict:merge");
+            let parents = vec![target_head.clone(), source_head.clone()];
+            nodes.push(predicted_node(&id, parents, format!("Merge {source} into {target}")));
+            id
+        }
+    };
+
+    Ok(Prediction {
+        ok,
+        action,
+        message,
+        conflict_files,
+        touched_files,
+        head_name: target.to_string(),
+        nodes,
+        labels: vec![(predicted_head.clone(), target.to_string()), (source_head.clone(), source.to_string())],
+        tips: vec![target_head, source_head],
+        predicted_head,
+    })
+}
+
 fn parse_touched_files_from_patch_text(text: &str) -> Vec<String> {
     let mut files: Vec<String> = Vec::new();
     let mut seen: HashSet<String> = HashSet::new();
@@ -347,6 +487,7 @@ fn parse_git_log_records(repo_path: &str, stdout: &str) -> Vec<GitCommit> {
         let author = parts.next().unwrap_or_default().to_string();
         let author_email = parts.next().unwrap_or_default().to_string();
         let date = parts.next().unwrap_or_default().to_string();
+        let committer_date = parts.next().unwrap_or_default().to_string();
         let subject = parts.next().unwrap_or_default().to_string();
         let _refs = parts.next().unwrap_or_default().to_string();
 
@@ -366,6 +507,7 @@ fn parse_git_log_records(repo_path: &str, stdout: &str) -> Vec<GitCommit> {
             author,
             author_email,
             date,
+            committer_date,
             subject,
             refs: String::new(),
             is_head: head == hash,
@@ -379,7 +521,7 @@ fn git_log_commits_multi(repo_path: &str, revs: &[String], max_count: u32) -> Re
         return Ok(Vec::new());
     }
 
-    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%s\x1f%D\x1e";
+    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%cd\x1f%s\x1f%D\x1e";
     let pretty = format!("--pretty=format:{format}");
 
     let mut args: Vec<String> = vec![String::from("--no-pager"), String::from("log")];
@@ -487,6 +629,7 @@ pub(crate) fn git_predict_patch_file(repo_path: String, patch_path: String, meth
 #[tauri::command]
 pub(crate) fn git_apply_patch_file(repo_path: String, patch_path: String, method: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let patch_path = patch_path.trim().to_string();
     if patch_path.is_empty() {
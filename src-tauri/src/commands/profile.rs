@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::graph::{load_graph_filter, set_graph_filter, GraphFilterSettings};
+use super::protected::{get_protected_branches, set_protected_branches};
+
+const PROFILE_VERSION: u32 = 1;
+
+/// Shareable Graphoria setup for a repository. `graph_filter` and `protected_branches` are
+/// owned by the backend; `settings` carries the frontend-owned sections (auto-fetch,
+/// terminal profile, external diff tool, saved searches) as an opaque JSON object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RepoProfile {
+    version: u32,
+    #[serde(default)]
+    graph_filter: Option<GraphFilterSettings>,
+    #[serde(default)]
+    protected_branches: Option<Vec<String>>,
+    #[serde(default)]
+    settings: serde_json::Map<String, serde_json::Value>,
+}
+
+#[tauri::command]
+pub(crate) fn export_repo_profile(
+    repo_path: String,
+    settings: serde_json::Value,
+    destination_path: String,
+) -> Result<RepoProfile, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let destination_path = destination_path.trim().to_string();
+    if destination_path.is_empty() {
+        return Err(String::from("destination_path is empty"));
+    }
+    let settings = match settings {
+        serde_json::Value::Object(m) => m,
+        serde_json::Value::Null => serde_json::Map::new(),
+        _ => return Err(String::from("Profile settings must be a JSON object.")),
+    };
+
+    let profile = RepoProfile {
+        version: PROFILE_VERSION,
+        graph_filter: Some(load_graph_filter(&repo_path)),
+        protected_branches: Some(get_protected_branches(repo_path.clone())?),
+        settings,
+    };
+    let json = serde_json::to_string_pretty(&profile).map_err(|e| format!("Failed to serialize profile: {e}"))?;
+    fs::write(&destination_path, json).map_err(|e| format!("Failed to write profile: {e}"))?;
+    Ok(profile)
+}
+
+/// Applies the backend-owned parts of a profile to `repo_path` and returns the whole
+/// profile so the frontend can apply its own settings.
+#[tauri::command]
+pub(crate) fn import_repo_profile(repo_path: String, source_path: String) -> Result<RepoProfile, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let source_path = source_path.trim().to_string();
+    if source_path.is_empty() {
+        return Err(String::from("source_path is empty"));
+    }
+    let raw = fs::read_to_string(&source_path).map_err(|e| format!("Failed to read profile: {e}"))?;
+    let mut profile: RepoProfile = serde_json::from_str(&raw).map_err(|e| format!("Invalid profile file: {e}"))?;
+    if profile.version == 0 || profile.version > PROFILE_VERSION {
+        return Err(format!(
+            "Unsupported profile version {} (this build supports up to {PROFILE_VERSION}).",
+            profile.version
+        ));
+    }
+
+    if let Some(filter) = profile.graph_filter.take() {
+        profile.graph_filter = Some(set_graph_filter(repo_path.clone(), filter)?);
+    }
+    if let Some(patterns) = profile.protected_branches.take() {
+        profile.protected_branches = Some(set_protected_branches(repo_path, patterns)?);
+    }
+    Ok(profile)
+}
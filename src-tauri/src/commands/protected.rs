@@ -0,0 +1,135 @@
+// Protected branches. Each repository keeps a list of branch patterns (`main`,
+// `release/*`) in its common git directory. Force pushes, branch deletion, hard resets
+// that move the branch and interactive rebases refuse to touch a matching branch unless
+// the caller passes `allow_protected`; the refusal is a `PROTECTED_BRANCH` error whose
+// second line is a JSON object naming the branch, the pattern and the operation.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const PROTECTED_FILE: &str = "graphoria-protected-branches.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ProtectedBranches {
+    patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ProtectedBranchError {
+    branch: String,
+    pattern: String,
+    operation: String,
+    message: String,
+}
+
+fn protected_path(repo_path: &str) -> Option<PathBuf> {
+    Some(super::handles::git_common_dir(repo_path)?.join(PROTECTED_FILE))
+}
+
+fn load_patterns(repo_path: &str) -> Vec<String> {
+    protected_path(repo_path)
+        .and_then(|p| fs::read_to_string(&p).ok())
+        .and_then(|s| serde_json::from_str::<ProtectedBranches>(&s).ok())
+        .map(|s| s.patterns)
+        .unwrap_or_default()
+}
+
+/// Glob match on branch names: `*` and `?` stay within one path segment, `**` spans
+/// segments.
+fn pattern_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) if rest.first() == Some(&b'*') => {
+            let rest = &rest[1..];
+            (0..=name.len()).any(|i| pattern_matches(rest, &name[i..]))
+        }
+        Some((b'*', rest)) => {
+            let segment = name.iter().position(|b| *b == b'/').unwrap_or(name.len());
+            (0..=segment).any(|i| pattern_matches(rest, &name[i..]))
+        }
+        Some((b'?', rest)) => name.first().is_some_and(|b| *b != b'/') && pattern_matches(rest, &name[1..]),
+        Some((p, rest)) => name.first() == Some(p) && pattern_matches(rest, &name[1..]),
+    }
+}
+
+/// The pattern protecting `branch`, if any.
+fn protecting_pattern(repo_path: &str, branch: &str) -> Option<String> {
+    let branch = branch.trim().trim_start_matches("refs/heads/");
+    load_patterns(repo_path)
+        .into_iter()
+        .find(|p| pattern_matches(p.as_bytes(), branch.as_bytes()))
+}
+
+/// Refuses `operation` on a protected `branch` unless `allow_protected` is set.
+pub(crate) fn ensure_not_protected(
+    repo_path: &str,
+    branch: &str,
+    operation: &str,
+    allow_protected: Option<bool>,
+) -> Result<(), String> {
+    if allow_protected.unwrap_or(false) {
+        return Ok(());
+    }
+    let Some(pattern) = protecting_pattern(repo_path, branch) else {
+        return Ok(());
+    };
+    let branch = branch.trim().trim_start_matches("refs/heads/").to_string();
+    let error = ProtectedBranchError {
+        message: format!("'{branch}' is a protected branch ({pattern}); {operation} was not run."),
+        branch,
+        pattern,
+        operation: operation.to_string(),
+    };
+    let json = serde_json::to_string(&error).map_err(|e| format!("Failed to serialize error: {e}"))?;
+    Err(format!("PROTECTED_BRANCH\n{json}"))
+}
+
+/// `ensure_not_protected` for the branch HEAD is on; a detached HEAD is never protected.
+pub(crate) fn ensure_head_not_protected(
+    repo_path: &str,
+    operation: &str,
+    allow_protected: Option<bool>,
+) -> Result<(), String> {
+    match crate::run_git(repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"]) {
+        Ok(branch) => ensure_not_protected(repo_path, branch.trim(), operation, allow_protected),
+        Err(_) => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_protected_branches(repo_path: String) -> Result<Vec<String>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    Ok(load_patterns(&repo_path))
+}
+
+#[tauri::command]
+pub(crate) fn set_protected_branches(repo_path: String, patterns: Vec<String>) -> Result<Vec<String>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let mut cleaned: Vec<String> = Vec::new();
+    for p in patterns {
+        let p = p.trim().trim_start_matches("refs/heads/").to_string();
+        if p.is_empty() || cleaned.contains(&p) {
+            continue;
+        }
+        if p.contains(char::is_whitespace) {
+            return Err(format!("Invalid branch pattern: {p}"));
+        }
+        cleaned.push(p);
+    }
+
+    let path = protected_path(&repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    if cleaned.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove protected branches: {e}"))?;
+        }
+        return Ok(cleaned);
+    }
+    let json = serde_json::to_string_pretty(&ProtectedBranches { patterns: cleaned.clone() })
+        .map_err(|e| format!("Failed to serialize protected branches: {e}"))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write protected branches: {e}"))?;
+    Ok(cleaned)
+}
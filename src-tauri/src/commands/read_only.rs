@@ -0,0 +1,59 @@
+// Read-only repositories. A repository can be flagged read-only (stored like the other
+// per-repo settings, in its common git directory) so a production or reference clone can
+// be browsed without the UI changing it. Every command that changes the working tree, the
+// index, refs or the repository's config calls `ensure_writable` first and is refused with
+// a `READ_ONLY` error. Fetching stays allowed, as do dry runs and changes to the global or
+// system config.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const READ_ONLY_FILE: &str = "graphoria-read-only.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ReadOnlySettings {
+    read_only: bool,
+}
+
+fn read_only_path(repo_path: &str) -> Option<PathBuf> {
+    Some(super::handles::git_common_dir(repo_path)?.join(READ_ONLY_FILE))
+}
+
+pub(crate) fn is_read_only(repo_path: &str) -> bool {
+    read_only_path(repo_path)
+        .and_then(|p| fs::read_to_string(&p).ok())
+        .and_then(|s| serde_json::from_str::<ReadOnlySettings>(&s).ok())
+        .is_some_and(|s| s.read_only)
+}
+
+/// Fails with a `READ_ONLY` error when `repo_path` is in read-only mode. Every command
+/// that changes the repository calls this before touching it.
+pub(crate) fn ensure_writable(repo_path: &str) -> Result<(), String> {
+    if !is_read_only(repo_path) {
+        return Ok(());
+    }
+    Err(format!("READ_ONLY\n{repo_path} is in read-only mode. Turn read-only mode off to make changes."))
+}
+
+#[tauri::command]
+pub(crate) fn get_repo_read_only(repo_path: String) -> Result<bool, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    Ok(is_read_only(&repo_path))
+}
+
+#[tauri::command]
+pub(crate) fn set_repo_read_only(repo_path: String, read_only: bool) -> Result<bool, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let path = read_only_path(&repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    if read_only {
+        let json = serde_json::to_string_pretty(&ReadOnlySettings { read_only })
+            .map_err(|e| format!("Failed to serialize read-only setting: {e}"))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write read-only setting: {e}"))?;
+    } else if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove read-only setting: {e}"))?;
+    }
+    Ok(read_only)
+}
@@ -0,0 +1,347 @@
+// Startup recovery. Repositories are remembered as they are opened; when the app starts
+// it looks through them for operations a crash or a closed window left half done
+// (merges, rebases, cherry-picks, reverts, `git am`) and a stale `index.lock`, and emits a
+// `recovery_needed` event for each with the commands that would finish or undo them.
+// Graphoria's own leftovers (temp files and directories of earlier runs, a reword map
+// without its rebase) are removed without asking.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+const RECENT_FILE: &str = "recent-repositories.json";
+const MAX_RECENT: usize = 20;
+/// Temp artifacts Graphoria creates; each name continues with the id of the process that
+/// made it, so those of a still running instance are left alone.
+const TEMP_PREFIXES: &[&str] = &[
+    "graphoria-diff-",
+    "graphoria_index_",
+    "graphoria-backup-",
+    "graphoria-restore-",
+    "graphoria-gitmodules-",
+    "graphoria_rebase_",
+    "graphoria_pick_",
+];
+/// Cherry-pick worktrees; they are registered in their repository as well.
+const PICK_WORKTREE_PREFIX: &str = "graphoria_pick_";
+/// A lock this old is not held by a git that is still working.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct RecentRepos {
+    repos: Vec<RecentRepo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecentRepo {
+    path: String,
+    opened_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct RecoveryAction {
+    /// The command that carries the action out, e.g. "git_rebase_abort".
+    command: String,
+    label: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct RepoRecoveryReport {
+    repo_path: String,
+    /// "merge" | "rebase" | "am" | "cherry-pick" | "revert"
+    operation: Option<String>,
+    conflict_files: Vec<String>,
+    stale_index_lock: bool,
+    actions: Vec<RecoveryAction>,
+    /// Graphoria leftovers that were removed from the repository.
+    cleaned: Vec<String>,
+}
+
+static STARTUP_REPORTS: OnceLock<Mutex<Vec<RepoRecoveryReport>>> = OnceLock::new();
+
+fn startup_reports() -> &'static Mutex<Vec<RepoRecoveryReport>> {
+    STARTUP_REPORTS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn older_than(path: &Path, age: Duration) -> bool {
+    fs::symlink_metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_some_and(|d| d >= age)
+}
+
+fn recent_path(app: &AppHandle) -> Option<PathBuf> {
+    Some(app.path().app_data_dir().ok()?.join(RECENT_FILE))
+}
+
+fn load_recent(app: &AppHandle) -> RecentRepos {
+    recent_path(app)
+        .and_then(|p| fs::read_to_string(&p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Puts `repo_path` at the front of the repositories checked at startup.
+pub(crate) fn remember_repo(app: &AppHandle, repo_path: &str) {
+    let Some(path) = recent_path(app) else {
+        return;
+    };
+    let key = crate::normalize_repo_path(repo_path);
+    let mut recent = load_recent(app);
+    recent.repos.retain(|r| crate::normalize_repo_path(&r.path) != key);
+    recent.repos.insert(
+        0,
+        RecentRepo {
+            path: repo_path.trim().to_string(),
+            opened_at: now_secs(),
+        },
+    );
+    recent.repos.truncate(MAX_RECENT);
+
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&recent) {
+        let _ = fs::write(&path, json);
+    }
+}
+
+/// The id of the process that created temp artifact `name`, if it is one of ours.
+fn temp_artifact_pid(name: &str) -> Option<(&'static str, u32)> {
+    let prefix = TEMP_PREFIXES.iter().find(|p| name.starts_with(**p))?;
+    let digits: String = name[prefix.len()..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    Some((prefix, digits.parse().ok()?))
+}
+
+fn process_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+    let pid = pid.to_string();
+
+    #[cfg(target_os = "windows")]
+    let alive = crate::new_command("tasklist")
+        .args(["/FI", format!("PID eq {pid}").as_str(), "/NH"])
+        .output()
+        .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).split_whitespace().any(|w| w == pid));
+
+    #[cfg(not(target_os = "windows"))]
+    let alive = crate::new_command("kill")
+        .args(["-0", pid.as_str()])
+        .output()
+        .is_ok_and(|o| o.status.success());
+
+    alive
+}
+
+/// Removes a leftover cherry-pick worktree through the repository it is registered in,
+/// so `git worktree list` does not keep a stale entry.
+fn remove_pick_worktree(path: &Path) {
+    let common_dir = fs::read_to_string(path.join(".git")).ok().and_then(|content| {
+        let git_dir = PathBuf::from(content.lines().find_map(|l| l.strip_prefix("gitdir:"))?.trim());
+        // <common dir>/worktrees/<name>
+        Some(git_dir.parent()?.parent()?.to_path_buf())
+    });
+    let path_s = path.to_string_lossy().to_string();
+    let git = |args: &[&str]| {
+        let Some(common_dir) = common_dir.as_ref() else {
+            return false;
+        };
+        crate::new_command("git")
+            .arg("--git-dir")
+            .arg(common_dir)
+            .args(args)
+            .output()
+            .is_ok_and(|o| o.status.success())
+    };
+    if git(&["worktree", "remove", "--force", path_s.as_str()]) {
+        return;
+    }
+    let _ = fs::remove_dir_all(path);
+    git(&["worktree", "prune"]);
+}
+
+/// Removes temp files and directories that Graphoria processes which are no longer
+/// running left in the system temp directory.
+fn clean_temp_artifacts() {
+    let Ok(entries) = fs::read_dir(std::env::temp_dir()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some((prefix, pid)) = temp_artifact_pid(&name) else {
+            continue;
+        };
+        if process_alive(pid) {
+            continue;
+        }
+        let path = entry.path();
+        if prefix == PICK_WORKTREE_PREFIX {
+            remove_pick_worktree(&path);
+            continue;
+        }
+        let _ = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+    }
+}
+
+fn action(command: &str, label: &str) -> RecoveryAction {
+    RecoveryAction {
+        command: command.to_string(),
+        label: label.to_string(),
+    }
+}
+
+/// The actions that end `operation`; empty for a bisect, which is not left half done.
+fn recovery_actions(operation: &str, git_dir: &Path) -> Vec<RecoveryAction> {
+    match operation {
+        "am" => vec![
+            action("git_am_continue_with_message", "Continue applying patches"),
+            action("git_am_abort", "Abort git am"),
+        ],
+        "rebase" => {
+            let continue_command = if git_dir.join("graphoria-reword-map.json").exists() {
+                "git_interactive_rebase_continue"
+            } else {
+                "git_rebase_continue"
+            };
+            vec![
+                action(continue_command, "Continue rebase"),
+                action("git_rebase_skip", "Skip the current commit"),
+                action("git_rebase_abort", "Abort rebase"),
+            ]
+        }
+        "merge" => vec![
+            action("git_merge_continue", "Continue merge"),
+            action("git_merge_abort", "Abort merge"),
+        ],
+        "cherry-pick" => vec![
+            action("git_cherry_pick_continue_with_message", "Continue cherry-pick"),
+            action("git_cherry_pick_abort", "Abort cherry-pick"),
+        ],
+        "revert" => vec![
+            action("git_revert_continue", "Continue revert"),
+            action("git_revert_abort", "Abort revert"),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Checks one repository; `None` when nothing needs attention.
+fn inspect_repo(repo_path: &str) -> Option<RepoRecoveryReport> {
+    crate::ensure_is_git_worktree(repo_path).ok()?;
+    let git_dir = super::handles::git_dir(repo_path)?;
+
+    let operation = super::status::operation_in_progress(&git_dir).filter(|op| *op != "bisect");
+
+    let mut cleaned: Vec<String> = Vec::new();
+    let reword_map = git_dir.join("graphoria-reword-map.json");
+    if operation != Some("rebase") && reword_map.exists() && fs::remove_file(&reword_map).is_ok() {
+        cleaned.push(String::from("graphoria-reword-map.json"));
+    }
+    let restore_tmp = git_dir.join("index.graphoria-restore");
+    if restore_tmp.exists() && older_than(&restore_tmp, STALE_LOCK_AGE) && fs::remove_file(&restore_tmp).is_ok() {
+        cleaned.push(String::from("index.graphoria-restore"));
+    }
+
+    let lock = git_dir.join("index.lock");
+    let stale_index_lock = lock.exists() && older_than(&lock, STALE_LOCK_AGE);
+
+    if operation.is_none() && !stale_index_lock {
+        return None;
+    }
+
+    let mut actions: Vec<RecoveryAction> = Vec::new();
+    if stale_index_lock {
+        actions.push(action("git_remove_stale_index_lock", "Remove the stale index.lock"));
+    }
+    let conflict_files = if operation.is_some() {
+        crate::list_unmerged_files(repo_path)
+    } else {
+        Vec::new()
+    };
+    let operation = operation.map(|name| {
+        actions.extend(recovery_actions(name, &git_dir));
+        name.to_string()
+    });
+
+    Some(RepoRecoveryReport {
+        repo_path: repo_path.to_string(),
+        operation,
+        conflict_files,
+        stale_index_lock,
+        actions,
+        cleaned,
+    })
+}
+
+/// Runs the startup scan on its own thread: cleans temp artifacts, checks the recently
+/// opened repositories and emits `recovery_needed` for each one that needs attention.
+pub(crate) fn start(app: AppHandle) {
+    std::thread::spawn(move || {
+        clean_temp_artifacts();
+
+        let mut reports: Vec<RepoRecoveryReport> = Vec::new();
+        for repo in load_recent(&app).repos {
+            if !Path::new(&repo.path).exists() {
+                continue;
+            }
+            if let Some(report) = inspect_repo(&repo.path) {
+                let _ = app.emit("recovery_needed", report.clone());
+                reports.push(report);
+            }
+        }
+        if let Ok(mut stored) = startup_reports().lock() {
+            *stored = reports;
+        }
+    });
+}
+
+/// What the startup scan found, for a window that was not listening yet when the
+/// `recovery_needed` events went out.
+#[tauri::command]
+pub(crate) fn get_startup_recovery() -> Result<Vec<RepoRecoveryReport>, String> {
+    startup_reports()
+        .lock()
+        .map(|r| r.clone())
+        .map_err(|_| String::from("Failed to read the startup recovery reports."))
+}
+
+/// Removes `index.lock` when it is old enough that no running git can hold it.
+#[tauri::command]
+pub(crate) fn git_remove_stale_index_lock(repo_path: String) -> Result<bool, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let git_dir = super::handles::git_dir(&repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    let lock = git_dir.join("index.lock");
+    if !lock.exists() {
+        return Ok(false);
+    }
+    if !older_than(&lock, STALE_LOCK_AGE) {
+        return Err(String::from(
+            "index.lock was created recently and may belong to a running git. Try again later.",
+        ));
+    }
+    fs::remove_file(&lock).map_err(|e| format!("Failed to remove index.lock: {e}"))?;
+    if let Ok(mut stored) = startup_reports().lock() {
+        let key = crate::normalize_repo_path(&repo_path);
+        stored.retain(|r| crate::normalize_repo_path(&r.repo_path) != key || r.operation.is_some());
+    }
+    Ok(true)
+}
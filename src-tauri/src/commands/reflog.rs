@@ -10,6 +10,7 @@ pub(crate) fn git_reflog(repo_path: String, max_count: Option<u32>) -> Result<St
 #[tauri::command]
 pub(crate) fn git_cherry_pick(repo_path: String, commits: Vec<String>) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let commits: Vec<String> = commits
         .into_iter()
@@ -20,6 +21,8 @@ pub(crate) fn git_cherry_pick(repo_path: String, commits: Vec<String>) -> Result
         return Err(String::from("No commits provided."));
     }
 
+    let _undo = super::undo::record_undo_point(&repo_path, "cherry-pick", &[]);
+
     let mut args: Vec<&str> = Vec::new();
     args.push("cherry-pick");
     for c in &commits {
@@ -37,6 +40,7 @@ pub(crate) fn git_cherry_pick_advanced(
     conflict_preference: Option<String>,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let commits: Vec<String> = commits
         .into_iter()
@@ -69,5 +73,282 @@ pub(crate) fn git_cherry_pick_advanced(
     for c in &commits {
         args.push(c.as_str());
     }
+
+    let _undo = super::undo::record_undo_point(&repo_path, "cherry-pick", &[]);
     crate::run_git(&repo_path, args.as_slice())
 }
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct GitCherryPickOntoResult {
+    status: String, // "ok" | "conflicts" | "empty" | "error"
+    branch: String,
+    /// The new tip of `branch` when the pick was committed.
+    commit: Option<String>,
+    message: String,
+    conflict_files: Vec<String>,
+}
+
+/// Worktree that has `branch` checked out, if any.
+fn branch_checked_out_at(repo_path: &str, branch_ref: &str) -> Option<String> {
+    let raw = crate::run_git(repo_path, &["worktree", "list", "--porcelain"]).ok()?;
+    let mut path: Option<&str> = None;
+    for line in raw.lines() {
+        if let Some(p) = line.strip_prefix("worktree ") {
+            path = Some(p);
+        } else if line.strip_prefix("branch ") == Some(branch_ref) {
+            return path.map(String::from);
+        }
+    }
+    None
+}
+
+/// Resolves `commit` to a commit id that can be picked onto another branch.
+fn resolve_pick_commit(repo_path: &str, commit: &str) -> Result<String, String> {
+    let commit_spec = format!("{commit}^{{commit}}");
+    let commit_id = crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", commit_spec.as_str()])
+        .map_err(|_| format!("Commit not found: {commit}"))?;
+    let parents = crate::run_git(repo_path, &["rev-list", "--parents", "-n", "1", commit_id.as_str()])?;
+    if parents.split_whitespace().count() > 2 {
+        return Err(String::from("Merge commits cannot be cherry-picked onto another branch."));
+    }
+    Ok(commit_id)
+}
+
+static PICK_WORKTREE_SEQ: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+/// Picks `commit_id` onto `branch` in a temporary worktree and moves the branch only if
+/// the pick succeeded. Must run under the repository lock.
+fn cherry_pick_onto_branch(
+    repo_path: &str,
+    commit_id: &str,
+    branch: &str,
+    append_origin: bool,
+) -> Result<GitCherryPickOntoResult, String> {
+    let branch_ref = format!("refs/heads/{branch}");
+    let old_tip = crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", branch_ref.as_str()])
+        .map_err(|_| format!("Branch not found: {branch}"))?;
+    if let Some(path) = branch_checked_out_at(repo_path, &branch_ref) {
+        return Err(format!("Branch '{branch}' is checked out at {path}. Cherry-pick there instead."));
+    }
+
+    let seq = PICK_WORKTREE_SEQ.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let tmp = std::env::temp_dir().join(format!("graphoria_pick_{}_{seq}", std::process::id()));
+    let tmp_s = tmp.to_string_lossy().to_string();
+    crate::run_git(repo_path, &["worktree", "add", "--detach", tmp_s.as_str(), old_tip.as_str()])?;
+
+    let mut pick_args: Vec<&str> = vec!["cherry-pick"];
+    if append_origin {
+        pick_args.push("-x");
+    }
+    pick_args.push(commit_id);
+    let picked = crate::run_git_status(&tmp_s, &pick_args);
+    let result = match picked {
+        Ok((true, stdout, stderr)) => crate::run_git(&tmp_s, &["rev-parse", "HEAD"]).and_then(|new_tip| {
+            let reflog = format!("cherry-pick: {commit_id}");
+            crate::run_git(
+                repo_path,
+                &["update-ref", "-m", reflog.as_str(), branch_ref.as_str(), new_tip.as_str(), old_tip.as_str()],
+            )?;
+            Ok(GitCherryPickOntoResult {
+                status: String::from("ok"),
+                branch: branch.to_string(),
+                commit: Some(new_tip),
+                message: if !stdout.is_empty() { stdout } else { stderr },
+                conflict_files: Vec::new(),
+            })
+        }),
+        Ok((false, stdout, stderr)) => {
+            let message = if !stderr.is_empty() { stderr } else { stdout };
+            let conflict_files = crate::list_unmerged_files(&tmp_s);
+            let _ = crate::run_git_status(&tmp_s, &["cherry-pick", "--abort"]);
+            if !conflict_files.is_empty() {
+                Ok(GitCherryPickOntoResult {
+                    status: String::from("conflicts"),
+                    branch: branch.to_string(),
+                    commit: None,
+                    message,
+                    conflict_files,
+                })
+            } else if message.contains("empty") {
+                Ok(GitCherryPickOntoResult {
+                    status: String::from("empty"),
+                    branch: branch.to_string(),
+                    commit: None,
+                    message: format!("The changes are already on '{branch}'."),
+                    conflict_files: Vec::new(),
+                })
+            } else {
+                Err(format!("git cherry-pick failed: {message}"))
+            }
+        }
+        Err(e) => Err(e),
+    };
+
+    let _ = crate::run_git_status(repo_path, &["worktree", "remove", "--force", tmp_s.as_str()]);
+    if tmp.exists() {
+        let _ = std::fs::remove_dir_all(&tmp);
+        let _ = crate::run_git_status(repo_path, &["worktree", "prune"]);
+    }
+    result
+}
+
+fn branch_name(raw: &str) -> String {
+    raw.trim().trim_start_matches("refs/heads/").to_string()
+}
+
+/// Cherry-picks `commit` onto `target_branch` without touching the current checkout: the
+/// pick runs in a temporary worktree and the branch is moved only if it succeeded.
+/// Conflicts are reported and the pick abandoned, leaving the branch where it was.
+#[tauri::command]
+pub(crate) async fn git_cherry_pick_onto(
+    repo_path: String,
+    commit: String,
+    target_branch: String,
+) -> Result<GitCherryPickOntoResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+
+        let commit = commit.trim().to_string();
+        if commit.is_empty() {
+            return Err(String::from("commit is empty"));
+        }
+        let branch = branch_name(&target_branch);
+        if branch.is_empty() {
+            return Err(String::from("target_branch is empty"));
+        }
+
+        crate::with_repo_git_lock(&repo_path, || {
+            let commit_id = resolve_pick_commit(&repo_path, &commit)?;
+            let branch_ref = format!("refs/heads/{branch}");
+            let _undo = super::undo::record_undo_point(&repo_path, "cherry-pick onto branch", &[branch_ref.as_str()]);
+            cherry_pick_onto_branch(&repo_path, &commit_id, &branch, false)
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to cherry-pick onto branch: {e}"))?
+}
+
+/// Backports `commit` to each of `branches` (e.g. `release/1.x`, `release/2.x`), one
+/// temporary worktree per branch. A branch that fails or conflicts is reported with status
+/// "error" or "conflicts" and does not stop the others. `append_origin` (default true)
+/// records "(cherry picked from commit ...)" like `git cherry-pick -x`.
+#[tauri::command]
+pub(crate) async fn git_backport(
+    repo_path: String,
+    commit: String,
+    branches: Vec<String>,
+    append_origin: Option<bool>,
+) -> Result<Vec<GitCherryPickOntoResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+
+        let commit = commit.trim().to_string();
+        if commit.is_empty() {
+            return Err(String::from("commit is empty"));
+        }
+        let mut targets: Vec<String> = Vec::new();
+        for b in branches.iter().map(|b| branch_name(b)).filter(|b| !b.is_empty()) {
+            if !targets.contains(&b) {
+                targets.push(b);
+            }
+        }
+        if targets.is_empty() {
+            return Err(String::from("No branches selected."));
+        }
+
+        crate::with_repo_git_lock(&repo_path, || {
+            let commit_id = resolve_pick_commit(&repo_path, &commit)?;
+            let refs: Vec<String> = targets.iter().map(|b| format!("refs/heads/{b}")).collect();
+            let refs: Vec<&str> = refs.iter().map(|r| r.as_str()).collect();
+            let _undo = super::undo::record_undo_point(&repo_path, "backport", &refs);
+
+            Ok(targets
+                .iter()
+                .map(|branch| {
+                    cherry_pick_onto_branch(&repo_path, &commit_id, branch, append_origin.unwrap_or(true)).unwrap_or_else(
+                        |e| GitCherryPickOntoResult {
+                            status: String::from("error"),
+                            branch: branch.clone(),
+                            commit: None,
+                            message: e,
+                            conflict_files: Vec::new(),
+                        },
+                    )
+                })
+                .collect())
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to run backport: {e}"))?
+}
+
+#[tauri::command]
+pub(crate) fn git_revert(
+    repo_path: String,
+    commits: Vec<String>,
+    no_commit: Option<bool>,
+) -> Result<crate::PullResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let commits: Vec<String> = commits
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if commits.is_empty() {
+        return Err(String::from("No commits provided."));
+    }
+
+    crate::with_repo_git_lock(&repo_path, || {
+        if crate::is_revert_in_progress(&repo_path) {
+            return Err(String::from("A revert is already in progress. Continue or abort it first."));
+        }
+
+        let mut args: Vec<&str> = Vec::new();
+        args.push("revert");
+        args.push("--no-edit");
+        if no_commit.unwrap_or(false) {
+            args.push("--no-commit");
+        }
+        for c in &commits {
+            args.push(c.as_str());
+        }
+
+        let _undo = super::undo::record_undo_point(&repo_path, "revert", &[]);
+
+        let (ok, stdout, stderr) = crate::run_git_status(&repo_path, args.as_slice())?;
+        if ok {
+            return Ok(crate::PullResult {
+                status: String::from("ok"),
+                operation: String::from("revert"),
+                message: if !stdout.is_empty() { stdout } else { stderr },
+                conflict_files: Vec::new(),
+            });
+        }
+
+        let message = if !stderr.is_empty() {
+            stderr.clone()
+        } else {
+            stdout.clone()
+        };
+
+        let mut conflict_files = crate::list_unmerged_files(&repo_path);
+        if conflict_files.is_empty() {
+            conflict_files = crate::parse_conflict_files(message.as_str());
+        }
+
+        if crate::is_revert_in_progress(&repo_path) || !conflict_files.is_empty() {
+            return Ok(crate::PullResult {
+                status: String::from("conflicts"),
+                operation: String::from("revert"),
+                message,
+                conflict_files,
+            });
+        }
+
+        Err(if !stderr.is_empty() { stderr } else { stdout })
+    })
+}
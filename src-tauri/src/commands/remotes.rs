@@ -0,0 +1,501 @@
+use serde::Serialize;
+
+use std::io::Read;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+const PUSH_PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRemoteFetchConfig {
+    remote: String,
+    refspecs: Vec<String>,
+    tags: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRemoteCapability {
+    remote: String,
+    push_url: Option<String>,
+    /// `None` when it cannot be told without probing the server.
+    can_push: Option<bool>,
+    reason: Option<String>,
+}
+
+fn normalize_remote_name(remote_name: Option<String>) -> Result<String, String> {
+    let remote_name = remote_name.unwrap_or_else(|| String::from("origin")).trim().to_string();
+    if remote_name.is_empty() {
+        return Err(String::from("remote_name is empty"));
+    }
+    Ok(remote_name)
+}
+
+fn ensure_remote_exists(repo_path: &str, remote_name: &str) -> Result<(), String> {
+    let remotes = crate::run_git(repo_path, &["remote"])?;
+    if remotes.lines().any(|r| r.trim() == remote_name) {
+        Ok(())
+    } else {
+        Err(format!("Remote '{remote_name}' does not exist."))
+    }
+}
+
+fn config_values(repo_path: &str, key: &str) -> Vec<String> {
+    crate::run_git(repo_path, &["config", "--get-all", key])
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+/// Checks one side of a refspec; a single `*` is allowed as in `refs/heads/*`.
+fn ensure_refspec_side_valid(repo_path: &str, side: &str, spec: &str) -> Result<(), String> {
+    let (ok, _, _) = crate::run_git_status(
+        repo_path,
+        &["check-ref-format", "--allow-onelevel", "--refspec-pattern", side],
+    )?;
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("Invalid refspec: {spec}"))
+    }
+}
+
+fn ensure_fetch_refspec_valid(repo_path: &str, spec: &str) -> Result<(), String> {
+    if spec.chars().any(char::is_whitespace) {
+        return Err(format!("Invalid refspec: {spec}"));
+    }
+
+    // Negative refspecs (`^refs/heads/x`) only exclude a source pattern.
+    if let Some(negative) = spec.strip_prefix('^') {
+        if negative.contains(':') {
+            return Err(format!("Negative refspec cannot have a destination: {spec}"));
+        }
+        return ensure_refspec_side_valid(repo_path, negative, spec);
+    }
+
+    let spec_body = spec.strip_prefix('+').unwrap_or(spec);
+    let (src, dst) = spec_body.split_once(':').unwrap_or((spec_body, ""));
+    if src.is_empty() {
+        return Err(format!("Invalid refspec: {spec}"));
+    }
+    if !dst.is_empty() && src.contains('*') != dst.contains('*') {
+        return Err(format!("Refspec patterns must use '*' on both sides: {spec}"));
+    }
+    ensure_refspec_side_valid(repo_path, src, spec)?;
+    if !dst.is_empty() {
+        ensure_refspec_side_valid(repo_path, dst, spec)?;
+    }
+    Ok(())
+}
+
+fn read_fetch_config(repo_path: &str, remote_name: &str) -> GitRemoteFetchConfig {
+    let tag_opt = config_values(repo_path, format!("remote.{remote_name}.tagOpt").as_str());
+    let tags = match tag_opt.last().map(|s| s.as_str()) {
+        Some("--no-tags") => "none",
+        Some("--tags") => "all",
+        _ => "auto",
+    };
+
+    GitRemoteFetchConfig {
+        remote: remote_name.to_string(),
+        refspecs: config_values(repo_path, format!("remote.{remote_name}.fetch").as_str()),
+        tags: tags.to_string(),
+    }
+}
+
+/// Fetch refspecs and tag following (`remote.<name>.tagOpt`) of a remote. `tags` is
+/// "auto" (tags pointing into fetched history), "none" or "all".
+#[tauri::command]
+pub(crate) fn git_remote_fetch_config(
+    repo_path: String,
+    remote_name: Option<String>,
+) -> Result<GitRemoteFetchConfig, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let remote_name = normalize_remote_name(remote_name)?;
+    ensure_remote_exists(&repo_path, &remote_name)?;
+    Ok(read_fetch_config(&repo_path, &remote_name))
+}
+
+#[tauri::command]
+pub(crate) fn git_set_remote_fetch_config(
+    repo_path: String,
+    remote_name: Option<String>,
+    refspecs: Vec<String>,
+    tags: Option<String>,
+) -> Result<GitRemoteFetchConfig, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let remote_name = normalize_remote_name(remote_name)?;
+    ensure_remote_exists(&repo_path, &remote_name)?;
+
+    let mut specs: Vec<String> = Vec::new();
+    for spec in refspecs {
+        let spec = spec.trim().to_string();
+        if spec.is_empty() || specs.contains(&spec) {
+            continue;
+        }
+        ensure_fetch_refspec_valid(&repo_path, &spec)?;
+        specs.push(spec);
+    }
+    if !specs.iter().any(|s| !s.starts_with('^')) {
+        return Err(String::from("At least one fetch refspec is required."));
+    }
+
+    let tag_opt = match tags.unwrap_or_else(|| String::from("auto")).trim() {
+        "auto" => None,
+        "none" => Some("--no-tags"),
+        "all" => Some("--tags"),
+        other => return Err(format!("Unknown tags mode: {other}")),
+    };
+
+    let fetch_key = format!("remote.{remote_name}.fetch");
+    let tag_opt_key = format!("remote.{remote_name}.tagOpt");
+    crate::with_repo_git_lock(&repo_path, || {
+        // --unset-all fails when the key is missing, which is fine here.
+        let _ = crate::run_git(&repo_path, &["config", "--unset-all", fetch_key.as_str()]);
+        for spec in &specs {
+            crate::run_git(&repo_path, &["config", "--add", fetch_key.as_str(), spec.as_str()])?;
+        }
+
+        let _ = crate::run_git(&repo_path, &["config", "--unset-all", tag_opt_key.as_str()]);
+        if let Some(opt) = tag_opt {
+            crate::run_git(&repo_path, &["config", tag_opt_key.as_str(), opt])?;
+        }
+        Ok(())
+    })?;
+
+    Ok(read_fetch_config(&repo_path, &remote_name))
+}
+
+fn has_embedded_password(url: &str) -> bool {
+    let Some((_, rest)) = url.split_once("://") else {
+        return false;
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    authority
+        .rsplit_once('@')
+        .is_some_and(|(userinfo, _)| userinfo.split_once(':').is_some_and(|(_, pass)| !pass.is_empty()))
+}
+
+/// What can be told about pushing to `url` without contacting the server: `Ok(None)` when
+/// it looks possible, `Ok(Some(reason))` when only a probe can tell.
+fn static_push_check(repo_path: &str, url: &str) -> Result<Option<String>, String> {
+    let lower = url.to_lowercase();
+    if lower.starts_with("git://") {
+        return Err(String::from("The git:// protocol is read-only."));
+    }
+
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        if has_embedded_password(url) {
+            return Ok(None);
+        }
+        // Credentials may still come from a prompt, ~/.netrc or an askpass helper.
+        let helper = crate::run_git(repo_path, &["config", "--get-urlmatch", "credential.helper", url]).unwrap_or_default();
+        if helper.trim().is_empty() {
+            return Ok(Some(String::from("No credential helper is configured; probe the remote to check access.")));
+        }
+        return Ok(None);
+    }
+
+    let is_ssh = lower.starts_with("ssh://")
+        || (!lower.contains("://") && url.split_once(':').is_some_and(|(host, _)| host.len() > 1 && !host.contains('/')));
+    if is_ssh {
+        return Ok(None);
+    }
+
+    // Local path or file:// URL.
+    let path = url.strip_prefix("file://").unwrap_or(url);
+    let path = if Path::new(path).is_absolute() { Path::new(path).to_path_buf() } else { Path::new(repo_path).join(path) };
+    match std::fs::metadata(&path) {
+        Ok(m) if m.permissions().readonly() => Err(String::from("The remote repository is not writable.")),
+        Ok(_) => Ok(None),
+        Err(_) => Err(String::from("The remote repository path does not exist.")),
+    }
+}
+
+fn looks_like_auth_failure(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    [
+        "authentication failed",
+        "permission denied",
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+        "access denied",
+        "write access",
+        "not allowed",
+        "read-only",
+        "read only",
+        "returned error: 401",
+        "returned error: 403",
+    ]
+    .iter()
+    .any(|p| lower.contains(p))
+}
+
+/// Asks the server whether it accepts a push with `push --dry-run`, with prompts disabled
+/// so missing credentials fail right away instead of waiting for input.
+fn probe_push(repo_path: &str, remote_name: &str) -> Result<(), String> {
+    let mut cmd = crate::git_command_in_repo(repo_path);
+    // The probe must stay silent, so the credential dialog is not offered to git or ssh.
+    cmd.env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "never")
+        .env_remove("GIT_ASKPASS")
+        .env_remove("SSH_ASKPASS")
+        .env_remove("SSH_ASKPASS_REQUIRE");
+    let ssh_configured = std::env::var_os("GIT_SSH_COMMAND").is_some()
+        || !crate::run_git(repo_path, &["config", "--get", "core.sshCommand"]).unwrap_or_default().trim().is_empty();
+    if !ssh_configured {
+        cmd.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+    }
+    cmd.args(["push", "--dry-run", "--porcelain", remote_name, "HEAD"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn git push: {e}"))?;
+    // stderr is drained on its own thread so a chatty remote cannot fill the pipe.
+    let stderr_reader = child.stderr.take().map(|mut err| {
+        std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = err.read_to_string(&mut buf);
+            buf
+        })
+    });
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started.elapsed() >= PUSH_PROBE_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(String::from("The remote did not answer a push check in time."));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("Failed to wait for git push: {e}")),
+        }
+    }
+
+    let stderr = stderr_reader.and_then(|h| h.join().ok()).unwrap_or_default();
+    // Rejections such as non-fast-forward still prove that pushing is allowed.
+    if looks_like_auth_failure(&stderr) {
+        let last = stderr.lines().map(|l| l.trim()).rfind(|l| !l.is_empty()).unwrap_or_default();
+        return Err(last.trim_start_matches("fatal:").trim().to_string());
+    }
+    Ok(())
+}
+
+/// Whether each remote (or just `remote_name`) can be pushed to, with the reason when
+/// not or when it is unknown. `probe` additionally contacts the server with a dry-run push.
+#[tauri::command]
+pub(crate) async fn git_remote_capabilities(
+    repo_path: String,
+    remote_name: Option<String>,
+    probe: Option<bool>,
+) -> Result<Vec<GitRemoteCapability>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+
+        let remotes: Vec<String> = match remote_name.map(|r| r.trim().to_string()).filter(|r| !r.is_empty()) {
+            Some(r) => {
+                ensure_remote_exists(&repo_path, &r)?;
+                vec![r]
+            }
+            None => crate::run_git(&repo_path, &["remote"])?
+                .lines()
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect(),
+        };
+
+        let mut out: Vec<GitRemoteCapability> = Vec::new();
+        for remote in remotes {
+            let push_url = crate::run_git(&repo_path, &["remote", "get-url", "--push", remote.as_str()])
+                .ok()
+                .map(|u| u.trim().to_string())
+                .filter(|u| !u.is_empty());
+
+            let check = match push_url.as_deref() {
+                None => Err(String::from("The remote has no URL.")),
+                Some(url) => static_push_check(&repo_path, url),
+            };
+            let check = match check {
+                Ok(_) if probe.unwrap_or(false) => probe_push(&repo_path, &remote).map(|()| None),
+                other => other,
+            };
+
+            let (can_push, reason) = match check {
+                Ok(None) => (Some(true), None),
+                Ok(Some(unknown)) => (None, Some(unknown)),
+                Err(reason) => (Some(false), Some(reason)),
+            };
+            out.push(GitRemoteCapability { remote, push_url, can_push, reason });
+        }
+        Ok(out)
+    })
+    .await
+    .map_err(|e| format!("Failed to check remote capabilities: {e}"))?
+}
+
+fn run_push(repo_path: &str, args: Vec<String>, on_progress: impl FnMut(String, u32, String)) -> Result<String, String> {
+    let mut cmd = crate::git_command_in_repo(repo_path);
+    cmd.arg("push").arg("--progress").args(args);
+    let (ok, stdout, stderr) = super::clone::run_git_status_with_progress(cmd, "git push", on_progress)?;
+    if !ok {
+        return Err(format!("git command failed: {stderr}"));
+    }
+    Ok(if !stdout.is_empty() { stdout } else { stderr })
+}
+
+fn ensure_refspec_ref_valid(name: &str, what: &str) -> Result<(), String> {
+    if name.starts_with('+') || name.starts_with('-') || name.contains(':') || name.contains(char::is_whitespace) {
+        return Err(format!("Invalid {what}: {name}"));
+    }
+    Ok(())
+}
+
+/// Pushes `src_ref` (any revision) to `dst_ref` on the remote; `dst_ref` defaults to the
+/// same name as `src_ref`. Force pushes to a protected branch need `allow_protected`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn git_push_refspec(
+    app: tauri::AppHandle,
+    repo_path: String,
+    remote_name: Option<String>,
+    src_ref: String,
+    dst_ref: Option<String>,
+    force: Option<bool>,
+    with_lease: Option<bool>,
+    allow_protected: Option<bool>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+        let remote_name = normalize_remote_name(remote_name)?;
+
+        let src_ref = src_ref.trim().to_string();
+        if src_ref.is_empty() {
+            return Err(String::from("src_ref is empty"));
+        }
+        ensure_refspec_ref_valid(&src_ref, "source ref")?;
+        let dst_ref = dst_ref.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        if let Some(dst) = dst_ref.as_deref() {
+            ensure_refspec_ref_valid(dst, "destination ref")?;
+        }
+
+        let mut args: Vec<String> = Vec::new();
+        if force.unwrap_or(false) {
+            let dst = dst_ref.as_deref().unwrap_or(src_ref.as_str());
+            super::protected::ensure_not_protected(&repo_path, dst, "force push", allow_protected)?;
+            args.push(if with_lease.unwrap_or(true) { "--force-with-lease" } else { "--force" }.to_string());
+        }
+        args.push(remote_name);
+        args.push(match dst_ref {
+            Some(dst) => format!("{src_ref}:{dst}"),
+            None => src_ref,
+        });
+
+        let on_progress = super::clone::transfer_progress_emitter(&app, &repo_path, "push");
+        run_push(&repo_path, args, on_progress)
+    })
+    .await
+    .map_err(|e| format!("Failed to run git push: {e}"))?
+}
+
+/// Deletes `branch` on the remote. With `dry_run` the remote is asked whether the branch
+/// exists, and the report lists the commits only its remote-tracking ref kept reachable
+/// and the local branches that track it.
+#[tauri::command]
+pub(crate) async fn git_push_delete_branch(
+    app: tauri::AppHandle,
+    repo_path: String,
+    remote_name: Option<String>,
+    branch: String,
+    dry_run: Option<bool>,
+    allow_protected: Option<bool>,
+) -> Result<crate::GitCommandOutcome, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        if !dry_run.unwrap_or(false) {
+            super::read_only::ensure_writable(&repo_path)?;
+        }
+        let remote_name = normalize_remote_name(remote_name)?;
+        ensure_remote_exists(&repo_path, &remote_name)?;
+
+        let branch = branch.trim();
+        let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch).to_string();
+        if branch.is_empty() {
+            return Err(String::from("branch is empty"));
+        }
+        ensure_refspec_ref_valid(&branch, "branch name")?;
+        let full_ref = format!("refs/heads/{branch}");
+
+        if dry_run.unwrap_or(false) {
+            let raw = crate::run_git(&repo_path, &["ls-remote", "--heads", remote_name.as_str(), full_ref.as_str()])?;
+            let old = raw
+                .lines()
+                .find(|l| l.ends_with(full_ref.as_str()))
+                .and_then(|l| l.split_whitespace().next())
+                .map(|s| s.to_string());
+            let Some(old) = old else {
+                return Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+                    operation: String::from("push --delete"),
+                    ref_updates: Vec::new(),
+                    files_removed: Vec::new(),
+                    files_changed: Vec::new(),
+                    commits_orphaned: Vec::new(),
+                    message: format!("Branch {branch} does not exist on {remote_name}; nothing would change."),
+                }));
+            };
+
+            let tracking_ref = format!("refs/remotes/{remote_name}/{branch}");
+            let commits_orphaned = match crate::resolve_commit(&repo_path, tracking_ref.as_str()) {
+                Some(tip) => crate::commits_orphaned_by(&repo_path, tip.as_str(), &[tracking_ref.as_str()], &[]),
+                None => Vec::new(),
+            };
+            let tracked_by: Vec<String> = crate::run_git(
+                &repo_path,
+                &["for-each-ref", "--format=%(refname:short)\x1f%(upstream)", "refs/heads"],
+            )?
+            .lines()
+            .filter_map(|l| l.split_once('\x1f'))
+            .filter(|(_, upstream)| upstream.trim() == tracking_ref)
+            .map(|(local, _)| local.trim().to_string())
+            .collect();
+
+            let mut message = format!("Would delete branch {branch} on {remote_name}.");
+            if !tracked_by.is_empty() {
+                message.push_str(&format!(" Tracked by local branch(es): {}.", tracked_by.join(", ")));
+            }
+            if !commits_orphaned.is_empty() {
+                message.push_str(&format!(
+                    " {} commit(s) are on no other branch and would no longer be reachable here.",
+                    commits_orphaned.len()
+                ));
+            }
+            return Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+                operation: String::from("push --delete"),
+                ref_updates: vec![crate::GitRefUpdate {
+                    name: format!("{remote_name}:{full_ref}"),
+                    old: Some(old),
+                    new: None,
+                }],
+                files_removed: Vec::new(),
+                files_changed: Vec::new(),
+                commits_orphaned,
+                message,
+            }));
+        }
+
+        super::protected::ensure_not_protected(&repo_path, &branch, "remote branch deletion", allow_protected)?;
+        let args = vec![remote_name, String::from("--delete"), full_ref];
+        let on_progress = super::clone::transfer_progress_emitter(&app, &repo_path, "push");
+        run_push(&repo_path, args, on_progress).map(crate::GitCommandOutcome::Done)
+    })
+    .await
+    .map_err(|e| format!("Failed to run git push: {e}"))?
+}
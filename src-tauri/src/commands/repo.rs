@@ -1,6 +1,7 @@
 use serde::Serialize;
 
 use std::path::Path;
+use std::process::Stdio;
 
 #[tauri::command]
 pub(crate) fn git_check_worktree(repo_path: String) -> Result<(), String> {
@@ -67,6 +68,7 @@ pub(crate) fn change_repo_ownership_to_current_user(repo_path: String) -> Result
     if repo_path.is_empty() {
         return Err(String::from("repo_path is empty"));
     }
+    super::read_only::ensure_writable(&repo_path)?;
 
     #[cfg(target_os = "windows")]
     {
@@ -130,6 +132,62 @@ pub(crate) struct RepoOverview {
     branches: Vec<String>,
     tags: Vec<String>,
     remotes: Vec<String>,
+    partial_clone_filter: Option<String>,
+}
+
+/// The promisor remote of a partial clone and its object filter (e.g. `blob:none`).
+pub(crate) fn partial_clone_remote(repo_path: &str) -> Option<(String, String)> {
+    let raw = crate::run_git(repo_path, &["config", "--get-regexp", r"^remote\..*\.partialclonefilter$"]).ok()?;
+    let line = raw.lines().map(|l| l.trim()).find(|l| !l.is_empty())?;
+    let (key, filter) = line.split_once(' ')?;
+    let remote = key.strip_prefix("remote.")?.strip_suffix(".partialclonefilter")?;
+    Some((remote.to_string(), filter.trim().to_string()))
+}
+
+/// In a partial clone, fetches the blobs from `oids` that are not present locally in one
+/// request, instead of letting git lazily fetch them one by one as they are read.
+pub(crate) fn prefetch_missing_blobs(repo_path: &str, oids: &[String]) {
+    if oids.is_empty() {
+        return;
+    }
+    let Some((remote, _)) = partial_clone_remote(repo_path) else {
+        return;
+    };
+
+    // Reading an object would lazily fetch it; GIT_NO_LAZY_FETCH makes the presence
+    // check fail instead.
+    let missing = oids
+        .iter()
+        .filter(|oid| {
+            !crate::git_command_in_repo(repo_path)
+                .env("GIT_NO_LAZY_FETCH", "1")
+                .args(["cat-file", "-e", oid.as_str()])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|s| s.success())
+        })
+        .map(|oid| format!("{oid}\n"))
+        .collect::<String>();
+    if missing.is_empty() {
+        return;
+    }
+
+    let _ = crate::run_git_with_stdin(
+        repo_path,
+        &[
+            "-c",
+            "fetch.negotiationAlgorithm=noop",
+            "fetch",
+            "--no-tags",
+            "--no-write-fetch-head",
+            "--recurse-submodules=no",
+            "--filter=blob:none",
+            "--stdin",
+            remote.as_str(),
+        ],
+        missing.as_str(),
+    );
 }
 
 #[tauri::command]
@@ -166,12 +224,15 @@ pub(crate) fn repo_overview(repo_path: String) -> Result<RepoOverview, String> {
         .map(|l| l.to_string())
         .collect();
 
+    let partial_clone_filter = partial_clone_remote(&repo_path).map(|(_, filter)| filter);
+
     Ok(RepoOverview {
         head,
         head_name,
         branches,
         tags,
         remotes,
+        partial_clone_filter,
     })
 }
 
@@ -211,16 +272,22 @@ pub(crate) fn init_repo(repo_path: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub(crate) fn git_ls_remote_heads(repo_url: String) -> Result<Vec<String>, String> {
+pub(crate) async fn git_ls_remote_heads(repo_url: String) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || git_ls_remote_heads_impl(repo_url))
+        .await
+        .map_err(|e| format!("Failed to list remote branches: {e}"))?
+}
+
+fn git_ls_remote_heads_impl(repo_url: String) -> Result<Vec<String>, String> {
     let repo_url = repo_url.trim().to_string();
     if repo_url.is_empty() {
         return Err(String::from("repo_url is empty"));
     }
 
-    let out = crate::new_command("git")
-        .args(["ls-remote", "--heads", repo_url.as_str()])
-        .output()
-        .map_err(|e| format!("Failed to spawn git ls-remote: {e}"))?;
+    let mut cmd = crate::new_command("git");
+    cmd.args(["ls-remote", "--heads", repo_url.as_str()]);
+    super::askpass::apply_env(&mut cmd);
+    let out = cmd.output().map_err(|e| format!("Failed to spawn git ls-remote: {e}"))?;
 
     if !out.status.success() {
         let stderr = String::from_utf8_lossy(&out.stderr);
@@ -248,3 +315,76 @@ pub(crate) fn git_ls_remote_heads(repo_url: String) -> Result<Vec<String>, Strin
     branches.dedup();
     Ok(branches)
 }
+
+const PATH_COMPLETION_DEFAULT_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitPathCompletions {
+    paths: Vec<String>,
+    truncated: bool,
+}
+
+/// Completes a repo-relative path one segment at a time: returns the tracked files
+/// and directories (with a trailing `/`) next to `prefix`, from the index or from `rev`.
+#[tauri::command]
+pub(crate) fn complete_paths(
+    repo_path: String,
+    prefix: String,
+    rev: Option<String>,
+    limit: Option<u32>,
+) -> Result<GitPathCompletions, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let prefix = prefix.trim().replace('\\', "/");
+    let prefix = prefix.trim_start_matches("./").trim_start_matches('/').to_string();
+    let limit = limit.map(|l| l as usize).unwrap_or(PATH_COMPLETION_DEFAULT_LIMIT).max(1);
+
+    let (dir, leaf) = match prefix.rfind('/') {
+        Some(i) => (&prefix[..=i], &prefix[i + 1..]),
+        None => ("", prefix.as_str()),
+    };
+
+    let rev = rev.unwrap_or_default().trim().to_string();
+    let mut args: Vec<&str> = vec!["--literal-pathspecs"];
+    if rev.is_empty() {
+        args.extend(["ls-files", "-z"]);
+    } else {
+        args.extend(["ls-tree", "-r", "-z", "--name-only", rev.as_str()]);
+    }
+    if !dir.is_empty() {
+        args.push("--");
+        args.push(dir);
+    }
+
+    let raw = crate::run_git_stdout_raw(&repo_path, args.as_slice())?;
+
+    let leaf_lower = leaf.to_lowercase();
+    let mut found: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+    let mut truncated = false;
+    for p in raw.split('\0') {
+        let Some(rest) = p.strip_prefix(dir) else {
+            continue;
+        };
+        if rest.is_empty() || !rest.to_lowercase().starts_with(leaf_lower.as_str()) {
+            continue;
+        }
+
+        let entry = match rest.find('/') {
+            Some(i) => format!("{dir}{}", &rest[..=i]),
+            None => p.to_string(),
+        };
+        if found.contains(&entry) {
+            continue;
+        }
+        if found.len() >= limit {
+            truncated = true;
+            break;
+        }
+        found.insert(entry);
+    }
+
+    Ok(GitPathCompletions {
+        paths: found.into_iter().collect(),
+        truncated,
+    })
+}
@@ -0,0 +1,580 @@
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const SSH_KEY_TYPES: &[&str] = &[
+    "ssh-ed25519",
+    "ssh-rsa",
+    "ecdsa-sha2-nistp256",
+    "ecdsa-sha2-nistp384",
+    "ecdsa-sha2-nistp521",
+    "sk-ssh-ed25519@openssh.com",
+    "sk-ecdsa-sha2-nistp256@openssh.com",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSigningConfig {
+    /// `openpgp` (git's default), `x509` or `ssh`.
+    format: String,
+    signing_key: Option<String>,
+    sign_commits: bool,
+    sign_tags: bool,
+    allowed_signers_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSshSigningSetup {
+    key_path: String,
+    key_type: String,
+    fingerprint: String,
+    allowed_signers_file: Option<String>,
+}
+
+enum ConfigScope {
+    Global,
+    Repo(String),
+}
+
+fn parse_scope(repo_path: Option<String>, scope: &str) -> Result<ConfigScope, String> {
+    match scope.trim().to_lowercase().as_str() {
+        "global" => Ok(ConfigScope::Global),
+        "repo" => {
+            let repo_path = repo_path.unwrap_or_default();
+            if repo_path.trim().is_empty() {
+                return Err(String::from("repo_path is required for repo scope."));
+            }
+            crate::ensure_is_git_worktree(repo_path.as_str())?;
+            Ok(ConfigScope::Repo(repo_path))
+        }
+        _ => Err(String::from("Invalid scope. Expected 'repo' or 'global'.")),
+    }
+}
+
+impl ConfigScope {
+    fn repo_path(&self) -> Option<&str> {
+        match self {
+            ConfigScope::Repo(repo_path) => Some(repo_path.as_str()),
+            ConfigScope::Global => None,
+        }
+    }
+}
+
+fn run_config(scope: &ConfigScope, args: &[&str]) -> Result<String, String> {
+    super::config::run_git_config(scope.repo_path(), args)
+}
+
+/// Effective value in a repository, or the global value for the global scope.
+fn config_get(scope: &ConfigScope, key: &str) -> Option<String> {
+    super::config::git_config_value(scope.repo_path(), key)
+}
+
+fn config_bool(scope: &ConfigScope, key: &str) -> bool {
+    run_config(scope, &["--type=bool", "--get", key]).is_ok_and(|v| v.trim() == "true")
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
+        Some(rest) => home_dir().map(|h| h.join(rest)).unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Resolves `key_path` to an SSH public key file and returns `(path, key type, key blob)`.
+/// A private key is accepted when its `.pub` sits next to it.
+fn read_ssh_public_key(key_path: &str) -> Result<(PathBuf, String, String), String> {
+    let key_path = key_path.trim();
+    if key_path.is_empty() {
+        return Err(String::from("key_path is empty"));
+    }
+    let mut path = expand_home(key_path);
+    let mut content = fs::read_to_string(&path).map_err(|e| format!("Failed to read key file: {e}"))?;
+    if content.contains("PRIVATE KEY") {
+        let mut pub_path = path.clone().into_os_string();
+        pub_path.push(".pub");
+        let pub_path = PathBuf::from(pub_path);
+        content = fs::read_to_string(&pub_path).map_err(|_| {
+            String::from("The selected file is a private key and no matching .pub file was found next to it.")
+        })?;
+        path = pub_path;
+    }
+
+    let line = content
+        .lines()
+        .map(|l| l.trim())
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .ok_or_else(|| String::from("The key file is empty."))?;
+    let mut parts = line.split_whitespace();
+    let key_type = parts.next().unwrap_or_default().to_string();
+    let blob = parts.next().unwrap_or_default().to_string();
+    if !SSH_KEY_TYPES.contains(&key_type.as_str()) || blob.is_empty() {
+        return Err(String::from("The file is not an SSH public key."));
+    }
+    Ok((path, key_type, blob))
+}
+
+/// Validates the key with `ssh-keygen`, which git also uses to sign, and returns its
+/// fingerprint.
+fn ssh_key_fingerprint(pub_path: &Path) -> Result<String, String> {
+    let out = crate::new_command("ssh-keygen")
+        .arg("-l")
+        .arg("-f")
+        .arg(pub_path)
+        .output()
+        .map_err(|_| String::from("ssh-keygen was not found. It is required for SSH signing."))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr).trim_end().to_string();
+        return Err(format!("ssh-keygen rejected the key: {stderr}"));
+    }
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    Ok(stdout.split_whitespace().nth(1).unwrap_or_default().to_string())
+}
+
+/// Adds `email` with the key to the allowed signers file (creating it if needed) and
+/// points `gpg.ssh.allowedSignersFile` at it. Returns the file used.
+fn append_allowed_signer(
+    scope: &ConfigScope,
+    email: &str,
+    key_type: &str,
+    blob: &str,
+    file_path: Option<String>,
+) -> Result<PathBuf, String> {
+    let configured = config_get(scope, "gpg.ssh.allowedSignersFile");
+    let file = match file_path.map(|f| f.trim().to_string()).filter(|f| !f.is_empty()) {
+        Some(f) => expand_home(f.as_str()),
+        None => match configured.as_deref() {
+            Some(f) => expand_home(f),
+            None => home_dir()
+                .map(|h| h.join(".ssh").join("allowed_signers"))
+                .ok_or_else(|| String::from("Failed to determine home directory."))?,
+        },
+    };
+
+    let existing = fs::read_to_string(&file).unwrap_or_default();
+    let present = existing.lines().any(|l| {
+        let l = l.trim();
+        !l.starts_with('#') && l.split_whitespace().next().is_some_and(|p| p.split(',').any(|e| e == email)) && l.contains(blob)
+    });
+    if !present {
+        if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {e}"))?;
+        }
+        let mut f = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&file)
+            .map_err(|e| format!("Failed to open allowed signers file: {e}"))?;
+        let sep = if existing.is_empty() || existing.ends_with('\n') { "" } else { "\n" };
+        writeln!(f, "{sep}{email} namespaces=\"git\" {key_type} {blob}")
+            .map_err(|e| format!("Failed to write allowed signers file: {e}"))?;
+    }
+
+    let file_s = file.to_string_lossy().to_string();
+    if configured.as_deref().map(expand_home).as_deref() != Some(file.as_path()) {
+        run_config(scope, &["gpg.ssh.allowedSignersFile", file_s.as_str()])?;
+    }
+    Ok(file)
+}
+
+#[tauri::command]
+pub(crate) fn git_signing_config(repo_path: Option<String>, scope: Option<String>) -> Result<GitSigningConfig, String> {
+    let scope = parse_scope(repo_path, scope.as_deref().unwrap_or("repo"))?;
+    Ok(GitSigningConfig {
+        format: config_get(&scope, "gpg.format").unwrap_or_else(|| String::from("openpgp")),
+        signing_key: config_get(&scope, "user.signingkey"),
+        sign_commits: config_bool(&scope, "commit.gpgsign"),
+        sign_tags: config_bool(&scope, "tag.gpgsign"),
+        allowed_signers_file: config_get(&scope, "gpg.ssh.allowedSignersFile"),
+    })
+}
+
+/// Configures SSH signing (`gpg.format=ssh`, `user.signingkey`) with a validated public
+/// key, optionally turning on signing by default and trusting the key for verification.
+#[tauri::command]
+pub(crate) fn git_setup_ssh_signing(
+    repo_path: Option<String>,
+    scope: String,
+    key_path: String,
+    sign_commits: Option<bool>,
+    sign_tags: Option<bool>,
+    add_allowed_signer: Option<bool>,
+    email: Option<String>,
+) -> Result<GitSshSigningSetup, String> {
+    let scope = parse_scope(repo_path, scope.as_str())?;
+    if let Some(repo_path) = scope.repo_path() {
+        super::read_only::ensure_writable(repo_path)?;
+    }
+    let (pub_path, key_type, blob) = read_ssh_public_key(key_path.as_str())?;
+    let fingerprint = ssh_key_fingerprint(&pub_path)?;
+    let pub_path_s = pub_path.to_string_lossy().to_string();
+
+    run_config(&scope, &["gpg.format", "ssh"])?;
+    run_config(&scope, &["user.signingkey", pub_path_s.as_str()])?;
+    if let Some(v) = sign_commits {
+        run_config(&scope, &["--type=bool", "commit.gpgsign", if v { "true" } else { "false" }])?;
+    }
+    if let Some(v) = sign_tags {
+        run_config(&scope, &["--type=bool", "tag.gpgsign", if v { "true" } else { "false" }])?;
+    }
+
+    let allowed_signers_file = if add_allowed_signer.unwrap_or(true) {
+        let email = email
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .or_else(|| config_get(&scope, "user.email"))
+            .ok_or_else(|| String::from("user.email is not set; it is needed for the allowed signers file."))?;
+        let file = append_allowed_signer(&scope, email.as_str(), key_type.as_str(), blob.as_str(), None)?;
+        Some(file.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(GitSshSigningSetup {
+        key_path: pub_path_s,
+        key_type,
+        fingerprint,
+        allowed_signers_file,
+    })
+}
+
+/// Trusts an SSH public key for `email` when verifying signatures, e.g. a teammate's key.
+#[tauri::command]
+pub(crate) fn git_ssh_allowed_signers_add(
+    repo_path: Option<String>,
+    scope: String,
+    email: String,
+    key_path: String,
+    file_path: Option<String>,
+) -> Result<String, String> {
+    let scope = parse_scope(repo_path, scope.as_str())?;
+    if let Some(repo_path) = scope.repo_path() {
+        super::read_only::ensure_writable(repo_path)?;
+    }
+    let email = email.trim().to_string();
+    if email.is_empty() || email.contains(char::is_whitespace) {
+        return Err(String::from("email is empty or invalid"));
+    }
+    let (_, key_type, blob) = read_ssh_public_key(key_path.as_str())?;
+    let file = append_allowed_signer(&scope, email.as_str(), key_type.as_str(), blob.as_str(), file_path)?;
+    Ok(file.to_string_lossy().to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSignatureCheck {
+    /// Commit id, or the tag name for tags.
+    target: String,
+    kind: String, // "commit" | "tag"
+    subject: String,
+    committer_name: String,
+    committer_email: String,
+    /// "good" | "untrusted" | "unsigned" | "bad" | "expired" | "revoked" | "unchecked"
+    status: String,
+    signer: Option<String>,
+    key: Option<String>,
+    problems: Vec<String>,
+    ok: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitVerifyRangeReport {
+    range: String,
+    checks: Vec<GitSignatureCheck>,
+    signed: u32,
+    unsigned: u32,
+    failed: u32,
+    /// Every commit and tag in the range is signed with a good signature by its committer.
+    ok: bool,
+}
+
+/// Maps `%G?` to a status and, when it is not a good signature, the problem to report.
+pub(crate) fn signature_status(code: &str) -> (&'static str, Option<&'static str>) {
+    match code {
+        "G" => ("good", None),
+        "U" => ("untrusted", Some("Signature is good but the key is not trusted.")),
+        "N" => ("unsigned", Some("Not signed.")),
+        "B" => ("bad", Some("Signature is bad.")),
+        "X" => ("expired", Some("Signature has expired.")),
+        "Y" => ("expired", Some("Signed with a key that has expired.")),
+        "R" => ("revoked", Some("Signed with a revoked key.")),
+        _ => (
+            "unchecked",
+            Some("Signature cannot be checked; the key is missing or gpg.ssh.allowedSignersFile is not set."),
+        ),
+    }
+}
+
+/// What `git verify-tag --raw` reported: gpg's `[GNUPG:]` status lines, or ssh-keygen's
+/// one-line verdict for SSH signatures.
+struct TagVerification {
+    status: &'static str,
+    problem: Option<&'static str>,
+    signer: Option<String>,
+    key: Option<String>,
+    /// gpg's trust level in the key ("ultimate", "full", "marginal", "never", "undefined").
+    trust: Option<String>,
+}
+
+fn parse_tag_verification(success: bool, output: &str) -> TagVerification {
+    let mut code: Option<&str> = None;
+    let mut signer: Option<String> = None;
+    let mut key: Option<String> = None;
+    let mut trust: Option<String> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(status) = line.strip_prefix("[GNUPG:] ") {
+            let mut parts = status.splitn(3, ' ');
+            let keyword = parts.next().unwrap_or_default();
+            let arg = parts.next().map(String::from);
+            let rest = parts.next().map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+            match keyword {
+                "GOODSIG" | "EXPSIG" | "EXPKEYSIG" | "REVKEYSIG" | "BADSIG" => {
+                    code = Some(match keyword {
+                        "GOODSIG" => "G",
+                        "EXPSIG" => "X",
+                        "EXPKEYSIG" => "Y",
+                        "REVKEYSIG" => "R",
+                        _ => "B",
+                    });
+                    key = key.or(arg);
+                    signer = rest;
+                }
+                "ERRSIG" | "NO_PUBKEY" => {
+                    code = code.or(Some("E"));
+                    key = key.or(arg);
+                }
+                // The full fingerprint replaces the long key id from GOODSIG.
+                "VALIDSIG" => key = arg,
+                _ => {
+                    if let Some(level) = keyword.strip_prefix("TRUST_") {
+                        trust = Some(level.to_lowercase());
+                    }
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Good \"git\" signature") {
+            // `for <principal> with <type> key <fingerprint>`; no principal means the key
+            // is not in the allowed signers file.
+            code = Some("G");
+            let (who, with) = match rest.trim().strip_prefix("for ") {
+                Some(r) => match r.split_once(" with ") {
+                    Some((who, with)) => (Some(who.trim().to_string()), with),
+                    None => (Some(r.trim().to_string()), ""),
+                },
+                None => (None, rest.trim().strip_prefix("with ").unwrap_or_default()),
+            };
+            if who.is_none() {
+                code = Some("U");
+            }
+            signer = who;
+            key = with.split_once(" key ").map(|(_, k)| k.trim().to_string());
+        } else if line.contains("no signature found") {
+            code = code.or(Some("N"));
+        } else if line.contains("allowedSignersFile") {
+            code = code.or(Some("E"));
+        } else if line.contains("Could not verify signature") || line.contains("Signature verification failed") {
+            code = code.or(Some("B"));
+        }
+    }
+
+    // gpg signs as good for any known key; trust decides whether it counts.
+    if code == Some("G") && matches!(trust.as_deref(), Some("never" | "undefined")) {
+        code = Some("U");
+    }
+    let code = code.unwrap_or(if success { "G" } else { "E" });
+    let (status, problem) = signature_status(code);
+    TagVerification {
+        status,
+        problem,
+        signer,
+        key,
+        trust,
+    }
+}
+
+fn run_verify_tag(repo_path: &str, tag: &str) -> Result<(bool, String), String> {
+    let spec = format!("refs/tags/{tag}");
+    let out = crate::git_command_in_repo(repo_path)
+        .args(["verify-tag", "--raw", spec.as_str()])
+        .output()
+        .map_err(|e| format!("Failed to spawn git verify-tag: {e}"))?;
+    let mut output = String::from_utf8_lossy(&out.stderr).to_string();
+    output.push_str(&String::from_utf8_lossy(&out.stdout));
+    Ok((out.status.success(), output))
+}
+
+fn verify_tag(repo_path: &str, tag: &str, tagger_name: String, tagger_email: String, subject: String) -> GitSignatureCheck {
+    let v = match run_verify_tag(repo_path, tag) {
+        Ok((success, output)) => parse_tag_verification(success, &output),
+        Err(_) => parse_tag_verification(false, ""),
+    };
+    let problems: Vec<String> = v.problem.map(String::from).into_iter().collect();
+    GitSignatureCheck {
+        target: tag.to_string(),
+        kind: String::from("tag"),
+        subject,
+        committer_name: tagger_name,
+        committer_email: tagger_email,
+        status: v.status.to_string(),
+        signer: v.signer,
+        key: v.key,
+        ok: problems.is_empty(),
+        problems,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitTagVerification {
+    tag: String,
+    /// Lightweight tags have no tag object and so cannot be signed.
+    annotated: bool,
+    /// "good" | "untrusted" | "unsigned" | "bad" | "expired" | "revoked" | "unchecked"
+    status: String,
+    signer: Option<String>,
+    key: Option<String>,
+    trust: Option<String>,
+    problem: Option<String>,
+    /// What gpg or ssh-keygen printed.
+    output: String,
+    ok: bool,
+}
+
+/// Verifies a tag's signature with `git verify-tag`.
+#[tauri::command]
+pub(crate) fn git_verify_tag(repo_path: String, tag: String) -> Result<GitTagVerification, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let tag = tag.trim().to_string();
+    if tag.is_empty() {
+        return Err(String::from("tag is empty"));
+    }
+    let spec = format!("refs/tags/{tag}");
+    let kind = crate::run_git(&repo_path, &["cat-file", "-t", spec.as_str()])
+        .map_err(|_| format!("Tag '{tag}' not found."))?;
+
+    if kind.trim() != "tag" {
+        let (status, problem) = signature_status("N");
+        return Ok(GitTagVerification {
+            tag,
+            annotated: false,
+            status: status.to_string(),
+            signer: None,
+            key: None,
+            trust: None,
+            problem: problem.map(String::from),
+            output: String::new(),
+            ok: false,
+        });
+    }
+
+    let (success, output) = run_verify_tag(&repo_path, &tag)?;
+    let v = parse_tag_verification(success, &output);
+    Ok(GitTagVerification {
+        tag,
+        annotated: true,
+        status: v.status.to_string(),
+        signer: v.signer,
+        key: v.key,
+        trust: v.trust,
+        ok: v.problem.is_none(),
+        problem: v.problem.map(String::from),
+        output: output.trim_end().to_string(),
+    })
+}
+
+/// Verifies the signature of every commit in `range` (default: what a push would send,
+/// `@{upstream}..HEAD`, or the commits on no remote without an upstream) and of annotated
+/// tags pointing into it. A good signature also has to belong to the committer.
+#[tauri::command]
+pub(crate) fn git_verify_range(repo_path: String, range: Option<String>) -> Result<GitVerifyRangeReport, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let range = range.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if range.as_deref().is_some_and(|r| r.starts_with('-')) {
+        return Err(String::from("range is invalid"));
+    }
+    let (label, rev_args): (String, Vec<String>) = match range {
+        Some(r) => (r.clone(), vec![r]),
+        None => match crate::run_git(&repo_path, &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{upstream}"]) {
+            Ok(u) if !u.trim().is_empty() => (format!("{}..HEAD", u.trim()), vec![format!("{}..HEAD", u.trim())]),
+            _ => (
+                String::from("HEAD --not --remotes"),
+                vec![String::from("HEAD"), String::from("--not"), String::from("--remotes")],
+            ),
+        },
+    };
+
+    let mut args: Vec<&str> = vec!["log", "--format=%H%x00%s%x00%cn%x00%ce%x00%G?%x00%GS%x00%GK%x1e"];
+    args.extend(rev_args.iter().map(|s| s.as_str()));
+    args.push("--");
+    let raw = crate::run_git(&repo_path, &args)?;
+
+    let mut checks: Vec<GitSignatureCheck> = Vec::new();
+    let mut hashes: std::collections::HashSet<String> = std::collections::HashSet::new();
+    for record in raw.split('\x1e') {
+        let f: Vec<&str> = record.trim_start_matches('\n').split('\0').collect();
+        if f.len() < 7 || f[0].trim().is_empty() {
+            continue;
+        }
+        let committer_email = f[3].trim().to_string();
+        let signer = Some(f[5].trim().to_string()).filter(|s| !s.is_empty());
+        let (status, problem) = signature_status(f[4].trim());
+        let mut problems: Vec<String> = problem.map(String::from).into_iter().collect();
+        // SSH signers are the allowed-signers principal (usually the e-mail), GPG signers
+        // the key's user id; either way it should name the committer.
+        if status == "good" || status == "untrusted" {
+            let matches = signer
+                .as_deref()
+                .is_some_and(|s| !committer_email.is_empty() && s.to_lowercase().contains(&committer_email.to_lowercase()));
+            if !matches {
+                problems.push(format!(
+                    "Signed by {} but committed as <{committer_email}>.",
+                    signer.as_deref().unwrap_or("an unknown signer")
+                ));
+            }
+        }
+        hashes.insert(f[0].trim().to_string());
+        checks.push(GitSignatureCheck {
+            target: f[0].trim().to_string(),
+            kind: String::from("commit"),
+            subject: f[1].to_string(),
+            committer_name: f[2].to_string(),
+            committer_email,
+            status: status.to_string(),
+            signer,
+            key: Some(f[6].trim().to_string()).filter(|s| !s.is_empty()),
+            ok: problems.is_empty(),
+            problems,
+        });
+    }
+
+    let tags = crate::run_git(
+        &repo_path,
+        &["for-each-ref", "refs/tags", "--format=%(objecttype)%00%(refname:short)%00%(*objectname)%00%(taggername)%00%(taggeremail:trim)%00%(subject)"],
+    )
+    .unwrap_or_default();
+    for line in tags.lines() {
+        let f: Vec<&str> = line.split('\0').collect();
+        if f.len() < 6 || f[0] != "tag" || !hashes.contains(f[2]) {
+            continue;
+        }
+        checks.push(verify_tag(&repo_path, f[1], f[3].to_string(), f[4].to_string(), f[5].to_string()));
+    }
+
+    let signed = checks.iter().filter(|c| c.status != "unsigned").count() as u32;
+    let unsigned = checks.len() as u32 - signed;
+    let failed = checks.iter().filter(|c| !c.ok).count() as u32;
+    Ok(GitVerifyRangeReport {
+        range: label,
+        ok: failed == 0,
+        checks,
+        signed,
+        unsigned,
+        failed,
+    })
+}
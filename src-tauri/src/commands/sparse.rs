@@ -0,0 +1,125 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSparseCheckoutStatus {
+    enabled: bool,
+    cone: bool,
+    patterns: Vec<String>,
+}
+
+/// Cone-mode sparse spec: the listed directories are checked out recursively, and
+/// files directly inside the root or inside any parent of a listed directory too.
+pub(crate) struct SparseCone {
+    dirs: Vec<String>,
+}
+
+impl SparseCone {
+    pub(crate) fn contains(&self, path: &str) -> bool {
+        let path = path.trim_end_matches('/');
+        let parent = match path.rfind('/') {
+            Some(i) => &path[..i],
+            None => return true,
+        };
+
+        self.dirs.iter().any(|d| {
+            path.strip_prefix(d.as_str()).is_some_and(|rest| rest.starts_with('/'))
+                || d == parent
+                || d.strip_prefix(parent).is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+fn config_bool(repo_path: &str, key: &str) -> bool {
+    crate::run_git(repo_path, &["config", "--bool", "--get", key])
+        .map(|v| v.trim() == "true")
+        .unwrap_or(false)
+}
+
+fn sparse_patterns(repo_path: &str) -> Vec<String> {
+    crate::run_git(repo_path, &["sparse-checkout", "list"])
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().trim_end_matches('/').to_string())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+/// The active cone spec, or `None` when sparse checkout is off or not in cone mode.
+pub(crate) fn sparse_cone(repo_path: &str) -> Option<SparseCone> {
+    if !config_bool(repo_path, "core.sparseCheckout") || !config_bool(repo_path, "core.sparseCheckoutCone") {
+        return None;
+    }
+    Some(SparseCone {
+        dirs: sparse_patterns(repo_path),
+    })
+}
+
+fn normalize_dirs(paths: Vec<String>) -> Result<Vec<String>, String> {
+    let mut out: Vec<String> = Vec::new();
+    for p in paths {
+        let p = p.trim().replace('\\', "/").trim_matches('/').to_string();
+        if p.is_empty() {
+            continue;
+        }
+        crate::ensure_rel_path_safe(&p)?;
+        out.push(p);
+    }
+    Ok(out)
+}
+
+#[tauri::command]
+pub(crate) fn git_sparse_checkout_status(repo_path: String) -> Result<GitSparseCheckoutStatus, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let enabled = config_bool(&repo_path, "core.sparseCheckout");
+    Ok(GitSparseCheckoutStatus {
+        enabled,
+        cone: enabled && config_bool(&repo_path, "core.sparseCheckoutCone"),
+        patterns: if enabled { sparse_patterns(&repo_path) } else { Vec::new() },
+    })
+}
+
+#[tauri::command]
+pub(crate) fn git_sparse_checkout_init(repo_path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+    crate::with_repo_git_lock(&repo_path, || crate::run_git(&repo_path, &["sparse-checkout", "init", "--cone"]))
+}
+
+#[tauri::command]
+pub(crate) fn git_sparse_checkout_set(repo_path: String, paths: Vec<String>) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let dirs = normalize_dirs(paths)?;
+    let input = dirs.iter().map(|d| format!("{d}\n")).collect::<String>();
+    crate::with_repo_git_lock(&repo_path, || {
+        crate::run_git_with_stdin(&repo_path, &["sparse-checkout", "set", "--cone", "--stdin"], input.as_str())
+    })
+}
+
+#[tauri::command]
+pub(crate) fn git_sparse_checkout_add(repo_path: String, paths: Vec<String>) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let dirs = normalize_dirs(paths)?;
+    if dirs.is_empty() {
+        return Err(String::from("No paths provided."));
+    }
+    if !config_bool(&repo_path, "core.sparseCheckout") {
+        return Err(String::from("Sparse checkout is not enabled."));
+    }
+
+    let input = dirs.iter().map(|d| format!("{d}\n")).collect::<String>();
+    crate::with_repo_git_lock(&repo_path, || {
+        crate::run_git_with_stdin(&repo_path, &["sparse-checkout", "add", "--stdin"], input.as_str())
+    })
+}
+
+#[tauri::command]
+pub(crate) fn git_sparse_checkout_disable(repo_path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+    crate::with_repo_git_lock(&repo_path, || crate::run_git(&repo_path, &["sparse-checkout", "disable"]))
+}
@@ -1,4 +1,5 @@
 use serde::Serialize;
+use std::fs;
 
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct GitStashEntry {
@@ -61,6 +62,205 @@ pub(crate) fn git_stash_show(repo_path: String, stash_ref: String) -> Result<Str
     )
 }
 
+/// The untracked files saved in the stash's third parent, if it was made with `-u`/`-a`.
+fn stash_untracked_files(repo_path: &str, stash_ref: &str) -> Vec<String> {
+    let spec = format!("{stash_ref}^3");
+    if crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", spec.as_str()]).is_err() {
+        return Vec::new();
+    }
+    crate::run_git_stdout_raw(repo_path, &["ls-tree", "-r", "-z", "--name-only", spec.as_str()])
+        .unwrap_or_default()
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// The files a stash changes relative to the commit it was made on, followed by the
+/// untracked files it saved (status `??`).
+#[tauri::command]
+pub(crate) fn git_stash_changes(
+    repo_path: String,
+    stash_ref: String,
+) -> Result<Vec<super::diff::GitChangeEntry>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let stash_ref = stash_ref.trim().to_string();
+    if stash_ref.is_empty() {
+        return Err(String::from("stash_ref is empty"));
+    }
+
+    let base = format!("{stash_ref}^1");
+    let out = crate::git_command_in_repo(&repo_path)
+        .args(["diff", "--name-status", "-z", "-M", base.as_str(), stash_ref.as_str()])
+        .output()
+        .map_err(|e| format!("Failed to spawn git: {e}"))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(format!("git command failed: {stderr}"));
+    }
+
+    let mut changes = super::diff::parse_name_status_z(&out.stdout);
+    changes.extend(
+        stash_untracked_files(&repo_path, &stash_ref)
+            .into_iter()
+            .map(super::diff::GitChangeEntry::untracked),
+    );
+    Ok(changes)
+}
+
+/// The patch of one file in a stash; untracked files show as added.
+#[tauri::command]
+pub(crate) fn git_stash_file_diff(repo_path: String, stash_ref: String, path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let stash_ref = stash_ref.trim().to_string();
+    let path = path.trim().to_string();
+    if stash_ref.is_empty() {
+        return Err(String::from("stash_ref is empty"));
+    }
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+
+    let base = format!("{stash_ref}^1");
+    let diff = crate::run_git_stdout_raw(
+        &repo_path,
+        &["diff", "--no-color", base.as_str(), stash_ref.as_str(), "--", path.as_str()],
+    )?;
+    if !diff.is_empty() {
+        return Ok(diff);
+    }
+
+    if stash_untracked_files(&repo_path, &stash_ref).contains(&path) {
+        // The untracked tree is a parentless commit, so it shows every file as added.
+        let untracked = format!("{stash_ref}^3");
+        return crate::run_git_stdout_raw(
+            &repo_path,
+            &["show", "--no-color", "--format=", untracked.as_str(), "--", path.as_str()],
+        );
+    }
+    Ok(diff)
+}
+
+/// A file's content as stored in a stash. `source` picks the commit it is read from:
+/// "worktree" (the stashed working tree), "index", "base" (the commit the stash was made
+/// on) or "untracked"; by default the working tree, falling back to the untracked files.
+#[tauri::command]
+pub(crate) fn git_stash_file_content(
+    repo_path: String,
+    stash_ref: String,
+    path: String,
+    source: Option<String>,
+) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let stash_ref = stash_ref.trim().to_string();
+    let path = path.trim().to_string();
+    if stash_ref.is_empty() {
+        return Err(String::from("stash_ref is empty"));
+    }
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+
+    let rev = match source.as_deref().map(|s| s.trim()).unwrap_or("") {
+        "worktree" => stash_ref.clone(),
+        "index" => format!("{stash_ref}^2"),
+        "base" => format!("{stash_ref}^1"),
+        "untracked" => format!("{stash_ref}^3"),
+        "" => {
+            let tracked = format!("{stash_ref}:{path}");
+            if crate::run_git(&repo_path, &["cat-file", "-e", tracked.as_str()]).is_ok() {
+                stash_ref.clone()
+            } else {
+                format!("{stash_ref}^3")
+            }
+        }
+        other => return Err(format!("Invalid stash source: {other}")),
+    };
+
+    let spec = format!("{rev}:{path}");
+    crate::run_git_stdout_raw(&repo_path, &["show", spec.as_str()])
+}
+
+/// Diffs a file as stored in a stash (its stashed working tree version, or the untracked
+/// copy) against the current working tree file, to see what applying it would change.
+#[tauri::command]
+pub(crate) fn git_stash_file_vs_working_diff(
+    repo_path: String,
+    stash_ref: String,
+    path: String,
+    unified: u32,
+) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let stash_ref = stash_ref.trim().to_string();
+    let path = path.trim().to_string();
+    if stash_ref.is_empty() {
+        return Err(String::from("stash_ref is empty"));
+    }
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+
+    let full = crate::safe_repo_join(&repo_path, path.as_str()).map_err(|e| format!("Invalid path: {e}"))?;
+    if full.is_dir() {
+        return Err(String::from("Path is a directory."));
+    }
+
+    let tracked = format!("{stash_ref}:{path}");
+    let untracked = format!("{stash_ref}^3:{path}");
+    let stash_bytes = [tracked, untracked]
+        .iter()
+        .find_map(|spec| {
+            match crate::git_command_in_repo(&repo_path).args(["show", spec.as_str()]).output() {
+                Ok(o) if o.status.success() => Some(o.stdout),
+                _ => None,
+            }
+        })
+        .unwrap_or_default();
+
+    let working_bytes = fs::read(full).unwrap_or_default();
+
+    if stash_bytes.contains(&0) || working_bytes.contains(&0) {
+        return Err(String::from("Binary file preview is not supported."));
+    }
+
+    let dir = crate::make_temp_diff_dir()?;
+    let safe = crate::sanitize_filename(path.as_str());
+    let left = dir.join(format!("STASH_{safe}"));
+    let right = dir.join(format!("WORK_{safe}"));
+    fs::write(&left, stash_bytes.as_slice()).map_err(|e| format!("Failed to write temp file: {e}"))?;
+    fs::write(&right, working_bytes.as_slice()).map_err(|e| format!("Failed to write temp file: {e}"))?;
+
+    let u = unified.min(50);
+    let unified_arg = format!("--unified={u}");
+    let out = crate::new_command("git")
+        .args([
+            "diff",
+            "--no-index",
+            "--no-color",
+            unified_arg.as_str(),
+            "--",
+            left.to_string_lossy().as_ref(),
+            right.to_string_lossy().as_ref(),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to spawn git: {e}"))?;
+
+    // Exit code 1 only means the files differ.
+    if out.status.success() || out.status.code() == Some(1) {
+        return Ok(String::from_utf8_lossy(out.stdout.as_slice()).trim_end().to_string());
+    }
+
+    let stderr = String::from_utf8_lossy(&out.stderr).trim_end().to_string();
+    if !stderr.is_empty() {
+        return Err(format!("git diff failed: {stderr}"));
+    }
+    Err(String::from("git diff failed."))
+}
+
 #[tauri::command]
 pub(crate) fn git_stash_base_commit(repo_path: String, stash_ref: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
@@ -74,21 +274,95 @@ pub(crate) fn git_stash_base_commit(repo_path: String, stash_ref: String) -> Res
     crate::run_git(&repo_path, &["rev-parse", spec.as_str()])
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitStashApplyResult {
+    /// "ok" | "conflicts"
+    status: String,
+    message: String,
+    conflict_files: Vec<String>,
+}
+
+/// Runs `git stash apply` or `git stash pop`. When the stash only applies partially the
+/// conflicted files are reported instead of an error; a conflicting pop keeps the stash.
+fn apply_stash(repo_path: &str, stash_ref: &str, pop: bool) -> Result<GitStashApplyResult, String> {
+    let stash_ref = stash_ref.trim().to_string();
+    if stash_ref.is_empty() {
+        return Err(String::from("stash_ref is empty"));
+    }
+
+    // Files that are already unmerged would otherwise be mistaken for the stash's conflicts.
+    if !crate::list_unmerged_files(repo_path).is_empty() {
+        return Err(String::from("Resolve the current conflicts before applying a stash."));
+    }
+
+    let action = if pop { "pop" } else { "apply" };
+    let (ok, stdout, stderr) = crate::run_git_status(repo_path, &["stash", action, stash_ref.as_str()])?;
+    if ok {
+        return Ok(GitStashApplyResult {
+            status: String::from("ok"),
+            message: stdout.trim_end().to_string(),
+            conflict_files: Vec::new(),
+        });
+    }
+
+    let conflict_files = crate::list_unmerged_files(repo_path);
+    if conflict_files.is_empty() {
+        return Err(format!("git stash {action} failed: {}", stderr.trim_end()));
+    }
+
+    let mut message = String::from("The stash was applied with conflicts. Resolve them in the working tree.");
+    if pop {
+        message.push_str(&format!(" {stash_ref} was kept; drop it once the conflicts are resolved."));
+    }
+    Ok(GitStashApplyResult {
+        status: String::from("conflicts"),
+        message,
+        conflict_files,
+    })
+}
+
 #[tauri::command]
-pub(crate) fn git_stash_apply(repo_path: String, stash_ref: String) -> Result<String, String> {
+pub(crate) fn git_stash_apply(repo_path: String, stash_ref: String) -> Result<GitStashApplyResult, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+    apply_stash(&repo_path, &stash_ref, false)
+}
+
+/// Applies a stash and drops it, unless applying it conflicted.
+#[tauri::command]
+pub(crate) fn git_stash_pop(repo_path: String, stash_ref: String) -> Result<GitStashApplyResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+    apply_stash(&repo_path, &stash_ref, true)
+}
+
+/// Creates `branch_name` at the commit the stash was made on, checks it out and pops the
+/// stash onto it.
+#[tauri::command]
+pub(crate) fn git_stash_branch(repo_path: String, stash_ref: String, branch_name: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let stash_ref = stash_ref.trim().to_string();
     if stash_ref.is_empty() {
         return Err(String::from("stash_ref is empty"));
     }
 
-    crate::run_git(&repo_path, &["stash", "apply", stash_ref.as_str()])
+    let branch_name = branch_name.trim().to_string();
+    if branch_name.is_empty() {
+        return Err(String::from("branch is empty"));
+    }
+    if branch_name.starts_with('-') {
+        return Err(format!("Invalid branch name: {branch_name}"));
+    }
+
+    crate::run_git(&repo_path, &["stash", "branch", branch_name.as_str(), stash_ref.as_str()])
 }
 
 #[tauri::command]
 pub(crate) fn git_stash_drop(repo_path: String, stash_ref: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let stash_ref = stash_ref.trim().to_string();
     if stash_ref.is_empty() {
@@ -101,19 +375,106 @@ pub(crate) fn git_stash_drop(repo_path: String, stash_ref: String) -> Result<Str
 #[tauri::command]
 pub(crate) fn git_stash_clear(repo_path: String) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
     crate::run_git(&repo_path, &["stash", "clear"])
 }
 
+/// Checks the pathspecs of a stash push. Besides files, directories ("src/featureX/") and
+/// globs ("*.rs", ":(glob)src/**/*.ts") are accepted, as are exclusions (":!vendor/").
+/// Every other pathspec must match a change that the stash would take, so a typo is
+/// reported instead of silently stashing nothing. Returns the trimmed pathspecs.
+fn validate_stash_pathspecs(
+    repo_path: &str,
+    paths: &[String],
+    staged_only: bool,
+    untracked: bool,
+    ignored: bool,
+) -> Result<Vec<String>, String> {
+    let paths: Vec<String> = paths.iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect();
+
+    let mut unmatched: Vec<&str> = Vec::new();
+    for spec in &paths {
+        let (exclude, path) = if let Some(rest) = spec.strip_prefix(":(") {
+            let (magic, path) = rest
+                .split_once(')')
+                .ok_or_else(|| format!("Invalid pathspec: {spec}"))?;
+            let mut exclude = false;
+            for word in magic.split(',').map(|w| w.trim()) {
+                match word {
+                    "glob" | "icase" | "literal" => {}
+                    "exclude" => exclude = true,
+                    _ => return Err(format!("Unsupported pathspec magic '{word}' in: {spec}")),
+                }
+            }
+            (exclude, path)
+        } else if let Some(path) = spec.strip_prefix(":!").or_else(|| spec.strip_prefix(":^")) {
+            (true, path)
+        } else if spec.starts_with(':') {
+            return Err(format!("Unsupported pathspec: {spec}"));
+        } else {
+            (false, spec.as_str())
+        };
+        crate::ensure_rel_path_safe(path).map_err(|e| format!("{e}: {spec}"))?;
+        if exclude {
+            continue;
+        }
+
+        let matched = if staged_only {
+            crate::run_git(repo_path, &["diff", "--cached", "--name-only", "--", spec.as_str()])?
+        } else {
+            let mut args: Vec<&str> = vec!["status", "--porcelain"];
+            args.push(if untracked { "--untracked-files=all" } else { "--untracked-files=no" });
+            if ignored {
+                args.push("--ignored");
+            }
+            args.push("--");
+            args.push(spec.as_str());
+            crate::run_git(repo_path, &args)?
+        };
+        if matched.trim().is_empty() {
+            unmatched.push(spec.as_str());
+        }
+    }
+
+    if !unmatched.is_empty() {
+        return Err(format!("Nothing to stash matches: {}", unmatched.join(", ")));
+    }
+    Ok(paths)
+}
+
 #[tauri::command]
 pub(crate) fn git_stash_push_paths(
     repo_path: String,
     message: String,
     paths: Vec<String>,
     include_untracked: Option<bool>,
+    include_staged_only: Option<bool>,
+    keep_index: Option<bool>,
+    include_all: Option<bool>,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
-    if paths.is_empty() {
+    let include_untracked = include_untracked.unwrap_or(false);
+    let include_staged_only = include_staged_only.unwrap_or(false);
+    let keep_index = keep_index.unwrap_or(false);
+    let include_all = include_all.unwrap_or(false);
+
+    if include_staged_only && (include_untracked || include_all) {
+        return Err(String::from("Stashing only staged changes cannot include untracked files."));
+    }
+    if include_staged_only && keep_index {
+        return Err(String::from("Stashing only staged changes cannot keep the index."));
+    }
+    let paths = validate_stash_pathspecs(
+        &repo_path,
+        &paths,
+        include_staged_only,
+        include_untracked || include_all,
+        include_all,
+    )?;
+    // With only staged changes the index selects what is stashed, so no paths are needed.
+    if paths.is_empty() && !include_staged_only {
         return Err(String::from("No files selected to stash."));
     }
 
@@ -123,22 +484,24 @@ pub(crate) fn git_stash_push_paths(
         message.trim().to_string()
     };
 
-    let include_untracked = include_untracked.unwrap_or(false);
-
     let mut args: Vec<&str> = Vec::new();
     args.push("stash");
     args.push("push");
-    if include_untracked {
+    if include_all {
+        args.push("-a");
+    } else if include_untracked {
         args.push("-u");
     }
+    if include_staged_only {
+        args.push("--staged");
+    }
+    if keep_index {
+        args.push("--keep-index");
+    }
     args.push("-m");
     args.push(message.as_str());
     args.push("--");
-    for p in &paths {
-        if !p.trim().is_empty() {
-            args.push(p);
-        }
-    }
+    args.extend(paths.iter().map(|p| p.as_str()));
 
     let out = crate::git_command_in_repo(&repo_path)
         .args(&args)
@@ -161,6 +524,7 @@ pub(crate) fn git_stash_push_patch(
     keep_patch: String,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let path = path.trim().to_string();
     if path.is_empty() {
@@ -178,6 +542,16 @@ pub(crate) fn git_stash_push_patch(
         ));
     }
 
+    super::index::with_index_snapshot(&repo_path, || {
+        stash_push_keeping_patch(&repo_path, message.as_str(), path.as_str(), keep_patch.as_str())
+    })
+}
+
+/// Stashes `path` except for the changes in `keep_patch`, which stay in the working tree.
+fn stash_push_keeping_patch(repo_path: &str, message: &str, path: &str, keep_patch: &str) -> Result<String, String> {
+    let repo_path = repo_path.to_string();
+    let keep_patch = keep_patch.to_string();
+
     let mut keep_patch_reversed = false;
     if !keep_patch.is_empty() {
         if let Err(e) = crate::run_git_with_stdin(
@@ -218,7 +592,7 @@ pub(crate) fn git_stash_push_patch(
     };
 
     let stash_out = crate::git_command_in_repo(&repo_path)
-        .args(["stash", "push", "-m", message.as_str(), "--", path.as_str()])
+        .args(["stash", "push", "-m", message.as_str(), "--", path])
         .output()
         .map_err(|e| format!("Failed to spawn git stash push: {e}"))?;
 
@@ -1,10 +1,51 @@
 use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSubmoduleStatus {
+    /// The submodule's checked-out commit differs from the recorded one.
+    commit_changed: bool,
+    tracked_changes: bool,
+    untracked_changes: bool,
+    /// Commit recorded in HEAD; None for a newly added submodule.
+    old_commit: Option<String>,
+    /// Commit checked out in the submodule when `commit_changed`, else the one staged.
+    new_commit: Option<String>,
+}
 
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct GitStatusEntry {
+    /// Two-character short-format code, e.g. "M ", " D", "??", "UU".
     status: String,
+    /// Index side of `status` (staged); ' ' when unchanged.
+    index_status: String,
+    /// Working tree side of `status` (unstaged); ' ' when unchanged.
+    worktree_status: String,
     path: String,
     old_path: Option<String>,
+    similarity: Option<u32>,
+    /// Set for submodules.
+    submodule: Option<GitSubmoduleStatus>,
+}
+
+/// The `# branch.*` headers of `git status --porcelain=v2 --branch`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct GitStatusBranch {
+    /// None before the first commit.
+    oid: Option<String>,
+    /// None when HEAD is detached.
+    head: Option<String>,
+    upstream: Option<String>,
+    /// None without an upstream, or when the upstream is gone.
+    ahead: Option<u32>,
+    behind: Option<u32>,
+    stash_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitStatusReport {
+    branch: GitStatusBranch,
+    entries: Vec<GitStatusEntry>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -12,6 +53,20 @@ pub(crate) struct GitStatusSummary {
     changed: u32,
 }
 
+/// Everything the UI polls on a refresh, from one status call.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitRepoState {
+    branch: GitStatusBranch,
+    /// Entries of `git status`, staged or not.
+    changed: u32,
+    staged: u32,
+    unstaged: u32,
+    untracked: u32,
+    conflicts: u32,
+    /// "merge" | "rebase" | "am" | "cherry-pick" | "revert" | "bisect"
+    operation: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct GitAheadBehind {
     ahead: u32,
@@ -19,12 +74,139 @@ pub(crate) struct GitAheadBehind {
     upstream: Option<String>,
 }
 
+impl GitStatusEntry {
+    /// `xy` in porcelain v2 form, where '.' means unchanged.
+    fn new(xy: &str, path: String, old_path: Option<String>, similarity: Option<u32>, sub: &str) -> Self {
+        let mut codes = xy.chars().map(|c| if c == '.' { ' ' } else { c });
+        let x = codes.next().unwrap_or(' ');
+        let y = codes.next().unwrap_or(' ');
+        // `N...` for files, `S<c><m><u>` for submodules.
+        let submodule = sub.strip_prefix('S').map(|flags| {
+            let flags = flags.as_bytes();
+            GitSubmoduleStatus {
+                commit_changed: flags.first() == Some(&b'C'),
+                tracked_changes: flags.get(1) == Some(&b'M'),
+                untracked_changes: flags.get(2) == Some(&b'U'),
+                old_commit: None,
+                new_commit: None,
+            }
+        });
+        GitStatusEntry {
+            status: format!("{x}{y}"),
+            index_status: x.to_string(),
+            worktree_status: y.to_string(),
+            path,
+            old_path,
+            similarity,
+            submodule,
+        }
+    }
+
+    /// Records a submodule's commits from the `<hH> <hI>` fields of its status record.
+    fn with_submodule_commits(mut self, head: &str, index: &str) -> Self {
+        if let Some(sub) = self.submodule.as_mut() {
+            sub.old_commit = non_zero_oid(head);
+            sub.new_commit = non_zero_oid(index);
+        }
+        self
+    }
+
+    fn set_status(&mut self, xy: &str) {
+        let mut codes = xy.chars();
+        self.index_status = codes.next().unwrap_or(' ').to_string();
+        self.worktree_status = codes.next().unwrap_or(' ').to_string();
+        self.status = xy.to_string();
+    }
+
+    /// Unmerged paths, the `u` records of porcelain v2.
+    fn is_conflict(&self) -> bool {
+        matches!(self.status.as_str(), "DD" | "AU" | "UD" | "UA" | "DU" | "AA" | "UU")
+    }
+}
+
+/// An object name, or None for the all-zero one git prints for a missing side.
+pub(crate) fn non_zero_oid(oid: &str) -> Option<String> {
+    (!oid.is_empty() && !oid.bytes().all(|b| b == b'0')).then(|| oid.to_string())
+}
+
+/// Parses `git status --porcelain=v2 -z --branch --show-stash` output.
+fn parse_status_v2(raw: &[u8]) -> GitStatusReport {
+    let mut branch = GitStatusBranch::default();
+    let mut entries: Vec<GitStatusEntry> = Vec::new();
+
+    let mut records = raw.split(|b| *b == 0).map(|r| String::from_utf8_lossy(r).to_string());
+    while let Some(rec) = records.next() {
+        if let Some(header) = rec.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.oid" => branch.oid = (value != "(initial)").then(|| value.to_string()),
+                "branch.head" => branch.head = (value != "(detached)").then(|| value.to_string()),
+                "branch.upstream" => branch.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    let mut counts = value.split_whitespace();
+                    branch.ahead = counts.next().and_then(|a| a.trim_start_matches('+').parse().ok());
+                    branch.behind = counts.next().and_then(|b| b.trim_start_matches('-').parse().ok());
+                }
+                "stash" => branch.stash_count = value.trim().parse().unwrap_or(0),
+                _ => {}
+            }
+            continue;
+        }
+
+        let (kind, rest) = rec.split_once(' ').unwrap_or((rec.as_str(), ""));
+        match kind {
+            // 1 <XY> <sub> <mH> <mI> <mW> <hH> <hI> <path>
+            "1" => {
+                let fields: Vec<&str> = rest.splitn(8, ' ').collect();
+                if let [xy, sub, _, _, _, h_head, h_index, path] = fields[..] {
+                    entries.push(GitStatusEntry::new(xy, path.to_string(), None, None, sub).with_submodule_commits(h_head, h_index));
+                }
+            }
+            // 2 <XY> <sub> <mH> <mI> <mW> <hH> <hI> <X><score> <path> NUL <origPath>
+            "2" => {
+                let fields: Vec<&str> = rest.splitn(9, ' ').collect();
+                let old_path = records.next().filter(|p| !p.is_empty());
+                if let [xy, sub, _, _, _, h_head, h_index, score, path] = fields[..] {
+                    let similarity = score.get(1..).and_then(|s| s.parse::<u32>().ok());
+                    entries.push(
+                        GitStatusEntry::new(xy, path.to_string(), old_path, similarity, sub).with_submodule_commits(h_head, h_index),
+                    );
+                }
+            }
+            // u <XY> <sub> <m1> <m2> <m3> <mW> <h1> <h2> <h3> <path>
+            "u" => {
+                let fields: Vec<&str> = rest.splitn(10, ' ').collect();
+                if let [xy, sub, _, _, _, _, _, _, _, path] = fields[..] {
+                    entries.push(GitStatusEntry::new(xy, path.to_string(), None, None, sub));
+                }
+            }
+            "?" if !rest.is_empty() => entries.push(GitStatusEntry::new("??", rest.to_string(), None, None, "N...")),
+            _ => {}
+        }
+    }
+
+    GitStatusReport { branch, entries }
+}
+
+/// Working tree status with the branch header (HEAD, upstream, ahead/behind, stash count)
+/// in one `git status --porcelain=v2` call.
 #[tauri::command]
-pub(crate) fn git_status(repo_path: String) -> Result<Vec<GitStatusEntry>, String> {
+pub(crate) fn git_status(repo_path: String, rename_threshold: Option<u32>) -> Result<GitStatusReport, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
 
+    let rename_threshold = rename_threshold.unwrap_or(DEFAULT_RENAME_THRESHOLD).clamp(1, 100);
+    let find_renames = format!("--find-renames={rename_threshold}%");
+
     let out = crate::git_command_in_repo(&repo_path)
-        .args(["status", "--porcelain", "-z", "--find-renames", "--untracked-files=all"])
+        .args([
+            "status",
+            "--porcelain=v2",
+            "-z",
+            "--branch",
+            "--show-stash",
+            find_renames.as_str(),
+            "--untracked-files=all",
+        ])
         .output()
         .map_err(|e| format!("Failed to spawn git: {e}"))?;
 
@@ -33,83 +215,48 @@ pub(crate) fn git_status(repo_path: String) -> Result<Vec<GitStatusEntry>, Strin
         return Err(format!("git command failed: {stderr}"));
     }
 
-    let mut entries: Vec<GitStatusEntry> = Vec::new();
-    let mut i: usize = 0;
-    let b = out.stdout.as_slice();
-    while i < b.len() {
-        let start = i;
-        while i < b.len() && b[i] != 0 {
-            i += 1;
-        }
-        let rec = &b[start..i];
-        i += 1;
-        if rec.is_empty() {
-            continue;
-        }
-        if rec.len() < 3 {
-            continue;
-        }
-
-        let status_bytes = &rec[0..2];
-        let status = String::from_utf8_lossy(status_bytes).to_string();
+    let mut report = parse_status_v2(&out.stdout);
+    report.entries.retain(|e| !e.path.trim().is_empty());
 
-        let path_bytes = if rec.len() >= 4 { &rec[3..] } else { &[] };
-        if path_bytes.is_empty() {
-            continue;
-        }
+    // Untracked leftovers in directories outside the sparse cone are not part of the checkout.
+    if let Some(cone) = super::sparse::sparse_cone(&repo_path) {
+        report.entries.retain(|e| e.status != "??" || cone.contains(e.path.as_str()));
+    }
 
-        let has_rename = status_bytes[0] == b'R'
-            || status_bytes[1] == b'R'
-            || status_bytes[0] == b'C'
-            || status_bytes[1] == b'C';
+    detect_unstaged_renames(&repo_path, &mut report.entries, rename_threshold);
+    resolve_submodule_checkouts(&repo_path, &mut report.entries);
 
-        if has_rename {
-            let first_path = String::from_utf8_lossy(path_bytes).to_string();
+    Ok(report)
+}
 
-            let start2 = i;
-            while i < b.len() && b[i] != 0 {
-                i += 1;
-            }
-            let new_path_bytes = &b[start2..i];
-            i += 1;
-
-            let second_path = String::from_utf8_lossy(new_path_bytes).to_string();
-
-            // In practice (especially during conflict resolution and after staging), Git may report
-            // rename/copy paths in the order: <new_path> NUL <old_path> NUL.
-            // We normalize here so that `path` is always the *new/current* path and `old_path` is the previous path.
-            let new_path = first_path;
-            let old_path = second_path;
-
-            if !new_path.trim().is_empty() {
-                entries.push(GitStatusEntry {
-                    status,
-                    path: new_path,
-                    old_path: if !old_path.trim().is_empty() { Some(old_path) } else { None },
-                });
-            } else if !old_path.trim().is_empty() {
-                entries.push(GitStatusEntry {
-                    status,
-                    path: old_path,
-                    old_path: None,
-                });
-            }
-        } else {
-            let path = String::from_utf8_lossy(path_bytes).to_string();
-            if !path.trim().is_empty() {
-                entries.push(GitStatusEntry { status, path, old_path: None });
-            }
-        }
+/// Status records only carry the commits in HEAD and the index; for submodules whose
+/// checked-out commit moved, read it from the submodule itself.
+fn resolve_submodule_checkouts(repo_path: &str, entries: &mut [GitStatusEntry]) {
+    for e in entries.iter_mut() {
+        let Some(sub) = e.submodule.as_mut().filter(|s| s.commit_changed) else {
+            continue;
+        };
+        let sub_dir = Path::new(repo_path).join(&e.path);
+        sub.new_commit = crate::run_git(&sub_dir.to_string_lossy(), &["rev-parse", "-q", "--verify", "HEAD"])
+            .ok()
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty());
     }
+}
 
-    detect_unstaged_renames(&repo_path, &mut entries);
+/// Same default as Git's own rename detection (`-M50%`).
+const DEFAULT_RENAME_THRESHOLD: u32 = 50;
 
-    Ok(entries)
-}
+/// Files larger than this are only matched by exact blob hash.
+const SIMILARITY_MAX_FILE_SIZE: usize = 1024 * 1024;
 
-/// Post-process status entries: detect renames among unstaged D + (??/A) pairs
-/// by comparing blob hashes (HEAD version vs working-tree file).
-fn detect_unstaged_renames(repo_path: &str, entries: &mut Vec<GitStatusEntry>) {
+/// Upper bound on the number of deleted x added pairs scored by content similarity.
+const SIMILARITY_MAX_PAIRS: usize = 10_000;
+
+/// Post-process status entries: detect renames among unstaged D + (??/A) pairs.
+/// Exact blob matches (HEAD version vs working-tree file) are paired first, the
+/// remaining candidates are scored by content similarity against `threshold` (percent).
+fn detect_unstaged_renames(repo_path: &str, entries: &mut Vec<GitStatusEntry>, threshold: u32) {
     use std::collections::HashMap;
 
     let mut del_indices: Vec<usize> = Vec::new();
@@ -199,13 +346,78 @@ fn detect_unstaged_renames(repo_path: &str, entries: &mut Vec<GitStatusEntry>) {
 
     // Match add entries to delete entries by identical blob hash
     let mut matched_del: Vec<usize> = Vec::new();
-    let mut rename_pairs: Vec<(usize, usize)> = Vec::new(); // (add_idx, del_idx)
+    let mut rename_pairs: Vec<(usize, usize, u32)> = Vec::new(); // (add_idx, del_idx, similarity)
 
     for (&add_idx, hash) in &work_hash_by_add_idx {
         if let Some(&del_idx) = hash_to_del.get(hash) {
             if !matched_del.contains(&del_idx) {
                 matched_del.push(del_idx);
-                rename_pairs.push((add_idx, del_idx));
+                rename_pairs.push((add_idx, del_idx, 100));
+            }
+        }
+    }
+
+    // Score the remaining candidates by content similarity (best pairs first)
+    if threshold < 100 {
+        let matched_add: Vec<usize> = rename_pairs.iter().map(|&(a, _, _)| a).collect();
+        let rest_del: Vec<usize> = head_hash_by_del_idx
+            .keys()
+            .copied()
+            .filter(|i| !matched_del.contains(i))
+            .collect();
+        let rest_add: Vec<usize> = add_indices.iter().copied().filter(|i| !matched_add.contains(i)).collect();
+
+        if !rest_del.is_empty() && !rest_add.is_empty() && rest_del.len() * rest_add.len() <= SIMILARITY_MAX_PAIRS {
+            let del_hashes: Vec<String> = rest_del.iter().filter_map(|i| head_hash_by_del_idx.get(i).cloned()).collect();
+            super::repo::prefetch_missing_blobs(repo_path, &del_hashes);
+
+            let mut del_sigs: Vec<(usize, ContentSignature)> = Vec::new();
+            for &idx in &rest_del {
+                let hash = match head_hash_by_del_idx.get(&idx) {
+                    Some(h) => h.as_str(),
+                    None => continue,
+                };
+                if let Ok(out) = crate::git_command_in_repo(repo_path).args(["cat-file", "blob", hash]).output() {
+                    if out.status.success() && out.stdout.len() <= SIMILARITY_MAX_FILE_SIZE {
+                        del_sigs.push((idx, ContentSignature::new(&out.stdout)));
+                    }
+                }
+            }
+
+            let mut add_sigs: Vec<(usize, ContentSignature)> = Vec::new();
+            for &idx in &rest_add {
+                let full = match crate::safe_repo_join(repo_path, entries[idx].path.as_str()) {
+                    Ok(p) => p,
+                    Err(_) => continue,
+                };
+                match std::fs::metadata(&full) {
+                    Ok(m) if m.is_file() && (m.len() as usize) <= SIMILARITY_MAX_FILE_SIZE => {}
+                    _ => continue,
+                }
+                if let Ok(bytes) = std::fs::read(&full) {
+                    add_sigs.push((idx, ContentSignature::new(&bytes)));
+                }
+            }
+
+            let mut scored: Vec<(u32, usize, usize)> = Vec::new(); // (similarity, add_idx, del_idx)
+            for (add_idx, add_sig) in &add_sigs {
+                for (del_idx, del_sig) in &del_sigs {
+                    let score = del_sig.similarity(add_sig);
+                    if score >= threshold {
+                        scored.push((score, *add_idx, *del_idx));
+                    }
+                }
+            }
+            scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+            let mut used_add: Vec<usize> = Vec::new();
+            for (score, add_idx, del_idx) in scored {
+                if used_add.contains(&add_idx) || matched_del.contains(&del_idx) {
+                    continue;
+                }
+                used_add.push(add_idx);
+                matched_del.push(del_idx);
+                rename_pairs.push((add_idx, del_idx, score));
             }
         }
     }
@@ -215,9 +427,10 @@ fn detect_unstaged_renames(repo_path: &str, entries: &mut Vec<GitStatusEntry>) {
     }
 
     // Update add entries to become rename entries
-    for &(add_idx, del_idx) in &rename_pairs {
-        entries[add_idx].status = "R ".to_string();
+    for &(add_idx, del_idx, similarity) in &rename_pairs {
+        entries[add_idx].set_status("R ");
         entries[add_idx].old_path = Some(entries[del_idx].path.clone());
+        entries[add_idx].similarity = Some(similarity);
     }
 
     // Remove matched delete entries (reverse sorted order to preserve indices)
@@ -228,6 +441,54 @@ fn detect_unstaged_renames(repo_path: &str, entries: &mut Vec<GitStatusEntry>) {
     }
 }
 
+/// Content fingerprint in the spirit of Git's diffcore-delta: the file is cut into
+/// line-terminated chunks (at most 64 bytes each) and the byte count per chunk hash
+/// is kept, so similarity is "bytes in common / size of the larger file".
+struct ContentSignature {
+    size: usize,
+    chunks: std::collections::HashMap<u64, usize>,
+}
+
+impl ContentSignature {
+    fn new(bytes: &[u8]) -> Self {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        let mut chunks: std::collections::HashMap<u64, usize> = std::collections::HashMap::new();
+        let mut start: usize = 0;
+        for (i, &b) in bytes.iter().enumerate() {
+            if b == b'\n' || i + 1 - start >= 64 || i + 1 == bytes.len() {
+                let chunk = &bytes[start..=i];
+                let mut hasher = DefaultHasher::new();
+                hasher.write(chunk);
+                *chunks.entry(hasher.finish()).or_insert(0) += chunk.len();
+                start = i + 1;
+            }
+        }
+
+        ContentSignature {
+            size: bytes.len(),
+            chunks,
+        }
+    }
+
+    fn similarity(&self, other: &ContentSignature) -> u32 {
+        let max_size = self.size.max(other.size);
+        if max_size == 0 {
+            return 100;
+        }
+
+        let mut common: usize = 0;
+        for (k, n) in &self.chunks {
+            if let Some(m) = other.chunks.get(k) {
+                common += (*n).min(*m);
+            }
+        }
+
+        ((common * 100) / max_size) as u32
+    }
+}
+
 #[tauri::command]
 pub(crate) fn git_has_staged_changes(repo_path: String) -> Result<bool, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
@@ -248,9 +509,84 @@ pub(crate) fn git_status_summary(repo_path: String) -> Result<GitStatusSummary,
     Ok(GitStatusSummary { changed })
 }
 
+/// The operation the repository is in the middle of, read from the marker files git
+/// leaves in `git_dir`. `git am` and a rebase share `rebase-apply`, so am is checked first.
+pub(crate) fn operation_in_progress(git_dir: &Path) -> Option<&'static str> {
+    if git_dir.join("rebase-apply").join("applying").exists() {
+        Some("am")
+    } else if git_dir.join("rebase-merge").is_dir() || git_dir.join("rebase-apply").is_dir() {
+        Some("rebase")
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        Some("merge")
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        Some("cherry-pick")
+    } else if git_dir.join("REVERT_HEAD").exists() {
+        Some("revert")
+    } else if git_dir.join("BISECT_START").exists() {
+        Some("bisect")
+    } else {
+        None
+    }
+}
+
+/// Change counts, conflicts, stash count, the operation in progress, the current branch
+/// and ahead/behind in one call, in place of the separate status, summary, ahead/behind
+/// and conflict polls.
+#[tauri::command]
+pub(crate) fn git_repo_state(repo_path: String) -> Result<GitRepoState, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let out = crate::git_command_in_repo(&repo_path)
+        .args([
+            "status",
+            "--porcelain=v2",
+            "-z",
+            "--branch",
+            "--show-stash",
+            "--untracked-files=all",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to spawn git: {e}"))?;
+
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(format!("git command failed: {stderr}"));
+    }
+
+    let report = parse_status_v2(&out.stdout);
+    let mut state = GitRepoState {
+        branch: report.branch,
+        changed: report.entries.len() as u32,
+        staged: 0,
+        unstaged: 0,
+        untracked: 0,
+        conflicts: 0,
+        operation: super::handles::git_dir(&repo_path)
+            .and_then(|dir| operation_in_progress(&dir))
+            .map(String::from),
+    };
+    for e in &report.entries {
+        if e.status == "??" {
+            state.untracked += 1;
+        } else if e.is_conflict() {
+            state.conflicts += 1;
+        } else {
+            if e.index_status != " " {
+                state.staged += 1;
+            }
+            if e.worktree_status != " " {
+                state.unstaged += 1;
+            }
+        }
+    }
+
+    Ok(state)
+}
+
 #[tauri::command]
 pub(crate) fn git_stage_paths(repo_path: String, paths: Vec<String>) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let mut cleaned: Vec<String> = Vec::new();
     for p in paths.into_iter() {
@@ -291,6 +627,7 @@ pub(crate) fn git_stage_paths(repo_path: String, paths: Vec<String>) -> Result<S
 #[tauri::command]
 pub(crate) fn git_unstage_paths(repo_path: String, paths: Vec<String>) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let mut cleaned: Vec<String> = Vec::new();
     for p in paths.into_iter() {
@@ -429,6 +766,7 @@ pub(crate) fn git_get_remote_url(repo_path: String, remote_name: Option<String>)
 #[tauri::command]
 pub(crate) fn git_set_remote_url(repo_path: String, remote_name: Option<String>, url: String) -> Result<(), String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let remote_name = remote_name.unwrap_or_else(|| String::from("origin"));
     let url = url.trim().to_string();
@@ -465,3 +803,109 @@ pub(crate) fn git_set_remote_url(repo_path: String, remote_name: Option<String>,
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZERO: &str = "0000000000000000000000000000000000000000";
+    const A: &str = "1111111111111111111111111111111111111111";
+    const B: &str = "2222222222222222222222222222222222222222";
+
+    fn records(parts: &[String]) -> Vec<u8> {
+        let mut raw = parts.join("\0").into_bytes();
+        raw.push(0);
+        raw
+    }
+
+    #[test]
+    fn test_parse_status_v2_reads_branch_headers() {
+        let raw = records(&[
+            format!("# branch.oid {A}"),
+            String::from("# branch.head main"),
+            String::from("# branch.upstream origin/main"),
+            String::from("# branch.ab +2 -3"),
+            String::from("# stash 4"),
+        ]);
+        let report = parse_status_v2(&raw);
+        assert_eq!(report.branch.oid.as_deref(), Some(A));
+        assert_eq!(report.branch.head.as_deref(), Some("main"));
+        assert_eq!(report.branch.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((report.branch.ahead, report.branch.behind), (Some(2), Some(3)));
+        assert_eq!(report.branch.stash_count, 4);
+        assert!(report.entries.is_empty());
+
+        let raw = records(&[String::from("# branch.oid (initial)"), String::from("# branch.head (detached)")]);
+        let report = parse_status_v2(&raw);
+        assert_eq!((report.branch.oid, report.branch.head, report.branch.ahead), (None, None, None));
+    }
+
+    #[test]
+    fn test_parse_status_v2_reads_changed_renamed_unmerged_and_untracked() {
+        let raw = records(&[
+            format!("1 M. N... 100644 100644 100644 {A} {B} src/with space.rs"),
+            format!("2 R. N... 100644 100644 100644 {A} {A} R87 new name.txt"),
+            String::from("old name.txt"),
+            format!("u UU N... 100644 100644 100644 100644 {A} {B} {A} conflict.txt"),
+            String::from("? notes.md"),
+        ]);
+        let entries = parse_status_v2(&raw).entries;
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!((entries[0].status.as_str(), entries[0].index_status.as_str(), entries[0].worktree_status.as_str()), ("M ", "M", " "));
+        assert_eq!(entries[0].path, "src/with space.rs");
+        assert!(entries[0].submodule.is_none());
+
+        assert_eq!(entries[1].path, "new name.txt");
+        assert_eq!(entries[1].old_path.as_deref(), Some("old name.txt"));
+        assert_eq!(entries[1].similarity, Some(87));
+
+        assert!(entries[2].is_conflict());
+        assert_eq!(entries[3].status, "??");
+        assert_eq!(entries[3].path, "notes.md");
+    }
+
+    #[test]
+    fn test_parse_status_v2_reads_submodule_state() {
+        let raw = records(&[
+            format!("1 .M SC.U 160000 160000 160000 {A} {A} libs/dep"),
+            format!("1 A. S... 000000 160000 160000 {ZERO} {B} libs/new"),
+        ]);
+        let entries = parse_status_v2(&raw).entries;
+
+        let dep = entries[0].submodule.as_ref().unwrap();
+        assert!(dep.commit_changed && !dep.tracked_changes && dep.untracked_changes);
+        assert_eq!(dep.old_commit.as_deref(), Some(A));
+
+        let new = entries[1].submodule.as_ref().unwrap();
+        assert_eq!(new.old_commit, None);
+        assert_eq!(new.new_commit.as_deref(), Some(B));
+    }
+
+    #[test]
+    fn test_content_signature_similarity() {
+        let text = b"line one\nline two\nline three\nline four\n";
+        let sig = ContentSignature::new(text);
+        assert_eq!(sig.similarity(&ContentSignature::new(text)), 100);
+        assert_eq!(ContentSignature::new(b"").similarity(&ContentSignature::new(b"")), 100);
+
+        // 30 of the 39 bytes survive.
+        let edited = ContentSignature::new(b"line one\nline 2\nline three\nline four\n");
+        assert_eq!(sig.similarity(&edited), 30 * 100 / 39);
+
+        // Reordering lines keeps every chunk.
+        let moved = ContentSignature::new(b"line four\nline one\nline two\nline three\n");
+        assert_eq!(sig.similarity(&moved), 100);
+
+        assert_eq!(sig.similarity(&ContentSignature::new(b"something else entirely\n")), 0);
+    }
+
+    #[test]
+    fn test_content_signature_splits_long_lines_into_chunks() {
+        let long = vec![b'a'; 200];
+        let mut changed_tail = long.clone();
+        changed_tail[199] = b'b';
+        // Only the last 8-byte chunk differs.
+        assert_eq!(ContentSignature::new(&long).similarity(&ContentSignature::new(&changed_tail)), 192 * 100 / 200);
+    }
+}
@@ -0,0 +1,647 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSubmoduleEntry {
+    name: String,
+    path: String,
+    url: Option<String>,
+    branch: Option<String>,
+    recorded_sha: Option<String>,
+    current_sha: Option<String>,
+    initialized: bool,
+    dirty: bool,
+    status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitSubmoduleProgressEvent {
+    repo_path: String,
+    path: String,
+    index: u32,
+    total: u32,
+    phase: Option<String>,
+    percent: Option<u32>,
+    message: String,
+}
+
+#[derive(Default)]
+struct SubmoduleConfig {
+    name: String,
+    path: String,
+    url: Option<String>,
+    branch: Option<String>,
+}
+
+/// Reads `.gitmodules` entries, keyed by submodule name.
+fn read_gitmodules(repo_path: &str) -> HashMap<String, SubmoduleConfig> {
+    let raw = crate::run_git(
+        repo_path,
+        &[
+            "config",
+            "-f",
+            ".gitmodules",
+            "--get-regexp",
+            r"^submodule\..*\.(path|url|branch)$",
+        ],
+    )
+    .unwrap_or_default();
+
+    let mut out: HashMap<String, SubmoduleConfig> = HashMap::new();
+    for line in raw.lines() {
+        let Some((key, value)) = line.trim().split_once(' ') else {
+            continue;
+        };
+        let Some(rest) = key.strip_prefix("submodule.") else {
+            continue;
+        };
+        let Some((name, field)) = rest.rsplit_once('.') else {
+            continue;
+        };
+
+        let entry = out.entry(name.to_string()).or_insert_with(|| SubmoduleConfig {
+            name: name.to_string(),
+            ..SubmoduleConfig::default()
+        });
+        let value = value.trim().to_string();
+        match field {
+            "path" => entry.path = value,
+            "url" => entry.url = Some(value),
+            "branch" => entry.branch = Some(value),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn submodule_name_for_path(repo_path: &str, path: &str) -> Option<String> {
+    read_gitmodules(repo_path)
+        .into_values()
+        .find(|c| c.path == path)
+        .map(|c| c.name)
+}
+
+/// Gitlink entries (mode 160000) from the index: path -> recorded commit.
+fn recorded_gitlinks(repo_path: &str) -> HashMap<String, String> {
+    let raw = crate::run_git_stdout_raw(repo_path, &["ls-files", "-s", "-z"]).unwrap_or_default();
+    let mut out: HashMap<String, String> = HashMap::new();
+    for rec in raw.split('\0') {
+        let Some((meta, path)) = rec.split_once('\t') else {
+            continue;
+        };
+        let mut parts = meta.split_whitespace();
+        if parts.next() != Some("160000") {
+            continue;
+        }
+        if let Some(sha) = parts.next() {
+            out.insert(path.to_string(), sha.to_string());
+        }
+    }
+    out
+}
+
+fn normalize_paths(paths: Option<Vec<String>>) -> Vec<String> {
+    paths
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p.trim().replace('\\', "/").trim_end_matches('/').to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+#[tauri::command]
+pub(crate) fn git_submodule_list(repo_path: String) -> Result<Vec<GitSubmoduleEntry>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let configs = read_gitmodules(&repo_path);
+    let gitlinks = recorded_gitlinks(&repo_path);
+
+    // `submodule status` prefixes: ' ' in sync, '-' not initialized,
+    // '+' checked out commit differs from the recorded one, 'U' merge conflict.
+    let mut status_by_path: HashMap<String, (char, String)> = HashMap::new();
+    let raw = crate::run_git_stdout_raw(&repo_path, &["submodule", "status"]).unwrap_or_default();
+    for line in raw.lines() {
+        let mut chars = line.chars();
+        let Some(flag) = chars.next() else {
+            continue;
+        };
+        let rest = chars.as_str();
+        let mut parts = rest.split_whitespace();
+        let sha = parts.next().unwrap_or_default().to_string();
+        let path = parts.next().unwrap_or_default().to_string();
+        if !path.is_empty() {
+            status_by_path.insert(path, (flag, sha));
+        }
+    }
+
+    let mut paths: Vec<String> = gitlinks.keys().cloned().collect();
+    for c in configs.values() {
+        if !c.path.is_empty() && !paths.contains(&c.path) {
+            paths.push(c.path.clone());
+        }
+    }
+    paths.sort();
+
+    let mut out: Vec<GitSubmoduleEntry> = Vec::new();
+    for path in paths {
+        let config = configs.values().find(|c| c.path == path);
+        let name = config.map(|c| c.name.clone()).unwrap_or_else(|| path.clone());
+
+        // A local url (set by `submodule init`) wins over the one in .gitmodules.
+        let url_key = format!("submodule.{name}.url");
+        let local_url = crate::run_git(&repo_path, &["config", "--get", url_key.as_str()])
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let initialized = local_url.is_some();
+        let url = local_url.or_else(|| config.and_then(|c| c.url.clone()));
+
+        let (flag, status_sha) = status_by_path.get(&path).cloned().unwrap_or((' ', String::new()));
+        let sub_dir = Path::new(&repo_path).join(&path);
+        let checked_out = initialized && flag != '-' && sub_dir.join(".git").exists();
+
+        let current_sha = if checked_out && !status_sha.is_empty() { Some(status_sha) } else { None };
+        let dirty = checked_out
+            && !crate::run_git(&sub_dir.to_string_lossy(), &["status", "--porcelain"])
+                .unwrap_or_default()
+                .trim()
+                .is_empty();
+
+        let status = match flag {
+            'U' => "conflict",
+            _ if !checked_out => "uninitialized",
+            '+' => "out_of_sync",
+            _ if dirty => "modified",
+            _ => "clean",
+        };
+
+        out.push(GitSubmoduleEntry {
+            name,
+            recorded_sha: gitlinks.get(&path).cloned(),
+            path,
+            url,
+            branch: config.and_then(|c| c.branch.clone()),
+            current_sha,
+            initialized,
+            dirty,
+            status: status.to_string(),
+        });
+    }
+
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSubmoduleAddPrediction {
+    name: String,
+    path: String,
+    gitmodules_before: String,
+    gitmodules_after: String,
+    /// Reasons `git submodule add` would refuse; empty when it should go through.
+    problems: Vec<String>,
+}
+
+struct SubmoduleAddArgs {
+    url: String,
+    path: String,
+    branch: String,
+    name: String,
+}
+
+fn submodule_add_args(
+    url: String,
+    path: String,
+    branch: Option<String>,
+    name: Option<String>,
+) -> Result<SubmoduleAddArgs, String> {
+    let url = url.trim().to_string();
+    if url.is_empty() {
+        return Err(String::from("url is empty"));
+    }
+    let path = path.trim().replace('\\', "/").trim_end_matches('/').to_string();
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    crate::ensure_rel_path_safe(&path)?;
+
+    let branch = branch.unwrap_or_default().trim().to_string();
+    // Like git, the name defaults to the path.
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).unwrap_or_else(|| path.clone());
+    Ok(SubmoduleAddArgs { url, path, branch, name })
+}
+
+/// Shows the `.gitmodules` change `git_submodule_add` would make, and why it would fail.
+#[tauri::command]
+pub(crate) fn git_submodule_add_predict(
+    repo_path: String,
+    url: String,
+    path: String,
+    branch: Option<String>,
+    name: Option<String>,
+) -> Result<GitSubmoduleAddPrediction, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    let args = submodule_add_args(url, path, branch, name)?;
+
+    let mut problems: Vec<String> = Vec::new();
+    let existing = read_gitmodules(&repo_path);
+    if existing.contains_key(&args.name) {
+        problems.push(format!("A submodule named '{}' already exists.", args.name));
+    }
+    if existing.values().any(|c| c.path == args.path) {
+        problems.push(format!("'{}' is already a submodule path.", args.path));
+    }
+    let (in_index, _, _) = crate::run_git_status(&repo_path, &["ls-files", "--error-unmatch", "--", args.path.as_str()])?;
+    if in_index {
+        problems.push(format!("'{}' already exists in the index.", args.path));
+    }
+    let abs = Path::new(&repo_path).join(&args.path);
+    if abs.is_file() {
+        problems.push(format!("'{}' already exists and is a file.", args.path));
+    } else if abs.is_dir() && !abs.join(".git").exists() && fs::read_dir(&abs).is_ok_and(|mut d| d.next().is_some()) {
+        problems.push(format!("'{}' already exists and is not a valid git repo.", args.path));
+    }
+
+    let before = fs::read_to_string(Path::new(&repo_path).join(".gitmodules")).unwrap_or_default();
+
+    // Let git format the new section by applying it to a scratch copy.
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let scratch = std::env::temp_dir().join(format!("graphoria-gitmodules-{}-{stamp}", std::process::id()));
+    fs::write(&scratch, before.as_bytes()).map_err(|e| format!("Failed to write temp file: {e}"))?;
+    let scratch_s = scratch.to_string_lossy().to_string();
+    let mut entries: Vec<(&str, &str)> = vec![("path", args.path.as_str()), ("url", args.url.as_str())];
+    if !args.branch.is_empty() {
+        entries.push(("branch", args.branch.as_str()));
+    }
+    let mut result: Result<(), String> = Ok(());
+    for (field, value) in entries {
+        let key = format!("submodule.{}.{field}", args.name);
+        if let Err(e) = crate::run_git(&repo_path, &["config", "-f", scratch_s.as_str(), key.as_str(), value]) {
+            result = Err(e);
+            break;
+        }
+    }
+    let after = fs::read_to_string(&scratch);
+    let _ = fs::remove_file(&scratch);
+    result?;
+    let after = after.map_err(|e| format!("Failed to read temp file: {e}"))?;
+
+    Ok(GitSubmoduleAddPrediction {
+        name: args.name,
+        path: args.path,
+        gitmodules_before: before,
+        gitmodules_after: after,
+        problems,
+    })
+}
+
+/// Clones `url` into `path` as a new submodule, reporting the clone through
+/// `git_submodule_progress` events.
+#[tauri::command]
+pub(crate) async fn git_submodule_add(
+    app: AppHandle,
+    repo_path: String,
+    url: String,
+    path: String,
+    branch: Option<String>,
+    name: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+        let args = submodule_add_args(url, path, branch, name)?;
+
+        let mut git_args: Vec<&str> = vec!["submodule", "add", "--progress"];
+        if !args.branch.is_empty() {
+            git_args.push("-b");
+            git_args.push(args.branch.as_str());
+        }
+        git_args.push("--name");
+        git_args.push(args.name.as_str());
+        git_args.push("--");
+        git_args.push(args.url.as_str());
+        git_args.push(args.path.as_str());
+
+        let emit = |phase: Option<String>, percent: Option<u32>, message: String| {
+            let _ = app.emit(
+                "git_submodule_progress",
+                GitSubmoduleProgressEvent {
+                    repo_path: repo_path.clone(),
+                    path: args.path.clone(),
+                    index: 0,
+                    total: 1,
+                    phase,
+                    percent,
+                    message,
+                },
+            );
+        };
+
+        crate::with_repo_git_lock(&repo_path, || {
+            emit(None, Some(0), format!("Adding {}", args.path));
+            let mut cmd = crate::git_command_in_repo(&repo_path);
+            cmd.args(git_args.as_slice());
+            let (ok, stderr) = super::clone::run_git_with_progress(cmd, "git submodule add", |phase, pct, message| {
+                emit(Some(phase), Some(pct), message)
+            })?;
+            if !ok {
+                emit(None, None, stderr.clone());
+                return Err(format!("git command failed: {stderr}"));
+            }
+            emit(None, Some(100), format!("Added {}", args.path));
+            Ok(stderr)
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to add submodule: {e}"))?
+}
+
+/// Submodule names end up in paths under `.git/modules`; like git, refuse names that
+/// could point outside it.
+fn is_safe_submodule_name(name: &str) -> bool {
+    !name.is_empty()
+        && !Path::new(name).is_absolute()
+        && !name.starts_with(['/', '\\'])
+        && name.split(['/', '\\']).all(|c| !c.is_empty() && c != "..")
+}
+
+/// The existing repository of submodule `name` under `.git/modules`, resolved through any
+/// symlinks and required to stay inside the modules directory.
+fn submodule_git_dir(repo_path: &str, name: &str) -> Option<PathBuf> {
+    let resolve = |git_path: &str| -> Option<PathBuf> {
+        let dir = PathBuf::from(crate::run_git(repo_path, &["rev-parse", "--git-path", git_path]).ok()?.trim());
+        let dir = if dir.is_absolute() { dir } else { Path::new(repo_path).join(dir) };
+        fs::canonicalize(dir).ok()
+    };
+    let modules = resolve("modules")?;
+    let dir = resolve(format!("modules/{name}").as_str())?;
+    (dir.starts_with(&modules) && dir != modules && dir.is_dir()).then_some(dir)
+}
+
+#[tauri::command]
+pub(crate) fn git_submodule_remove(repo_path: String, path: String) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let path = path.trim().replace('\\', "/").trim_end_matches('/').to_string();
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    crate::ensure_rel_path_safe(&path)?;
+
+    crate::with_repo_git_lock(&repo_path, || {
+        let name = submodule_name_for_path(&repo_path, &path).unwrap_or_else(|| path.clone());
+        if !is_safe_submodule_name(&name) {
+            return Err(format!("Invalid submodule name: {name}"));
+        }
+
+        let _ = crate::run_git(&repo_path, &["submodule", "deinit", "-f", "--", path.as_str()]);
+        let out = crate::run_git(&repo_path, &["rm", "-f", "--", path.as_str()])?;
+
+        // `git rm` keeps the submodule's repository under .git/modules; drop it so the
+        // path can be reused by a different submodule.
+        if let Some(dir) = submodule_git_dir(&repo_path, &name) {
+            let _ = fs::remove_dir_all(&dir);
+        }
+        let section = format!("submodule.{name}");
+        let _ = crate::run_git(&repo_path, &["config", "--remove-section", section.as_str()]);
+
+        Ok(out)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn git_submodule_init(repo_path: String, paths: Option<Vec<String>>) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let paths = normalize_paths(paths);
+    let mut args: Vec<&str> = vec!["submodule", "init", "--"];
+    for p in &paths {
+        args.push(p.as_str());
+    }
+    crate::run_git(&repo_path, args.as_slice())
+}
+
+#[tauri::command]
+pub(crate) fn git_submodule_sync(repo_path: String, recursive: Option<bool>) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let mut args: Vec<&str> = vec!["submodule", "sync"];
+    if recursive.unwrap_or(false) {
+        args.push("--recursive");
+    }
+    crate::run_git(&repo_path, args.as_slice())
+}
+
+/// Updates submodules one at a time so progress can be reported per submodule
+/// (`git_submodule_progress` events).
+#[tauri::command]
+pub(crate) async fn git_submodule_update(
+    app: AppHandle,
+    repo_path: String,
+    paths: Option<Vec<String>>,
+    init: Option<bool>,
+    recursive: Option<bool>,
+    remote: Option<bool>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+
+        let mut paths = normalize_paths(paths);
+        if paths.is_empty() {
+            paths = git_submodule_list(repo_path.clone())?
+                .into_iter()
+                .filter(|s| init.unwrap_or(false) || s.initialized)
+                .map(|s| s.path)
+                .collect();
+        }
+
+        let mut base_args: Vec<&str> = vec!["submodule", "update", "--progress"];
+        if init.unwrap_or(false) {
+            base_args.push("--init");
+        }
+        if recursive.unwrap_or(false) {
+            base_args.push("--recursive");
+        }
+        if remote.unwrap_or(false) {
+            base_args.push("--remote");
+        }
+
+        let total = paths.len() as u32;
+        let mut failed: Vec<String> = Vec::new();
+        for (i, path) in paths.iter().enumerate() {
+            let emit = |phase: Option<String>, percent: Option<u32>, message: String| {
+                let _ = app.emit(
+                    "git_submodule_progress",
+                    GitSubmoduleProgressEvent {
+                        repo_path: repo_path.clone(),
+                        path: path.clone(),
+                        index: i as u32,
+                        total,
+                        phase,
+                        percent,
+                        message,
+                    },
+                );
+            };
+            emit(None, Some(0), format!("Updating {path}"));
+
+            let mut cmd = crate::git_command_in_repo(&repo_path);
+            cmd.args(base_args.as_slice()).arg("--").arg(path.as_str());
+            let (ok, stderr) = super::clone::run_git_with_progress(cmd, "git submodule update", |phase, pct, message| {
+                emit(Some(phase), Some(pct), message)
+            })?;
+
+            if ok {
+                emit(None, Some(100), format!("Updated {path}"));
+            } else {
+                emit(None, None, stderr.clone());
+                failed.push(format!("{path}: {stderr}"));
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err(format!("git submodule update failed:\n{}", failed.join("\n")));
+        }
+        Ok(format!("Updated {total} submodule(s)."))
+    })
+    .await
+    .map_err(|e| format!("Failed to update submodules: {e}"))?
+}
+
+/// Upper bound on the commits `git_submodule_diff` lists.
+const SUBMODULE_DIFF_MAX_COMMITS: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSubmoduleCommit {
+    hash: String,
+    short_hash: String,
+    subject: String,
+    author: String,
+    date: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSubmoduleDiff {
+    path: String,
+    old_commit: Option<String>,
+    new_commit: Option<String>,
+    /// Commits the new pointer has that the old one does not, newest first.
+    added: Vec<GitSubmoduleCommit>,
+    /// Commits only the old pointer has, when the submodule was moved back or sideways.
+    removed: Vec<GitSubmoduleCommit>,
+    /// More commits than `SUBMODULE_DIFF_MAX_COMMITS` differ; the lists are cut.
+    truncated: bool,
+}
+
+/// Commits between two pointers of the submodule at `path`. `old_commit` defaults to the
+/// commit recorded in HEAD and `new_commit` to the one checked out in the submodule, so
+/// without either this shows what staging the submodule would bring in. Commits are
+/// read from the submodule's own repository, which must be checked out.
+#[tauri::command]
+pub(crate) fn git_submodule_diff(
+    repo_path: String,
+    path: String,
+    old_commit: Option<String>,
+    new_commit: Option<String>,
+) -> Result<GitSubmoduleDiff, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let path = path.trim().replace('\\', "/").trim_end_matches('/').to_string();
+    if path.is_empty() {
+        return Err(String::from("path is empty"));
+    }
+    crate::ensure_rel_path_safe(&path)?;
+
+    let sub_dir = Path::new(&repo_path).join(&path);
+    if !sub_dir.join(".git").exists() {
+        return Err(format!("Submodule {path} is not checked out."));
+    }
+    let sub_repo = sub_dir.to_string_lossy().to_string();
+
+    let given = |c: Option<String>| c.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    let old_commit = match given(old_commit) {
+        Some(c) => Some(c),
+        None => crate::run_git(&repo_path, &["rev-parse", "-q", "--verify", format!("HEAD:{path}").as_str()])
+            .ok()
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty()),
+    };
+    let new_commit = match given(new_commit) {
+        Some(c) => Some(c),
+        None => crate::resolve_commit(&sub_repo, "HEAD"),
+    };
+    for c in [&old_commit, &new_commit].into_iter().flatten() {
+        if c.starts_with('-') || crate::resolve_commit(&sub_repo, c).is_none() {
+            return Err(format!("Commit {c} is not available in {path}; fetch the submodule first."));
+        }
+    }
+
+    let range = match (&old_commit, &new_commit) {
+        (Some(old), Some(new)) => format!("{old}...{new}"),
+        (None, Some(new)) => new.clone(),
+        (Some(old), None) => old.clone(),
+        (None, None) => return Err(format!("Submodule {path} has no commits to compare.")),
+    };
+    let limit = format!("-n{}", SUBMODULE_DIFF_MAX_COMMITS + 1);
+    let raw = crate::run_git(
+        &sub_repo,
+        &[
+            "log",
+            "--left-right",
+            "--format=%m%x1f%H%x1f%h%x1f%s%x1f%an%x1f%aI",
+            limit.as_str(),
+            range.as_str(),
+        ],
+    )?;
+
+    let mut added: Vec<GitSubmoduleCommit> = Vec::new();
+    let mut removed: Vec<GitSubmoduleCommit> = Vec::new();
+    let mut truncated = false;
+    for (i, line) in raw.lines().enumerate() {
+        if i >= SUBMODULE_DIFF_MAX_COMMITS {
+            truncated = true;
+            break;
+        }
+        let parts: Vec<&str> = line.split('\u{1f}').collect();
+        let [side, hash, short_hash, subject, author, date] = parts[..] else {
+            continue;
+        };
+        let commit = GitSubmoduleCommit {
+            hash: hash.to_string(),
+            short_hash: short_hash.to_string(),
+            subject: subject.to_string(),
+            author: author.to_string(),
+            date: date.to_string(),
+        };
+        // A single revision lists commits of whichever side exists.
+        let is_removed = if old_commit.is_some() && new_commit.is_some() {
+            side == "<"
+        } else {
+            new_commit.is_none()
+        };
+        if is_removed {
+            removed.push(commit);
+        } else {
+            added.push(commit);
+        }
+    }
+
+    Ok(GitSubmoduleDiff {
+        path,
+        old_commit,
+        new_commit,
+        added,
+        removed,
+        truncated,
+    })
+}
@@ -0,0 +1,266 @@
+// Sync: the one-button "pull then push" for the current branch. Each phase is reported
+// through `sync_progress` events; anything that needs a decision (no upstream, predicted
+// conflicts, a rejected push, ...) stops the sync with a reason instead of guessing.
+
+use serde::Serialize;
+use tauri::Emitter;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitSyncResult {
+    /// "ok" | "up_to_date" | "stopped"
+    status: String,
+    /// Why the sync stopped: "detached_head" | "operation_in_progress" | "no_upstream" |
+    /// "upstream_rewritten" | "diverged" | "conflicts_predicted" | "conflicts" | "push_rejected" |
+    /// "push_failed".
+    reason: Option<String>,
+    message: String,
+    upstream: Option<String>,
+    /// Ahead/behind counts after fetching, before pulling.
+    ahead: u32,
+    behind: u32,
+    /// "fast-forward" | "merge" | "rebase" when something was pulled.
+    pulled: Option<String>,
+    pushed: bool,
+    conflict_files: Vec<String>,
+}
+
+impl GitSyncResult {
+    fn stopped(reason: &str, message: String) -> Self {
+        GitSyncResult {
+            status: String::from("stopped"),
+            reason: Some(reason.to_string()),
+            message,
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+            pulled: None,
+            pushed: false,
+            conflict_files: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SyncProgressEvent {
+    repo_path: String,
+    /// "fetch" | "predict" | "pull" | "push" | "done"
+    phase: String,
+    message: String,
+}
+
+fn emit_phase(app: &tauri::AppHandle, repo_path: &str, phase: &str, message: &str) {
+    let _ = app.emit(
+        "sync_progress",
+        SyncProgressEvent {
+            repo_path: repo_path.to_string(),
+            phase: phase.to_string(),
+            message: message.to_string(),
+        },
+    );
+}
+
+fn config_value(repo_path: &str, key: &str) -> Option<String> {
+    crate::run_git(repo_path, &["config", "--get", key])
+        .ok()
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+}
+
+/// Whether `git pull` would rebase this branch: `branch.<name>.rebase`, then `pull.rebase`.
+fn pulls_with_rebase(repo_path: &str, branch: &str) -> bool {
+    let value = config_value(repo_path, &format!("branch.{branch}.rebase"))
+        .or_else(|| config_value(repo_path, "pull.rebase"));
+    matches!(value.as_deref(), Some("true" | "yes" | "on" | "1" | "merges" | "interactive" | "i" | "m"))
+}
+
+fn ahead_behind(repo_path: &str, upstream: &str) -> (u32, u32) {
+    let raw = crate::run_git(repo_path, &["rev-list", "--left-right", "--count", &format!("{upstream}...HEAD")])
+        .unwrap_or_default();
+    let mut parts = raw.split_whitespace().map(|s| s.parse::<u32>().unwrap_or(0));
+    let behind = parts.next().unwrap_or(0);
+    let ahead = parts.next().unwrap_or(0);
+    (ahead, behind)
+}
+
+fn output_message(stdout: &str, stderr: &str) -> String {
+    if !stdout.trim().is_empty() {
+        stdout.trim().to_string()
+    } else {
+        stderr.trim().to_string()
+    }
+}
+
+fn sync_impl(app: &tauri::AppHandle, repo_path: &str) -> Result<GitSyncResult, String> {
+    if crate::is_merge_in_progress(repo_path) || crate::is_rebase_in_progress(repo_path) {
+        return Ok(GitSyncResult::stopped(
+            "operation_in_progress",
+            String::from("A merge or rebase is in progress. Finish it first."),
+        ));
+    }
+    let Ok(branch) = crate::run_git(repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"]) else {
+        return Ok(GitSyncResult::stopped("detached_head", String::from("Cannot sync a detached HEAD.")));
+    };
+    let Ok(upstream) = crate::run_git(repo_path, &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{upstream}"])
+    else {
+        return Ok(GitSyncResult::stopped(
+            "no_upstream",
+            format!("Branch '{branch}' has no upstream. Push it first to set one."),
+        ));
+    };
+    let remote = config_value(repo_path, &format!("branch.{branch}.remote")).unwrap_or_else(|| String::from("."));
+    let merge_ref = crate::run_git(repo_path, &["config", "--get", &format!("branch.{branch}.merge")]).unwrap_or_default();
+
+    // A local upstream ("." as remote) has nothing to fetch or push.
+    let local_upstream = remote == ".";
+    if !local_upstream {
+        emit_phase(app, repo_path, "fetch", &format!("Fetching {remote}"));
+        let upstream_before = super::upstream::upstream_snapshot(repo_path);
+        let mut cmd = crate::git_command_in_repo(repo_path);
+        cmd.args(["fetch", "--progress", remote.as_str()]);
+        let on_progress = super::clone::transfer_progress_emitter(app, repo_path, "fetch");
+        let (ok, _, stderr) = super::clone::run_git_status_with_progress(cmd, "git fetch", on_progress)?;
+        if !ok {
+            return Err(format!("git fetch failed: {stderr}"));
+        }
+        if super::upstream::detect_force_push(app, repo_path, upstream_before.as_ref()).is_some() {
+            let mut result = GitSyncResult::stopped(
+                "upstream_rewritten",
+                format!("'{upstream}' was force-pushed. Reset onto it or rebase your commits instead of merging."),
+            );
+            result.upstream = Some(upstream.clone());
+            return Ok(result);
+        }
+    }
+
+    emit_phase(app, repo_path, "predict", &format!("Comparing with {upstream}"));
+    let (ahead, behind) = ahead_behind(repo_path, &upstream);
+    let rebase = pulls_with_rebase(repo_path, &branch);
+    let mut result = GitSyncResult {
+        status: String::from("up_to_date"),
+        reason: None,
+        message: format!("'{branch}' is up to date with '{upstream}'."),
+        upstream: Some(upstream.clone()),
+        ahead,
+        behind,
+        pulled: None,
+        pushed: false,
+        conflict_files: Vec::new(),
+    };
+
+    if behind > 0 {
+        if ahead > 0 {
+            if config_value(repo_path, "pull.ff").as_deref() == Some("only") {
+                result.status = String::from("stopped");
+                result.reason = Some(String::from("diverged"));
+                result.message = format!(
+                    "'{branch}' and '{upstream}' have diverged and pull.ff=only allows fast-forwards only."
+                );
+                return Ok(result);
+            }
+            let conflict_files = crate::predict_merge_conflicts(repo_path, &upstream);
+            if !conflict_files.is_empty() {
+                result.status = String::from("stopped");
+                result.reason = Some(String::from("conflicts_predicted"));
+                result.message = format!("Pulling '{upstream}' would conflict. Pull manually to resolve the conflicts.");
+                result.conflict_files = conflict_files;
+                return Ok(result);
+            }
+        }
+
+        let operation = if ahead == 0 {
+            "fast-forward"
+        } else if rebase {
+            "rebase"
+        } else {
+            "merge"
+        };
+        emit_phase(app, repo_path, "pull", &format!("Updating '{branch}' from '{upstream}' ({operation})"));
+        let undo_label = format!("sync with {upstream}");
+        let _undo = super::undo::record_undo_point(repo_path, undo_label.as_str(), &[]);
+
+        let args: Vec<&str> = match operation {
+            "fast-forward" => vec!["merge", "--ff-only", "--autostash", upstream.as_str()],
+            "rebase" => vec!["rebase", "--autostash", upstream.as_str()],
+            _ => vec!["merge", "--no-edit", "--autostash", upstream.as_str()],
+        };
+        let mut cmd = crate::git_command_in_repo(repo_path);
+        cmd.env("GIT_EDITOR", ":");
+        let out = cmd
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to spawn git {}: {e}", args[0]))?;
+        if !out.status.success() {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            let conflict_files = crate::list_unmerged_files(repo_path);
+            if conflict_files.is_empty()
+                && !crate::is_merge_in_progress(repo_path)
+                && !crate::is_rebase_in_progress(repo_path)
+            {
+                return Err(format!("git {} failed: {}", args[0], output_message(&stdout, &stderr)));
+            }
+            result.status = String::from("stopped");
+            result.reason = Some(String::from("conflicts"));
+            result.message = format!("The {operation} stopped with conflicts. Resolve them and push afterwards.");
+            result.pulled = Some(operation.to_string());
+            result.conflict_files = conflict_files;
+            return Ok(result);
+        }
+        result.status = String::from("ok");
+        result.pulled = Some(operation.to_string());
+    }
+
+    let (ahead_now, _) = ahead_behind(repo_path, &upstream);
+    if ahead_now > 0 && !local_upstream {
+        let dst = merge_ref.trim().strip_prefix("refs/heads/").unwrap_or(merge_ref.trim()).to_string();
+        let refspec = format!("HEAD:refs/heads/{dst}");
+        emit_phase(app, repo_path, "push", &format!("Pushing {ahead_now} commit(s) to '{upstream}'"));
+        let mut cmd = crate::git_command_in_repo(repo_path);
+        cmd.args(["push", "--progress", remote.as_str(), refspec.as_str()]);
+        let on_progress = super::clone::transfer_progress_emitter(app, repo_path, "push");
+        let (ok, stdout, stderr) = super::clone::run_git_status_with_progress(cmd, "git push", on_progress)?;
+        if !ok {
+            let rejected = stderr.contains("[rejected]") || stderr.contains("non-fast-forward") || stderr.contains("fetch first");
+            result.status = String::from("stopped");
+            result.reason = Some(String::from(if rejected { "push_rejected" } else { "push_failed" }));
+            result.message = if rejected {
+                format!("'{upstream}' moved while syncing. Sync again to pick up the new commits.")
+            } else {
+                format!("git push failed: {}", output_message(&stdout, &stderr))
+            };
+            return Ok(result);
+        }
+        result.status = String::from("ok");
+        result.pushed = true;
+        result.message = if result.pulled.is_some() {
+            format!("Pulled from and pushed to '{upstream}'.")
+        } else {
+            format!("Pushed {ahead_now} commit(s) to '{upstream}'.")
+        };
+    } else if result.pulled.is_some() {
+        result.message = format!("Pulled from '{upstream}'.");
+    }
+
+    Ok(result)
+}
+
+/// Syncs the current branch with its upstream: fetches, pulls (merging or rebasing as
+/// `git pull` would, with autostash) and pushes when there is something to push. Conflicts
+/// are predicted before pulling, so the sync stops with `conflicts_predicted` rather than
+/// leaving a half-done merge behind.
+#[tauri::command]
+pub(crate) async fn git_sync(app: tauri::AppHandle, repo_path: String) -> Result<GitSyncResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ensure_is_git_worktree(&repo_path)?;
+        super::read_only::ensure_writable(&repo_path)?;
+        let result = crate::with_repo_git_lock(&repo_path, || sync_impl(&app, &repo_path));
+        let done = match &result {
+            Ok(r) => r.message.clone(),
+            Err(e) => e.clone(),
+        };
+        emit_phase(&app, &repo_path, "done", &done);
+        result
+    })
+    .await
+    .map_err(|e| format!("Failed to run sync: {e}"))?
+}
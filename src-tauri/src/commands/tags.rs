@@ -1,5 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
 
 #[derive(Serialize, Clone)]
 pub(crate) struct GitTagTarget {
@@ -52,8 +54,57 @@ fn parse_tag_targets_from_lines(lines: &str) -> Vec<GitTagTarget> {
         .collect()
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct GitTagReleaseOptions {
+    changelog: Option<bool>,
+    archive: Option<bool>,
+    archive_format: Option<String>,
+    output_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitCreateTagResult {
+    output: String,
+    previous_tag: Option<String>,
+    changelog: Option<String>,
+    changelog_path: Option<String>,
+    archive_path: Option<String>,
+}
+
+fn previous_tag_before(repo_path: &str, target: &str) -> Option<String> {
+    let parent = format!("{target}^");
+    crate::run_git(repo_path, &["describe", "--tags", "--abbrev=0", parent.as_str()])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn release_changelog(repo_path: &str, tag: &str, target: &str, previous_tag: Option<&str>) -> Result<String, String> {
+    let range = match previous_tag {
+        Some(prev) => format!("{prev}..{target}"),
+        None => target.to_string(),
+    };
+    let raw = crate::run_git(repo_path, &["log", "--no-merges", "--format=%h%x1f%s", range.as_str()])?;
+    let date = crate::run_git(repo_path, &["log", "-1", "--format=%cs", target]).unwrap_or_default();
+
+    let mut out = format!("## {tag} ({})\n\n", date.trim());
+    for line in raw.lines() {
+        let mut parts = line.splitn(2, '\x1f');
+        let hash = parts.next().unwrap_or_default().trim();
+        let subject = parts.next().unwrap_or_default().trim();
+        if !subject.is_empty() {
+            out.push_str(format!("- {subject} ({hash})\n").as_str());
+        }
+    }
+    if let Some(prev) = previous_tag {
+        out.push_str(format!("\nChanges since {prev}.\n").as_str());
+    }
+    Ok(out)
+}
+
 #[tauri::command]
 #[allow(dead_code)]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn git_create_tag(
     repo_path: String,
     tag: String,
@@ -61,8 +112,11 @@ pub(crate) fn git_create_tag(
     annotated: Option<bool>,
     message: Option<String>,
     force: Option<bool>,
-) -> Result<String, String> {
+    release: Option<GitTagReleaseOptions>,
+    sign: Option<bool>,
+) -> Result<GitCreateTagResult, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let tag = tag.trim().to_string();
     if tag.is_empty() {
@@ -70,10 +124,42 @@ pub(crate) fn git_create_tag(
     }
 
     let target = target.unwrap_or_else(|| String::from("HEAD")).trim().to_string();
-    let annotated = annotated.unwrap_or(false);
-    let message = message.unwrap_or_default().trim().to_string();
+    let sign = sign.unwrap_or(false);
+    // A signature lives in the tag object, so signed tags are always annotated.
+    let annotated = annotated.unwrap_or(false) || sign;
+    let mut message = message.unwrap_or_default().trim().to_string();
     let force = force.unwrap_or(false);
 
+    let release = release.unwrap_or_default();
+    let want_changelog = release.changelog.unwrap_or(false);
+    let want_archive = release.archive.unwrap_or(false);
+    let output_dir = release.output_dir.unwrap_or_default().trim().to_string();
+    if want_archive && output_dir.is_empty() {
+        return Err(String::from("output_dir is empty"));
+    }
+    if !output_dir.is_empty() && !Path::new(&output_dir).is_dir() {
+        return Err(format!("Output folder does not exist: {output_dir}"));
+    }
+    let archive_format = match release.archive_format.unwrap_or_default().trim().to_lowercase().as_str() {
+        "" | "zip" => "zip",
+        "tar.gz" | "tgz" => "tar.gz",
+        "tar" => "tar",
+        _ => return Err(String::from("Invalid archive format. Use: zip, tar or tar.gz.")),
+    };
+
+    let target_rev = if target.is_empty() { String::from("HEAD") } else { target.clone() };
+    let previous_tag = if want_changelog { previous_tag_before(&repo_path, target_rev.as_str()) } else { None };
+    let changelog = if want_changelog {
+        Some(release_changelog(&repo_path, tag.as_str(), target_rev.as_str(), previous_tag.as_deref())?)
+    } else {
+        None
+    };
+
+    // The changelog doubles as the tag message when none was given.
+    let message_from_changelog = annotated && message.is_empty() && changelog.is_some();
+    if message_from_changelog {
+        message = changelog.as_deref().unwrap_or_default().trim().to_string();
+    }
     if annotated && message.is_empty() {
         return Err(String::from("message is empty"));
     }
@@ -86,45 +172,132 @@ pub(crate) fn git_create_tag(
     }
 
     if annotated {
-        args.push("-a");
+        if message_from_changelog {
+            // Keep the markdown headings, which the default cleanup would strip as comments.
+            args.push("--cleanup=whitespace");
+        }
+        args.push(if sign { "-s" } else { "-a" });
         args.push(tag.as_str());
         args.push("-m");
         args.push(message.as_str());
-        if !target.is_empty() {
-            args.push(target.as_str());
-        }
-        return crate::run_git(&repo_path, args.as_slice());
+    } else {
+        args.push(tag.as_str());
     }
-
-    args.push(tag.as_str());
     if !target.is_empty() {
         args.push(target.as_str());
     }
 
-    crate::run_git(&repo_path, args.as_slice())
+    let output = crate::run_git(&repo_path, args.as_slice())?;
+
+    let file_stem = tag.replace(['/', '\\'], "-");
+    let mut changelog_path: Option<String> = None;
+    match changelog.as_ref() {
+        Some(c) if !output_dir.is_empty() => {
+            let p = Path::new(&output_dir).join(format!("{file_stem}-CHANGELOG.md"));
+            fs::write(&p, c).map_err(|e| format!("Failed to write changelog: {e}"))?;
+            changelog_path = Some(p.to_string_lossy().to_string());
+        }
+        _ => {}
+    }
+
+    let mut archive_path: Option<String> = None;
+    if want_archive {
+        let repo_name = Path::new(&repo_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from("repo"));
+        let p = Path::new(&output_dir).join(format!("{repo_name}-{file_stem}.{archive_format}"));
+        let p_s = p.to_string_lossy().to_string();
+        let prefix = format!("--prefix={repo_name}-{file_stem}/");
+        let format_arg = format!("--format={archive_format}");
+        let tag_ref = format!("refs/tags/{tag}");
+        crate::run_git(
+            &repo_path,
+            &["archive", format_arg.as_str(), prefix.as_str(), "-o", p_s.as_str(), tag_ref.as_str()],
+        )
+        .map_err(|e| format!("Tag created, but the archive failed: {e}"))?;
+        archive_path = Some(p_s);
+    }
+
+    Ok(GitCreateTagResult {
+        output,
+        previous_tag,
+        changelog,
+        changelog_path,
+        archive_path,
+    })
 }
 
 #[tauri::command]
 #[allow(dead_code)]
-pub(crate) fn git_delete_tag(repo_path: String, tag: String) -> Result<String, String> {
+pub(crate) fn git_delete_tag(
+    repo_path: String,
+    tag: String,
+    dry_run: Option<bool>,
+) -> Result<crate::GitCommandOutcome, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    if !dry_run.unwrap_or(false) {
+        super::read_only::ensure_writable(&repo_path)?;
+    }
 
     let tag = tag.trim().to_string();
     if tag.is_empty() {
         return Err(String::from("tag is empty"));
     }
 
-    crate::run_git(&repo_path, &["tag", "-d", tag.as_str()])
+    if dry_run.unwrap_or(false) {
+        let full_ref = format!("refs/tags/{tag}");
+        let old = crate::run_git(&repo_path, &["rev-parse", "-q", "--verify", full_ref.as_str()])
+            .map_err(|_| format!("Tag not found: {tag}"))?;
+        let commits_orphaned = match crate::resolve_commit(&repo_path, full_ref.as_str()) {
+            Some(tip) => {
+                let head = crate::resolve_commit(&repo_path, "HEAD").unwrap_or_default();
+                crate::commits_orphaned_by(&repo_path, tip.as_str(), &[full_ref.as_str()], &[head.as_str()])
+            }
+            None => Vec::new(),
+        };
+        return Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+            operation: String::from("tag delete"),
+            ref_updates: vec![crate::GitRefUpdate {
+                name: full_ref,
+                old: Some(old),
+                new: None,
+            }],
+            files_removed: Vec::new(),
+            files_changed: Vec::new(),
+            message: format!("Would delete tag {tag}; {} commit(s) become unreachable.", commits_orphaned.len()),
+            commits_orphaned,
+        }));
+    }
+
+    crate::run_git(&repo_path, &["tag", "-d", tag.as_str()]).map(crate::GitCommandOutcome::Done)
 }
 
 #[tauri::command]
 #[allow(dead_code)]
-pub(crate) fn git_delete_remote_tag(
+pub(crate) async fn git_delete_remote_tag(
     repo_path: String,
     remote_name: Option<String>,
     tag: String,
-) -> Result<String, String> {
+    dry_run: Option<bool>,
+) -> Result<crate::GitCommandOutcome, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        git_delete_remote_tag_impl(repo_path, remote_name, tag, dry_run)
+    })
+    .await
+    .map_err(|e| format!("Failed to delete remote tag: {e}"))?
+}
+
+fn git_delete_remote_tag_impl(
+    repo_path: String,
+    remote_name: Option<String>,
+    tag: String,
+    dry_run: Option<bool>,
+) -> Result<crate::GitCommandOutcome, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    if !dry_run.unwrap_or(false) {
+        super::read_only::ensure_writable(&repo_path)?;
+    }
 
     let remote_name = remote_name.unwrap_or_else(|| String::from("origin"));
     let remote_name = remote_name.trim().to_string();
@@ -137,15 +310,60 @@ pub(crate) fn git_delete_remote_tag(
         return Err(String::from("tag is empty"));
     }
 
+    if dry_run.unwrap_or(false) {
+        // Ask the remote what it has, without changing anything.
+        let full_ref = format!("refs/tags/{tag}");
+        let raw = crate::run_git(&repo_path, &["ls-remote", "--tags", remote_name.as_str(), full_ref.as_str()])?;
+        let old = raw
+            .lines()
+            .find(|l| l.ends_with(full_ref.as_str()))
+            .and_then(|l| l.split_whitespace().next())
+            .map(|s| s.to_string());
+        return Ok(crate::GitCommandOutcome::DryRun(crate::DryRunReport {
+            operation: String::from("push --delete"),
+            message: if old.is_some() {
+                format!("Would delete tag {tag} on {remote_name}.")
+            } else {
+                format!("Tag {tag} does not exist on {remote_name}; nothing would change.")
+            },
+            ref_updates: match old {
+                Some(old) => vec![crate::GitRefUpdate {
+                    name: format!("{remote_name}:{full_ref}"),
+                    old: Some(old),
+                    new: None,
+                }],
+                None => Vec::new(),
+            },
+            files_removed: Vec::new(),
+            files_changed: Vec::new(),
+            commits_orphaned: Vec::new(),
+        }));
+    }
+
     crate::run_git(
         &repo_path,
         &["push", remote_name.as_str(), "--delete", tag.as_str()],
     )
+    .map(crate::GitCommandOutcome::Done)
 }
 
 #[tauri::command]
 #[allow(dead_code)]
-pub(crate) fn git_rename_tag(
+pub(crate) async fn git_rename_tag(
+    repo_path: String,
+    old_tag: String,
+    new_tag: String,
+    rename_on_remote: Option<bool>,
+    remote_name: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        git_rename_tag_impl(repo_path, old_tag, new_tag, rename_on_remote, remote_name)
+    })
+    .await
+    .map_err(|e| format!("Failed to rename tag: {e}"))?
+}
+
+fn git_rename_tag_impl(
     repo_path: String,
     old_tag: String,
     new_tag: String,
@@ -153,6 +371,7 @@ pub(crate) fn git_rename_tag(
     remote_name: Option<String>,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let old_tag = old_tag.trim().to_string();
     if old_tag.is_empty() {
@@ -243,7 +462,18 @@ pub(crate) fn git_list_tag_targets(repo_path: String) -> Result<Vec<GitTagTarget
 
 #[tauri::command]
 #[allow(dead_code)]
-pub(crate) fn git_list_remote_tag_targets(
+pub(crate) async fn git_list_remote_tag_targets(
+    repo_path: String,
+    remote_name: Option<String>,
+) -> Result<Vec<GitTagTarget>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        git_list_remote_tag_targets_impl(repo_path, remote_name)
+    })
+    .await
+    .map_err(|e| format!("Failed to list remote tags: {e}"))?
+}
+
+fn git_list_remote_tag_targets_impl(
     repo_path: String,
     remote_name: Option<String>,
 ) -> Result<Vec<GitTagTarget>, String> {
@@ -260,13 +490,27 @@ pub(crate) fn git_list_remote_tag_targets(
 
 #[tauri::command]
 #[allow(dead_code)]
-pub(crate) fn git_push_tags(
+pub(crate) async fn git_push_tags(
+    repo_path: String,
+    remote_name: Option<String>,
+    tags: Vec<String>,
+    force: Option<bool>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        git_push_tags_impl(repo_path, remote_name, tags, force)
+    })
+    .await
+    .map_err(|e| format!("Failed to push tags: {e}"))?
+}
+
+fn git_push_tags_impl(
     repo_path: String,
     remote_name: Option<String>,
     tags: Vec<String>,
     force: Option<bool>,
 ) -> Result<String, String> {
     crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
 
     let remote_name = remote_name.unwrap_or_else(|| String::from("origin"));
     let remote_name = remote_name.trim().to_string();
@@ -297,3 +541,416 @@ pub(crate) fn git_push_tags(
     let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
     crate::run_git(&repo_path, args_ref.as_slice())
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitReleaseCommit {
+    hash: String,
+    short_hash: String,
+    author_name: String,
+    author_email: String,
+    date: String,
+    subject: String,
+    is_merge: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitReleaseContributor {
+    name: String,
+    email: String,
+    commit_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitReleaseFileNode {
+    name: String,
+    path: String,
+    is_dir: bool,
+    /// Status letter for files ("A", "M", "D", "R", ...); empty for directories.
+    status: String,
+    old_path: Option<String>,
+    /// Summed over the files below for directories; 0 for binary files.
+    additions: u64,
+    deletions: u64,
+    binary: bool,
+    children: Vec<GitReleaseFileNode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitReleaseComparison {
+    tag_a: String,
+    tag_b: String,
+    commit_a: String,
+    commit_b: String,
+    commits: Vec<GitReleaseCommit>,
+    contributors: Vec<GitReleaseContributor>,
+    files_changed: u32,
+    additions: u64,
+    deletions: u64,
+    files: GitReleaseFileNode,
+}
+
+fn resolve_tag_commit(repo_path: &str, tag: &str) -> Result<String, String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(String::from("tag is empty"));
+    }
+    let spec = format!("refs/tags/{tag}^{{commit}}");
+    crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", spec.as_str()])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Tag '{tag}' not found."))
+}
+
+fn release_commits(repo_path: &str, range: &str) -> Result<Vec<GitReleaseCommit>, String> {
+    // %aN/%aE apply .mailmap so contributors are not split across identities.
+    let raw = crate::run_git(
+        repo_path,
+        &["log", "--format=%H%x1f%h%x1f%aN%x1f%aE%x1f%aI%x1f%P%x1f%s", range],
+    )?;
+    let mut out: Vec<GitReleaseCommit> = Vec::new();
+    for line in raw.lines() {
+        let parts: Vec<&str> = line.splitn(7, '\x1f').collect();
+        if parts.len() < 7 {
+            continue;
+        }
+        out.push(GitReleaseCommit {
+            hash: parts[0].to_string(),
+            short_hash: parts[1].to_string(),
+            author_name: parts[2].to_string(),
+            author_email: parts[3].to_string(),
+            date: parts[4].to_string(),
+            is_merge: parts[5].split_whitespace().count() > 1,
+            subject: parts[6].to_string(),
+        });
+    }
+    Ok(out)
+}
+
+fn release_contributors(commits: &[GitReleaseCommit]) -> Vec<GitReleaseContributor> {
+    let mut by_email: BTreeMap<String, GitReleaseContributor> = BTreeMap::new();
+    for c in commits {
+        by_email
+            .entry(c.author_email.to_lowercase())
+            .or_insert_with(|| GitReleaseContributor {
+                name: c.author_name.clone(),
+                email: c.author_email.clone(),
+                commit_count: 0,
+            })
+            .commit_count += 1;
+    }
+    let mut out: Vec<GitReleaseContributor> = by_email.into_values().collect();
+    out.sort_by(|a, b| b.commit_count.cmp(&a.commit_count).then_with(|| a.name.cmp(&b.name)));
+    out
+}
+
+struct ReleaseFileChange {
+    path: String,
+    old_path: Option<String>,
+    status: String,
+    additions: u64,
+    deletions: u64,
+    binary: bool,
+}
+
+fn release_file_changes(repo_path: &str, a: &str, b: &str) -> Result<Vec<ReleaseFileChange>, String> {
+    // -z keeps unusual paths intact: "<status>\0<path>\0" or "R<score>\0<old>\0<new>\0".
+    let names = crate::run_git_stdout_raw(repo_path, &["diff", "--name-status", "-z", "-M", a, b])?;
+    let mut changes: Vec<ReleaseFileChange> = Vec::new();
+    let mut fields = names.split('\0').filter(|f| !f.is_empty());
+    while let Some(status) = fields.next() {
+        let status = status.chars().next().map(String::from).unwrap_or_default();
+        let first = fields.next().unwrap_or_default().to_string();
+        let (path, old_path) = if status == "R" || status == "C" {
+            (fields.next().unwrap_or_default().to_string(), Some(first))
+        } else {
+            (first, None)
+        };
+        changes.push(ReleaseFileChange {
+            path,
+            old_path,
+            status,
+            additions: 0,
+            deletions: 0,
+            binary: false,
+        });
+    }
+
+    let numstat = crate::run_git_stdout_raw(repo_path, &["diff", "--numstat", "-z", "-M", a, b])?;
+    let stats: BTreeMap<String, super::diff::GitNumstatEntry> = super::diff::parse_numstat_z(&numstat)
+        .into_iter()
+        .map(|e| (e.path.clone(), e))
+        .collect();
+    for c in &mut changes {
+        if let Some(stat) = stats.get(&c.path) {
+            c.additions = stat.additions;
+            c.deletions = stat.deletions;
+            c.binary = stat.binary;
+        }
+    }
+    Ok(changes)
+}
+
+fn insert_file_node(root: &mut GitReleaseFileNode, change: &ReleaseFileChange) {
+    let mut node = root;
+    node.additions += change.additions;
+    node.deletions += change.deletions;
+    let segments: Vec<&str> = change.path.split('/').collect();
+    for (i, segment) in segments.iter().enumerate() {
+        let is_dir = i + 1 < segments.len();
+        let path = segments[..=i].join("/");
+        let idx = match node.children.iter().position(|c| c.name == *segment && c.is_dir == is_dir) {
+            Some(idx) => idx,
+            None => {
+                node.children.push(GitReleaseFileNode {
+                    name: segment.to_string(),
+                    path,
+                    is_dir,
+                    status: String::new(),
+                    old_path: None,
+                    additions: 0,
+                    deletions: 0,
+                    binary: false,
+                    children: Vec::new(),
+                });
+                node.children.len() - 1
+            }
+        };
+        node = &mut node.children[idx];
+        node.additions += change.additions;
+        node.deletions += change.deletions;
+        if !is_dir {
+            node.status = change.status.clone();
+            node.old_path = change.old_path.clone();
+            node.binary = change.binary;
+        }
+    }
+}
+
+fn sort_file_nodes(node: &mut GitReleaseFileNode) {
+    node.children
+        .sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    for child in &mut node.children {
+        sort_file_nodes(child);
+    }
+}
+
+/// Everything between two tags for a release report: the commits reachable from `tag_b`
+/// but not `tag_a`, who wrote them, and the files changed between the two tagged trees
+/// as a directory tree with line counts.
+#[tauri::command]
+pub(crate) fn compare_releases(repo_path: String, tag_a: String, tag_b: String) -> Result<GitReleaseComparison, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let tag_a = tag_a.trim().to_string();
+    let tag_b = tag_b.trim().to_string();
+    let commit_a = resolve_tag_commit(&repo_path, &tag_a)?;
+    let commit_b = resolve_tag_commit(&repo_path, &tag_b)?;
+
+    let range = format!("{commit_a}..{commit_b}");
+    let commits = release_commits(&repo_path, range.as_str())?;
+    let contributors = release_contributors(&commits);
+
+    let changes = release_file_changes(&repo_path, &commit_a, &commit_b)?;
+    let mut files = GitReleaseFileNode {
+        name: String::new(),
+        path: String::new(),
+        is_dir: true,
+        status: String::new(),
+        old_path: None,
+        additions: 0,
+        deletions: 0,
+        binary: false,
+        children: Vec::new(),
+    };
+    for change in &changes {
+        insert_file_node(&mut files, change);
+    }
+    sort_file_nodes(&mut files);
+
+    Ok(GitReleaseComparison {
+        tag_a,
+        tag_b,
+        commit_a,
+        commit_b,
+        commits,
+        contributors,
+        files_changed: changes.len() as u32,
+        additions: files.additions,
+        deletions: files.deletions,
+        files,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitVersionSuggestion {
+    latest_tag: Option<String>,
+    latest_version: Option<String>,
+    /// "major" | "minor" | "patch"
+    bump: String,
+    next_version: String,
+    /// `next_version` with the latest tag's prefix, e.g. "v1.3.0".
+    next_tag: String,
+    /// Commits since the latest tag; 0 when HEAD is tagged already.
+    commit_count: u32,
+    breaking_changes: Vec<String>,
+    features: Vec<String>,
+    fixes: Vec<String>,
+}
+
+/// `[prefix]MAJOR.MINOR.PATCH` without pre-release or build suffix, e.g. "v1.2.3".
+fn parse_release_version(tag: &str) -> Option<(String, [u64; 3])> {
+    let start = tag.find(|c: char| c.is_ascii_digit())?;
+    let (prefix, version) = tag.split_at(start);
+    if !prefix.is_empty() && !prefix.ends_with(['v', 'V', '-', '/', '_']) {
+        return None;
+    }
+    let mut parts = version.split('.');
+    let mut out = [0u64; 3];
+    for slot in out.iter_mut() {
+        *slot = parts.next()?.parse().ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((prefix.to_string(), out))
+}
+
+/// The bump `scheme` asks for and the version it leads to from `base`, the latest
+/// release (None without one).
+fn next_release_version(base: Option<[u64; 3]>, scheme: &str, breaking: bool, feature: bool) -> (&'static str, [u64; 3]) {
+    let bump = match scheme {
+        "major" => "major",
+        "minor" => "minor",
+        "patch" => "patch",
+        _ if base.is_none() => "minor",
+        _ if breaking => "major",
+        _ if feature => "minor",
+        _ => "patch",
+    };
+    let [major, minor, patch] = base.unwrap_or([0, 0, 0]);
+    let bump = if bump == "major" && major == 0 && scheme == "conventional" { "minor" } else { bump };
+    let next = match bump {
+        "major" => [major + 1, 0, 0],
+        "minor" => [major, minor + 1, 0],
+        _ => [major, minor, patch + 1],
+    };
+    (bump, next)
+}
+
+/// Suggests the next release version. The latest semver tag reachable from HEAD is the
+/// base; with the default "conventional" scheme the commits since it decide the bump:
+/// a breaking change (`feat!:` or a `BREAKING CHANGE:` footer) is major, a `feat` minor
+/// and anything else patch. While the major version is 0, breaking changes bump the
+/// minor version, and without any version tag the suggestion is v0.1.0. The schemes
+/// "major", "minor" and "patch" force that bump.
+#[tauri::command]
+pub(crate) fn suggest_next_version(repo_path: String, scheme: Option<String>) -> Result<GitVersionSuggestion, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let scheme = scheme.unwrap_or_default().trim().to_lowercase();
+    let scheme = if scheme.is_empty() { String::from("conventional") } else { scheme };
+    if !matches!(scheme.as_str(), "conventional" | "major" | "minor" | "patch") {
+        return Err(String::from("Invalid scheme. Use: conventional, major, minor or patch."));
+    }
+
+    let has_head = crate::run_git(&repo_path, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok();
+    let tags = if has_head {
+        crate::run_git(&repo_path, &["tag", "--list", "--merged", "HEAD"])?
+    } else {
+        String::new()
+    };
+    let latest = tags
+        .lines()
+        .map(|t| t.trim())
+        .filter_map(|t| parse_release_version(t).map(|(prefix, v)| (v, prefix, t.to_string())))
+        .max_by(|a, b| a.0.cmp(&b.0));
+
+    let mut breaking_changes: Vec<String> = Vec::new();
+    let mut features: Vec<String> = Vec::new();
+    let mut fixes: Vec<String> = Vec::new();
+    let mut commit_count = 0u32;
+    if has_head {
+        let range = match &latest {
+            Some((_, _, tag)) => format!("refs/tags/{tag}..HEAD"),
+            None => String::from("HEAD"),
+        };
+        let raw = crate::run_git(&repo_path, &["log", "--no-merges", "--format=%s%x1f%b%x1e", range.as_str()])?;
+        for record in raw.split('\x1e') {
+            let record = record.trim_start_matches('\n');
+            if record.trim().is_empty() {
+                continue;
+            }
+            commit_count += 1;
+            let (subject, body) = record.split_once('\x1f').unwrap_or((record, ""));
+            let subject = subject.trim().to_string();
+            let header = super::commit_lint::parse_header(&subject);
+            let breaking_footer = body
+                .lines()
+                .any(|l| l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:"));
+            if header.as_ref().is_some_and(|h| h.breaking) || breaking_footer {
+                breaking_changes.push(subject);
+            } else {
+                match header.as_ref().map(|h| h.commit_type.to_lowercase()).as_deref() {
+                    Some("feat") => features.push(subject),
+                    Some("fix") => fixes.push(subject),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let prefix = latest.as_ref().map(|(_, prefix, _)| prefix.clone()).unwrap_or_else(|| String::from("v"));
+    let (bump, next) = next_release_version(
+        latest.as_ref().map(|(v, _, _)| *v),
+        scheme.as_str(),
+        !breaking_changes.is_empty(),
+        !features.is_empty(),
+    );
+    let next_version = format!("{}.{}.{}", next[0], next[1], next[2]);
+
+    Ok(GitVersionSuggestion {
+        latest_tag: latest.as_ref().map(|(_, _, tag)| tag.clone()),
+        latest_version: latest.as_ref().map(|(v, _, _)| format!("{}.{}.{}", v[0], v[1], v[2])),
+        bump: bump.to_string(),
+        next_tag: format!("{prefix}{next_version}"),
+        next_version,
+        commit_count,
+        breaking_changes,
+        features,
+        fixes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_release_version_accepts_prefixed_plain_versions() {
+        assert_eq!(parse_release_version("v1.2.3"), Some((String::from("v"), [1, 2, 3])));
+        assert_eq!(parse_release_version("release/10.0.1"), Some((String::from("release/"), [10, 0, 1])));
+        assert_eq!(parse_release_version("0.4.0"), Some((String::new(), [0, 4, 0])));
+        assert_eq!(parse_release_version("v1.2"), None);
+        assert_eq!(parse_release_version("v1.2.3-rc1"), None);
+        assert_eq!(parse_release_version("v1.2.3.4"), None);
+        assert_eq!(parse_release_version("build42.0.0"), None);
+    }
+
+    #[test]
+    fn test_next_release_version_follows_conventional_commits() {
+        assert_eq!(next_release_version(Some([1, 2, 3]), "conventional", true, true), ("major", [2, 0, 0]));
+        assert_eq!(next_release_version(Some([1, 2, 3]), "conventional", false, true), ("minor", [1, 3, 0]));
+        assert_eq!(next_release_version(Some([1, 2, 3]), "conventional", false, false), ("patch", [1, 2, 4]));
+        // Breaking changes stay minor bumps before 1.0.
+        assert_eq!(next_release_version(Some([0, 3, 1]), "conventional", true, false), ("minor", [0, 4, 0]));
+        assert_eq!(next_release_version(None, "conventional", true, false), ("minor", [0, 1, 0]));
+    }
+
+    #[test]
+    fn test_next_release_version_forced_scheme() {
+        assert_eq!(next_release_version(Some([0, 3, 1]), "major", false, false), ("major", [1, 0, 0]));
+        assert_eq!(next_release_version(Some([1, 2, 3]), "patch", true, true), ("patch", [1, 2, 4]));
+        assert_eq!(next_release_version(None, "patch", false, false), ("patch", [0, 0, 1]));
+    }
+}
@@ -0,0 +1,418 @@
+use serde::Serialize;
+
+// Undo points: before a destructive operation, the state it is about to change
+// (HEAD, the refs it touches and a `stash create` of local changes) is written
+// into a snapshot commit. The snapshot's parents keep those objects alive and
+// each snapshot is an entry in the reflog of UNDO_REF, newest first. Once the
+// operation returns, the snapshot is rewritten with where HEAD and those refs ended
+// up, so an undo can tell whether anything moved them since.
+
+const UNDO_REF: &str = "refs/graphoria/undo";
+const SNAPSHOT_SUBJECT_PREFIX: &str = "graphoria undo: ";
+const ZERO_OID: &str = "0000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitUndoRef {
+    name: String,
+    target: Option<String>,
+    /// Where the operation left the ref; None when it was deleted.
+    after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitUndoPoint {
+    id: String,
+    index: u32,
+    created_at: u64,
+    operation: String,
+    head: String,
+    head_ref: Option<String>,
+    stash: Option<String>,
+    refs: Vec<GitUndoRef>,
+    /// HEAD after the operation; None while it is still running.
+    head_after: Option<String>,
+}
+
+fn oid_or_none(oid: &str) -> Option<String> {
+    if oid.is_empty() || oid == ZERO_OID {
+        None
+    } else {
+        Some(oid.to_string())
+    }
+}
+
+fn parse_snapshot(id: &str, index: u32, created_at: u64, body: &str) -> Option<GitUndoPoint> {
+    let mut lines = body.lines();
+    let operation = lines.next()?.strip_prefix(SNAPSHOT_SUBJECT_PREFIX)?.trim().to_string();
+
+    let mut head = String::new();
+    let mut head_ref: Option<String> = None;
+    let mut stash: Option<String> = None;
+    let mut refs: Vec<GitUndoRef> = Vec::new();
+    let mut head_after: Option<String> = None;
+    for line in lines {
+        let line = line.trim();
+        if let Some(v) = line.strip_prefix("after-head ") {
+            head_after = Some(v.trim().to_string());
+        } else if let Some(v) = line.strip_prefix("after-ref ") {
+            let mut parts = v.split_whitespace();
+            let name = parts.next().unwrap_or_default();
+            let after = oid_or_none(parts.next().unwrap_or_default());
+            match refs.iter_mut().find(|r| r.name == name) {
+                Some(r) => r.after = after,
+                None if head_ref.as_deref() == Some(name) => refs.push(GitUndoRef {
+                    name: name.to_string(),
+                    target: Some(head.clone()),
+                    after,
+                }),
+                None => {}
+            }
+        } else if let Some(v) = line.strip_prefix("head ") {
+            head = v.trim().to_string();
+        } else if let Some(v) = line.strip_prefix("head-ref ") {
+            head_ref = Some(v.trim().to_string());
+        } else if let Some(v) = line.strip_prefix("stash ") {
+            stash = Some(v.trim().to_string());
+        } else if let Some(v) = line.strip_prefix("ref ") {
+            let mut parts = v.split_whitespace();
+            let name = parts.next().unwrap_or_default().to_string();
+            let target = parts.next().unwrap_or_default();
+            if name.is_empty() {
+                continue;
+            }
+            refs.push(GitUndoRef {
+                name,
+                target: oid_or_none(target),
+                after: None,
+            });
+        }
+    }
+
+    if head.is_empty() {
+        return None;
+    }
+
+    Some(GitUndoPoint {
+        id: id.to_string(),
+        index,
+        created_at,
+        operation,
+        head,
+        head_ref,
+        stash,
+        refs,
+        head_after,
+    })
+}
+
+fn list_undo_points(repo_path: &str, max_count: u32) -> Vec<GitUndoPoint> {
+    if crate::run_git(repo_path, &["rev-parse", "-q", "--verify", UNDO_REF]).is_err() {
+        return Vec::new();
+    }
+
+    let max_count_s = max_count.to_string();
+    let raw = crate::run_git_stdout_raw(
+        repo_path,
+        &[
+            "log",
+            "-g",
+            "--no-color",
+            "-n",
+            max_count_s.as_str(),
+            "--format=%H%x1f%ct%x1f%B%x1e",
+            UNDO_REF,
+        ],
+    )
+    .unwrap_or_default();
+
+    let mut out: Vec<GitUndoPoint> = Vec::new();
+    for (i, rec) in raw.split('\x1e').map(|r| r.trim_start_matches('\n')).filter(|r| !r.trim().is_empty()).enumerate() {
+        let mut fields = rec.splitn(3, '\x1f');
+        let id = fields.next().unwrap_or_default().trim();
+        let created_at = fields.next().unwrap_or_default().trim().parse::<u64>().unwrap_or(0);
+        let body = fields.next().unwrap_or_default();
+        if let Some(p) = parse_snapshot(id, i as u32, created_at, body) {
+            out.push(p);
+        }
+    }
+    out
+}
+
+/// An undo point whose operation is still running. Dropping it records where HEAD and
+/// the touched refs ended up, so hold it until the operation has returned.
+pub(crate) struct PendingUndoPoint {
+    repo_path: String,
+    snapshot: String,
+}
+
+impl Drop for PendingUndoPoint {
+    fn drop(&mut self) {
+        let _ = complete_undo_point(&self.repo_path, &self.snapshot);
+    }
+}
+
+/// Rewrites `snapshot` with the post-operation tips of HEAD and the refs it names. Skipped
+/// when a newer undo point was recorded in the meantime.
+fn complete_undo_point(repo_path: &str, snapshot: &str) -> Result<(), String> {
+    let latest = crate::run_git(repo_path, &["rev-parse", "-q", "--verify", UNDO_REF])?;
+    if latest.trim() != snapshot {
+        return Ok(());
+    }
+    let Some(point) = list_undo_points(repo_path, 1).into_iter().next() else {
+        return Ok(());
+    };
+    let Some(head_after) = crate::resolve_commit(repo_path, "HEAD") else {
+        return Ok(());
+    };
+
+    let body = crate::run_git_stdout_raw(repo_path, &["log", "-1", "--format=%B", snapshot])?;
+    let mut message = format!("{}\nafter-head {head_after}\n", body.trim_end());
+    let mut names: Vec<&str> = point.refs.iter().map(|r| r.name.as_str()).collect();
+    if let Some(head_ref) = point.head_ref.as_deref() {
+        names.push(head_ref);
+    }
+    for name in names {
+        let after = crate::resolve_commit(repo_path, name).unwrap_or_else(|| ZERO_OID.to_string());
+        message.push_str(format!("after-ref {name} {after}\n").as_str());
+    }
+
+    let parents = crate::run_git(repo_path, &["log", "-1", "--format=%P", snapshot])?;
+    let tree = format!("{snapshot}^{{tree}}");
+    let mut args: Vec<&str> = vec![
+        "-c",
+        "user.name=Graphoria",
+        "-c",
+        "user.email=graphoria@localhost",
+        "-c",
+        "commit.gpgsign=false",
+        "commit-tree",
+        tree.as_str(),
+    ];
+    for p in parents.split_whitespace() {
+        args.push("-p");
+        args.push(p);
+    }
+    args.push("-m");
+    args.push(message.as_str());
+    let completed = crate::run_git(repo_path, args.as_slice())?;
+
+    drop_latest_undo_point(repo_path)?;
+    let reflog_message = format!("{SNAPSHOT_SUBJECT_PREFIX}{}", point.operation);
+    crate::run_git(
+        repo_path,
+        &["update-ref", "--create-reflog", "-m", reflog_message.as_str(), UNDO_REF, completed.trim()],
+    )?;
+    Ok(())
+}
+
+/// Records an undo point before `operation` runs. `extra_refs` are full ref names the
+/// operation may move or delete besides the current branch. Returns `None` when there is
+/// nothing to record yet (unborn HEAD); otherwise keep the returned point alive until the
+/// operation has finished.
+pub(crate) fn record_undo_point(
+    repo_path: &str,
+    operation: &str,
+    extra_refs: &[&str],
+) -> Result<Option<PendingUndoPoint>, String> {
+    let Some(head) = crate::resolve_commit(repo_path, "HEAD") else {
+        return Ok(None);
+    };
+    let head_ref = crate::run_git(repo_path, &["symbolic-ref", "--quiet", "HEAD"])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let stash = crate::run_git(repo_path, &["stash", "create"])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let mut message = format!("{SNAPSHOT_SUBJECT_PREFIX}{operation}\n\nhead {head}\n");
+    let mut parents: Vec<String> = vec![head.clone()];
+    if let Some(r) = head_ref.as_ref() {
+        message.push_str(format!("head-ref {r}\n").as_str());
+    }
+    if let Some(s) = stash.as_ref() {
+        message.push_str(format!("stash {s}\n").as_str());
+        parents.push(s.clone());
+    }
+    for name in extra_refs.iter().map(|r| r.trim()).filter(|r| !r.is_empty()) {
+        if head_ref.as_deref() == Some(name) {
+            continue;
+        }
+        match crate::resolve_commit(repo_path, name) {
+            Some(target) => {
+                message.push_str(format!("ref {name} {target}\n").as_str());
+                if !parents.contains(&target) {
+                    parents.push(target);
+                }
+            }
+            None => message.push_str(format!("ref {name} {ZERO_OID}\n").as_str()),
+        }
+    }
+
+    let empty_tree = crate::run_git_with_stdin(repo_path, &["mktree"], "")?;
+    let empty_tree = empty_tree.trim().to_string();
+
+    let mut args: Vec<&str> = vec![
+        "-c",
+        "user.name=Graphoria",
+        "-c",
+        "user.email=graphoria@localhost",
+        "-c",
+        "commit.gpgsign=false",
+        "commit-tree",
+        empty_tree.as_str(),
+    ];
+    for p in &parents {
+        args.push("-p");
+        args.push(p.as_str());
+    }
+    args.push("-m");
+    args.push(message.as_str());
+    let snapshot = crate::run_git(repo_path, args.as_slice())?;
+    let snapshot = snapshot.trim().to_string();
+
+    let reflog_message = format!("{SNAPSHOT_SUBJECT_PREFIX}{operation}");
+    crate::run_git(
+        repo_path,
+        &["update-ref", "--create-reflog", "-m", reflog_message.as_str(), UNDO_REF, snapshot.as_str()],
+    )?;
+
+    Ok(Some(PendingUndoPoint {
+        repo_path: repo_path.to_string(),
+        snapshot,
+    }))
+}
+
+fn drop_latest_undo_point(repo_path: &str) -> Result<(), String> {
+    let count = crate::run_git(repo_path, &["log", "-g", "--format=%H", UNDO_REF])
+        .unwrap_or_default()
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .count();
+    if count <= 1 {
+        crate::run_git(repo_path, &["update-ref", "-d", UNDO_REF])?;
+    } else {
+        let entry = format!("{UNDO_REF}@{{0}}");
+        crate::run_git(repo_path, &["reflog", "delete", "--updateref", "--rewrite", entry.as_str()])?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn git_list_undo_points(repo_path: String, max_count: Option<u32>) -> Result<Vec<GitUndoPoint>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let max_count = max_count.unwrap_or(50).clamp(1, 500);
+    Ok(list_undo_points(&repo_path, max_count))
+}
+
+#[tauri::command]
+pub(crate) fn git_undo_last_operation(repo_path: String, force: Option<bool>) -> Result<String, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    crate::with_repo_git_lock(&repo_path, || {
+        let Some(point) = list_undo_points(&repo_path, 1).into_iter().next() else {
+            return Err(String::from("Nothing to undo."));
+        };
+
+        if crate::is_merge_in_progress(&repo_path)
+            || crate::is_rebase_in_progress(&repo_path)
+            || crate::is_cherry_pick_in_progress(&repo_path)
+            || crate::is_revert_in_progress(&repo_path)
+        {
+            return Err(String::from("Finish or abort the operation in progress before undoing."));
+        }
+
+        let Some(head_after) = point.head_after.as_deref() else {
+            return Err(format!(
+                "The undo point for {} was not completed, so it cannot be checked safely.",
+                point.operation
+            ));
+        };
+        let head_now = crate::resolve_commit(&repo_path, "HEAD").unwrap_or_default();
+        if head_now != head_after {
+            return Err(format!(
+                "HEAD has moved since {}. Undoing it now would drop the newer commits.",
+                point.operation
+            ));
+        }
+        for r in &point.refs {
+            if crate::resolve_commit(&repo_path, r.name.as_str()) != r.after {
+                return Err(format!(
+                    "{} has moved since {}. Undoing it now would drop the newer commits.",
+                    r.name, point.operation
+                ));
+            }
+        }
+
+        let moves_head = point.head != head_after;
+        if moves_head {
+            let dirty = crate::run_git(&repo_path, &["status", "--porcelain", "--untracked-files=no"]).unwrap_or_default();
+            if !dirty.trim().is_empty() {
+                if !force.unwrap_or(false) {
+                    return Err(String::from(
+                        "Working tree has local changes. Commit or stash them before undoing, or force the undo.",
+                    ));
+                }
+                // Keep a copy of what the hard reset below throws away.
+                let current = crate::run_git(&repo_path, &["stash", "create"])?;
+                if !current.trim().is_empty() {
+                    crate::run_git(
+                        &repo_path,
+                        &["stash", "store", "-m", "graphoria: local changes discarded by undo", current.trim()],
+                    )?;
+                }
+            }
+        }
+
+        // Only refs the operation actually moved are put back, each from where it left them.
+        for r in point.refs.iter().filter(|r| r.target != r.after) {
+            let old = r.after.as_deref().unwrap_or(ZERO_OID);
+            match r.target.as_ref() {
+                Some(t) => {
+                    crate::run_git(&repo_path, &["update-ref", r.name.as_str(), t.as_str(), old])?;
+                }
+                None => {
+                    crate::run_git(&repo_path, &["update-ref", "-d", r.name.as_str(), old])?;
+                }
+            }
+        }
+
+        if moves_head {
+            match point.head_ref.as_ref() {
+                Some(head_ref) => {
+                    crate::run_git(&repo_path, &["symbolic-ref", "HEAD", head_ref.as_str()])?;
+                }
+                None => {
+                    crate::run_git(&repo_path, &["update-ref", "--no-deref", "HEAD", point.head.as_str()])?;
+                }
+            }
+            crate::run_git(&repo_path, &["reset", "--hard", "--quiet"])?;
+        } else if let Some(head_ref) = point.head_ref.as_ref() {
+            crate::run_git(&repo_path, &["symbolic-ref", "HEAD", head_ref.as_str()])?;
+        }
+
+        let mut message = format!("Undid {}.", point.operation);
+        if let Some(stash) = point.stash.as_ref().filter(|_| moves_head) {
+            let applied = crate::run_git(&repo_path, &["stash", "apply", "--index", stash.as_str()]).is_ok()
+                || crate::run_git(&repo_path, &["stash", "apply", stash.as_str()]).is_ok();
+            if !applied {
+                // Keep the changes reachable from the stash list rather than losing them.
+                let _ = crate::run_git(&repo_path, &["reset", "--hard", "--quiet"]);
+                let _ = crate::run_git(
+                    &repo_path,
+                    &["stash", "store", "-m", "graphoria: local changes before undo", stash.as_str()],
+                );
+                message = format!(
+                    "Undid {}, but local changes could not be re-applied; they were saved to the stash list.",
+                    point.operation
+                );
+            }
+        }
+
+        drop_latest_undo_point(&repo_path)?;
+        Ok(message)
+    })
+}
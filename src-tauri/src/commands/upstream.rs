@@ -0,0 +1,320 @@
+// Upstream rewrites. When a fetch moves the current branch's upstream to a commit that
+// does not contain the old one, the upstream was force-pushed; merging it would bring the
+// replaced commits back. Fetches report that through an `upstream_force_pushed` event and
+// `git_reset_to_upstream` offers the way out, keeping local commits in a backup ref.
+// `git_cherry` finds local commits whose patches are already upstream.
+
+use serde::Serialize;
+use tauri::Emitter;
+
+/// The current branch's upstream as it was before a fetch.
+#[derive(Debug, Clone)]
+pub(crate) struct UpstreamSnapshot {
+    branch: String,
+    /// Full ref name, e.g. `refs/remotes/origin/main`.
+    upstream_ref: String,
+    commit: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitUpstreamRewrite {
+    repo_path: String,
+    branch: String,
+    upstream: String,
+    old: String,
+    new: String,
+    /// Commits on the branch that are on neither the old nor the new upstream.
+    local_commits: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitUpstreamLocalCommit {
+    hash: String,
+    short_hash: String,
+    subject: String,
+    /// False for commits that only came from the old upstream.
+    local_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitUpstreamResetPreview {
+    branch: String,
+    upstream: String,
+    head: String,
+    upstream_commit: String,
+    /// Where the upstream pointed before it last moved, from the tracking ref's reflog.
+    previous_upstream: Option<String>,
+    /// Whether that move was a force-push.
+    rewritten: bool,
+    /// Commits the reset leaves behind, newest first.
+    commits: Vec<GitUpstreamLocalCommit>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitUpstreamResetResult {
+    /// Ref keeping the branch as it was; None when nothing was left behind.
+    backup_ref: Option<String>,
+    preview: GitUpstreamResetPreview,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GitCherryCommit {
+    hash: String,
+    short_hash: String,
+    subject: String,
+    /// An equivalent patch is already upstream, so a rebase drops this commit.
+    applied: bool,
+}
+
+fn short_upstream(upstream_ref: &str) -> &str {
+    upstream_ref
+        .strip_prefix("refs/remotes/")
+        .or_else(|| upstream_ref.strip_prefix("refs/heads/"))
+        .unwrap_or(upstream_ref)
+}
+
+fn is_ancestor(repo_path: &str, ancestor: &str, descendant: &str) -> bool {
+    crate::run_git_status(repo_path, &["merge-base", "--is-ancestor", ancestor, descendant])
+        .map(|(ok, _, _)| ok)
+        .unwrap_or(false)
+}
+
+pub(crate) fn upstream_snapshot(repo_path: &str) -> Option<UpstreamSnapshot> {
+    let branch = crate::run_git(repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"]).ok()?;
+    let upstream_ref = crate::run_git(repo_path, &["rev-parse", "--symbolic-full-name", "@{upstream}"]).ok()?;
+    let commit = crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", upstream_ref.trim()]).ok()?;
+    Some(UpstreamSnapshot {
+        branch: branch.trim().to_string(),
+        upstream_ref: upstream_ref.trim().to_string(),
+        commit: commit.trim().to_string(),
+    })
+}
+
+/// Compares the upstream with `before` after a fetch and emits `upstream_force_pushed`
+/// when the old upstream commit is no longer part of the new one's history.
+pub(crate) fn detect_force_push(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    before: Option<&UpstreamSnapshot>,
+) -> Option<GitUpstreamRewrite> {
+    let before = before?;
+    let new = crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", before.upstream_ref.as_str()]).ok()?;
+    let new = new.trim().to_string();
+    if new == before.commit || is_ancestor(repo_path, &before.commit, &new) {
+        return None;
+    }
+
+    let local_commits = crate::run_git(
+        repo_path,
+        &["rev-list", "--count", "HEAD", "--not", new.as_str(), before.commit.as_str()],
+    )
+    .ok()
+    .and_then(|c| c.trim().parse::<u32>().ok())
+    .unwrap_or(0);
+    let rewrite = GitUpstreamRewrite {
+        repo_path: repo_path.to_string(),
+        branch: before.branch.clone(),
+        upstream: short_upstream(&before.upstream_ref).to_string(),
+        old: before.commit.clone(),
+        new,
+        local_commits,
+    };
+    let _ = app.emit("upstream_force_pushed", rewrite.clone());
+    Some(rewrite)
+}
+
+fn reset_preview(repo_path: &str) -> Result<GitUpstreamResetPreview, String> {
+    let branch = crate::run_git(repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .map_err(|_| String::from("HEAD is detached."))?;
+    let upstream_ref = crate::run_git(repo_path, &["rev-parse", "--symbolic-full-name", "@{upstream}"])
+        .map_err(|_| format!("Branch '{}' has no upstream.", branch.trim()))?;
+    let upstream_ref = upstream_ref.trim().to_string();
+    let head = crate::run_git(repo_path, &["rev-parse", "HEAD"])?;
+    let upstream_commit = crate::run_git(repo_path, &["rev-parse", "--verify", upstream_ref.as_str()])?;
+
+    let previous_ref = format!("{upstream_ref}@{{1}}");
+    let previous_upstream = crate::run_git(repo_path, &["rev-parse", "--verify", "--quiet", previous_ref.as_str()])
+        .ok()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty());
+    let rewritten = previous_upstream
+        .as_deref()
+        .is_some_and(|prev| !is_ancestor(repo_path, prev, upstream_commit.trim()));
+
+    let raw = crate::run_git(
+        repo_path,
+        &["log", "--format=%H%x1f%h%x1f%s", "HEAD", "--not", upstream_commit.trim()],
+    )?;
+    let mut local_args: Vec<&str> = vec!["rev-list", "HEAD", "--not", upstream_commit.trim()];
+    if let Some(prev) = previous_upstream.as_deref() {
+        local_args.push(prev);
+    }
+    let local_only: std::collections::HashSet<String> = crate::run_git(repo_path, &local_args)?
+        .lines()
+        .map(|l| l.trim().to_string())
+        .collect();
+
+    let commits = raw
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\x1f');
+            let hash = parts.next()?.trim().to_string();
+            let short_hash = parts.next()?.to_string();
+            let subject = parts.next().unwrap_or_default().to_string();
+            Some(GitUpstreamLocalCommit {
+                local_only: local_only.contains(&hash),
+                hash,
+                short_hash,
+                subject,
+            })
+        })
+        .collect();
+
+    Ok(GitUpstreamResetPreview {
+        branch: branch.trim().to_string(),
+        upstream: short_upstream(&upstream_ref).to_string(),
+        head: head.trim().to_string(),
+        upstream_commit: upstream_commit.trim().to_string(),
+        previous_upstream,
+        rewritten,
+        commits,
+    })
+}
+
+/// Lists the commits a reset of the current branch onto its upstream would leave behind,
+/// telling local work apart from commits that only came from a rewritten upstream.
+#[tauri::command]
+pub(crate) fn git_reset_to_upstream_preview(repo_path: String) -> Result<GitUpstreamResetPreview, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    reset_preview(&repo_path)
+}
+
+/// Resets the current branch onto its upstream with `mode` ("soft", "mixed", "hard" or
+/// "keep"). Commits that would be left behind are kept in
+/// `refs/graphoria/backup/upstream-reset-<time>` first. A hard reset of a protected branch
+/// needs `allow_protected`.
+#[tauri::command]
+pub(crate) fn git_reset_to_upstream(
+    repo_path: String,
+    mode: String,
+    allow_protected: Option<bool>,
+) -> Result<GitUpstreamResetResult, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    super::read_only::ensure_writable(&repo_path)?;
+
+    let flag = match mode.trim().to_lowercase().as_str() {
+        "soft" => "--soft",
+        "mixed" => "--mixed",
+        "hard" => "--hard",
+        "keep" => "--keep",
+        _ => return Err(String::from("Invalid reset mode. Use: soft, mixed, hard or keep.")),
+    };
+    if crate::is_merge_in_progress(&repo_path) || crate::is_rebase_in_progress(&repo_path) {
+        return Err(String::from("A merge or rebase is in progress. Finish or abort it first."));
+    }
+
+    crate::with_repo_git_lock(&repo_path, || {
+        let preview = reset_preview(&repo_path)?;
+        if flag == "--hard" {
+            super::protected::ensure_not_protected(&repo_path, &preview.branch, "reset --hard", allow_protected)?;
+        }
+
+        let backup_ref = if preview.commits.is_empty() {
+            None
+        } else {
+            let stamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let backup_ref = format!("refs/graphoria/backup/upstream-reset-{stamp}");
+            crate::run_git(
+                &repo_path,
+                &["update-ref", "-m", "graphoria: before reset to upstream", backup_ref.as_str(), preview.head.as_str()],
+            )?;
+            Some(backup_ref)
+        };
+
+        let undo_label = format!("reset {flag} {}", preview.upstream);
+        let _undo = super::undo::record_undo_point(&repo_path, undo_label.as_str(), &[]);
+        let (ok, stdout, stderr) = crate::run_git_status(&repo_path, &["reset", flag, preview.upstream_commit.as_str()])?;
+        if !ok {
+            return Err(format!("git reset failed: {}", if !stderr.is_empty() { stderr } else { stdout }));
+        }
+
+        let message = match &backup_ref {
+            Some(r) => format!(
+                "Reset '{}' to '{}'. {} commit(s) left behind are kept in {r}.",
+                preview.branch,
+                preview.upstream,
+                preview.commits.len()
+            ),
+            None => format!("Reset '{}' to '{}'.", preview.branch, preview.upstream),
+        };
+        Ok(GitUpstreamResetResult {
+            backup_ref,
+            preview,
+            message,
+        })
+    })
+}
+
+/// Commits on `head` (default HEAD) that are not on `upstream` (default the current
+/// branch's upstream), oldest first, marking those whose patch the upstream already has
+/// under another hash, like `git cherry`. Merge commits are left out.
+#[tauri::command]
+pub(crate) fn git_cherry(
+    repo_path: String,
+    upstream: Option<String>,
+    head: Option<String>,
+) -> Result<Vec<GitCherryCommit>, String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+
+    let upstream = upstream
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(|| String::from("@{upstream}"));
+    let head = head
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| String::from("HEAD"));
+    let upstream_commit = crate::resolve_commit(&repo_path, &upstream).ok_or_else(|| {
+        if upstream == "@{upstream}" {
+            String::from("The current branch has no upstream.")
+        } else {
+            format!("Unknown upstream: {upstream}")
+        }
+    })?;
+    let head_commit = crate::resolve_commit(&repo_path, &head).ok_or_else(|| format!("Unknown revision: {head}"))?;
+
+    let range = format!("{upstream_commit}...{head_commit}");
+    let raw = crate::run_git(
+        &repo_path,
+        &[
+            "log",
+            "--cherry-mark",
+            "--right-only",
+            "--no-merges",
+            "--reverse",
+            "--format=%m%x1f%H%x1f%h%x1f%s",
+            range.as_str(),
+        ],
+    )?;
+
+    Ok(raw
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, '\x1f');
+            let mark = parts.next()?.trim();
+            let hash = parts.next()?.trim().to_string();
+            let short_hash = parts.next()?.to_string();
+            let subject = parts.next().unwrap_or_default().to_string();
+            Some(GitCherryCommit {
+                hash,
+                short_hash,
+                subject,
+                applied: mark == "=",
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,195 @@
+// Ref watcher: notices when HEAD, loose refs or packed-refs change on disk, e.g. after a
+// commit, fetch or checkout in a terminal or IDE, and emits `refs_changed` with the refs
+// that moved, so the graph does not have to poll git.
+
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use tauri::Emitter;
+
+/// Only file metadata is read per tick; git runs only when something changed.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+static REF_WATCHERS: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+
+fn ref_watchers() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    REF_WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RefsChangedEvent {
+    repo_path: String,
+    /// `refs/heads/main`, `refs/stash`, ... with their old and new object ids.
+    refs: Vec<crate::GitRefUpdate>,
+    head_changed: bool,
+    /// Symbolic ref HEAD points at, or the commit id when detached.
+    head: String,
+}
+
+#[derive(Default, PartialEq, Eq)]
+struct RefState {
+    head: String,
+    refs: BTreeMap<String, String>,
+}
+
+fn read_state(repo_path: &str) -> RefState {
+    let head = match crate::run_git(repo_path, &["symbolic-ref", "-q", "HEAD"]) {
+        Ok(r) if !r.trim().is_empty() => r.trim().to_string(),
+        _ => crate::run_git(repo_path, &["rev-parse", "-q", "--verify", "HEAD"]).unwrap_or_default(),
+    };
+    let refs = crate::run_git(repo_path, &["for-each-ref", "--format=%(refname)%09%(objectname)"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.split_once('\t'))
+        .map(|(name, oid)| (name.trim().to_string(), oid.trim().to_string()))
+        .collect();
+    RefState { head, refs }
+}
+
+/// Paths whose metadata changes whenever a ref does: the worktree's own HEAD, and the
+/// ref storage shared by all worktrees.
+fn watched_paths(repo_path: &str) -> Result<(Vec<PathBuf>, Vec<PathBuf>), String> {
+    let git_dir = super::handles::git_dir(repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    let common_dir =
+        super::handles::git_common_dir(repo_path).ok_or_else(|| String::from("Failed to resolve git directory."))?;
+    let files = vec![git_dir.join("HEAD"), common_dir.join("packed-refs")];
+    let dirs = vec![common_dir.join("refs"), common_dir.join("reftable")];
+    Ok((files, dirs))
+}
+
+fn hash_metadata(path: &Path, hasher: &mut DefaultHasher) {
+    match fs::metadata(path) {
+        Ok(meta) => {
+            path.hash(hasher);
+            meta.len().hash(hasher);
+            meta.modified().ok().hash(hasher);
+        }
+        Err(_) => path.hash(hasher),
+    }
+}
+
+fn hash_dir(dir: &Path, hasher: &mut DefaultHasher) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for e in entries {
+        let path = e.path();
+        if e.file_type().is_ok_and(|t| t.is_dir()) {
+            hash_dir(&path, hasher);
+        } else {
+            hash_metadata(&path, hasher);
+        }
+    }
+}
+
+fn signature(files: &[PathBuf], dirs: &[PathBuf]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for f in files {
+        hash_metadata(f, &mut hasher);
+    }
+    for d in dirs {
+        hash_dir(d, &mut hasher);
+    }
+    hasher.finish()
+}
+
+fn diff_states(old: &RefState, new: &RefState) -> Vec<crate::GitRefUpdate> {
+    let mut updates: Vec<crate::GitRefUpdate> = Vec::new();
+    for (name, oid) in &old.refs {
+        if new.refs.get(name) != Some(oid) {
+            updates.push(crate::GitRefUpdate {
+                name: name.clone(),
+                old: Some(oid.clone()),
+                new: new.refs.get(name).cloned(),
+            });
+        }
+    }
+    for (name, oid) in &new.refs {
+        if !old.refs.contains_key(name) {
+            updates.push(crate::GitRefUpdate {
+                name: name.clone(),
+                old: None,
+                new: Some(oid.clone()),
+            });
+        }
+    }
+    updates
+}
+
+/// Starts watching the refs of `repo_path`. Watching an already watched repository is a
+/// no-op. Changes made by Graphoria itself are reported too.
+#[tauri::command]
+pub(crate) fn watch_repo_refs(app: tauri::AppHandle, repo_path: String) -> Result<(), String> {
+    crate::ensure_is_git_worktree(&repo_path)?;
+    let (files, dirs) = watched_paths(&repo_path)?;
+
+    let key = crate::normalize_repo_path(&repo_path);
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let mut watchers = ref_watchers()
+            .lock()
+            .map_err(|_| String::from("Failed to lock ref watchers."))?;
+        if watchers.contains_key(&key) {
+            return Ok(());
+        }
+        watchers.insert(key, stop.clone());
+    }
+
+    std::thread::spawn(move || {
+        let mut last_signature = signature(&files, &dirs);
+        let mut state = read_state(&repo_path);
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            if stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let sig = signature(&files, &dirs);
+            if sig == last_signature {
+                continue;
+            }
+            last_signature = sig;
+            // HEAD or the refs moved: working-tree diffs against HEAD are out of date.
+            super::diff_cache::invalidate_worktree(&repo_path, None);
+
+            let new_state = read_state(&repo_path);
+            if new_state == state {
+                continue;
+            }
+            let refs = diff_states(&state, &new_state);
+            let head_changed = new_state.head != state.head;
+            let _ = app.emit(
+                "refs_changed",
+                RefsChangedEvent {
+                    repo_path: repo_path.clone(),
+                    refs,
+                    head_changed,
+                    head: new_state.head.clone(),
+                },
+            );
+            state = new_state;
+        }
+    });
+    Ok(())
+}
+
+/// Stops the watcher started by `watch_repo_refs`, e.g. when the repository tab closes.
+#[tauri::command]
+pub(crate) fn unwatch_repo_refs(repo_path: String) -> Result<bool, String> {
+    let stop = ref_watchers()
+        .lock()
+        .map_err(|_| String::from("Failed to lock ref watchers."))?
+        .remove(&crate::normalize_repo_path(&repo_path));
+    if let Some(stop) = stop.as_ref() {
+        stop.store(true, Ordering::SeqCst);
+    }
+    Ok(stop.is_some())
+}
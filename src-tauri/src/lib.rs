@@ -37,9 +37,21 @@ use tauri::Manager;
 mod commands;
 
 use commands::terminal::{open_terminal, open_terminal_profile};
-use commands::clone::git_clone_repo;
+use commands::clone::{git_clone_cancel, git_clone_repo, git_fetch_deepen, git_repo_shallow_info};
+use commands::askpass::git_credential_prompt_reply;
+use commands::submodules::{
+    git_submodule_add,
+    git_submodule_add_predict,
+    git_submodule_diff,
+    git_submodule_init,
+    git_submodule_list,
+    git_submodule_remove,
+    git_submodule_sync,
+    git_submodule_update,
+};
 use commands::repo::{
     change_repo_ownership_to_current_user,
+    complete_paths,
     get_current_username,
     git_check_worktree,
     git_ls_remote_heads,
@@ -49,11 +61,20 @@ use commands::repo::{
     init_repo,
     repo_overview,
 };
-use commands::commits::{list_commits, list_commits_full};
+use commands::remotes::{
+    git_push_delete_branch, git_push_refspec, git_remote_capabilities, git_remote_fetch_config,
+    git_set_remote_fetch_config,
+};
+use commands::commits::{
+    git_commit_details, git_suggest_co_authors, list_commits, list_commits_full, list_merge_side_commits, list_repo_authors,
+};
+use commands::graph::{find_commit_in_graph, get_graph_filter, graph_delta, graph_snapshot, set_graph_filter};
+use commands::profile::{export_repo_profile, import_repo_profile};
 use commands::status::{
     git_ahead_behind,
     git_get_remote_url,
     git_has_staged_changes,
+    git_repo_state,
     git_set_remote_url,
     git_stage_paths,
     git_status,
@@ -65,6 +86,9 @@ use commands::branches::{
     git_branches_points_at,
     git_checkout_branch,
     git_checkout_commit,
+    git_checkout_remote_branch,
+    git_checkout_tag,
+    git_clean,
     git_create_branch,
     git_create_branch_advanced,
     git_delete_branch,
@@ -73,19 +97,53 @@ use commands::branches::{
     git_rename_branch,
     git_reset,
     git_reset_hard,
+    git_set_upstream,
+    git_stale_branches,
     git_switch,
+    git_switch_predict,
+    git_unset_upstream,
+};
+use commands::health::{
+    git_fsck,
+    git_repo_health,
+    git_repo_size_stats,
+};
+use commands::history_cleanup::{
+    git_history_remove_paths,
+    git_largest_blobs,
+};
+use commands::maintenance::{
+    git_enable_perf_features,
+    git_gc,
+    git_prune,
+    git_repack,
 };
+use commands::sparse::{
+    git_sparse_checkout_add,
+    git_sparse_checkout_disable,
+    git_sparse_checkout_init,
+    git_sparse_checkout_set,
+    git_sparse_checkout_status,
+};
+use commands::undo::{git_list_undo_points, git_undo_last_operation};
 use commands::stashes::{
     git_stash_apply,
     git_stash_base_commit,
+    git_stash_branch,
+    git_stash_changes,
     git_stash_clear,
     git_stash_drop,
+    git_stash_file_content,
+    git_stash_file_diff,
+    git_stash_file_vs_working_diff,
     git_stash_list,
+    git_stash_pop,
     git_stash_push_patch,
     git_stash_push_paths,
     git_stash_show,
 };
 use commands::tags::{
+    compare_releases,
     git_create_tag,
     git_delete_remote_tag,
     git_delete_tag,
@@ -93,20 +151,36 @@ use commands::tags::{
     git_list_tag_targets,
     git_push_tags,
     git_rename_tag,
+    suggest_next_version,
 };
+use commands::difftools::detect_external_diff_tools;
 use commands::diff::{
     git_commit_changes,
+    git_commit_diff_stream,
+    git_commit_diff_stream_cancel,
     git_commit_file_content,
     git_commit_file_diff,
+    git_commit_file_diff_limited,
+    git_commit_file_diff_structured,
+    git_commit_file_image_base64,
+    git_commit_numstat,
+    git_commit_parent_file_image_base64,
+    git_diff_context_lines,
     git_diff_no_index,
+    git_file_binary_info,
+    git_file_diff_words,
     git_head_file_content,
     git_head_file_text_preview,
     git_head_vs_working_diff,
     git_head_vs_working_text_diff,
     git_launch_external_diff_commit,
     git_launch_external_diff_working,
+    git_range_changes,
+    git_range_file_diff,
+    git_range_file_diff_structured,
     git_working_file_content,
     git_working_file_diff,
+    git_working_file_diff_structured,
     git_working_file_diff_unified,
     git_working_file_image_base64,
     git_working_file_text_preview,
@@ -114,15 +188,21 @@ use commands::diff::{
     write_text_file,
     write_binary_file,
 };
+use commands::diff_cache::git_diff_cache_invalidate;
 use commands::reflog::{
     git_cherry_pick,
     git_cherry_pick_advanced,
+    git_cherry_pick_onto,
+    git_backport,
     git_reflog,
+    git_revert,
 };
 use commands::conflicts::{
     git_conflict_apply,
     git_conflict_apply_and_stage,
     git_conflict_file_versions,
+    git_conflict_reopen,
+    git_conflict_resolutions,
     git_conflict_resolve_rename_with_content,
     git_conflict_resolve_rename,
     git_conflict_state,
@@ -135,14 +215,18 @@ use commands::conflicts::{
     git_am_continue_with_message,
     git_cherry_pick_abort,
     git_cherry_pick_continue_with_message,
+    git_revert_abort,
+    git_revert_continue,
     git_merge_continue_with_message,
     git_rebase_skip,
     git_rebase_continue_with_message,
 };
 
+use commands::index::{git_index_restore, git_index_snapshot, git_index_snapshot_info};
 use commands::patches::{
     git_apply_patch_file,
     git_format_patch_to_file,
+    git_predict_merge_graph,
     git_predict_patch_graph,
     git_predict_patch_file,
 };
@@ -153,6 +237,9 @@ use commands::interactive_rebase::{
     git_interactive_rebase_amend,
     git_interactive_rebase_continue,
     git_interactive_rebase_status,
+    git_rebase_upstream,
+    git_rewrite_author,
+    git_rewrite_author_preview,
     git_interactive_rebase_edit_files,
     git_read_working_file,
     git_write_working_file,
@@ -164,13 +251,80 @@ use commands::interactive_rebase::{
 use commands::startup::{get_open_on_startup, set_open_on_startup};
 
 use commands::gitlog::git_log_search;
+use commands::signing::{
+    git_setup_ssh_signing,
+    git_signing_config,
+    git_ssh_allowed_signers_add,
+    git_verify_range,
+    git_verify_tag,
+};
+use commands::backup::{
+    backup_repository,
+    get_backup_schedule,
+    list_scheduled_backups,
+    restore_repository,
+    restore_scheduled_backup,
+    run_scheduled_backup_now,
+    set_backup_schedule,
+};
+use commands::config::{
+    git_config_apply_fix,
+    git_config_check,
+    git_config_get,
+    git_config_list,
+    git_config_set,
+    git_config_unset,
+};
+use commands::attributes::{git_attributes_list, git_attributes_set, git_attributes_validate, git_check_attr};
+use commands::watcher::{unwatch_repo_refs, watch_repo_refs};
+use commands::handles::{close_repository, list_open_repositories, open_repository};
+use commands::commit_hooks::git_commit_amend;
+use commands::sync::git_sync;
+use commands::commit_lint::validate_commit_message;
+use commands::archive::git_archive;
+use commands::read_only::{
+    get_repo_read_only,
+    set_repo_read_only,
+};
+use commands::protected::{
+    get_protected_branches,
+    set_protected_branches,
+};
+use commands::recovery::{
+    get_startup_recovery,
+    git_remove_stale_index_lock,
+};
+use commands::upstream::{
+    git_cherry,
+    git_reset_to_upstream,
+    git_reset_to_upstream_preview,
+};
 
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-fn parse_git_log_records(repo_path: &str, stdout: &str) -> Vec<GitCommit> {
+/// Sets `is_pushed` on commits reachable from a remote-tracking ref. The unpushed side is
+/// computed once per listing since it is normally far smaller than the pushed history;
+/// without remote refs nothing is pushed.
+fn mark_pushed_commits(repo_path: &str, commits: &mut [GitCommit]) {
+    let has_remotes = run_git(repo_path, &["for-each-ref", "--count=1", "--format=%(refname)", "refs/remotes/"])
+        .map(|r| !r.trim().is_empty())
+        .unwrap_or(false);
+    if !has_remotes {
+        return;
+    }
+    let Ok(raw) = run_git(repo_path, &["rev-list", "--all", "--not", "--remotes"]) else {
+        return;
+    };
+    let unpushed: HashSet<&str> = raw.lines().map(|l| l.trim()).collect();
+    for c in commits.iter_mut() {
+        c.is_pushed = !unpushed.contains(c.hash.as_str());
+    }
+}
+
+fn parse_git_log_records(repo_path: &str, stdout: &str, with_refs: bool) -> Vec<GitCommit> {
     let head = run_git(repo_path, &["rev-parse", "HEAD"]).unwrap_or_default();
     let head = head.trim().to_string();
 
@@ -187,8 +341,9 @@ fn parse_git_log_records(repo_path: &str, stdout: &str) -> Vec<GitCommit> {
         let author = parts.next().unwrap_or_default().to_string();
         let author_email = parts.next().unwrap_or_default().to_string();
         let date = parts.next().unwrap_or_default().to_string();
+        let committer_date = parts.next().unwrap_or_default().to_string();
         let subject = parts.next().unwrap_or_default().to_string();
-        let _refs = parts.next().unwrap_or_default().to_string();
+        let refs = parts.next().unwrap_or_default().to_string();
 
         if hash.is_empty() {
             continue;
@@ -206,20 +361,38 @@ fn parse_git_log_records(repo_path: &str, stdout: &str) -> Vec<GitCommit> {
             author,
             author_email,
             date,
+            committer_date,
             subject,
-            refs: String::new(),
+            refs: if with_refs { refs } else { String::new() },
             is_head: head == hash,
+            is_pushed: false,
+            wip: None,
         });
     }
+    mark_pushed_commits(repo_path, &mut commits);
     commits
 }
 
 fn git_log_commits_multi(repo_path: &str, revs: &[String], max_count: u32) -> Result<Vec<GitCommit>, String> {
+    git_log_commits_for_revs(repo_path, revs, max_count, false)
+}
+
+/// Like `git_log_commits_multi`, but keeps the ref decorations of each commit.
+fn git_log_commits_decorated(repo_path: &str, revs: &[String], max_count: u32) -> Result<Vec<GitCommit>, String> {
+    git_log_commits_for_revs(repo_path, revs, max_count, true)
+}
+
+fn git_log_commits_for_revs(
+    repo_path: &str,
+    revs: &[String],
+    max_count: u32,
+    with_refs: bool,
+) -> Result<Vec<GitCommit>, String> {
     if revs.is_empty() {
         return Ok(Vec::new());
     }
 
-    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%s\x1f%D\x1e";
+    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%cd\x1f%s\x1f%D\x1e";
     let pretty = format!("--pretty=format:{format}");
 
     let mut args: Vec<String> = vec![String::from("--no-pager"), String::from("log")];
@@ -228,6 +401,9 @@ fn git_log_commits_multi(repo_path: &str, revs: &[String], max_count: u32) -> Re
     args.push(pretty);
     args.push(String::from("-n"));
     args.push(max_count.to_string());
+    if with_refs {
+        args.extend(commands::graph::load_graph_filter(repo_path).decorate_args());
+    }
 
     for r in revs {
         let t = r.trim();
@@ -255,7 +431,7 @@ fn git_log_commits_multi(repo_path: &str, revs: &[String], max_count: u32) -> Re
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_git_log_records(repo_path, stdout.as_ref()))
+    Ok(parse_git_log_records(repo_path, stdout.as_ref(), with_refs))
 }
 
 fn git_log_subjects_for_range(repo_path: &str, range: &str, max_count: u32) -> Result<Vec<String>, String> {
@@ -328,6 +504,17 @@ fn with_repo_git_lock<T>(repo_path: &str, f: impl FnOnce() -> Result<T, String>)
     f()
 }
 
+/// Drops the operation lock of a closed repository unless an operation still holds or
+/// waits for it; those keep the entry so later operations stay serialized with them.
+fn forget_repo_git_lock(repo_path: &str) {
+    if let Ok(mut guard) = repo_git_locks().lock() {
+        let key = normalize_repo_path(repo_path);
+        if guard.get(&key).is_some_and(|lock| Arc::strong_count(lock) == 1) {
+            guard.remove(&key);
+        }
+    }
+}
+
 fn is_repo_session_safe(repo_path: &str) -> bool {
     let normalized = normalize_repo_path(repo_path);
     let set = session_safe_directories();
@@ -346,6 +533,7 @@ fn git_command_in_repo(repo_path: &str) -> Command {
     }
     cmd.arg("-c").arg("core.quotepath=false");
     cmd.args(["-C", repo_path]);
+    commands::askpass::apply_env(&mut cmd);
     cmd
 }
 
@@ -405,6 +593,7 @@ fn git_set_user_identity(
         return Err(String::from("repo_path is required for repo scope."));
     }
     ensure_is_git_worktree(repo_path.as_str())?;
+    commands::read_only::ensure_writable(repo_path.as_str())?;
 
     if !user_name.is_empty() {
         run_git(repo_path.as_str(), &["config", "user.name", user_name.as_str()])?;
@@ -509,9 +698,137 @@ fn delete_working_path(repo_path: &str, rel: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Moves a file or directory to the OS trash / recycle bin using the platform's own tooling.
+fn move_path_to_trash(abs: &Path) -> Result<(), String> {
+    let p = abs.to_string_lossy().to_string();
+
+    #[cfg(target_os = "windows")]
+    {
+        let quoted = p.replace('\'', "''");
+        let method = if abs.is_dir() { "DeleteDirectory" } else { "DeleteFile" };
+        let script = format!(
+            "Add-Type -AssemblyName Microsoft.VisualBasic; [Microsoft.VisualBasic.FileIO.FileSystem]::{method}('{quoted}', 'OnlyErrorDialogs', 'SendToRecycleBin')"
+        );
+        let out = new_command("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", script.as_str()])
+            .output()
+            .map_err(|e| format!("Failed to spawn powershell: {e}"))?;
+        if out.status.success() {
+            return Ok(());
+        }
+        return Err(format!("Failed to move to Recycle Bin: {}", String::from_utf8_lossy(&out.stderr).trim_end()));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let quoted = p.replace('\\', "\\\\").replace('"', "\\\"");
+        let script = format!("tell application \"Finder\" to delete POSIX file \"{quoted}\"");
+        let out = new_command("osascript")
+            .args(["-e", script.as_str()])
+            .output()
+            .map_err(|e| format!("Failed to spawn osascript: {e}"))?;
+        if out.status.success() {
+            return Ok(());
+        }
+        return Err(format!("Failed to move to Trash: {}", String::from_utf8_lossy(&out.stderr).trim_end()));
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let attempts: Vec<(&str, Vec<&str>)> = vec![
+            ("gio", vec!["trash", "--", p.as_str()]),
+            ("trash-put", vec!["--", p.as_str()]),
+            ("kioclient5", vec!["move", p.as_str(), "trash:/"]),
+        ];
+        for (bin, args) in attempts {
+            if let Ok(out) = new_command(bin).args(args).output() {
+                if out.status.success() {
+                    return Ok(());
+                }
+            }
+        }
+        return Err(String::from("Could not move to trash (no gio, trash-put or kioclient5 available)."));
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitDiscardAllResult {
+    backup_commit: Option<String>,
+    reset_files: Vec<String>,
+    trashed_files: Vec<String>,
+    failed_files: Vec<String>,
+}
+
+/// Discards every local change. Tracked changes are first saved to the stash list
+/// (`backup_commit`, restorable with `git stash apply`); untracked files are moved to the
+/// OS trash instead of being deleted. Before the first commit there is nothing to reset
+/// to, so staged files are only unstaged.
+#[tauri::command]
+fn git_discard_all(repo_path: String, include_untracked: Option<bool>) -> Result<GitDiscardAllResult, String> {
+    ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
+    let include_untracked = include_untracked.unwrap_or(false);
+
+    with_repo_git_lock(&repo_path, || {
+        let unborn = resolve_commit(&repo_path, "HEAD").is_none();
+        let reset_raw = if unborn {
+            run_git_stdout_raw(&repo_path, &["ls-files", "-z"])?
+        } else {
+            run_git_stdout_raw(&repo_path, &["diff", "HEAD", "--name-only", "-z"])?
+        };
+        let reset_files: Vec<String> = reset_raw
+            .split('\0')
+            .filter(|p| !p.is_empty())
+            .map(|p| p.to_string())
+            .collect();
+
+        let mut backup_commit: Option<String> = None;
+        if unborn && !reset_files.is_empty() {
+            run_git(&repo_path, &["read-tree", "--empty"])?;
+        } else if !reset_files.is_empty() {
+            let created = run_git(&repo_path, &["stash", "create", "Graphoria: discard all"])?;
+            if !created.trim().is_empty() {
+                let created = created.trim().to_string();
+                // A created stash is unreferenced until stored, and gc may prune it.
+                run_git(&repo_path, &["stash", "store", "-m", "Graphoria: discard all", created.as_str()])?;
+                backup_commit = Some(created);
+            }
+            run_git(&repo_path, &["reset", "--hard", "-q", "HEAD"])?;
+        }
+
+        let mut trashed_files: Vec<String> = Vec::new();
+        let mut failed_files: Vec<String> = Vec::new();
+        if include_untracked {
+            let raw = run_git_stdout_raw(&repo_path, &["ls-files", "--others", "--exclude-standard", "--directory", "-z"])?;
+            for rel in raw.split('\0').filter(|p| !p.is_empty()) {
+                let rel = rel.trim_end_matches('/');
+                let abs = match repo_join_path(&repo_path, rel) {
+                    Ok(p) => p,
+                    Err(_) => {
+                        failed_files.push(rel.to_string());
+                        continue;
+                    }
+                };
+                match move_path_to_trash(abs.as_path()) {
+                    Ok(()) => trashed_files.push(rel.to_string()),
+                    Err(_) => failed_files.push(rel.to_string()),
+                }
+            }
+        }
+
+        Ok(GitDiscardAllResult {
+            backup_commit,
+            reset_files,
+            trashed_files,
+            failed_files,
+        })
+    })
+}
+
 #[tauri::command]
 fn git_delete_working_path(repo_path: String, path: String) -> Result<(), String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
     let path = path.trim().to_string();
     if path.is_empty() {
         return Err(String::from("path is empty"));
@@ -522,6 +839,7 @@ fn git_delete_working_path(repo_path: String, path: String) -> Result<(), String
 #[tauri::command]
 fn git_discard_working_path(repo_path: String, path: String, is_untracked: Option<bool>) -> Result<(), String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
     let path = path.trim().to_string();
     if path.is_empty() {
         return Err(String::from("path is empty"));
@@ -569,6 +887,7 @@ fn git_discard_working_path(repo_path: String, path: String, is_untracked: Optio
 #[tauri::command]
 fn git_add_to_gitignore(repo_path: String, pattern: String) -> Result<(), String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     let pattern = pattern.trim().replace('\\', "/");
     if pattern.is_empty() {
@@ -611,9 +930,47 @@ struct GitCommit {
     author: String,
     author_email: String,
     date: String,
+    committer_date: String,
     subject: String,
     refs: String,
     is_head: bool,
+    /// Whether the commit is on a remote-tracking branch; see `mark_pushed_commits`.
+    is_pushed: bool,
+    /// Change counts of the synthetic "Uncommitted changes" node; None on real commits.
+    wip: Option<GitWipCounts>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitWipCounts {
+    staged: u32,
+    unstaged: u32,
+    untracked: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitRefUpdate {
+    name: String,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DryRunReport {
+    operation: String,
+    ref_updates: Vec<GitRefUpdate>,
+    files_removed: Vec<String>,
+    files_changed: Vec<String>,
+    commits_orphaned: Vec<String>,
+    message: String,
+}
+
+/// Result of a destructive command: git's output, or with `dry_run` a report of
+/// what would happen. Serialized untagged, so the normal case stays a plain string.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum GitCommandOutcome {
+    Done(String),
+    DryRun(DryRunReport),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -624,6 +981,23 @@ struct PullResult {
     conflict_files: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct GitFetchRefUpdate {
+    kind: String,
+    source: String,
+    remote_ref: String,
+    local_ref: String,
+    old: Option<String>,
+    new: Option<String>,
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitFetchResult {
+    updates: Vec<GitFetchRefUpdate>,
+    message: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct PullPredictResult {
     upstream: Option<String>,
@@ -652,6 +1026,18 @@ struct GitBranchInfo {
     kind: String,
     target: String,
     committer_date: String,
+    /// Unix time of the latest reflog entry for a local branch, counting checkouts of it.
+    last_activity: Option<i64>,
+    /// What that entry was, e.g. "commit", "reset", "rebase (finish)" or "checkout".
+    last_activity_action: Option<String>,
+    upstream: Option<String>,
+    /// The upstream is configured but its remote branch no longer exists.
+    upstream_gone: bool,
+    subject: String,
+    author: String,
+    author_date: String,
+    /// Worktree other than this one that has the branch checked out.
+    checked_out_in: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -730,6 +1116,9 @@ fn parse_for_each_ref(raw: &str, kind: &str) -> Vec<GitBranchInfo> {
         let name = parts.get(0).unwrap_or(&"").trim().to_string();
         let target = parts.get(1).unwrap_or(&"").trim().to_string();
         let committer_date = parts.get(2).unwrap_or(&"").trim().to_string();
+        let field = |i: usize| parts.get(i).map(|p| p.trim().to_string()).unwrap_or_default();
+        let upstream = Some(field(3)).filter(|u| !u.is_empty());
+        let upstream_gone = upstream.is_some() && field(4) == "[gone]";
 
         if name.is_empty() {
             continue;
@@ -744,11 +1133,55 @@ fn parse_for_each_ref(raw: &str, kind: &str) -> Vec<GitBranchInfo> {
             kind: kind.to_string(),
             target,
             committer_date,
+            last_activity: None,
+            last_activity_action: None,
+            upstream,
+            upstream_gone,
+            subject: field(5),
+            author: field(6),
+            author_date: field(7),
+            checked_out_in: Some(field(8)).filter(|w| !w.is_empty()),
         });
     }
     out
 }
 
+fn resolve_commit(repo_path: &str, rev: &str) -> Option<String> {
+    let spec = format!("{rev}^{{commit}}");
+    run_git(repo_path, &["rev-parse", "-q", "--verify", spec.as_str()])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Commits reachable from `tip` that no ref would reach anymore once `removed_refs`
+/// are gone (`keep` lists extra revisions that stay reachable, e.g. a reset target).
+fn commits_orphaned_by(repo_path: &str, tip: &str, removed_refs: &[&str], keep: &[&str]) -> Vec<String> {
+    let refs_raw = run_git(repo_path, &["for-each-ref", "--format=%(refname)"]).unwrap_or_default();
+
+    let mut input = format!("{tip}\n");
+    for r in refs_raw.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if removed_refs.contains(&r) || r.starts_with("refs/graphoria/") {
+            continue;
+        }
+        input.push_str(format!("^{r}\n").as_str());
+    }
+    for k in keep.iter().filter(|k| !k.trim().is_empty()) {
+        input.push_str(format!("^{}\n", k.trim()).as_str());
+    }
+
+    run_git_with_stdin(repo_path, &["rev-list", "--stdin", "-n", "500"], input.as_str())
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+fn name_only_list(raw: &str) -> Vec<String> {
+    raw.split('\0').filter(|p| !p.is_empty()).map(|p| p.to_string()).collect()
+}
+
 fn list_unmerged_files(repo_path: &str) -> Vec<String> {
     let raw = match run_git(repo_path, &["diff", "--name-only", "--diff-filter=U"]) {
         Ok(s) => s,
@@ -933,7 +1366,7 @@ fn make_temp_diff_dir() -> Result<PathBuf, String> {
         .duration_since(UNIX_EPOCH)
         .map_err(|e| format!("Failed to get system time: {e}"))?
         .as_millis();
-    let dir = std::env::temp_dir().join(format!("graphoria-diff-{ts}"));
+    let dir = std::env::temp_dir().join(format!("graphoria-diff-{}-{ts}", std::process::id()));
     fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp dir: {e}"))?;
     Ok(dir)
 }
@@ -1088,6 +1521,14 @@ fn is_cherry_pick_in_progress(repo_path: &str) -> bool {
         .unwrap_or(false)
 }
 
+fn is_revert_in_progress(repo_path: &str) -> bool {
+    git_command_in_repo(repo_path)
+        .args(["rev-parse", "--verify", "-q", "REVERT_HEAD"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
 fn parse_conflict_files(text: &str) -> Vec<String> {
     let mut out: Vec<String> = Vec::new();
 
@@ -1264,22 +1705,30 @@ fn parse_merge_tree_conflict_paths(stdout: &str) -> Vec<String> {
 }
 
 fn predict_merge_conflicts(repo_path: &str, upstream: &str) -> Vec<String> {
-    let base = match run_git(repo_path, &["merge-base", "HEAD", upstream]) {
+    predict_merge_conflicts_between(repo_path, "HEAD", upstream)
+}
+
+fn predict_merge_conflicts_between(repo_path: &str, ours: &str, theirs: &str) -> Vec<String> {
+    let base = match run_git(repo_path, &["merge-base", ours, theirs]) {
         Ok(s) if !s.trim().is_empty() => s,
         _ => return Vec::new(),
     };
 
-    let base = base.trim().to_string();
+    predict_conflicts_with_base(repo_path, base.trim(), ours, theirs)
+}
 
+/// Three-way `merge-tree` with an explicit base. With `base = upstream` this
+/// approximates replaying `upstream..theirs` on top of `ours` (a rebase --onto).
+fn predict_conflicts_with_base(repo_path: &str, base: &str, ours: &str, theirs: &str) -> Vec<String> {
     let out = match git_command_in_repo(repo_path)
         .args([
             "merge-tree",
             "--write-tree",
             "--messages",
             "--merge-base",
-            base.as_str(),
-            "HEAD",
-            upstream,
+            base,
+            ours,
+            theirs,
         ])
         .output()
     {
@@ -1343,6 +1792,10 @@ fn format_worktree_validation_error(summary: &str, details: &str) -> String {
 }
 
 fn ensure_is_git_worktree(repo_path: &str) -> Result<(), String> {
+    if commands::handles::is_open(repo_path) {
+        return Ok(());
+    }
+
     let check = git_command_in_repo(repo_path)
         .args(["rev-parse", "--is-inside-work-tree"])
         .output()
@@ -1439,6 +1892,9 @@ fn push_history_order_args(args: &mut Vec<String>, history_order: &str) {
         "date" => {
             args.push(String::from("--date-order"));
         }
+        "author_date" => {
+            args.push(String::from("--author-date-order"));
+        }
         "first_parent" => {
             args.push(String::from("--first-parent"));
             args.push(String::from("--topo-order"));
@@ -1449,29 +1905,29 @@ fn push_history_order_args(args: &mut Vec<String>, history_order: &str) {
     }
 }
 
-fn list_commits_impl_v2(
+/// `git log` arguments listing the commits of the graph in the order it draws them.
+fn graph_log_args(
     repo_path: &str,
+    format: &str,
     max_count: Option<u32>,
     only_head: bool,
     history_order: &str,
-) -> Result<Vec<GitCommit>, String> {
-    ensure_is_git_worktree(repo_path)?;
-
-    let head = run_git(repo_path, &["rev-parse", "HEAD"]).unwrap_or_default();
-    let head = head.trim().to_string();
-
-    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%s\x1f%D\x1e";
+    first_parent: bool,
+) -> Vec<String> {
     let pretty = format!("--pretty=format:{format}");
 
     let mut args: Vec<String> = vec![String::from("--no-pager"), String::from("log")];
 
     if !only_head {
-        args.push(String::from("--branches"));
-        args.push(String::from("--tags"));
-        args.push(String::from("--remotes"));
+        let filter = commands::graph::load_graph_filter(repo_path);
+        args.extend(filter.decorate_args());
+        args.extend(filter.rev_args());
     }
 
     push_history_order_args(&mut args, history_order);
+    if first_parent && history_order != "first_parent" {
+        args.push(String::from("--first-parent"));
+    }
     args.push(String::from("--date=iso-strict"));
     args.push(pretty);
 
@@ -1481,6 +1937,23 @@ fn list_commits_impl_v2(
     }
 
     args.push(String::from("HEAD"));
+    args
+}
+
+fn list_commits_impl_v2(
+    repo_path: &str,
+    max_count: Option<u32>,
+    only_head: bool,
+    history_order: &str,
+    first_parent: bool,
+) -> Result<Vec<GitCommit>, String> {
+    ensure_is_git_worktree(repo_path)?;
+
+    let head = run_git(repo_path, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let head = head.trim().to_string();
+
+    let format = "%H\x1f%P\x1f%an\x1f%ae\x1f%ad\x1f%cd\x1f%s\x1f%D\x1e";
+    let args = graph_log_args(repo_path, format, max_count, only_head, history_order, first_parent);
 
     let output = git_command_in_repo(repo_path)
         .args(args)
@@ -1515,6 +1988,7 @@ fn list_commits_impl_v2(
         let author = parts.next().unwrap_or_default().to_string();
         let author_email = parts.next().unwrap_or_default().to_string();
         let date = parts.next().unwrap_or_default().to_string();
+        let committer_date = parts.next().unwrap_or_default().to_string();
         let subject = parts.next().unwrap_or_default().to_string();
         let refs = parts.next().unwrap_or_default().to_string();
 
@@ -1536,18 +2010,29 @@ fn list_commits_impl_v2(
             author,
             author_email,
             date,
+            committer_date,
             subject,
             refs,
             is_head,
+            is_pushed: false,
+            wip: None,
         });
     }
 
+    mark_pushed_commits(repo_path, &mut commits);
     Ok(commits)
 }
 
 #[tauri::command]
-fn git_commit(repo_path: String, message: String, paths: Vec<String>) -> Result<String, String> {
+fn git_commit(
+    repo_path: String,
+    message: String,
+    paths: Vec<String>,
+    no_verify: Option<bool>,
+    co_authors: Option<Vec<String>>,
+) -> Result<commands::commit_hooks::GitCommitResult, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     if message.trim().is_empty() {
         return Err(String::from("Commit message is empty."));
@@ -1557,6 +2042,8 @@ fn git_commit(repo_path: String, message: String, paths: Vec<String>) -> Result<
         return Err(String::from("No files selected to commit."));
     }
 
+    let message = commands::commits::add_co_author_trailers(&repo_path, &message, co_authors.as_deref())?;
+
     let mut add_args: Vec<&str> = Vec::new();
     add_args.push("add");
     add_args.push("--");
@@ -1576,19 +2063,59 @@ fn git_commit(repo_path: String, message: String, paths: Vec<String>) -> Result<
         return Err(format!("git add failed: {stderr}"));
     }
 
-    let commit_out = git_command_in_repo(&repo_path)
-        .args(["commit", "-m", &message])
-        .output()
-        .map_err(|e| format!("Failed to spawn git commit: {e}"))?;
+    commands::commit_hooks::commit_with_hooks(&repo_path, &message, false, no_verify.unwrap_or(false))
+}
 
-    if !commit_out.status.success() {
-        let stderr = String::from_utf8_lossy(&commit_out.stderr);
-        return Err(format!("git commit failed: {stderr}"));
+#[tauri::command]
+fn git_commit_fixup(repo_path: String, target_commit: String, paths: Vec<String>) -> Result<String, String> {
+    ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
+
+    let target_commit = target_commit.trim().to_string();
+    if target_commit.is_empty() {
+        return Err(String::from("target_commit is empty"));
     }
 
-    let new_head = run_git(&repo_path, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let mut cleaned: Vec<String> = Vec::new();
+    for p in paths.into_iter() {
+        let p = p.trim().to_string();
+        if p.is_empty() {
+            continue;
+        }
+        ensure_rel_path_safe(p.as_str())?;
+        cleaned.push(p);
+    }
 
-    Ok(new_head)
+    with_repo_git_lock(&repo_path, || {
+        let target_spec = format!("{target_commit}^{{commit}}");
+        let target = run_git(&repo_path, &["rev-parse", "--verify", target_spec.as_str()])
+            .map_err(|_| format!("Commit not found: {target_commit}"))?;
+
+        if !cleaned.is_empty() {
+            let mut add_args: Vec<&str> = vec!["add", "-A", "--"];
+            for p in cleaned.iter() {
+                add_args.push(p.as_str());
+            }
+            run_git(&repo_path, add_args.as_slice())?;
+        }
+
+        if !has_staged_changes(&repo_path)? {
+            return Err(String::from("No changes staged for the fixup commit."));
+        }
+
+        let fixup_arg = format!("--fixup={target}");
+        let commit_out = git_command_in_repo(&repo_path)
+            .args(["commit", fixup_arg.as_str()])
+            .output()
+            .map_err(|e| format!("Failed to spawn git commit: {e}"))?;
+
+        if !commit_out.status.success() {
+            let stderr = String::from_utf8_lossy(&commit_out.stderr);
+            return Err(format!("git commit failed: {stderr}"));
+        }
+
+        Ok(run_git(&repo_path, &["rev-parse", "HEAD"]).unwrap_or_default())
+    })
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -1600,6 +2127,7 @@ struct GitPatchEntry {
 #[tauri::command]
 fn git_commit_patch(repo_path: String, message: String, patches: Vec<GitPatchEntry>) -> Result<String, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     let message = message.trim().to_string();
     if message.is_empty() {
@@ -1760,43 +2288,92 @@ fn git_commit_patch(repo_path: String, message: String, patches: Vec<GitPatchEnt
 }
 
 #[tauri::command]
-fn git_push(
+#[allow(clippy::too_many_arguments)]
+async fn git_push(
+    app: tauri::AppHandle,
     repo_path: String,
     remote_name: Option<String>,
     branch: Option<String>,
     force: Option<bool>,
     with_lease: Option<bool>,
+    follow_tags: Option<bool>,
+    allow_protected: Option<bool>,
 ) -> Result<String, String> {
-    ensure_is_git_worktree(&repo_path)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let on_progress = commands::clone::transfer_progress_emitter(&app, &repo_path, "push");
+        git_push_impl(&repo_path, remote_name, branch, force, with_lease, follow_tags, allow_protected, on_progress)
+    })
+    .await
+    .map_err(|e| format!("Failed to run git push: {e}"))?
+}
+
+#[allow(clippy::too_many_arguments)]
+fn git_push_impl(
+    repo_path: &str,
+    remote_name: Option<String>,
+    branch: Option<String>,
+    force: Option<bool>,
+    with_lease: Option<bool>,
+    follow_tags: Option<bool>,
+    allow_protected: Option<bool>,
+    on_progress: impl FnMut(String, u32, String),
+) -> Result<String, String> {
+    ensure_is_git_worktree(repo_path)?;
+    commands::read_only::ensure_writable(repo_path)?;
 
     let remote_name = remote_name.unwrap_or_else(|| String::from("origin"));
     let force = force.unwrap_or(false);
     let with_lease = with_lease.unwrap_or(true);
+    let follow_tags = follow_tags.unwrap_or(false);
 
     let branch = match branch {
         Some(b) if !b.trim().is_empty() => b,
-        _ => run_git(&repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        _ => run_git(repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
             .map_err(|e| format!("Failed to determine current branch: {e}"))?,
     };
 
-    let mut args: Vec<&str> = vec!["push"];
+    let mut args: Vec<&str> = vec!["push", "--progress"];
     if force {
+        commands::protected::ensure_not_protected(repo_path, &branch, "force push", allow_protected)?;
         if with_lease {
             args.push("--force-with-lease");
         } else {
             args.push("--force");
         }
     }
+    if follow_tags {
+        args.push("--follow-tags");
+    }
     args.push("-u");
     args.push(remote_name.as_str());
     args.push(branch.as_str());
 
-    run_git(&repo_path, args.as_slice())
+    let mut cmd = git_command_in_repo(repo_path);
+    cmd.args(args.as_slice());
+    let (ok, stdout, stderr) = commands::clone::run_git_status_with_progress(cmd, "git push", on_progress)?;
+    if !ok {
+        return Err(format!("git command failed: {stderr}"));
+    }
+    Ok(stdout)
 }
 
 #[tauri::command]
-fn git_pull(repo_path: String, remote_name: Option<String>) -> Result<PullResult, String> {
+async fn git_pull(app: tauri::AppHandle, repo_path: String, remote_name: Option<String>) -> Result<PullResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let on_progress = commands::clone::transfer_progress_emitter(&app, &repo_path, "pull");
+        git_pull_impl(repo_path.clone(), remote_name, on_progress)
+    })
+    .await
+    .map_err(|e| format!("Failed to run git pull: {e}"))?
+}
+
+fn git_pull_impl(
+    repo_path: String,
+    remote_name: Option<String>,
+    on_progress: impl FnMut(String, u32, String),
+) -> Result<PullResult, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     with_repo_git_lock(&repo_path, || {
         let remote_name = remote_name.unwrap_or_else(|| String::from("origin"));
@@ -1807,8 +2384,9 @@ fn git_pull(repo_path: String, remote_name: Option<String>) -> Result<PullResult
             return Err(String::from("Cannot pull from detached HEAD."));
         }
 
-        let (ok, stdout, stderr) =
-            run_git_status(&repo_path, &["pull", "--no-rebase", remote_name.as_str(), head_name.as_str()])?;
+        let mut cmd = git_command_in_repo(&repo_path);
+        cmd.args(["pull", "--progress", "--no-rebase", remote_name.as_str(), head_name.as_str()]);
+        let (ok, stdout, stderr) = commands::clone::run_git_status_with_progress(cmd, "git pull", on_progress)?;
         if ok {
             return Ok(PullResult {
                 status: String::from("ok"),
@@ -1856,8 +2434,22 @@ fn git_pull(repo_path: String, remote_name: Option<String>) -> Result<PullResult
 }
 
 #[tauri::command]
-fn git_pull_rebase(repo_path: String, remote_name: Option<String>) -> Result<PullResult, String> {
+async fn git_pull_rebase(app: tauri::AppHandle, repo_path: String, remote_name: Option<String>) -> Result<PullResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let on_progress = commands::clone::transfer_progress_emitter(&app, &repo_path, "pull");
+        git_pull_rebase_impl(repo_path.clone(), remote_name, on_progress)
+    })
+    .await
+    .map_err(|e| format!("Failed to run git pull: {e}"))?
+}
+
+fn git_pull_rebase_impl(
+    repo_path: String,
+    remote_name: Option<String>,
+    on_progress: impl FnMut(String, u32, String),
+) -> Result<PullResult, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     with_repo_git_lock(&repo_path, || {
         let remote_name = remote_name.unwrap_or_else(|| String::from("origin"));
@@ -1868,8 +2460,9 @@ fn git_pull_rebase(repo_path: String, remote_name: Option<String>) -> Result<Pul
             return Err(String::from("Cannot pull from detached HEAD."));
         }
 
-        let (ok, stdout, stderr) =
-            run_git_status(&repo_path, &["pull", "--rebase", remote_name.as_str(), head_name.as_str()])?;
+        let mut cmd = git_command_in_repo(&repo_path);
+        cmd.args(["pull", "--progress", "--rebase", remote_name.as_str(), head_name.as_str()]);
+        let (ok, stdout, stderr) = commands::clone::run_git_status_with_progress(cmd, "git pull", on_progress)?;
         if ok {
             return Ok(PullResult {
                 status: String::from("ok"),
@@ -1919,6 +2512,7 @@ fn git_pull_rebase(repo_path: String, remote_name: Option<String>) -> Result<Pul
 #[tauri::command]
 fn git_merge_continue(repo_path: String) -> Result<String, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     let (ok, stdout, stderr) = run_git_status(&repo_path, &["merge", "--continue"])?;
     if ok {
@@ -1936,18 +2530,21 @@ fn git_merge_continue(repo_path: String) -> Result<String, String> {
 #[tauri::command]
 fn git_merge_abort(repo_path: String) -> Result<String, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
     run_git(&repo_path, &["merge", "--abort"])
 }
 
 #[tauri::command]
 fn git_rebase_continue(repo_path: String) -> Result<String, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
     run_git(&repo_path, &["rebase", "--continue"])
 }
 
 #[tauri::command]
 fn git_rebase_abort(repo_path: String) -> Result<String, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
     run_git(&repo_path, &["rebase", "--abort"])
 }
 
@@ -1998,14 +2595,96 @@ fn find_branch_fork_point(repo_path: &str) -> Option<String> {
     best_fork
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct RebaseOntoPredictResult {
+    onto: String,
+    upstream: String,
+    branch: String,
+    commits: Vec<String>,
+    conflict_files: Vec<String>,
+}
+
+/// Predicts `git rebase --onto <target> <upstream> [<branch>]`: the commits that
+/// would be replayed and the files expected to conflict. Without `upstream` the
+/// same fork-point logic as `git_rebase_onto` is used.
+#[tauri::command]
+fn git_rebase_onto_predict(
+    repo_path: String,
+    target: String,
+    upstream: Option<String>,
+    branch: Option<String>,
+) -> Result<RebaseOntoPredictResult, String> {
+    ensure_is_git_worktree(&repo_path)?;
+
+    let target = target.trim().to_string();
+    if target.is_empty() {
+        return Err(String::from("Target commit/branch is empty."));
+    }
+    let upstream = upstream.unwrap_or_default().trim().to_string();
+    let branch = branch.unwrap_or_default().trim().to_string();
+
+    with_repo_git_lock(&repo_path, || {
+        let tip_rev = if branch.is_empty() { "HEAD" } else { branch.as_str() };
+        let target_hash = run_git(&repo_path, &["rev-parse", "--verify", format!("{target}^{{commit}}").as_str()])
+            .map_err(|_| format!("Revision not found: {target}"))?;
+        let tip_hash = run_git(&repo_path, &["rev-parse", "--verify", format!("{tip_rev}^{{commit}}").as_str()])
+            .map_err(|_| format!("Revision not found: {tip_rev}"))?;
+
+        let upstream_hash = if !upstream.is_empty() {
+            run_git(&repo_path, &["rev-parse", "--verify", format!("{upstream}^{{commit}}").as_str()])
+                .map_err(|_| format!("Revision not found: {upstream}"))?
+        } else {
+            let target_is_ancestor = git_command_in_repo(&repo_path)
+                .args(["merge-base", "--is-ancestor", target_hash.as_str(), tip_hash.as_str()])
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false);
+            if target_is_ancestor && branch.is_empty() {
+                find_branch_fork_point(&repo_path).unwrap_or_else(|| target_hash.clone())
+            } else {
+                target_hash.clone()
+            }
+        };
+
+        let range = format!("{upstream_hash}..{tip_hash}");
+        let commits = git_log_subjects_for_range(&repo_path, range.as_str(), 200)?;
+
+        let conflict_files = if commits.is_empty() {
+            Vec::new()
+        } else if upstream_hash == target_hash {
+            // Plain rebase: the real merge-base is the replay base.
+            predict_merge_conflicts_between(&repo_path, target_hash.as_str(), tip_hash.as_str())
+        } else {
+            predict_conflicts_with_base(&repo_path, upstream_hash.as_str(), target_hash.as_str(), tip_hash.as_str())
+        };
+
+        Ok(RebaseOntoPredictResult {
+            onto: target_hash,
+            upstream: upstream_hash,
+            branch: if branch.is_empty() { String::from("HEAD") } else { branch.clone() },
+            commits,
+            conflict_files,
+        })
+    })
+}
+
 #[tauri::command]
-fn git_rebase_onto(repo_path: String, target: String) -> Result<PullResult, String> {
+fn git_rebase_onto(
+    repo_path: String,
+    target: String,
+    upstream: Option<String>,
+    branch: Option<String>,
+) -> Result<PullResult, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
+
+    let upstream = upstream.unwrap_or_default().trim().to_string();
+    let branch = branch.unwrap_or_default().trim().to_string();
 
     with_repo_git_lock(&repo_path, || {
         let head_name = run_git(&repo_path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
             .unwrap_or_else(|_| String::from("(detached)"));
-        if head_name == "(detached)" {
+        if head_name == "(detached)" && branch.is_empty() {
             return Err(String::from("Cannot rebase from detached HEAD."));
         }
 
@@ -2046,7 +2725,27 @@ fn git_rebase_onto(repo_path: String, target: String) -> Result<PullResult, Stri
             false
         };
 
-        let rebase_args: Vec<String> = if target_is_ancestor {
+        let rebase_args: Vec<String> = if !upstream.is_empty() {
+            // Explicit `git rebase --onto <target> <upstream> [<branch>]`
+            let mut args = vec![
+                String::from("rebase"),
+                String::from("--autostash"),
+                String::from("--onto"),
+                target.to_string(),
+                upstream.clone(),
+            ];
+            if !branch.is_empty() {
+                args.push(branch.clone());
+            }
+            args
+        } else if !branch.is_empty() {
+            vec![
+                String::from("rebase"),
+                String::from("--autostash"),
+                target.to_string(),
+                branch.clone(),
+            ]
+        } else if target_is_ancestor {
             // Find where the current branch forks from other branches
             if let Some(fork) = find_branch_fork_point(&repo_path) {
                 if fork == target_hash {
@@ -2081,6 +2780,10 @@ fn git_rebase_onto(repo_path: String, target: String) -> Result<PullResult, Stri
             ]
         };
 
+        let undo_label = format!("rebase onto {target}");
+        let branch_ref = if branch.is_empty() { String::new() } else { format!("refs/heads/{branch}") };
+        let _undo = commands::undo::record_undo_point(&repo_path, undo_label.as_str(), &[branch_ref.as_str()]);
+
         let args_ref: Vec<&str> = rebase_args.iter().map(|s| s.as_str()).collect();
         let (ok, stdout, stderr) = run_git_status(&repo_path, &args_ref)?;
         if ok {
@@ -2124,7 +2827,19 @@ fn git_rebase_onto(repo_path: String, target: String) -> Result<PullResult, Stri
 }
 
 #[tauri::command]
-fn git_pull_predict(
+async fn git_pull_predict(
+    repo_path: String,
+    remote_name: Option<String>,
+    rebase: Option<bool>,
+) -> Result<PullPredictResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        git_pull_predict_impl(repo_path, remote_name, rebase)
+    })
+    .await
+    .map_err(|e| format!("Failed to predict pull: {e}"))?
+}
+
+fn git_pull_predict_impl(
     repo_path: String,
     remote_name: Option<String>,
     rebase: Option<bool>,
@@ -2181,7 +2896,20 @@ fn git_pull_predict(
 }
 
 #[tauri::command]
-fn git_pull_predict_graph(
+async fn git_pull_predict_graph(
+    repo_path: String,
+    remote_name: Option<String>,
+    rebase: Option<bool>,
+    max_commits: Option<u32>,
+) -> Result<PullPredictGraphResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        git_pull_predict_graph_impl(repo_path, remote_name, rebase, max_commits)
+    })
+    .await
+    .map_err(|e| format!("Failed to predict pull: {e}"))?
+}
+
+fn git_pull_predict_graph_impl(
     repo_path: String,
     remote_name: Option<String>,
     rebase: Option<bool>,
@@ -2266,9 +2994,12 @@ fn git_pull_predict_graph(
                 author: String::from("(predict)"),
                 author_email: String::new(),
                 date: String::new(),
+                committer_date: String::new(),
                 subject: String::from("Merge commit"),
                 refs: String::new(),
                 is_head: true,
+                is_pushed: false,
+                wip: None,
             });
 
             let revs = vec![local_head.clone(), upstream_head.clone()]
@@ -2296,9 +3027,12 @@ fn git_pull_predict_graph(
                     author: String::from("(predict)"),
                     author_email: String::new(),
                     date: String::new(),
+                    committer_date: String::new(),
                     subject: subj.clone(),
                     refs: String::new(),
                     is_head: false,
+                    is_pushed: false,
+                    wip: None,
                 });
                 last_parent = id;
             }
@@ -2404,14 +3138,120 @@ fn git_pull_predict_conflict_preview(repo_path: String, upstream: String, path:
     }
 }
 
+/// Parses the ref summary lines of `git fetch`, e.g.
+/// ` + 1a2b3c4...5d6e7f8 topic -> origin/topic  (forced update)`.
+fn parse_fetch_ref_updates(output: &str) -> Vec<GitFetchRefUpdate> {
+    let mut updates: Vec<GitFetchRefUpdate> = Vec::new();
+    let mut source = String::new();
+
+    for line in output.lines() {
+        if let Some(url) = line.strip_prefix("From ") {
+            source = url.trim().to_string();
+            continue;
+        }
+        let mut chars = line.chars();
+        let (Some(' '), Some(flag), Some(' ')) = (chars.next(), chars.next(), chars.next()) else {
+            continue;
+        };
+        let rest = chars.as_str().trim();
+        let Some((left, right)) = rest.split_once(" -> ") else {
+            continue;
+        };
+
+        let (summary, remote_ref) = if let Some(inner) = left.strip_prefix('[') {
+            let Some((summary, from)) = inner.split_once(']') else {
+                continue;
+            };
+            (summary.trim(), from.trim())
+        } else {
+            let Some((summary, from)) = left.split_once(char::is_whitespace) else {
+                continue;
+            };
+            (summary.trim(), from.trim())
+        };
+
+        let (local_ref, note) = match right.split_once('(') {
+            Some((to, note)) => (to.trim(), Some(note.trim_end_matches(')').trim().to_string())),
+            None => (right.trim(), None),
+        };
+
+        let (old, new) = match summary.split_once("..") {
+            Some((o, n)) => (Some(o.to_string()), Some(n.trim_start_matches('.').to_string())),
+            None => (None, None),
+        };
+
+        let kind = match (flag, summary) {
+            ('*', "new tag") => "new_tag",
+            ('*', _) => "new",
+            ('+', _) => "forced",
+            ('-', _) => "deleted",
+            ('t', _) => "tag_updated",
+            ('!', _) => "rejected",
+            ('=', _) => "up_to_date",
+            _ => "updated",
+        };
+
+        updates.push(GitFetchRefUpdate {
+            kind: kind.to_string(),
+            source: source.clone(),
+            remote_ref: remote_ref.to_string(),
+            local_ref: local_ref.to_string(),
+            old,
+            new,
+            note,
+        });
+    }
+
+    updates
+}
+
 #[tauri::command]
-async fn git_fetch(repo_path: String, remote_name: Option<String>) -> Result<String, String> {
+async fn git_fetch(
+    app: tauri::AppHandle,
+    repo_path: String,
+    remote_name: Option<String>,
+    all: Option<bool>,
+    prune: Option<bool>,
+    tags: Option<bool>,
+    prune_tags: Option<bool>,
+) -> Result<GitFetchResult, String> {
     tauri::async_runtime::spawn_blocking(move || {
         ensure_is_git_worktree(&repo_path)?;
 
         with_repo_git_lock(&repo_path, || {
             let remote_name = remote_name.unwrap_or_else(|| String::from("origin"));
-            run_git(&repo_path, &["fetch", remote_name.as_str()])
+            let prune_tags = prune_tags.unwrap_or(false);
+
+            let mut args: Vec<&str> = vec!["fetch", "--progress"];
+            // --prune-tags only has an effect together with --prune.
+            if prune.unwrap_or(false) || prune_tags {
+                args.push("--prune");
+            }
+            if prune_tags {
+                args.push("--prune-tags");
+            }
+            if tags.unwrap_or(false) {
+                args.push("--tags");
+            }
+            if all.unwrap_or(false) {
+                args.push("--all");
+            } else {
+                args.push(remote_name.as_str());
+            }
+
+            let upstream_before = commands::upstream::upstream_snapshot(&repo_path);
+            let mut cmd = git_command_in_repo(&repo_path);
+            cmd.args(args.as_slice());
+            let on_progress = commands::clone::transfer_progress_emitter(&app, &repo_path, "fetch");
+            let (ok, stdout, stderr) = commands::clone::run_git_status_with_progress(cmd, "git fetch", on_progress)?;
+            if !ok {
+                return Err(format!("git command failed: {stderr}"));
+            }
+            commands::upstream::detect_force_push(&app, &repo_path, upstream_before.as_ref());
+            Ok(GitFetchResult {
+                updates: parse_fetch_ref_updates(stderr.as_str()),
+                message: if !stdout.is_empty() { stdout } else { stderr },
+            })
         })
     })
     .await
@@ -2454,6 +3294,7 @@ fn git_commit_summary(repo_path: String, commit: String) -> Result<GitCommitSumm
 #[tauri::command]
 fn git_commit_all(repo_path: String, message: String) -> Result<String, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     let message = message.trim().to_string();
     if message.is_empty() {
@@ -2477,6 +3318,7 @@ fn git_commit_all(repo_path: String, message: String) -> Result<String, String>
 #[tauri::command]
 fn git_merge_branch(repo_path: String, branch: String) -> Result<PullResult, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     let branch = branch.trim().to_string();
     if branch.is_empty() {
@@ -2484,6 +3326,9 @@ fn git_merge_branch(repo_path: String, branch: String) -> Result<PullResult, Str
     }
 
     with_repo_git_lock(&repo_path, || {
+        let undo_label = format!("merge {branch}");
+        let _undo = commands::undo::record_undo_point(&repo_path, undo_label.as_str(), &[]);
+
         let (ok, stdout, stderr) = run_git_status(&repo_path, &["merge", branch.as_str()])?;
         if ok {
             let merge_in_progress = is_merge_in_progress(&repo_path);
@@ -2549,6 +3394,7 @@ fn git_merge_branch_advanced(
     message: Option<String>,
 ) -> Result<PullResult, String> {
     ensure_is_git_worktree(&repo_path)?;
+    commands::read_only::ensure_writable(&repo_path)?;
 
     let branch = branch.trim().to_string();
     if branch.is_empty() {
@@ -2619,6 +3465,9 @@ fn git_merge_branch_advanced(
 
         args.push(branch.clone());
 
+        let undo_label = format!("merge {branch}");
+        let _undo = commands::undo::record_undo_point(&repo_path, undo_label.as_str(), &[]);
+
         let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
         let (ok, stdout, stderr) = run_git_status(&repo_path, arg_refs.as_slice())?;
         if ok {
@@ -2725,6 +3574,12 @@ fn get_system_info() -> SystemInfo {
     }
 }
 
+/// Runs the git askpass relay when git started this executable as its GIT_ASKPASS
+/// helper; returns the process exit code in that case.
+pub fn askpass_main() -> Option<i32> {
+    commands::askpass::run_client()
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -2748,6 +3603,11 @@ pub fn run() {
                 _app.set_menu(menu)?;
             }
 
+            // Without the relay, network commands fall back to git's own prompting.
+            let _ = commands::askpass::start(_app.handle().clone());
+            commands::backup::start_scheduler(_app.handle().clone());
+            commands::recovery::start(_app.handle().clone());
+
             // Set window icon so it shows correctly in dev mode too
             if let Some(window) = _app.get_webview_window("main") {
                 let _ = window.set_icon(tauri::include_image!("./icons/32x32.png"));
@@ -2763,8 +3623,39 @@ pub fn run() {
             get_open_on_startup,
             set_open_on_startup,
             repo_overview,
+            git_repo_health,
+            git_fsck,
+            git_repo_size_stats,
+            git_gc,
+            git_prune,
+            git_repack,
+            git_enable_perf_features,
+            git_largest_blobs,
+            git_history_remove_paths,
+            git_sparse_checkout_status,
+            git_sparse_checkout_init,
+            git_sparse_checkout_set,
+            git_sparse_checkout_add,
+            git_sparse_checkout_disable,
             list_commits,
             list_commits_full,
+            list_merge_side_commits,
+            git_commit_details,
+            list_repo_authors,
+            git_suggest_co_authors,
+            graph_snapshot,
+            graph_delta,
+            find_commit_in_graph,
+            get_graph_filter,
+            set_graph_filter,
+            get_repo_read_only,
+            set_repo_read_only,
+            get_protected_branches,
+            set_protected_branches,
+            get_startup_recovery,
+            git_remove_stale_index_lock,
+            export_repo_profile,
+            import_repo_profile,
             init_repo,
             open_in_file_explorer,
             reveal_in_file_explorer,
@@ -2772,29 +3663,80 @@ pub fn run() {
             git_trust_repo_global,
             git_trust_repo_session,
             git_set_user_identity,
+            git_signing_config,
+            git_setup_ssh_signing,
+            git_ssh_allowed_signers_add,
+            git_verify_range,
+            git_verify_tag,
+            git_config_list,
+            git_config_get,
+            git_config_set,
+            git_config_unset,
+            git_config_check,
+            git_config_apply_fix,
+            git_attributes_list,
+            git_attributes_set,
+            git_attributes_validate,
+            git_check_attr,
+            watch_repo_refs,
+            unwatch_repo_refs,
+            open_repository,
+            close_repository,
+            list_open_repositories,
             get_current_username,
             change_repo_ownership_to_current_user,
             git_resolve_ref,
+            complete_paths,
             git_ls_remote_heads,
             git_clone_repo,
+            git_clone_cancel,
+            git_credential_prompt_reply,
+            git_repo_shallow_info,
+            git_fetch_deepen,
+            git_submodule_list,
+            git_submodule_diff,
+            git_submodule_add,
+            git_submodule_add_predict,
+            git_submodule_remove,
+            git_submodule_init,
+            git_submodule_update,
+            git_submodule_sync,
             git_status,
             git_has_staged_changes,
             git_stage_paths,
             git_unstage_paths,
             git_stash_list,
             git_stash_show,
+            git_stash_changes,
+            git_stash_file_diff,
+            git_stash_file_vs_working_diff,
+            git_stash_file_content,
             git_stash_base_commit,
             git_stash_apply,
+            git_stash_pop,
+            git_stash_branch,
             git_stash_drop,
             git_stash_clear,
             git_stash_push_paths,
             git_stash_push_patch,
             git_commit_changes,
+            git_commit_numstat,
             git_commit_file_diff,
+            git_commit_file_diff_structured,
+            git_commit_file_diff_limited,
+            git_range_changes,
+            git_range_file_diff,
+            git_range_file_diff_structured,
+            git_file_diff_words,
+            git_commit_diff_stream,
+            git_commit_diff_stream_cancel,
+            git_diff_cache_invalidate,
             git_commit_file_content,
             git_working_file_diff,
             git_working_file_diff_unified,
+            git_working_file_diff_structured,
             git_working_file_content,
+            git_diff_context_lines,
             git_working_file_text_preview,
             git_head_file_content,
             git_head_file_text_preview,
@@ -2805,25 +3747,45 @@ pub fn run() {
             git_head_vs_working_text_diff,
             git_diff_no_index,
             git_working_file_image_base64,
+            git_commit_file_image_base64,
+            git_commit_parent_file_image_base64,
+            git_file_binary_info,
             git_launch_external_diff_working,
             git_launch_external_diff_commit,
+            detect_external_diff_tools,
             git_discard_working_path,
+            git_discard_all,
             git_delete_working_path,
             git_add_to_gitignore,
             git_commit,
+            git_commit_amend,
+            validate_commit_message,
+            git_commit_fixup,
             git_commit_patch,
             git_status_summary,
+            git_repo_state,
             git_ahead_behind,
             git_get_remote_url,
             git_set_remote_url,
+            git_remote_fetch_config,
+            git_set_remote_fetch_config,
+            git_remote_capabilities,
             git_push,
+            git_push_refspec,
+            git_push_delete_branch,
             git_fetch,
             git_checkout_commit,
             git_checkout_branch,
+            git_checkout_remote_branch,
             git_list_branches,
+            git_stale_branches,
             git_commit_summary,
             git_switch,
+            git_switch_predict,
+            git_checkout_tag,
             git_rename_branch,
+            git_set_upstream,
+            git_unset_upstream,
             git_create_branch_advanced,
             git_reset_hard,
             git_reset,
@@ -2831,11 +3793,17 @@ pub fn run() {
             git_commit_all,
             git_create_branch,
             git_delete_branch,
+            git_clean,
+            git_list_undo_points,
+            git_undo_last_operation,
             git_merge_branch,
             git_merge_branch_advanced,
             git_reflog,
             git_cherry_pick,
             git_cherry_pick_advanced,
+            git_cherry_pick_onto,
+            git_backport,
+            git_revert,
             git_am_abort,
             git_am_continue_with_message,
             git_branches_points_at,
@@ -2844,16 +3812,24 @@ pub fn run() {
             open_terminal_profile,
             git_pull,
             git_pull_rebase,
+            git_sync,
+            git_reset_to_upstream,
+            git_reset_to_upstream_preview,
+            git_cherry,
+            git_archive,
             git_merge_continue,
             git_merge_abort,
             git_rebase_continue,
             git_rebase_abort,
             git_rebase_onto,
+            git_rebase_onto_predict,
             git_rebase_skip,
             git_conflict_state,
             git_conflict_file_versions,
             git_conflict_take_ours,
             git_conflict_take_theirs,
+            git_conflict_resolutions,
+            git_conflict_reopen,
             git_conflict_resolve_rename,
             git_conflict_resolve_rename_with_content,
             git_conflict_apply_and_stage,
@@ -2865,11 +3841,17 @@ pub fn run() {
             git_rebase_continue_with_message,
             git_cherry_pick_abort,
             git_cherry_pick_continue_with_message,
+            git_revert_abort,
+            git_revert_continue,
             git_pull_predict,
             git_pull_predict_graph,
             git_pull_predict_conflict_preview,
             git_format_patch_to_file,
             git_predict_patch_file,
+            git_index_snapshot,
+            git_index_snapshot_info,
+            git_index_restore,
+            git_predict_merge_graph,
             git_predict_patch_graph,
             git_apply_patch_file,
             git_create_tag,
@@ -2878,12 +3860,17 @@ pub fn run() {
             git_list_tag_targets,
             git_list_remote_tag_targets,
             git_push_tags,
+            compare_releases,
+            suggest_next_version,
             git_rename_tag,
             git_interactive_rebase_commits,
             git_interactive_rebase_start,
             git_interactive_rebase_amend,
             git_interactive_rebase_continue,
             git_interactive_rebase_status,
+            git_rebase_upstream,
+            git_rewrite_author_preview,
+            git_rewrite_author,
             git_interactive_rebase_edit_files,
             git_read_working_file,
             git_write_working_file,
@@ -2891,6 +3878,13 @@ pub fn run() {
             git_delete_working_file,
             git_restore_working_file,
             git_log_search,
+            backup_repository,
+            restore_repository,
+            get_backup_schedule,
+            set_backup_schedule,
+            run_scheduled_backup_now,
+            list_scheduled_backups,
+            restore_scheduled_backup,
             get_system_info
         ])
         .run(tauri::generate_context!())
@@ -2949,17 +3943,23 @@ mod tests {
             repo_dir.to_string_lossy().to_string(),
             message.to_string(),
             vec![rel_path.to_string()],
+            None,
+            None,
         )
         .unwrap()
+        .commit
     }
 
     fn push_via_graphoria(repo_dir: &Path, remote: &str, branch: &str) {
-        git_push(
-            repo_dir.to_string_lossy().to_string(),
+        git_push_impl(
+            repo_dir.to_string_lossy().as_ref(),
             Some(remote.to_string()),
             Some(branch.to_string()),
             Some(false),
             Some(true),
+            None,
+            None,
+            |_, _, _| {},
         )
         .unwrap();
     }
@@ -3059,7 +4059,7 @@ mod tests {
 
         git_trust_repo_session(repo.to_string_lossy().to_string()).unwrap();
 
-        let commits = list_commits_impl_v2(repo.to_string_lossy().as_ref(), Some(50), false, "topo").unwrap();
+        let commits = list_commits_impl_v2(repo.to_string_lossy().as_ref(), Some(50), false, "topo", false).unwrap();
         assert!(commits.len() >= 2);
 
         let head_hash = run_git(repo.to_string_lossy().as_ref(), &["rev-parse", "HEAD"]).unwrap();
@@ -3120,14 +4120,14 @@ mod tests {
         git_trust_repo_session(repo_b.to_string_lossy().to_string()).unwrap();
         let before = run_git(repo_b.to_string_lossy().as_ref(), &["rev-parse", "HEAD"]).unwrap();
 
-        let result = git_pull(repo_b.to_string_lossy().to_string(), Some(String::from("origin"))).unwrap();
+        let result = git_pull_impl(repo_b.to_string_lossy().to_string(), Some(String::from("origin")), |_, _, _| {}).unwrap();
         assert_eq!(result.status, "ok");
         assert_eq!(result.operation, "merge");
 
         let after = run_git(repo_b.to_string_lossy().as_ref(), &["rev-parse", "HEAD"]).unwrap();
         assert_ne!(before.trim(), after.trim());
 
-        let commits = list_commits_impl_v2(repo_b.to_string_lossy().as_ref(), Some(50), false, "topo").unwrap();
+        let commits = list_commits_impl_v2(repo_b.to_string_lossy().as_ref(), Some(50), false, "topo", false).unwrap();
         assert!(commits.iter().any(|c| c.subject == "New commit"));
     }
 
@@ -3143,7 +4143,7 @@ mod tests {
         let alice_head = head_hash(&env.alice);
 
         trust_repo(&env.bob);
-        let result = git_pull(env.bob.to_string_lossy().to_string(), Some(String::from("origin"))).unwrap();
+        let result = git_pull_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), |_, _, _| {}).unwrap();
         assert_eq!(result.status, "ok");
         assert_eq!(result.operation, "merge");
 
@@ -3154,7 +4154,7 @@ mod tests {
         assert_eq!(parents.len(), 3);
         assert!(parents.iter().any(|p| p == &alice_head));
 
-        let commits = list_commits_impl_v2(env.bob.to_string_lossy().as_ref(), Some(50), false, "topo").unwrap();
+        let commits = list_commits_impl_v2(env.bob.to_string_lossy().as_ref(), Some(50), false, "topo", false).unwrap();
         assert!(commits.iter().any(|c| c.subject == "Bob local"));
         assert!(commits.iter().any(|c| c.subject == "Alice upstream"));
     }
@@ -3170,7 +4170,7 @@ mod tests {
         let alice_head = head_hash(&env.alice);
 
         trust_repo(&env.bob);
-        let result = git_pull_rebase(env.bob.to_string_lossy().to_string(), Some(String::from("origin"))).unwrap();
+        let result = git_pull_rebase_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), |_, _, _| {}).unwrap();
         assert_eq!(result.status, "ok");
         assert_eq!(result.operation, "rebase");
 
@@ -3181,7 +4181,7 @@ mod tests {
         assert_eq!(parents.len(), 2);
         assert_eq!(parents[1].trim(), alice_head.trim());
 
-        let commits = list_commits_impl_v2(env.bob.to_string_lossy().as_ref(), Some(50), false, "topo").unwrap();
+        let commits = list_commits_impl_v2(env.bob.to_string_lossy().as_ref(), Some(50), false, "topo", false).unwrap();
         assert!(commits.iter().any(|c| c.subject == "Bob local"));
         assert!(commits.iter().any(|c| c.subject == "Alice upstream"));
     }
@@ -3195,7 +4195,7 @@ mod tests {
         push_via_graphoria(&env.alice, "origin", env.branch.as_str());
 
         trust_repo(&env.bob);
-        let pred = git_pull_predict(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(false)).unwrap();
+        let pred = git_pull_predict_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(false)).unwrap();
         assert!(pred.behind > 0);
         assert!(pred.conflict_files.is_empty());
     }
@@ -3215,7 +4215,7 @@ mod tests {
         push_via_graphoria(&env.alice, "origin", env.branch.as_str());
 
         trust_repo(&env.bob);
-        let pred = git_pull_predict(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(false)).unwrap();
+        let pred = git_pull_predict_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(false)).unwrap();
         assert!(pred.behind > 0);
         assert!(pred.conflict_files.iter().any(|p| p == "conflict.txt"));
     }
@@ -3235,7 +4235,7 @@ mod tests {
         push_via_graphoria(&env.alice, "origin", env.branch.as_str());
 
         trust_repo(&env.bob);
-        let pred = git_pull_predict(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(true)).unwrap();
+        let pred = git_pull_predict_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(true)).unwrap();
         assert!(pred.behind > 0);
         assert!(pred.conflict_files.iter().any(|p| p == "conflict.txt"));
     }
@@ -3249,11 +4249,11 @@ mod tests {
         push_via_graphoria(&env.alice, "origin", env.branch.as_str());
 
         trust_repo(&env.bob);
-        let pred = git_pull_predict(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(true)).unwrap();
+        let pred = git_pull_predict_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(true)).unwrap();
         assert!(pred.behind > 0);
         assert!(pred.conflict_files.is_empty());
 
-        let result = git_pull_rebase(env.bob.to_string_lossy().to_string(), Some(String::from("origin"))).unwrap();
+        let result = git_pull_rebase_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), |_, _, _| {}).unwrap();
         assert_eq!(result.status, "ok");
         assert_eq!(result.operation, "rebase");
         let parents = head_parents(&env.bob);
@@ -3275,13 +4275,89 @@ mod tests {
         push_via_graphoria(&env.alice, "origin", env.branch.as_str());
 
         trust_repo(&env.bob);
-        let pred = git_pull_predict(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(true)).unwrap();
+        let pred = git_pull_predict_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), Some(true)).unwrap();
         assert!(pred.behind > 0);
         assert!(pred.conflict_files.iter().any(|p| p == "conflict.txt"));
 
-        let result = git_pull(env.bob.to_string_lossy().to_string(), Some(String::from("origin"))).unwrap();
+        let result = git_pull_impl(env.bob.to_string_lossy().to_string(), Some(String::from("origin")), |_, _, _| {}).unwrap();
         assert_eq!(result.operation, "merge");
         assert_eq!(result.status, "conflicts");
         assert!(result.conflict_files.iter().any(|p| p == "conflict.txt"));
     }
+
+    #[test]
+    fn test_git_revert_conflicts_then_continue() {
+        let td = TempDir::new().unwrap();
+        let repo = repo_path(&td, "repo");
+        init_repo(&repo);
+        trust_repo(&repo);
+
+        commit_file(&repo, "a.txt", "one\n", "One", ("Graphoria Test", "graphoria@test.local"));
+        let target = commit_file(&repo, "a.txt", "two\n", "Two", ("Graphoria Test", "graphoria@test.local"));
+        commit_file(&repo, "a.txt", "three\n", "Three", ("Graphoria Test", "graphoria@test.local"));
+
+        let repo_str = repo.to_string_lossy().to_string();
+        let result = git_revert(repo_str.clone(), vec![target], None).unwrap();
+        assert_eq!(result.operation, "revert");
+        assert_eq!(result.status, "conflicts");
+        assert!(result.conflict_files.iter().any(|p| p == "a.txt"));
+        assert!(is_revert_in_progress(repo_str.as_str()));
+
+        write_file(&repo, "a.txt", "resolved\n");
+        git(&repo, &["add", "--", "a.txt"]);
+        git_revert_continue(repo_str.clone(), Some(String::from("Revert two"))).unwrap();
+
+        assert!(!is_revert_in_progress(repo_str.as_str()));
+        assert_eq!(git(&repo, &["log", "-1", "--format=%s"]), "Revert two");
+    }
+
+    #[test]
+    fn test_undo_restores_deleted_branch_and_refuses_after_new_commits() {
+        let td = TempDir::new().unwrap();
+        let repo = repo_path(&td, "repo");
+        init_repo(&repo);
+        git(&repo, &["branch", "-M", "master"]);
+        trust_repo(&repo);
+        let repo_str = repo.to_string_lossy().to_string();
+
+        commit_via_graphoria(&repo, "a.txt", "a\n", "One");
+        git(&repo, &["checkout", "-b", "feature"]);
+        let feature_tip = commit_via_graphoria(&repo, "b.txt", "b\n", "Two");
+        git(&repo, &["checkout", "master"]);
+
+        git_delete_branch(repo_str.clone(), String::from("feature"), Some(true), None, None).unwrap();
+        git_undo_last_operation(repo_str.clone(), None).unwrap();
+        assert_eq!(git(&repo, &["rev-parse", "feature"]), feature_tip);
+
+        git_delete_branch(repo_str.clone(), String::from("feature"), Some(true), None, None).unwrap();
+        let master_tip = commit_via_graphoria(&repo, "c.txt", "c\n", "Three");
+        let err = git_undo_last_operation(repo_str.clone(), Some(true)).unwrap_err();
+        assert!(err.contains("has moved"), "{err}");
+        assert_eq!(git(&repo, &["rev-parse", "HEAD"]), master_tip);
+    }
+
+    #[test]
+    fn test_parse_fetch_ref_updates_reads_summary_lines() {
+        let output = "From https://example.com/repo
+   1a2b3c4..5d6e7f8  main       -> origin/main
+ + 1111111...2222222 topic      -> origin/topic  (forced update)
+ * [new branch]      feature    -> origin/feature
+ * [new tag]         v1.0       -> v1.0
+ - [deleted]         (none)     -> origin/gone
+ ! [rejected]        v0.9       -> v0.9  (would clobber existing tag)
+";
+        let updates = parse_fetch_ref_updates(output);
+        let kinds: Vec<&str> = updates.iter().map(|u| u.kind.as_str()).collect();
+        assert_eq!(kinds, ["updated", "forced", "new", "new_tag", "deleted", "rejected"]);
+        assert!(updates.iter().all(|u| u.source == "https://example.com/repo"));
+
+        assert_eq!((updates[0].old.as_deref(), updates[0].new.as_deref()), (Some("1a2b3c4"), Some("5d6e7f8")));
+        assert_eq!((updates[0].remote_ref.as_str(), updates[0].local_ref.as_str()), ("main", "origin/main"));
+
+        assert_eq!((updates[1].old.as_deref(), updates[1].new.as_deref()), (Some("1111111"), Some("2222222")));
+        assert_eq!(updates[1].note.as_deref(), Some("forced update"));
+
+        assert_eq!((updates[2].old.as_ref(), updates[2].remote_ref.as_str()), (None, "feature"));
+        assert_eq!(updates[5].note.as_deref(), Some("would clobber existing tag"));
+    }
 }
@@ -2,5 +2,8 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    if let Some(code) = tauri_app_lib::askpass_main() {
+        std::process::exit(code);
+    }
     tauri_app_lib::run()
 }
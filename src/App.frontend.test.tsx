@@ -12,9 +12,12 @@ function makeCommit(partial: Partial<GitCommit>): GitCommit {
     author: partial.author ?? "",
     author_email: partial.author_email ?? "",
     date: partial.date ?? "2026-01-01T00:00:00Z",
+    committer_date: partial.committer_date ?? partial.date ?? "2026-01-01T00:00:00Z",
     subject: partial.subject ?? "",
     refs: partial.refs ?? "",
     is_head: partial.is_head ?? false,
+    is_pushed: partial.is_pushed ?? false,
+    wip: partial.wip ?? null,
   };
 }
 
@@ -53,7 +56,7 @@ describe("Graphoria frontend flow", () => {
     };
 
     invokeMock.mockImplementation(async (cmd: string, args?: any) => {
-      if (cmd === "git_check_worktree") return;
+      if (cmd === "open_repository") return;
 
       if (cmd === "list_commits") {
         expect(args?.repoPath).toBe(repoPath);
@@ -33,6 +33,8 @@ import { CommitLaneSvg } from "./features/commits/CommitLaneSvg";
 import {
   computeCommitLaneRows,
   computeCompactLaneByHashForGraph,
+  expandedMergeHashes,
+  withExpandedMerges,
   type CommitLaneRow,
 } from "./features/commits/lanes";
 import { useCommitController } from "./features/commits/useCommitController";
@@ -98,6 +100,7 @@ import {
   gitSwitch,
   gitPush,
   gitSetRemoteUrl,
+  listMergeSideCommits,
   repoOverview,
 } from "./api/git";
 import { revealInFileExplorer } from "./api/system";
@@ -131,6 +134,7 @@ import { PreviewZoomModal } from "./components/modals/PreviewZoomModal";
 import { PullConflictModal } from "./components/modals/PullConflictModal";
 import { ConflictResolverModal } from "./components/modals/ConflictResolverModal";
 import { ContinueAfterConflictsModal } from "./components/modals/ContinueAfterConflictsModal";
+import { CredentialPromptModal } from "./components/modals/CredentialPromptModal";
 import { CherryStepsModal } from "./components/modals/CherryStepsModal";
 import { PullPredictModal } from "./components/modals/PullPredictModal";
 import { CreateBranchModal } from "./components/modals/CreateBranchModal";
@@ -160,6 +164,7 @@ import type {
   GitCommit,
   GitCloneProgressEvent,
   GitCommitSummary,
+  GitTransferProgressEvent,
   GitPatchPredictGraphResult,
   GitStashEntry,
   GitStatusSummary,
@@ -214,6 +219,7 @@ function App() {
   const [tagsExpandedByRepo, setTagsExpandedByRepo] = useState<Record<string, boolean>>({});
   const [overviewByRepo, setOverviewByRepo] = useState<Record<string, RepoOverview | undefined>>({});
   const [commitsByRepo, setCommitsByRepo] = useState<Record<string, GitCommit[] | undefined>>({});
+  const [mergeSideCommitsByRepo, setMergeSideCommitsByRepo] = useState<Record<string, Record<string, GitCommit[] | undefined> | undefined>>({});
   const [commitsFullByRepo, setCommitsFullByRepo] = useState<Record<string, boolean>>({});
   const [commitsFullLoadingByRepo, setCommitsFullLoadingByRepo] = useState<Record<string, boolean>>({});
   const [commitsHasMoreByRepo, setCommitsHasMoreByRepo] = useState<Record<string, boolean | undefined>>({});
@@ -524,6 +530,7 @@ function App() {
 
   const [pullMenuOpen, setPullMenuOpen] = useState(false);
   const [pullBusy, setPullBusy] = useState(false);
+  const [transferProgress, setTransferProgress] = useState<GitTransferProgressEvent | null>(null);
   const [pullErrorByRepo, setPullErrorByRepo] = useState<Record<string, string>>({});
 
   const [terminalMenuOpen, setTerminalMenuOpen] = useState(false);
@@ -1191,7 +1198,67 @@ function App() {
     };
   }, [cloneModalOpen]);
 
-  const commitsAll = commitsByRepo[activeRepoPath] ?? [];
+  useEffect(() => {
+    setTransferProgress(null);
+    if (!activeRepoPath) return;
+
+    let alive = true;
+    let unlisten: (() => void) | null = null;
+    let clearTimer: number | null = null;
+    void listen<GitTransferProgressEvent>("git_transfer_progress", (event) => {
+      if (event.payload.repo_path !== activeRepoPath) return;
+      setTransferProgress(event.payload);
+      // git sends no final event, so the indicator goes away once updates stop.
+      if (clearTimer !== null) window.clearTimeout(clearTimer);
+      clearTimer = window.setTimeout(() => setTransferProgress(null), 1500);
+    }).then((fn) => {
+      if (!alive) {
+        fn();
+        return;
+      }
+      unlisten = fn;
+    });
+
+    return () => {
+      alive = false;
+      if (clearTimer !== null) window.clearTimeout(clearTimer);
+      if (unlisten) unlisten();
+    };
+  }, [activeRepoPath]);
+
+  const commitsLoaded = commitsByRepo[activeRepoPath];
+  const mergeSideCommits = mergeSideCommitsByRepo[activeRepoPath];
+
+  // In first-parent mode merges are collapsed; expanded ones show their side commits below.
+  const commitsAll = useMemo(() => {
+    const commits = commitsLoaded ?? [];
+    if (commitsHistoryOrder !== "first_parent" || !mergeSideCommits) return commits;
+    return withExpandedMerges(commits, mergeSideCommits);
+  }, [commitsHistoryOrder, commitsLoaded, mergeSideCommits]);
+
+  const expandedCommitHashes = useMemo(
+    () => (commitsHistoryOrder === "first_parent" && mergeSideCommits ? expandedMergeHashes(mergeSideCommits) : new Set<string>()),
+    [commitsHistoryOrder, mergeSideCommits],
+  );
+
+  async function toggleMergeExpanded(hash: string) {
+    const repoPath = activeRepoPath;
+    if (!repoPath) return;
+    if (mergeSideCommitsByRepo[repoPath]?.[hash]) {
+      setMergeSideCommitsByRepo((prev) => {
+        const next = { ...(prev[repoPath] ?? {}) };
+        delete next[hash];
+        return { ...prev, [repoPath]: next };
+      });
+      return;
+    }
+    try {
+      const side = await listMergeSideCommits({ repoPath, merge: hash, maxCount: 200 });
+      setMergeSideCommitsByRepo((prev) => ({ ...prev, [repoPath]: { ...(prev[repoPath] ?? {}), [hash]: side } }));
+    } catch (e) {
+      setError(typeof e === "string" ? e : JSON.stringify(e));
+    }
+  }
 
   const commitSearchAuthors = useMemo(() => {
     const set = new Set<string>();
@@ -1405,8 +1472,8 @@ function App() {
   const commitLaneLayout = useMemo(() => {
     if (viewMode !== "commits") return { rows: [] as CommitLaneRow[], maxLanes: 0 };
     if (commitsAll.length === 0) return { rows: [] as CommitLaneRow[], maxLanes: 0 };
-    return computeCommitLaneRows(commitsAll, commitsHistoryOrder);
-  }, [commitsAll, commitsHistoryOrder, viewMode]);
+    return computeCommitLaneRows(commitsAll, commitsHistoryOrder, expandedCommitHashes);
+  }, [commitsAll, commitsHistoryOrder, expandedCommitHashes, viewMode]);
 
   const commitLaneRowByHash = useMemo(() => {
     const m = new Map<string, CommitLaneRow>();
@@ -1880,7 +1947,9 @@ function App() {
       .filter((b) => b.kind === "local")
       .map((b) => {
         const dt = new Date(b.committer_date);
-        const time = dt.getTime();
+        // A branch worked on locally (checked out, reset, rebased) is not stale even if its tip is old.
+        const activity = typeof b.last_activity === "number" ? b.last_activity * 1000 : NaN;
+        const time = Number.isFinite(activity) ? Math.max(dt.getTime() || 0, activity) : dt.getTime();
         const valid = Number.isFinite(time);
         const daysOld = valid ? Math.max(0, Math.floor((now - time) / 86_400_000)) : null;
         return {
@@ -3948,7 +4017,7 @@ function App() {
     const commits = commitsAll;
     const present = new Set(commits.map((c) => c.hash));
 
-    const laneByHash = computeCompactLaneByHashForGraph(commits, commitsHistoryOrder);
+    const laneByHash = computeCompactLaneByHashForGraph(commits, commitsHistoryOrder, expandedCommitHashes);
 
     const laneStep = Math.max(300, graphSettings.nodeSep);
     const rowStep = Math.max(90, graphSettings.rankSep);
@@ -3988,7 +4057,8 @@ function App() {
     }
 
     for (const c of commits) {
-      const parents = commitsHistoryOrder === "first_parent" ? (c.parents[0] ? [c.parents[0]] : []) : c.parents;
+      const collapsed = commitsHistoryOrder === "first_parent" && !expandedCommitHashes.has(c.hash);
+      const parents = collapsed ? (c.parents[0] ? [c.parents[0]] : []) : c.parents;
       for (const p of parents) {
         if (!p) continue;
         if (!present.has(p)) continue;
@@ -4010,7 +4080,15 @@ function App() {
       nodes: Array.from(nodes.values()),
       edges,
     };
-  }, [commitsAll, commitsHistoryOrder, graphSettings.edgeDirection, graphSettings.nodeSep, graphSettings.rankDir, graphSettings.rankSep]);
+  }, [
+    commitsAll,
+    commitsHistoryOrder,
+    expandedCommitHashes,
+    graphSettings.edgeDirection,
+    graphSettings.nodeSep,
+    graphSettings.rankDir,
+    graphSettings.rankSep,
+  ]);
 
   const { graphRef, zoomPct, requestAutoCenter, focusOnHash, focusOnHead, zoomBy } = useCyGraph({
     viewMode,
@@ -4759,6 +4837,7 @@ function App() {
           openTerminalDefault={() => void openTerminalProfile(terminalSettings.defaultProfileId)}
           openTerminalSettings={() => setSettingsOpen(true)}
           indicatorsUpdating={indicatorsUpdating}
+          transferProgress={transferProgress}
           error={parsedError.summary}
           errorHasDetails={parsedError.hasDetails}
           onOpenErrorDetails={() => setErrorDetailsOpen(true)}
@@ -5044,6 +5123,8 @@ function App() {
                                   isHead={c.is_head}
                                   showMergeStub={commitsHistoryOrder === "first_parent" && c.parents.length > 1}
                                   mergeParentCount={c.parents.length}
+                                  mergeExpanded={Boolean(mergeSideCommits?.[c.hash])}
+                                  onToggleMerge={() => void toggleMergeExpanded(c.hash)}
                                   nodeBg={commitLaneNodeBg}
                                   palette={commitLanePalette}
                                   refMarkers={parseRefs(c.refs, overview?.remotes ?? [])}
@@ -5127,6 +5208,12 @@ function App() {
         commitContextBranchesLoading={commitContextBranchesLoading}
         commitContextBranches={commitContextBranches}
         changedCount={changedCount}
+        mergeToggle={(() => {
+          if (!commitContextMenu || commitsHistoryOrder !== "first_parent") return null;
+          const c = commitsAll.find((x) => x.hash === commitContextMenu.hash);
+          if (!c || c.parents.length < 2) return null;
+          return mergeSideCommits?.[c.hash] ? "collapse" : "expand";
+        })()}
         pickPreferredBranch={pickPreferredBranch}
         onShowChanges={(hash) => {
           setCommitContextMenu(null);
@@ -5136,6 +5223,10 @@ function App() {
           void copyText(hash);
           setCommitContextMenu(null);
         }}
+        onToggleMerge={(hash) => {
+          setCommitContextMenu(null);
+          void toggleMergeExpanded(hash);
+        }}
         onCheckoutCommit={(hash) => {
           setCommitContextMenu(null);
           void checkoutCommit(hash);
@@ -6002,6 +6093,8 @@ function App() {
       ) : null}
 
       {aboutOpen ? <AboutModal onClose={() => setAboutOpen(false)} /> : null}
+
+      <CredentialPromptModal />
     </div>
   );
 }
@@ -755,6 +755,7 @@ export default function SettingsModal(props: { open: boolean; activeRepoPath: st
                     >
                       <option value="topo">Topological (current)</option>
                       <option value="date">Date order (compact)</option>
+                      <option value="author_date">Author date order</option>
                       <option value="first_parent">First parent (very compact)</option>
                     </select>,
                     "Affects the commit list order and the compactness of the graph (Graph and Commits views).",
@@ -1,18 +1,43 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   GitAheadBehind,
+  GitCherryCommit,
+  GitCommitDetails,
+  GitDiffPage,
+  GitDiffPageOptions,
+  GitDryRunReport,
+  GitNumstatEntry,
+  GitWordDiff,
+  RepoHandleInfo,
+  GitRemoteCheckoutResult,
   GitBranchInfo,
+  GitStaleBranch,
+  GitSubmoduleChange,
+  GitSubmoduleDiff,
   GitCommit,
+  GitCommitResult,
   GitCommitSummary,
   GitContinueInfo,
+  GitFetchResult,
+  GitFsckReport,
+  GitHistoryRewriteResult,
+  GitLargestBlobsReport,
+  GitMaintenanceResult,
   GitConflictFileVersions,
   GitConflictState,
   GitPatchPredictResult,
   GitPatchPredictGraphResult,
+  GitPerfFeaturesResult,
+  GitRepoSizeStats,
   GitStatusEntry,
+  GitStatusReport,
   GitStatusSummary,
+  GitRepoState,
+  GitStashApplyResult,
   GitStashEntry,
   GitTagTarget,
+  GitCreateTagResult,
+  GitVersionSuggestion,
   InteractiveRebaseCommitInfo,
   InteractiveRebaseResult,
   InteractiveRebaseStatusInfo,
@@ -21,6 +46,7 @@ import type {
   PullPredictResult,
   PullResult,
   RepoOverview,
+  RepoRecoveryReport,
 } from "../types/git";
 import type { GitHistoryOrder } from "../appSettingsStore";
 
@@ -28,6 +54,74 @@ export function gitCheckWorktree(repoPath: string) {
   return invoke<void>("git_check_worktree", { repoPath });
 }
 
+export function gitCredentialPromptReply(params: { id: number; value: string | null }) {
+  return invoke<boolean>("git_credential_prompt_reply", params);
+}
+
+export function openRepositoryHandle(repoPath: string) {
+  return invoke<RepoHandleInfo>("open_repository", { repoPath });
+}
+
+export function closeRepositoryHandle(repoPath: string) {
+  return invoke<boolean>("close_repository", { repoPath });
+}
+
+export function getRepoReadOnly(repoPath: string) {
+  return invoke<boolean>("get_repo_read_only", { repoPath });
+}
+
+export function setRepoReadOnly(repoPath: string, readOnly: boolean) {
+  return invoke<boolean>("set_repo_read_only", { repoPath, readOnly });
+}
+
+export function getProtectedBranches(repoPath: string) {
+  return invoke<string[]>("get_protected_branches", { repoPath });
+}
+
+export function setProtectedBranches(repoPath: string, patterns: string[]) {
+  return invoke<string[]>("set_protected_branches", { repoPath, patterns });
+}
+
+export function getStartupRecovery() {
+  return invoke<RepoRecoveryReport[]>("get_startup_recovery");
+}
+
+export function gitRemoveStaleIndexLock(repoPath: string) {
+  return invoke<boolean>("git_remove_stale_index_lock", { repoPath });
+}
+
+export function gitFsck(repoPath: string) {
+  return invoke<GitFsckReport>("git_fsck", { repoPath });
+}
+
+export function gitRepoSizeStats(repoPath: string) {
+  return invoke<GitRepoSizeStats>("git_repo_size_stats", { repoPath });
+}
+
+export function gitLargestBlobs(repoPath: string, topN?: number) {
+  return invoke<GitLargestBlobsReport>("git_largest_blobs", { repoPath, topN });
+}
+
+export function gitHistoryRemovePaths(repoPath: string, paths: string[], backupFile: string, dryRun?: boolean) {
+  return invoke<GitHistoryRewriteResult | GitDryRunReport>("git_history_remove_paths", { repoPath, paths, backupFile, dryRun });
+}
+
+export function gitGc(repoPath: string, aggressive?: boolean) {
+  return invoke<GitMaintenanceResult>("git_gc", { repoPath, aggressive });
+}
+
+export function gitPrune(repoPath: string, expire?: string) {
+  return invoke<GitMaintenanceResult>("git_prune", { repoPath, expire });
+}
+
+export function gitRepack(repoPath: string) {
+  return invoke<GitMaintenanceResult>("git_repack", { repoPath });
+}
+
+export function gitEnablePerfFeatures(repoPath: string) {
+  return invoke<GitPerfFeaturesResult>("git_enable_perf_features", { repoPath });
+}
+
 export function repoOverview(repoPath: string) {
   return invoke<RepoOverview>("repo_overview", { repoPath });
 }
@@ -36,6 +130,10 @@ export function gitStatusSummary(repoPath: string) {
   return invoke<GitStatusSummary>("git_status_summary", { repoPath });
 }
 
+export function gitRepoState(repoPath: string) {
+  return invoke<GitRepoState>("git_repo_state", { repoPath });
+}
+
 export function gitGetRemoteUrl(repoPath: string, remoteName: string) {
   return invoke<string | null>("git_get_remote_url", { repoPath, remoteName });
 }
@@ -44,8 +142,16 @@ export function gitAheadBehind(repoPath: string, remoteName: string) {
   return invoke<GitAheadBehind>("git_ahead_behind", { repoPath, remoteName });
 }
 
-export function gitFetch(repoPath: string, remoteName: string) {
-  return invoke<string>("git_fetch", { repoPath, remoteName });
+export function gitCherry(params: { repoPath: string; upstream?: string; head?: string }) {
+  return invoke<GitCherryCommit[]>("git_cherry", params);
+}
+
+export function gitFetch(
+  repoPath: string,
+  remoteName: string,
+  options?: { all?: boolean; prune?: boolean; tags?: boolean; pruneTags?: boolean },
+) {
+  return invoke<GitFetchResult>("git_fetch", { repoPath, remoteName, ...options });
 }
 
 export function gitStashList(repoPath: string) {
@@ -57,18 +163,38 @@ export function listCommits(params: {
   maxCount: number;
   onlyHead: boolean;
   historyOrder: GitHistoryOrder;
+  firstParent?: boolean;
+  includeWip?: boolean;
 }) {
   return invoke<GitCommit[]>("list_commits", params);
 }
 
-export function listCommitsFull(params: { repoPath: string; onlyHead: boolean; historyOrder: GitHistoryOrder }) {
+export function listCommitsFull(params: {
+  repoPath: string;
+  onlyHead: boolean;
+  historyOrder: GitHistoryOrder;
+  firstParent?: boolean;
+  includeWip?: boolean;
+}) {
   return invoke<GitCommit[]>("list_commits_full", params);
 }
 
+export function listMergeSideCommits(params: { repoPath: string; merge: string; maxCount?: number }) {
+  return invoke<GitCommit[]>("list_merge_side_commits", params);
+}
+
+export function gitCommitDetails(repoPath: string, hash: string) {
+  return invoke<GitCommitDetails>("git_commit_details", { repoPath, hash });
+}
+
 export function gitListBranches(params: { repoPath: string; includeRemote: boolean }) {
   return invoke<GitBranchInfo[]>("git_list_branches", params);
 }
 
+export function gitStaleBranches(repoPath: string, olderThanDays: number) {
+  return invoke<GitStaleBranch[]>("git_stale_branches", { repoPath, olderThanDays });
+}
+
 export function gitSwitch(params: {
   repoPath: string;
   branch: string;
@@ -76,18 +202,44 @@ export function gitSwitch(params: {
   force?: boolean;
   startPoint?: string;
   track?: boolean;
+  autostash?: boolean;
 }) {
-  return invoke<string>("git_switch", params);
+  return invoke<PullResult>("git_switch", params);
 }
 
-export function gitRenameBranch(params: { repoPath: string; oldName: string; newName: string }) {
+export function gitRenameBranch(params: {
+  repoPath: string;
+  oldName: string;
+  newName: string;
+  renameOnRemote?: boolean;
+  remoteName?: string;
+  allowProtected?: boolean;
+}) {
   return invoke<string>("git_rename_branch", params);
 }
 
-export function gitDeleteBranch(params: { repoPath: string; branch: string; force: boolean }) {
+export function gitSetUpstream(params: { repoPath: string; branch: string; upstream: string }) {
+  return invoke<string>("git_set_upstream", params);
+}
+
+export function gitUnsetUpstream(params: { repoPath: string; branch: string }) {
+  return invoke<string>("git_unset_upstream", params);
+}
+
+export function gitDeleteBranch(params: { repoPath: string; branch: string; force: boolean; allowProtected?: boolean }) {
   return invoke<string>("git_delete_branch", params);
 }
 
+export function gitDeleteRemoteBranch(params: {
+  repoPath: string;
+  remoteName?: string;
+  branch: string;
+  dryRun?: boolean;
+  allowProtected?: boolean;
+}) {
+  return invoke<string | GitDryRunReport>("git_push_delete_branch", params);
+}
+
 export function gitCreateBranchAdvanced(params: {
   repoPath: string;
   branch: string;
@@ -99,8 +251,12 @@ export function gitCreateBranchAdvanced(params: {
   return invoke<string>("git_create_branch_advanced", params);
 }
 
-export function gitStatus(repoPath: string) {
-  return invoke<GitStatusEntry[]>("git_status", { repoPath });
+export function gitStatusReport(repoPath: string) {
+  return invoke<GitStatusReport>("git_status", { repoPath });
+}
+
+export function gitStatus(repoPath: string): Promise<GitStatusEntry[]> {
+  return gitStatusReport(repoPath).then((r) => r.entries);
 }
 
 export function gitDiscardWorkingPath(params: { repoPath: string; path: string; isUntracked: boolean }) {
@@ -119,7 +275,12 @@ export function gitStashBaseCommit(params: { repoPath: string; stashRef: string
   return invoke<string>("git_stash_base_commit", params);
 }
 
-export function gitReset(params: { repoPath: string; mode: "soft" | "mixed" | "hard"; target: string }) {
+export function gitReset(params: {
+  repoPath: string;
+  mode: "soft" | "mixed" | "hard";
+  target: string;
+  allowProtected?: boolean;
+}) {
   return invoke<string>("git_reset", params);
 }
 
@@ -135,8 +296,26 @@ export function gitCheckoutCommit(params: { repoPath: string; commit: string })
   return invoke<string>("git_checkout_commit", params);
 }
 
-export function gitCheckoutBranch(params: { repoPath: string; branch: string }) {
-  return invoke<string>("git_checkout_branch", params);
+export function gitCheckoutBranch(params: { repoPath: string; branch: string; force?: boolean }) {
+  return invoke<PullResult>("git_checkout_branch", params);
+}
+
+export function gitCheckoutRemoteBranch(params: {
+  repoPath: string;
+  remoteBranch: string;
+  localName?: string;
+  force?: boolean;
+}) {
+  return invoke<GitRemoteCheckoutResult>("git_checkout_remote_branch", params);
+}
+
+export function gitCheckoutTag(params: {
+  repoPath: string;
+  tag: string;
+  createBranch?: string;
+  autostash?: boolean;
+}) {
+  return invoke<PullResult>("git_checkout_tag", params);
 }
 
 export function gitCommitAll(params: { repoPath: string; message: string }) {
@@ -170,8 +349,27 @@ export function gitCreateTag(params: {
   annotated: boolean;
   message?: string;
   force: boolean;
+  release?: { changelog?: boolean; archive?: boolean; archive_format?: string; output_dir?: string };
+  sign?: boolean;
+}) {
+  return invoke<GitCreateTagResult>("git_create_tag", params);
+}
+
+export function suggestNextVersion(params: {
+  repoPath: string;
+  scheme?: "conventional" | "major" | "minor" | "patch";
+}) {
+  return invoke<GitVersionSuggestion>("suggest_next_version", params);
+}
+
+export function gitArchive(params: {
+  repoPath: string;
+  rev: string;
+  format: "zip" | "tar" | "tar.gz";
+  outPath: string;
+  prefix?: string;
 }) {
-  return invoke<string>("git_create_tag", params);
+  return invoke<{ path: string; format: string; commit: string; files: number; bytes: number }>("git_archive", params);
 }
 
 export function gitDeleteTag(params: { repoPath: string; tag: string }) {
@@ -260,7 +458,7 @@ export function gitCherryPickAbort(repoPath: string) {
   return invoke<string>("git_cherry_pick_abort", { repoPath });
 }
 
-export function gitCherryPickContinueWithMessage(params: { repoPath: string; message: string }) {
+export function gitCherryPickContinueWithMessage(params: { repoPath: string; message: string; coAuthors?: string[] }) {
   return invoke<string>("git_cherry_pick_continue_with_message", params);
 }
 
@@ -304,7 +502,7 @@ export function gitRebaseAbort(repoPath: string) {
   return invoke<string>("git_rebase_abort", { repoPath });
 }
 
-export function gitRebaseOnto(params: { repoPath: string; target: string }) {
+export function gitRebaseOnto(params: { repoPath: string; target: string; upstream?: string; branch?: string }) {
   return invoke<PullResult>("git_rebase_onto", params);
 }
 
@@ -344,8 +542,8 @@ export function gitConflictApply(params: { repoPath: string; path: string; conte
   return invoke<string>("git_conflict_apply", params);
 }
 
-export function gitContinueInfo(repoPath: string) {
-  return invoke<GitContinueInfo>("git_continue_info", { repoPath });
+export function gitContinueInfo(repoPath: string, stripComments?: boolean) {
+  return invoke<GitContinueInfo>("git_continue_info", { repoPath, stripComments });
 }
 
 export function gitContinueFileDiff(params: { repoPath: string; path: string; unified: number }) {
@@ -356,11 +554,11 @@ export function gitContinueRenameDiff(params: { repoPath: string; oldPath: strin
   return invoke<string>("git_continue_rename_diff", params);
 }
 
-export function gitMergeContinueWithMessage(params: { repoPath: string; message: string }) {
+export function gitMergeContinueWithMessage(params: { repoPath: string; message: string; coAuthors?: string[] }) {
   return invoke<string>("git_merge_continue_with_message", params);
 }
 
-export function gitRebaseContinueWithMessage(params: { repoPath: string; message: string }) {
+export function gitRebaseContinueWithMessage(params: { repoPath: string; message: string; coAuthors?: string[] }) {
   return invoke<string>("git_rebase_continue_with_message", params);
 }
 
@@ -385,11 +583,20 @@ export function gitCloneRepo(params: {
   bare: boolean;
   origin?: string;
   singleBranch: boolean;
+  filter?: string;
 }) {
   return invoke<string>("git_clone_repo", params);
 }
 
-export function gitStashPushPaths(params: { repoPath: string; message: string; paths: string[]; includeUntracked: boolean }) {
+export function gitStashPushPaths(params: {
+  repoPath: string;
+  message: string;
+  paths: string[];
+  includeUntracked: boolean;
+  includeStagedOnly?: boolean;
+  keepIndex?: boolean;
+  includeAll?: boolean;
+}) {
   return invoke<string>("git_stash_push_paths", params);
 }
 
@@ -401,8 +608,37 @@ export function gitStashShow(params: { repoPath: string; stashRef: string }) {
   return invoke<string>("git_stash_show", params);
 }
 
+export function gitStashChanges(params: { repoPath: string; stashRef: string }) {
+  return invoke<Array<{ status: string; path: string; old_path?: string | null }>>("git_stash_changes", params);
+}
+
+export function gitStashFileDiff(params: { repoPath: string; stashRef: string; path: string }) {
+  return invoke<string>("git_stash_file_diff", params);
+}
+
+export function gitStashFileVsWorkingDiff(params: { repoPath: string; stashRef: string; path: string; unified: number }) {
+  return invoke<string>("git_stash_file_vs_working_diff", params);
+}
+
+export function gitStashFileContent(params: {
+  repoPath: string;
+  stashRef: string;
+  path: string;
+  source?: "worktree" | "index" | "base" | "untracked";
+}) {
+  return invoke<string>("git_stash_file_content", params);
+}
+
 export function gitStashApply(params: { repoPath: string; stashRef: string }) {
-  return invoke<string>("git_stash_apply", params);
+  return invoke<GitStashApplyResult>("git_stash_apply", params);
+}
+
+export function gitStashPop(params: { repoPath: string; stashRef: string }) {
+  return invoke<GitStashApplyResult>("git_stash_pop", params);
+}
+
+export function gitStashBranch(params: { repoPath: string; stashRef: string; branchName: string }) {
+  return invoke<string>("git_stash_branch", params);
 }
 
 export function gitStashDrop(params: { repoPath: string; stashRef: string }) {
@@ -425,15 +661,23 @@ export function gitUnstagePaths(params: { repoPath: string; paths: string[] }) {
   return invoke<string>("git_unstage_paths", params);
 }
 
-export function gitCommit(params: { repoPath: string; message: string; paths: string[] }) {
-  return invoke<string>("git_commit", params);
+export function gitCommit(params: { repoPath: string; message: string; paths: string[]; noVerify?: boolean; coAuthors?: string[] }) {
+  return invoke<GitCommitResult>("git_commit", params);
 }
 
  export function gitCommitPatch(params: { repoPath: string; message: string; patches: Array<{ path: string; patch: string }> }) {
    return invoke<string>("git_commit_patch", params);
  }
 
-export function gitPush(params: { repoPath: string; remoteName: string; branch?: string; force: boolean; withLease?: boolean }) {
+export function gitPush(params: {
+  repoPath: string;
+  remoteName: string;
+  branch?: string;
+  force: boolean;
+  withLease?: boolean;
+  followTags?: boolean;
+  allowProtected?: boolean;
+}) {
   return invoke<string>("git_push", params);
 }
 
@@ -458,17 +702,87 @@ export function initRepo(repoPath: string) {
 }
 
 export function gitCommitChanges(params: { repoPath: string; commit: string }) {
-  return invoke<Array<{ status: string; path: string; old_path?: string | null }>>("git_commit_changes", params);
+  return invoke<Array<{ status: string; path: string; old_path?: string | null; submodule?: GitSubmoduleChange | null }>>("git_commit_changes", params);
+}
+
+export function gitCommitNumstat(params: { repoPath: string; commit: string }) {
+  return invoke<GitNumstatEntry[]>("git_commit_numstat", params);
+}
+
+export function gitRangeChanges(params: { repoPath: string; fromRev: string; toRev: string; threeDot?: boolean }) {
+  return invoke<Array<{ status: string; path: string; old_path?: string | null; submodule?: GitSubmoduleChange | null }>>("git_range_changes", params);
+}
+
+export function gitRangeFileDiff(params: {
+  repoPath: string;
+  fromRev: string;
+  toRev: string;
+  path: string;
+  threeDot?: boolean;
+  functionContext?: boolean;
+  maxBytes?: number;
+  maxLines?: number;
+}) {
+  return invoke<string>("git_range_file_diff", params);
+}
+
+export function gitRangeFileDiffStructured(params: {
+  repoPath: string;
+  fromRev: string;
+  toRev: string;
+  path: string;
+  threeDot?: boolean;
+  functionContext?: boolean;
+  page?: GitDiffPageOptions;
+}) {
+  return invoke<GitDiffPage>("git_range_file_diff_structured", params);
+}
+
+export function gitFileDiffWords(params: {
+  repoPath: string;
+  path: string;
+  commit?: string;
+  fromRev?: string;
+  toRev?: string;
+  granularity?: "word" | "char";
+}) {
+  return invoke<GitWordDiff>("git_file_diff_words", params);
+}
+
+export function gitSubmoduleDiff(params: {
+  repoPath: string;
+  path: string;
+  oldCommit?: string | null;
+  newCommit?: string | null;
+}) {
+  return invoke<GitSubmoduleDiff>("git_submodule_diff", params);
 }
 
 export function gitCommitFileContent(params: { repoPath: string; commit: string; path: string }) {
   return invoke<string>("git_commit_file_content", params);
 }
 
-export function gitCommitFileDiff(params: { repoPath: string; commit: string; path: string }) {
+export function gitCommitFileDiff(params: {
+  repoPath: string;
+  commit: string;
+  path: string;
+  functionContext?: boolean;
+  maxBytes?: number;
+  maxLines?: number;
+}) {
   return invoke<string>("git_commit_file_diff", params);
 }
 
+export function gitCommitFileDiffStructured(params: {
+  repoPath: string;
+  commit: string;
+  path: string;
+  functionContext?: boolean;
+  page?: GitDiffPageOptions;
+}) {
+  return invoke<GitDiffPage>("git_commit_file_diff_structured", params);
+}
+
 export function gitLaunchExternalDiffCommit(params: {
   repoPath: string;
   commit: string;
@@ -488,7 +802,12 @@ export function gitInteractiveRebaseCommits(params: { repoPath: string; base?: s
   return invoke<InteractiveRebaseCommitInfo[]>("git_interactive_rebase_commits", params);
 }
 
-export function gitInteractiveRebaseStart(params: { repoPath: string; base: string; todoEntries: InteractiveRebaseTodoEntry[] }) {
+export function gitInteractiveRebaseStart(params: {
+  repoPath: string;
+  base: string;
+  todoEntries: InteractiveRebaseTodoEntry[];
+  allowProtected?: boolean;
+}) {
   return invoke<InteractiveRebaseResult>("git_interactive_rebase_start", params);
 }
 
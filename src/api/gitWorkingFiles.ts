@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { GitBinaryFileInfo, GitContextLines, GitDiffPage, GitDiffPageOptions } from "../types/git";
 
 export function gitLaunchExternalDiffWorking(params: {
   repoPath: string;
@@ -13,6 +14,23 @@ export function gitWorkingFileImageBase64(params: { repoPath: string; path: stri
   return invoke<string>("git_working_file_image_base64", params);
 }
 
+export function gitCommitFileImageBase64(params: { repoPath: string; commit: string; path: string }) {
+  return invoke<string>("git_commit_file_image_base64", params);
+}
+
+export function gitCommitParentFileImageBase64(params: {
+  repoPath: string;
+  commit: string;
+  path: string;
+  oldPath?: string | null;
+}) {
+  return invoke<string>("git_commit_parent_file_image_base64", params);
+}
+
+export function gitFileBinaryInfo(params: { repoPath: string; rev?: string; path: string; oldPath?: string | null }) {
+  return invoke<GitBinaryFileInfo>("git_file_binary_info", params);
+}
+
 export function gitHeadVsWorkingTextDiff(params: { repoPath: string; path: string; unified: number }) {
   return invoke<string>("git_head_vs_working_text_diff", params);
 }
@@ -21,18 +39,49 @@ export function gitWorkingFileTextPreview(params: { repoPath: string; path: stri
   return invoke<string>("git_working_file_text_preview", params);
 }
 
-export function gitWorkingFileDiff(params: { repoPath: string; path: string }) {
+export function gitWorkingFileDiff(params: {
+  repoPath: string;
+  path: string;
+  functionContext?: boolean;
+  maxBytes?: number;
+  maxLines?: number;
+}) {
   return invoke<string>("git_working_file_diff", params);
 }
 
-export function gitWorkingFileDiffUnified(params: { repoPath: string; path: string; unified: number }) {
+export function gitWorkingFileDiffUnified(params: {
+  repoPath: string;
+  path: string;
+  unified: number;
+  functionContext?: boolean;
+}) {
   return invoke<string>("git_working_file_diff_unified", params);
 }
 
+export function gitWorkingFileDiffStructured(params: {
+  repoPath: string;
+  path: string;
+  unified?: number;
+  functionContext?: boolean;
+  page?: GitDiffPageOptions;
+}) {
+  return invoke<GitDiffPage>("git_working_file_diff_structured", params);
+}
+
 export function gitWorkingFileContent(params: { repoPath: string; path: string }) {
   return invoke<string>("git_working_file_content", params);
 }
 
+export function gitDiffContextLines(params: {
+  repoPath: string;
+  rev?: string;
+  path: string;
+  startLine: number;
+  count: number;
+}) {
+  return invoke<GitContextLines>("git_diff_context_lines", params);
+}
+
 export function gitHeadFileContent(params: { repoPath: string; path: string }) {
   return invoke<string>("git_head_file_content", params);
 }
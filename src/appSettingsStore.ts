@@ -8,7 +8,7 @@ export type WorkingFilesViewMode = "flat" | "tree";
 export type RankDir = "TB" | "LR";
 export type EdgeDirection = "to_parent" | "to_child";
 
- export type GitHistoryOrder = "topo" | "date" | "first_parent";
+ export type GitHistoryOrder = "topo" | "date" | "author_date" | "first_parent";
 
  export type TooltipMode = "custom" | "native";
 
@@ -451,7 +451,12 @@ export const useAppSettings = create<AppSettingsState>()(
         if (typeof s.git.commitsOnlyHead !== "boolean") {
           s.git.commitsOnlyHead = defaultGitSettings.commitsOnlyHead;
         }
-        if (s.git.commitsHistoryOrder !== "topo" && s.git.commitsHistoryOrder !== "date" && s.git.commitsHistoryOrder !== "first_parent") {
+        if (
+          s.git.commitsHistoryOrder !== "topo" &&
+          s.git.commitsHistoryOrder !== "date" &&
+          s.git.commitsHistoryOrder !== "author_date" &&
+          s.git.commitsHistoryOrder !== "first_parent"
+        ) {
           s.git.commitsHistoryOrder = defaultGitSettings.commitsHistoryOrder;
         }
         if (typeof s.git.showOnlineAvatars !== "boolean") {
@@ -21,10 +21,14 @@ export function CommitContextMenu(props: {
   commitContextBranches: string[];
   changedCount: number;
 
+  /** Set for merge commits in first-parent mode. */
+  mergeToggle: "expand" | "collapse" | null;
+
   pickPreferredBranch: (branches: string[]) => string | undefined;
 
   onShowChanges: (hash: string) => void;
   onCopyHash: (hash: string) => void;
+  onToggleMerge: (hash: string) => void;
   onCheckoutCommit: (hash: string) => void;
   onCreateBranch: (hash: string) => void;
   onCreateTag: (hash: string) => void;
@@ -49,9 +53,11 @@ export function CommitContextMenu(props: {
     commitContextBranchesLoading,
     commitContextBranches,
     changedCount,
+    mergeToggle,
     pickPreferredBranch,
     onShowChanges,
     onCopyHash,
+    onToggleMerge,
     onCheckoutCommit,
     onCreateBranch,
     onCreateTag,
@@ -88,6 +94,11 @@ export function CommitContextMenu(props: {
       <button type="button" onClick={() => onCopyHash(menu.hash)}>
         Copy hash
       </button>
+      {mergeToggle ? (
+        <button type="button" disabled={!activeRepoPath} onClick={() => onToggleMerge(menu.hash)}>
+          {mergeToggle === "expand" ? "Expand merged commits" : "Collapse merged commits"}
+        </button>
+      ) : null}
       <button type="button" disabled={!activeRepoPath || loading} onClick={() => onCheckoutCommit(menu.hash)}>
         Checkout this commit
       </button>
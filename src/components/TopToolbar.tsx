@@ -1,6 +1,7 @@
 import type { Dispatch, ReactNode, RefObject, SetStateAction } from "react";
 import type { QuickButtonId, TerminalSettings } from "../appSettingsStore";
 import type { ShortcutActionId } from "../shortcuts";
+import type { GitTransferProgressEvent } from "../types/git";
 
 export function TopToolbar(props: {
   repos: string[];
@@ -43,6 +44,7 @@ export function TopToolbar(props: {
   openTerminalDefault: () => void | Promise<void>;
   openTerminalSettings: () => void;
   indicatorsUpdating: boolean;
+  transferProgress?: GitTransferProgressEvent | null;
   error: string;
   errorHasDetails?: boolean;
   onOpenErrorDetails?: () => void;
@@ -92,6 +94,7 @@ export function TopToolbar(props: {
     openTerminalDefault,
     openTerminalSettings,
     indicatorsUpdating,
+    transferProgress,
     error,
     errorHasDetails,
     onOpenErrorDetails,
@@ -377,6 +380,14 @@ export function TopToolbar(props: {
           <span className="miniSpinner" />
         </div>
       ) : null}
+      {transferProgress ? (
+        <div style={{ display: "flex", alignItems: "center", gap: 8, opacity: 0.7 }} title={transferProgress.message}>
+          <span className="miniSpinner" />
+          <span>
+            {transferProgress.phase} {transferProgress.percent}%
+          </span>
+        </div>
+      ) : null}
       {loading ? (
         <div style={{ display: "flex", alignItems: "center", gap: 8, opacity: 0.7 }}>
           <span className="miniSpinner" />
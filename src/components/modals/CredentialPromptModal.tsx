@@ -0,0 +1,89 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { gitCredentialPromptReply } from "../../api/git";
+import type { GitCredentialPromptEvent } from "../../types/git";
+
+export function CredentialPromptModal() {
+  const [queue, setQueue] = useState<GitCredentialPromptEvent[]>([]);
+  const [value, setValue] = useState("");
+
+  useEffect(() => {
+    let alive = true;
+    let unlisten: (() => void) | null = null;
+    void listen<GitCredentialPromptEvent>("git_credential_prompt", (event) => {
+      setQueue((prev) => [...prev, event.payload]);
+    }).then((fn) => {
+      if (!alive) {
+        fn();
+        return;
+      }
+      unlisten = fn;
+    });
+
+    return () => {
+      alive = false;
+      if (unlisten) unlisten();
+    };
+  }, []);
+
+  const current = queue[0];
+  if (!current) return null;
+
+  function answer(reply: string | null) {
+    if (!current) return;
+    void gitCredentialPromptReply({ id: current.id, value: reply }).catch(() => undefined);
+    setQueue((prev) => prev.filter((p) => p.id !== current.id));
+    setValue("");
+  }
+
+  const title =
+    current.kind === "username"
+      ? "Username"
+      : current.kind === "password"
+        ? "Password"
+        : current.kind === "passphrase"
+          ? "Key passphrase"
+          : current.kind === "confirm"
+            ? "Confirm host"
+            : "Credentials";
+
+  return (
+    <div className="modalOverlay" role="dialog" aria-modal="true">
+      <div className="modal" style={{ width: "min(560px, 96vw)", maxHeight: "min(50vh, 420px)" }}>
+        <div className="modalHeader">
+          <div style={{ fontWeight: 900 }}>{title}</div>
+          <button type="button" onClick={() => answer(null)}>
+            Close
+          </button>
+        </div>
+        <div className="modalBody">
+          <form
+            style={{ display: "grid", gap: 6 }}
+            onSubmit={(e) => {
+              e.preventDefault();
+              answer(value);
+            }}
+          >
+            <div style={{ fontWeight: 800, opacity: 0.8, whiteSpace: "pre-wrap" }}>{current.prompt}</div>
+            <input
+              value={value}
+              onChange={(e) => setValue(e.target.value)}
+              className="modalInput"
+              type={current.secret ? "password" : "text"}
+              placeholder={current.kind === "confirm" ? "yes" : ""}
+              autoFocus
+            />
+          </form>
+        </div>
+        <div className="modalFooter" style={{ display: "flex", justifyContent: "space-between", gap: 10 }}>
+          <button type="button" onClick={() => answer(null)}>
+            Cancel
+          </button>
+          <button type="button" onClick={() => answer(value)}>
+            OK
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
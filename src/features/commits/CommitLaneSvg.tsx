@@ -9,11 +9,13 @@ export function CommitLaneSvg(props: {
   isHead: boolean;
   showMergeStub: boolean;
   mergeParentCount: number;
+  mergeExpanded?: boolean;
+  onToggleMerge?: () => void;
   nodeBg: string;
   palette: CyPalette;
   refMarkers: Array<{ kind: "head" | "branch" | "tag" | "remote"; label: string }>;
 }) {
-  const { row, maxLanes, theme, selected, isHead, showMergeStub, mergeParentCount, nodeBg, palette, refMarkers } = props;
+  const { row, maxLanes, theme, selected, isHead, showMergeStub, mergeParentCount, mergeExpanded, onToggleMerge, nodeBg, palette, refMarkers } = props;
 
   const laneStep = 12;
   const lanePad = 10;
@@ -98,10 +100,28 @@ export function CommitLaneSvg(props: {
         strokeWidth={selected ? 2.6 : isHead ? 2.3 : 2}
       />
       {showMergeStub ? (
-        <g>
-          <title>{`Merge commit (${mergeParentCount} parents)`}</title>
+        <g
+          style={onToggleMerge ? { cursor: "pointer" } : undefined}
+          onClick={
+            onToggleMerge
+              ? (e) => {
+                  e.stopPropagation();
+                  onToggleMerge();
+                }
+              : undefined
+          }
+        >
+          <title>
+            {`Merge commit (${mergeParentCount} parents)` +
+              (onToggleMerge ? (mergeExpanded ? " – click to collapse side commits" : " – click to expand side commits") : "")}
+          </title>
+          {onToggleMerge ? <rect x={nodeX + 2} y={yMid - 10} width={14} height={20} fill="transparent" /> : null}
           <path
-            d={`M ${nodeX + 10} ${yMid - 7} L ${nodeX + 5} ${yMid} L ${nodeX + 10} ${yMid + 7}`}
+            d={
+              mergeExpanded
+                ? `M ${nodeX + 4} ${yMid + 5} L ${nodeX + 9} ${yMid + 10} L ${nodeX + 14} ${yMid + 5}`
+                : `M ${nodeX + 10} ${yMid - 7} L ${nodeX + 5} ${yMid} L ${nodeX + 10} ${yMid + 7}`
+            }
             fill="none"
             stroke={nodeColor}
             strokeWidth={selected ? 2.2 : 2}
@@ -17,7 +17,41 @@ export function laneStrokeColor(lane: number, theme: ThemeName) {
   return `hsl(${hue} ${sat}% ${light}%)`;
 }
 
-export function computeCommitLaneRows(commits: GitCommit[], historyOrder: GitHistoryOrder): { rows: CommitLaneRow[]; maxLanes: number } {
+/** First-parent mode: puts the side commits of each expanded merge right below the merge. */
+export function withExpandedMerges(commits: GitCommit[], sideByMerge: Record<string, GitCommit[] | undefined>): GitCommit[] {
+  if (Object.keys(sideByMerge).length === 0) return commits;
+  const seen = new Set(commits.map((c) => c.hash));
+  const out: GitCommit[] = [];
+  for (const c of commits) {
+    out.push(c);
+    for (const s of sideByMerge[c.hash] ?? []) {
+      if (seen.has(s.hash)) continue;
+      seen.add(s.hash);
+      out.push(s);
+    }
+  }
+  return out;
+}
+
+/** Commits whose merge parents are drawn in first-parent mode: expanded merges and their side commits. */
+export function expandedMergeHashes(sideByMerge: Record<string, GitCommit[] | undefined>): Set<string> {
+  const out = new Set<string>();
+  for (const [merge, side] of Object.entries(sideByMerge)) {
+    out.add(merge);
+    for (const s of side ?? []) out.add(s.hash);
+  }
+  return out;
+}
+
+function drawnParents(c: GitCommit, historyOrder: GitHistoryOrder, expanded?: ReadonlySet<string>) {
+  return historyOrder === "first_parent" && !expanded?.has(c.hash) ? [] : c.parents;
+}
+
+export function computeCommitLaneRows(
+  commits: GitCommit[],
+  historyOrder: GitHistoryOrder,
+  expanded?: ReadonlySet<string>,
+): { rows: CommitLaneRow[]; maxLanes: number } {
   const cols: Array<string | null> = [];
   const rows: CommitLaneRow[] = [];
   let maxLanes = 0;
@@ -70,7 +104,7 @@ export function computeCommitLaneRows(commits: GitCommit[], historyOrder: GitHis
     cols[lane] = p0 && present.has(p0) ? p0 : null;
 
     const parentLanes: number[] = [];
-    const parents = historyOrder === "first_parent" ? [] : c.parents;
+    const parents = drawnParents(c, historyOrder, expanded);
     for (let i = 1; i < parents.length; i++) {
       const p = parents[i];
       if (!p) continue;
@@ -105,7 +139,11 @@ export function computeCommitLaneRows(commits: GitCommit[], historyOrder: GitHis
   return { rows, maxLanes };
 }
 
-export function computeCompactLaneByHashForGraph(commits: GitCommit[], historyOrder: GitHistoryOrder): Map<string, number> {
+export function computeCompactLaneByHashForGraph(
+  commits: GitCommit[],
+  historyOrder: GitHistoryOrder,
+  expanded?: ReadonlySet<string>,
+): Map<string, number> {
   const present = new Set(commits.map((c) => c.hash));
   const cols: string[] = [];
   const laneByHash = new Map<string, number>();
@@ -141,7 +179,7 @@ export function computeCompactLaneByHashForGraph(commits: GitCommit[], historyOr
     const insertBase = primary ? lane + 1 : lane;
     let insertAt = Math.min(insertBase, cols.length);
 
-    const parents = historyOrder === "first_parent" ? [] : c.parents;
+    const parents = drawnParents(c, historyOrder, expanded);
     for (let i = 1; i < parents.length; i++) {
       const p = parents[i];
       if (!p) continue;
@@ -257,7 +257,16 @@ export function useCommitController(opts: {
       setCommitBusy(true);
       setCommitError("");
       try {
-        await gitCommit({ repoPath: activeRepoPath, message: commitMessage, paths });
+        const result = await gitCommit({ repoPath: activeRepoPath, message: commitMessage, paths });
+        if (!result.committed) {
+          const failed = result.hooks[result.hooks.length - 1];
+          setCommitError(
+            failed
+              ? `${failed.hook} hook failed with exit code ${failed.exit_code}.${failed.output ? `\n\n${failed.output}` : ""}`
+              : "Commit was not created.",
+          );
+          return;
+        }
 
         if (commitAlsoPush) {
           const currentRemote = await gitGetRemoteUrl(activeRepoPath, "origin");
@@ -1,7 +1,7 @@
 import { useCallback, type Dispatch, type SetStateAction } from "react";
 import { parseGitDubiousOwnershipError } from "../../utils/gitTrust";
 import type { GitAheadBehind, GitCommit, GitStatusSummary, GitStashEntry, RepoOverview } from "../../types/git";
-import { gitCheckWorktree } from "../../api/git";
+import { closeRepositoryHandle, openRepositoryHandle } from "../../api/git";
 
 export function useRepoOpenClose(opts: {
   defaultViewMode: "graph" | "commits";
@@ -88,7 +88,7 @@ export function useRepoOpenClose(opts: {
       setLoading(true);
 
       try {
-        await gitCheckWorktree(path);
+        await openRepositoryHandle(path);
       } catch (e) {
         const msg = typeof e === "string" ? e : JSON.stringify(e);
         const details = parseGitDubiousOwnershipError(msg);
@@ -162,6 +162,7 @@ export function useRepoOpenClose(opts: {
 
   const closeRepository = useCallback(
     async (path: string) => {
+      void closeRepositoryHandle(path).catch(() => undefined);
       setRepos((prev) => prev.filter((p) => p !== path));
       setViewModeByRepo((prev) => {
         const { [path]: _, ...rest } = prev;
@@ -1,7 +1,7 @@
 import { useEffect, useMemo, useState, type Dispatch, type SetStateAction } from "react";
 import type { DiffToolSettings } from "../../appSettingsStore";
 import { useAppSettings } from "../../appSettingsStore";
-import type { GitStatusEntry, GitStatusSummary, GitStashEntry } from "../../types/git";
+import type { GitStashApplyResult, GitStatusEntry, GitStatusSummary, GitStashEntry } from "../../types/git";
 import { buildPatchFromUnselectedHunks, computeHunkRanges } from "../../utils/diffPatch";
 import { fileExtLower, isDocTextPreviewExt, isImageExt } from "../../utils/filePreview";
 import { compileGraphoriaIgnore, filterGraphoriaIgnoredEntries } from "../../utils/graphoriaIgnore";
@@ -372,13 +372,22 @@ export function useStashController(opts: {
     }
   }
 
+  // A conflicting apply still changes the working tree, so it is reported rather than thrown.
+  function stashConflictText(result: GitStashApplyResult): string | null {
+    if (result.status !== "conflicts") return null;
+    const files = result.conflict_files.length > 0 ? `\n\nConflicted files:\n${result.conflict_files.join("\n")}` : "";
+    return `${result.message}${files}`;
+  }
+
   async function applyStashByRef(stashRef: string) {
     if (!activeRepoPath || !stashRef.trim()) return;
     setLoading(true);
     setError("");
     try {
-      await gitStashApply({ repoPath: activeRepoPath, stashRef });
+      const result = await gitStashApply({ repoPath: activeRepoPath, stashRef });
       await loadRepo(activeRepoPath);
+      const conflicts = stashConflictText(result);
+      if (conflicts) setError(conflicts);
     } catch (e) {
       setError(typeof e === "string" ? e : JSON.stringify(e));
     } finally {
@@ -391,8 +400,13 @@ export function useStashController(opts: {
     setStashViewLoading(true);
     setStashViewError("");
     try {
-      await gitStashApply({ repoPath: activeRepoPath, stashRef: stashViewRef });
-      setStashViewOpen(false);
+      const result = await gitStashApply({ repoPath: activeRepoPath, stashRef: stashViewRef });
+      const conflicts = stashConflictText(result);
+      if (conflicts) {
+        setStashViewError(conflicts);
+      } else {
+        setStashViewOpen(false);
+      }
       await loadRepo(activeRepoPath);
     } catch (e) {
       setStashViewError(typeof e === "string" ? e : JSON.stringify(e));
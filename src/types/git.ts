@@ -4,9 +4,181 @@ export type GitCommit = {
   author: string;
   author_email: string;
   date: string;
+  committer_date: string;
   subject: string;
   refs: string;
   is_head: boolean;
+  is_pushed: boolean;
+  wip: GitWipCounts | null;
+};
+
+export type GitWipCounts = {
+  staged: number;
+  unstaged: number;
+  untracked: number;
+};
+
+export type GitCommitSignature = {
+  status: "good" | "untrusted" | "unsigned" | "bad" | "expired" | "revoked" | "unchecked";
+  signer: string | null;
+  key: string | null;
+  problem: string | null;
+};
+
+export type GitCommitDetails = {
+  hash: string;
+  short_hash: string;
+  parents: string[];
+  author_name: string;
+  author_email: string;
+  author_date: string;
+  committer_name: string;
+  committer_email: string;
+  committer_date: string;
+  subject: string;
+  /** The message after the subject line. */
+  body: string;
+  /** e.g. "HEAD -> main", "origin/main", "tag: v1.0". */
+  refs: string[];
+  signature: GitCommitSignature;
+  /** Against the first parent. */
+  files_changed: number;
+  insertions: number;
+  deletions: number;
+};
+
+export type GitNumstatEntry = {
+  path: string;
+  old_path: string | null;
+  additions: number;
+  deletions: number;
+  /** Binary files have no line counts. */
+  binary: boolean;
+};
+
+export type GitWordDiffSegment = {
+  kind: "context" | "added" | "removed";
+  text: string;
+};
+
+export type GitWordDiffHunk = {
+  header: string;
+  old_start: number;
+  old_lines: number;
+  new_start: number;
+  new_lines: number;
+  /** The old line is the context and removed segments, the new line the context and added ones. */
+  lines: GitWordDiffSegment[][];
+};
+
+export type GitWordDiff = {
+  path: string;
+  binary: boolean;
+  hunks: GitWordDiffHunk[];
+};
+
+export type GitDiffLine = {
+  kind: "context" | "added" | "removed";
+  old_line: number | null;
+  new_line: number | null;
+  content: string;
+  /** Followed by "\ No newline at end of file". */
+  no_newline: boolean;
+};
+
+export type GitDiffHunk = {
+  header: string;
+  old_start: number;
+  old_lines: number;
+  new_start: number;
+  new_lines: number;
+  lines: GitDiffLine[];
+};
+
+export type GitDiffFile = {
+  old_path: string | null;
+  new_path: string | null;
+  status: "added" | "deleted" | "renamed" | "copied" | "modified";
+  binary: boolean;
+  hunks: GitDiffHunk[];
+};
+
+export type GitDiffPageOptions = {
+  max_bytes?: number;
+  max_lines?: number;
+  /** `continuation` of the previous page. */
+  continuation?: string | null;
+};
+
+export type GitDiffPage = {
+  /** A file whose hunks span pages is repeated on each with the hunks of that page. */
+  files: GitDiffFile[];
+  total_files: number;
+  total_hunks: number;
+  /** Index of the page's first hunk, counting across files. */
+  first_hunk: number;
+  truncated: boolean;
+  /** Passed back to fetch the next page; null on the last one. */
+  continuation: string | null;
+};
+
+export type GitContextLines = {
+  /** 1-based number of the first line. */
+  start_line: number;
+  lines: string[];
+  total_lines: number;
+};
+
+export type GitBinaryFileInfo = {
+  path: string;
+  old_path: string | null;
+  /** null when the file does not exist on that side. */
+  old_size: number | null;
+  new_size: number | null;
+  /** Git file modes, e.g. "100644", "100755", "120000". */
+  old_mode: string | null;
+  new_mode: string | null;
+  mime: string | null;
+};
+
+export type GitSubmoduleChange = {
+  old_commit: string | null;
+  new_commit: string | null;
+};
+
+export type GitSubmoduleCommit = {
+  hash: string;
+  short_hash: string;
+  subject: string;
+  author: string;
+  date: string;
+};
+
+export type GitSubmoduleDiff = {
+  path: string;
+  old_commit: string | null;
+  new_commit: string | null;
+  added: GitSubmoduleCommit[];
+  removed: GitSubmoduleCommit[];
+  truncated: boolean;
+};
+
+export type GraphFilterSettings = {
+  hidden_remotes: string[];
+  hidden_branch_patterns: string[];
+  show_tags: boolean;
+};
+
+export type RepoHandleInfo = {
+  /** Passed as `repoPath` to the other commands while the repository is open. */
+  key: string;
+  root: string;
+  git_dir: string;
+  common_dir: string;
+  /** Whether this is a linked worktree of another repository. */
+  linked_worktree: boolean;
+  open_count: number;
+  graph_filter: GraphFilterSettings;
 };
 
 export type RepoOverview = {
@@ -15,6 +187,7 @@ export type RepoOverview = {
   branches: string[];
   tags: string[];
   remotes: string[];
+  partial_clone_filter?: string | null;
 };
 
 export type GitTagTarget = {
@@ -22,10 +195,161 @@ export type GitTagTarget = {
   target: string;
 };
 
+export type GitCreateTagResult = {
+  output: string;
+  previous_tag?: string | null;
+  changelog?: string | null;
+  changelog_path?: string | null;
+  archive_path?: string | null;
+};
+
+export type GitVersionSuggestion = {
+  latest_tag: string | null;
+  latest_version: string | null;
+  bump: "major" | "minor" | "patch";
+  next_version: string;
+  next_tag: string;
+  commit_count: number;
+  breaking_changes: string[];
+  features: string[];
+  fixes: string[];
+};
+
+export type RecoveryAction = {
+  command: string;
+  label: string;
+};
+
+export type RepoRecoveryReport = {
+  repo_path: string;
+  operation: "merge" | "rebase" | "am" | "cherry-pick" | "revert" | null;
+  conflict_files: string[];
+  stale_index_lock: boolean;
+  actions: RecoveryAction[];
+  cleaned: string[];
+};
+
+export type GitObjectCounts = {
+  loose_objects: number;
+  loose_size_kib: number;
+  packs: number;
+  pack_size_kib: number;
+  garbage: number;
+};
+
+export type GitDirSize = {
+  path: string;
+  is_dir: boolean;
+  bytes: number;
+  files: number;
+  ignored: boolean;
+  children: GitDirSize[];
+};
+
+export type GitRepoSizeStats = {
+  objects: GitObjectCounts;
+  packs: { name: string; bytes: number; kept: boolean }[];
+  git_dir_bytes: number;
+  lfs_objects: number;
+  lfs_bytes: number;
+  worktree_bytes: number;
+  worktree_files: number;
+  entries: GitDirSize[];
+};
+
+export type GitLargeBlob = {
+  path: string;
+  blob: string;
+  bytes: number;
+  disk_bytes: number;
+  versions: number;
+  total_bytes: number;
+  in_head: boolean;
+  introduced_by: {
+    hash: string;
+    short_hash: string;
+    subject: string;
+    author: string;
+    date: number;
+  } | null;
+};
+
+export type GitLargestBlobsReport = {
+  blobs_scanned: number;
+  blobs: GitLargeBlob[];
+};
+
+export type GitHistoryRewriteResult = {
+  engine: "filter-repo" | "filter-branch";
+  backup: { path: string; head: string; refs: number; remotes: number; stashes: number };
+  paths: string[];
+  rewritten_refs: { name: string; old: string; new: string | null }[];
+  message: string;
+};
+
+export type GitMaintenanceResult = {
+  operation: "gc" | "prune" | "repack";
+  before: GitObjectCounts;
+  after: GitObjectCounts;
+  reclaimed_kib: number;
+  output: string;
+};
+
+export type GitFsckIssue = {
+  kind: "dangling" | "missing" | "broken_link" | "corrupt" | "error" | "warning" | "notice";
+  severity: "info" | "warning" | "error";
+  object_type: string | null;
+  object: string | null;
+  message: string;
+};
+
+export type GitFsckReport = {
+  complete: boolean;
+  errors: number;
+  warnings: number;
+  dangling: number;
+  issues: GitFsckIssue[];
+  suggestions: { action: "gc" | "backup" | "refetch" | "reclone"; message: string }[];
+};
+
+export type GitPerfFeaturesResult = {
+  enabled: string[];
+  commits: number;
+  sample: string;
+  before_ms: number;
+  after_ms: number;
+};
+
 export type GitStatusEntry = {
   status: string;
+  index_status?: string;
+  worktree_status?: string;
   path: string;
   old_path?: string | null;
+  similarity?: number | null;
+  submodule?: {
+    commit_changed: boolean;
+    tracked_changes: boolean;
+    untracked_changes: boolean;
+    /** Commit recorded in HEAD. */
+    old_commit?: string | null;
+    /** Commit checked out in the submodule when commit_changed, else the staged one. */
+    new_commit?: string | null;
+  } | null;
+};
+
+export type GitStatusBranch = {
+  oid: string | null;
+  head: string | null;
+  upstream: string | null;
+  ahead: number | null;
+  behind: number | null;
+  stash_count: number;
+};
+
+export type GitStatusReport = {
+  branch: GitStatusBranch;
+  entries: GitStatusEntry[];
 };
 
 export type GitStashEntry = {
@@ -38,12 +362,63 @@ export type GitStatusSummary = {
   changed: number;
 };
 
+export type GitRepoOperation = "merge" | "rebase" | "am" | "cherry-pick" | "revert" | "bisect";
+
+export type GitRepoState = {
+  branch: GitStatusBranch;
+  changed: number;
+  staged: number;
+  unstaged: number;
+  untracked: number;
+  conflicts: number;
+  operation: GitRepoOperation | null;
+};
+
 export type GitAheadBehind = {
   ahead: number;
   behind: number;
   upstream?: string | null;
 };
 
+export type GitCherryCommit = {
+  hash: string;
+  short_hash: string;
+  subject: string;
+  /** An equivalent patch is already upstream; the next rebase drops this commit. */
+  applied: boolean;
+};
+
+export type GitFetchRefUpdate = {
+  kind: "new" | "new_tag" | "updated" | "forced" | "deleted" | "tag_updated" | "rejected" | "up_to_date";
+  source: string;
+  remote_ref: string;
+  local_ref: string;
+  old: string | null;
+  new: string | null;
+  note: string | null;
+};
+
+export type GitFetchResult = {
+  updates: GitFetchRefUpdate[];
+  message: string;
+};
+
+export type GitRefUpdate = {
+  name: string;
+  old: string | null;
+  new: string | null;
+};
+
+/** What a destructive command would do when run with `dryRun`. */
+export type GitDryRunReport = {
+  operation: string;
+  ref_updates: GitRefUpdate[];
+  files_removed: string[];
+  files_changed: string[];
+  commits_orphaned: string[];
+  message: string;
+};
+
 export type PullResult = {
   status: string;
   operation: string;
@@ -51,6 +426,13 @@ export type PullResult = {
   conflict_files: string[];
 };
 
+export type GitRemoteCheckoutResult = PullResult & {
+  /** The local branch that was checked out. */
+  branch: string;
+  /** False when an existing branch tracking the remote branch was reused. */
+  created: boolean;
+};
+
 export type PullPredictResult = {
   upstream?: string | null;
   ahead: number;
@@ -71,6 +453,14 @@ export type PullPredictGraphResult = {
   remote_name: string;
 };
 
+export type GitCredentialPromptEvent = {
+  id: number;
+  prompt: string;
+  /** "confirm" is an SSH host key question. */
+  kind: "username" | "password" | "passphrase" | "confirm" | "other";
+  secret: boolean;
+};
+
 export type GitCloneProgressEvent = {
   destination_path: string;
   phase?: string | null;
@@ -78,6 +468,18 @@ export type GitCloneProgressEvent = {
   message: string;
 };
 
+/** Progress of a fetch, pull or push in an open repository. */
+export type GitTransferProgressEvent = {
+  repo_path: string;
+  operation: "fetch" | "pull" | "push" | string;
+  phase: string;
+  percent: number;
+  objects_done?: number | null;
+  objects_total?: number | null;
+  bytes?: number | null;
+  message: string;
+};
+
 export type GitCommitSummary = {
   hash: string;
   author: string;
@@ -91,6 +493,31 @@ export type GitBranchInfo = {
   kind: "local" | "remote" | string;
   target: string;
   committer_date: string;
+  /** Unix seconds of the branch's latest reflog entry (local branches only). */
+  last_activity?: number | null;
+  last_activity_action?: string | null;
+  upstream?: string | null;
+  /** The upstream is configured but its remote branch was deleted. */
+  upstream_gone?: boolean;
+  subject?: string;
+  author?: string;
+  author_date?: string;
+  /** Other worktree that has the branch checked out (local branches only). */
+  checked_out_in?: string | null;
+};
+
+export type GitStaleBranch = {
+  name: string;
+  target: string;
+  /** Unix seconds of the tip's committer date. */
+  committer_time: number;
+  age_days: number;
+  last_author: string;
+  upstream: string | null;
+  upstream_gone: boolean;
+  /** Commits HEAD does not have; 0 when the branch is fully merged. */
+  ahead: number;
+  behind: number;
 };
 
 export type GitConflictFileEntry = {
@@ -127,6 +554,8 @@ export type GitContinueInfo = {
   operation: string;
   message: string;
   files: GitContinueFileEntry[];
+  cleanup_mode: string;
+  comment_char: string;
 };
 
 export type GitPatchPredictResult = {
@@ -178,6 +607,7 @@ export type InteractiveRebaseStatusInfo = {
 
 export type GitPatchPredictGraphResult = {
   ok: boolean;
+  action: string;
   message: string;
   conflict_files: string[];
   touched_files: string[];
@@ -185,3 +615,21 @@ export type GitPatchPredictGraphResult = {
   created_node_ids: string[];
   head_name: string;
 };
+
+export type GitHookRun = {
+  hook: string;
+  exit_code: number;
+  output: string;
+};
+
+export type GitCommitResult = {
+  commit: string;
+  hooks: GitHookRun[];
+  committed: boolean;
+};
+
+export type GitStashApplyResult = {
+  status: "ok" | "conflicts";
+  message: string;
+  conflict_files: string[];
+};
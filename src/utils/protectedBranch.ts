@@ -0,0 +1,16 @@
+export type ProtectedBranchError = {
+  branch: string;
+  pattern: string;
+  operation: string;
+  message: string;
+};
+
+export function parseProtectedBranchError(raw: string): ProtectedBranchError | null {
+  const prefix = "PROTECTED_BRANCH\n";
+  if (!raw.startsWith(prefix)) return null;
+  try {
+    return JSON.parse(raw.slice(prefix.length)) as ProtectedBranchError;
+  } catch {
+    return null;
+  }
+}
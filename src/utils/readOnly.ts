@@ -0,0 +1,5 @@
+export function parseReadOnlyError(raw: string): string | null {
+  const prefix = "READ_ONLY\n";
+  if (!raw.startsWith(prefix)) return null;
+  return raw.slice(prefix.length).trim();
+}